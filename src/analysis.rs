@@ -0,0 +1,662 @@
+use chrono::{DateTime, Utc};
+use quick_xml::Reader;
+use quick_xml::events::Event;
+
+use crate::bitlocker::EncryptionChange;
+use crate::helpers;
+use crate::models::DisplayEvent;
+use crate::security;
+use crate::services::ServiceFailure;
+
+pub const KERNEL_POWER_PROVIDER: &str = "Microsoft-Windows-Kernel-Power";
+/// Kernel-Power event logged at boot when the previous session did not shut down cleanly.
+pub const UNEXPECTED_SHUTDOWN_EVENT_ID: u32 = 41;
+
+/// Providers and IDs of events recording a clean shutdown: the Event Log service stopping
+/// (6006), the kernel shutting down (13) and a user or process initiating it (1074).
+#[cfg(target_os = "windows")]
+pub const CLEAN_SHUTDOWN_EVENTS: &[(&str, u32)] = &[
+    ("EventLog", 6006),
+    ("Microsoft-Windows-Kernel-General", 13),
+    ("User32", 1074),
+];
+
+/// Providers and IDs of events logged after an unexpected shutdown: the bugcheck report
+/// (1001) and the Event Log notice of the unexpected shutdown (6008).
+#[cfg(target_os = "windows")]
+pub const CRASH_REPORT_EVENTS: &[(&str, u32)] = &[
+    ("Microsoft-Windows-WER-SystemErrorReporting", 1001),
+    ("BugCheck", 1001),
+    ("EventLog", 6008),
+];
+
+/// Provider and ID of the event recording the operating system start time.
+#[cfg(target_os = "windows")]
+pub const OS_START_EVENT: (&str, u32) = ("Microsoft-Windows-Kernel-General", 12);
+
+/// Events around an unexpected shutdown, gathered from the System log.
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownEvidence {
+    /// Last event recorded before the machine went down.
+    pub last_before_crash: Option<DisplayEvent>,
+    /// Most recent clean shutdown before the crash.
+    pub previous_clean_shutdown: Option<DisplayEvent>,
+    /// First event recorded once the machine was back up.
+    pub first_after_boot: Option<DisplayEvent>,
+    /// Bugcheck reports and unexpected shutdown notices logged after the boot.
+    pub crash_reports: Vec<DisplayEvent>,
+}
+
+pub fn is_unexpected_shutdown(event: &DisplayEvent) -> bool {
+    event.event_id == UNEXPECTED_SHUTDOWN_EVENT_ID
+        && event.provider_name_original == KERNEL_POWER_PROVIDER
+}
+
+/// Builds an XPath condition matching any of the given provider and event ID pairs.
+#[cfg(target_os = "windows")]
+pub fn provider_event_condition(events: &[(&str, u32)]) -> String {
+    events
+        .iter()
+        .map(|(provider, id)| format!("(Provider[@Name='{}'] and EventID={})", provider, id))
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+/// Formats a time for an XPath `TimeCreated/@SystemTime` comparison.
+pub fn xpath_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+fn numeric_field(event: &DisplayEvent, name: &str) -> u64 {
+    event
+        .event_data
+        .named()
+        .find(|(field, _)| *field == name)
+        .and_then(|(_, value)| {
+            let value = value.trim();
+            match value.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => value.parse().ok(),
+            }
+        })
+        .unwrap_or(0)
+}
+
+fn describe(event: &DisplayEvent) -> String {
+    let body = event.formatted_message.as_deref().unwrap_or(&event.message);
+    format!(
+        "{}  {} [{}]  {}",
+        event.local_time(),
+        event.source,
+        event.event_id,
+        helpers::truncate_at_word(&helpers::message_summary(body), 80)
+    )
+}
+
+/// Explains a Kernel-Power 41 event from its own data and the surrounding evidence.
+pub fn shutdown_analysis_lines(event: &DisplayEvent, evidence: &ShutdownEvidence) -> Vec<String> {
+    let mut lines = vec![
+        String::new(),
+        "--- Unexpected Shutdown Analysis ---".to_string(),
+    ];
+
+    let bugcheck = numeric_field(event, "BugcheckCode");
+    if bugcheck == 0 {
+        lines.push(
+            "Bugcheck:        none recorded. The machine lost power, hung or was switched off."
+                .to_string(),
+        );
+    } else {
+        lines.push(format!(
+            "Bugcheck:        0x{:08X} (parameter 1: 0x{:X})",
+            bugcheck,
+            numeric_field(event, "BugcheckParameter1")
+        ));
+    }
+    if numeric_field(event, "PowerButtonTimestamp") != 0 {
+        lines.push("Power button:    held down to force the shutdown.".to_string());
+    }
+    if numeric_field(event, "SleepInProgress") != 0 {
+        lines.push("Sleep:           the machine was entering or resuming from sleep.".to_string());
+    }
+
+    let entries = [
+        ("Last event before crash:", &evidence.last_before_crash),
+        (
+            "Previous clean shutdown:",
+            &evidence.previous_clean_shutdown,
+        ),
+        ("First event after boot: ", &evidence.first_after_boot),
+    ];
+    for (label, found) in entries {
+        lines.push(match found {
+            Some(found) => format!("{} {}", label, describe(found)),
+            None => format!("{} not found", label),
+        });
+    }
+    let time = |event: &Option<DisplayEvent>| event.as_ref().and_then(|event| event.timestamp);
+    if let (Some(last), Some(first)) = (
+        time(&evidence.last_before_crash),
+        time(&evidence.first_after_boot),
+    ) {
+        lines.push(format!(
+            "Downtime:        about {}",
+            helpers::format_duration(first - last)
+        ));
+    }
+    if let (Some(clean), Some(last)) = (
+        time(&evidence.previous_clean_shutdown),
+        time(&evidence.last_before_crash),
+    ) {
+        lines.push(format!(
+            "Up since last clean shutdown: {}",
+            helpers::format_duration(last - clean)
+        ));
+    }
+    if evidence.crash_reports.is_empty() {
+        lines.push("Crash reports:   none logged after the boot.".to_string());
+    } else {
+        lines.push("Crash reports:".to_string());
+        lines.extend(
+            evidence
+                .crash_reports
+                .iter()
+                .map(|report| format!("  {}", describe(report))),
+        );
+    }
+    lines
+}
+
+const APPLOCKER_PROVIDER: &str = "Microsoft-Windows-AppLocker";
+const CODE_INTEGRITY_PROVIDER: &str = "Microsoft-Windows-CodeIntegrity";
+
+/// What an AppLocker (8003/8004) or WDAC (3076/3077) event says about a binary it audited or
+/// blocked.
+#[derive(Debug, Clone)]
+pub struct AppControlDecision {
+    /// "AppLocker" or "WDAC".
+    pub product: &'static str,
+    /// False for audit mode events, where the binary ran but would have been blocked.
+    pub enforced: bool,
+    pub file_path: String,
+    pub publisher: Option<String>,
+    pub policy: Option<String>,
+    pub rule: Option<String>,
+    /// Process that tried to load the binary (WDAC only).
+    pub process: Option<String>,
+}
+
+impl AppControlDecision {
+    /// Decodes the event if it is an AppLocker or WDAC block or audit event.
+    pub fn decode(event: &DisplayEvent) -> Option<Self> {
+        match (event.provider_name_original.as_str(), event.event_id) {
+            (APPLOCKER_PROVIDER, 8003 | 8004) => {
+                // AppLocker keeps its fields in UserData elements rather than named Data.
+                let field = |name| xml_element_text(&event.raw_data, name);
+                Some(Self {
+                    product: "AppLocker",
+                    enforced: event.event_id == 8004,
+                    file_path: field("FilePath")?,
+                    publisher: field("Fqbn").map(|fqbn| fqbn_publisher(&fqbn)),
+                    policy: field("PolicyName"),
+                    rule: field("RuleName").filter(|rule| rule != "-"),
+                    process: None,
+                })
+            }
+            (CODE_INTEGRITY_PROVIDER, 3076 | 3077) => {
+                let field = |name| {
+                    event
+                        .event_data
+                        .named()
+                        .find(|(field, value)| *field == name && !value.is_empty())
+                        .map(|(_, value)| value.to_string())
+                };
+                Some(Self {
+                    product: "WDAC",
+                    enforced: event.event_id == 3077,
+                    file_path: field("File Name")?,
+                    publisher: field("PublisherName"),
+                    policy: field("PolicyName").or_else(|| field("PolicyID")),
+                    rule: None,
+                    process: field("Process Name"),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// File name of the binary, which reads the same in AppLocker and WDAC events even though
+    /// they spell the path differently.
+    pub fn file_name(&self) -> &str {
+        self.file_path
+            .rsplit(['\\', '/'])
+            .next()
+            .unwrap_or(&self.file_path)
+    }
+
+    /// Labeled fields shown in the preview.
+    pub fn lines(&self) -> Vec<String> {
+        let action = if self.enforced {
+            "Blocked"
+        } else {
+            "Audited (would have been blocked)"
+        };
+        let mut lines = vec![
+            String::new(),
+            format!("--- {} Decision ---", self.product),
+            format!("Action:      {}", action),
+            format!("Binary:      {}", self.file_path),
+            format!(
+                "Publisher:   {}",
+                self.publisher.as_deref().unwrap_or("not recorded")
+            ),
+            format!(
+                "Policy:      {}",
+                self.policy.as_deref().unwrap_or("not recorded")
+            ),
+        ];
+        if let Some(rule) = &self.rule {
+            lines.push(format!("Rule:        {}", rule));
+        }
+        if let Some(process) = &self.process {
+            lines.push(format!("Process:     {}", process));
+        }
+        lines.push(format!(
+            "Press [F] to show only loaded events for {}.",
+            self.file_name()
+        ));
+        lines
+    }
+}
+
+/// Extracts the organization from an AppLocker fully qualified binary name such as
+/// `O=CONTOSO, L=..., C=US\PRODUCT\APP.EXE\1.0.0.0`. Unsigned binaries have "-".
+fn fqbn_publisher(fqbn: &str) -> String {
+    let subject = fqbn.split('\\').next().unwrap_or(fqbn).trim();
+    if subject.is_empty() || subject == "-" {
+        return "unsigned".to_string();
+    }
+    subject
+        .split(", ")
+        .find_map(|part| part.strip_prefix("O="))
+        .unwrap_or(subject)
+        .to_string()
+}
+
+/// Returns the text of the first element named `name` in the event XML.
+pub fn xml_element_text(xml: &str, name: &str) -> Option<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut inside = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => inside = e.local_name().as_ref() == name.as_bytes(),
+            Ok(Event::Text(e)) if inside => {
+                let text = e.unescape().ok()?.trim().to_string();
+                return (!text.is_empty()).then_some(text);
+            }
+            Ok(Event::End(_)) if inside => return None,
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+    }
+}
+
+const SCHANNEL_PROVIDER: &str = "Schannel";
+const CAPI2_PROVIDER: &str = "Microsoft-Windows-CAPI2";
+
+/// TLS alert descriptions (RFC 8446 section 6) and what usually causes them.
+const TLS_ALERTS: &[(u32, &str, &str)] = &[
+    (
+        10,
+        "unexpected_message",
+        "a message arrived out of order; often a protocol bug or a middlebox interfering",
+    ),
+    (
+        20,
+        "bad_record_mac",
+        "a record failed its integrity check; data was corrupted or tampered with in transit",
+    ),
+    (21, "decryption_failed", "a record could not be decrypted"),
+    (22, "record_overflow", "a record was longer than allowed"),
+    (
+        40,
+        "handshake_failure",
+        "no protocol version, cipher suite or signature algorithm acceptable to both sides",
+    ),
+    (
+        42,
+        "bad_certificate",
+        "the certificate is corrupt or its signature does not verify",
+    ),
+    (
+        43,
+        "unsupported_certificate",
+        "the certificate type is not supported",
+    ),
+    (
+        44,
+        "certificate_revoked",
+        "the certificate was revoked by its issuer",
+    ),
+    (
+        45,
+        "certificate_expired",
+        "the certificate has expired or is not yet valid",
+    ),
+    (
+        46,
+        "certificate_unknown",
+        "the certificate was rejected for an unspecified reason, often an incomplete chain",
+    ),
+    (
+        47,
+        "illegal_parameter",
+        "a handshake field was out of range or inconsistent",
+    ),
+    (
+        48,
+        "unknown_ca",
+        "the peer does not trust the CA that issued the certificate",
+    ),
+    (
+        49,
+        "access_denied",
+        "the certificate is valid but the peer refused access",
+    ),
+    (50, "decode_error", "a message could not be decoded"),
+    (
+        51,
+        "decrypt_error",
+        "a handshake signature or key exchange failed to verify",
+    ),
+    (
+        70,
+        "protocol_version",
+        "the peer does not support the offered TLS version",
+    ),
+    (
+        71,
+        "insufficient_security",
+        "the peer requires stronger cipher suites than were offered",
+    ),
+    (
+        80,
+        "internal_error",
+        "an error on the peer unrelated to the protocol",
+    ),
+    (
+        86,
+        "inappropriate_fallback",
+        "a downgraded connection attempt was refused",
+    ),
+    (90, "user_canceled", "the handshake was cancelled"),
+    (
+        109,
+        "missing_extension",
+        "a required TLS extension was not sent",
+    ),
+    (
+        110,
+        "unsupported_extension",
+        "an extension was sent that the peer did not offer",
+    ),
+    (
+        112,
+        "unrecognized_name",
+        "the server has no certificate for the requested host name (SNI)",
+    ),
+    (
+        116,
+        "certificate_required",
+        "the server requires a client certificate and none was sent",
+    ),
+    (
+        120,
+        "no_application_protocol",
+        "no application protocol (ALPN) in common",
+    ),
+];
+
+/// Certificate and SSPI error codes found in Schannel and CAPI2 events.
+const CERTIFICATE_ERRORS: &[(u32, &str, &str)] = &[
+    (
+        0x800B0101,
+        "CERT_E_EXPIRED",
+        "a certificate in the chain has expired or is not yet valid",
+    ),
+    (
+        0x800B0109,
+        "CERT_E_UNTRUSTEDROOT",
+        "the chain ends in a root certificate that is not trusted",
+    ),
+    (
+        0x800B010A,
+        "CERT_E_CHAINING",
+        "the chain could not be built to a root; an intermediate is missing",
+    ),
+    (
+        0x800B010C,
+        "CERT_E_REVOKED",
+        "a certificate in the chain was revoked",
+    ),
+    (
+        0x800B010E,
+        "CERT_E_REVOCATION_FAILURE",
+        "revocation could not be checked",
+    ),
+    (
+        0x800B010F,
+        "CERT_E_CN_NO_MATCH",
+        "the certificate name does not match the host name",
+    ),
+    (
+        0x800B0110,
+        "CERT_E_WRONG_USAGE",
+        "the certificate is not valid for this purpose",
+    ),
+    (
+        0x800B0111,
+        "TRUST_E_EXPLICIT_DISTRUST",
+        "the certificate is explicitly distrusted",
+    ),
+    (
+        0x800B0112,
+        "CERT_E_UNTRUSTEDCA",
+        "an intermediate CA is not trusted",
+    ),
+    (0x80092010, "CRYPT_E_REVOKED", "the certificate was revoked"),
+    (
+        0x80092012,
+        "CRYPT_E_NO_REVOCATION_CHECK",
+        "the revocation function could not check the certificate",
+    ),
+    (
+        0x80092013,
+        "CRYPT_E_REVOCATION_OFFLINE",
+        "the revocation server (CRL or OCSP) could not be reached",
+    ),
+    (
+        0x80096004,
+        "TRUST_E_CERT_SIGNATURE",
+        "a certificate signature could not be verified",
+    ),
+    (
+        0x8009030D,
+        "SEC_E_UNKNOWN_CREDENTIALS",
+        "the server certificate's private key is missing or cannot be accessed",
+    ),
+    (
+        0x8009030E,
+        "SEC_E_NO_CREDENTIALS",
+        "no certificate is available to authenticate with",
+    ),
+    (
+        0x80090322,
+        "SEC_E_WRONG_PRINCIPAL",
+        "the target name does not match the certificate",
+    ),
+    (
+        0x80090325,
+        "SEC_E_UNTRUSTED_ROOT",
+        "the certificate chain was issued by an untrusted authority",
+    ),
+    (
+        0x80090326,
+        "SEC_E_ILLEGAL_MESSAGE",
+        "the peer sent a malformed message, often after rejecting the handshake",
+    ),
+    (
+        0x80090327,
+        "SEC_E_CERT_UNKNOWN",
+        "the certificate could not be processed",
+    ),
+    (
+        0x80090328,
+        "SEC_E_CERT_EXPIRED",
+        "the received certificate has expired",
+    ),
+    (
+        0x80090331,
+        "SEC_E_ALGORITHM_MISMATCH",
+        "client and server have no cipher suite in common",
+    ),
+    (
+        0x80090349,
+        "SEC_E_CERT_WRONG_USAGE",
+        "the certificate is not valid for the requested usage",
+    ),
+];
+
+/// Readable causes for a Schannel or CAPI2 event's alert codes and certificate errors.
+#[derive(Debug, Clone)]
+pub struct TlsDiagnosis {
+    pub alert: Option<u32>,
+    /// Known certificate or SSPI error codes appearing anywhere in the event.
+    pub errors: Vec<u32>,
+    pub subject: Option<String>,
+    pub thumbprint: Option<String>,
+}
+
+impl TlsDiagnosis {
+    /// Decodes a Schannel or CAPI2 event, or returns None when it carries nothing to explain.
+    pub fn decode(event: &DisplayEvent) -> Option<Self> {
+        if !matches!(
+            event.provider_name_original.as_str(),
+            SCHANNEL_PROVIDER | CAPI2_PROVIDER
+        ) {
+            return None;
+        }
+        let field = |names: &[&str]| {
+            event
+                .event_data
+                .named()
+                .find(|(field, value)| names.contains(field) && !value.trim().is_empty())
+                .map(|(_, value)| value.trim().to_string())
+        };
+        let alert = field(&["AlertDesc"]).and_then(|value| value.parse().ok());
+
+        let text = format!(
+            "{}\n{}",
+            event.formatted_message.as_deref().unwrap_or(&event.message),
+            event.raw_data
+        )
+        .to_uppercase();
+        let errors: Vec<u32> = CERTIFICATE_ERRORS
+            .iter()
+            .map(|(code, _, _)| *code)
+            .filter(|code| {
+                text.contains(&format!("{:08X}", code))
+                    || text.contains(&(*code as i32).to_string())
+            })
+            .collect();
+
+        // CAPI2 names the certificate file after its SHA-1 thumbprint.
+        let subject = field(&["Subject", "SubjectName", "TargetName"])
+            .or_else(|| xml_attribute(&event.raw_data, "subjectName"));
+        let thumbprint = field(&["Thumbprint", "CertificateThumbprint"]).or_else(|| {
+            xml_attribute(&event.raw_data, "fileRef")
+                .map(|file| file.trim_end_matches(".cer").to_string())
+        });
+
+        if alert.is_none() && errors.is_empty() && subject.is_none() && thumbprint.is_none() {
+            return None;
+        }
+        Some(Self {
+            alert,
+            errors,
+            subject,
+            thumbprint,
+        })
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            String::new(),
+            "--- TLS/Certificate Diagnosis ---".to_string(),
+        ];
+        if let Some(alert) = self.alert {
+            lines.push(
+                match TLS_ALERTS.iter().find(|(code, _, _)| *code == alert) {
+                    Some((_, name, cause)) => format!("Alert:       {} {}: {}", alert, name, cause),
+                    None => format!("Alert:       {} (not a standard TLS alert)", alert),
+                },
+            );
+        }
+        for error in &self.errors {
+            if let Some((_, name, cause)) =
+                CERTIFICATE_ERRORS.iter().find(|(code, _, _)| code == error)
+            {
+                lines.push(format!("Error:       0x{:08X} {}: {}", error, name, cause));
+            }
+        }
+        if let Some(subject) = &self.subject {
+            lines.push(format!("Subject:     {}", subject));
+        }
+        if let Some(thumbprint) = &self.thumbprint {
+            lines.push(format!("Thumbprint:  {}", thumbprint));
+        }
+        lines
+    }
+}
+
+/// Returns the lines decoding an event this module understands, or none.
+pub fn decoded_lines(event: &DisplayEvent) -> Vec<String> {
+    if let Some(decision) = AppControlDecision::decode(event) {
+        return decision.lines();
+    }
+    if let Some(lines) = security::summary_lines(event) {
+        return lines;
+    }
+    if let Some(change) = EncryptionChange::decode(event) {
+        return change.lines();
+    }
+    if let Some(failure) = ServiceFailure::decode(event) {
+        return failure.lines();
+    }
+    TlsDiagnosis::decode(event)
+        .map(|diagnosis| diagnosis.lines())
+        .unwrap_or_default()
+}
+
+/// Returns the value of the first attribute named `name` on any element of the event XML.
+fn xml_attribute(xml: &str, name: &str) -> Option<String> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                let value = e
+                    .attributes()
+                    .flatten()
+                    .find(|attr| attr.key.local_name().as_ref() == name.as_bytes())
+                    .and_then(|attr| attr.unescape_value().ok())
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty());
+                if value.is_some() {
+                    return value;
+                }
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => {}
+        }
+    }
+}
@@ -1,32 +1,64 @@
+use crate::analysis::{self, AppControlDecision};
+use crate::auditpol::AuditPolicyView;
+use crate::bookmarks::{self, Bookmark};
+use crate::catalog;
+use crate::cli::{Cli, SortOrder};
+use crate::clipboard;
+use crate::compare::{Comparison, ComparisonPreset};
+use crate::config::Config;
+use crate::detail;
+use crate::elevation;
+use crate::helpers;
+use crate::history::{self, FilterHistory};
+use crate::hosts::{self, HostInventory, HostPicker};
+use crate::instance::{AttachRequest, InstanceLock, InstanceRole};
 use crate::models::{
-    AppState, DisplayEvent, EventLevelFilter, FilterCriteria, FilterFieldFocus, LOG_NAMES,
-    PanelFocus, PreviewViewMode, StatusDialog, TimeFilterOption,
+    AppState, ChannelAccess, DisplayEvent, EventLevelFilter, FilterCriteria, FilterFieldFocus,
+    Incident, IncidentMarker, KeywordFilter, MAX_SPLIT_PERCENT, MERGED_TAB_NAME, MIN_SPLIT_PERCENT,
+    PREVIEW_HSCROLL_STEP, PanelFocus, PendingAction, PreviewViewMode, PromptKind,
+    SPLIT_STEP_PERCENT, SortKey, StatusDialog, TimeFilterOption,
 };
-use chrono::Local;
+use crate::notify;
+use crate::otlp::{OtlpConfig, OtlpExport};
+use crate::redact::{PreparedExport, Redactor};
+use crate::services;
+use crate::session::{self, Session};
+use crate::siem;
+use crate::sigma::{self, SigmaView};
+use crate::source::{self, EventSource, PlatformSource};
+use crate::split::{self, SplitPane};
+use crate::theme::Theme;
+use crate::triggers::TriggerRunner;
+use crate::view::ViewState;
+use crate::weblogs;
+use chrono::{DateTime, Duration, Local, Utc};
 use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::TableState;
 use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::io::{BufWriter, Write};
-use std::path::Path;
-
-#[cfg(target_os = "windows")]
-use windows::Win32::System::EventLog::EvtClose;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 impl AppState {
-    /// Creates a new instance of AppState with default values.
+    /// Creates a new instance of AppState reading the platform's event logs, with the
+    /// configuration, log file and saved state of the working directory.
     pub fn new() -> Self {
-        let initial_log_name = LOG_NAMES[0].to_string();
+        let (config, config_error) = match Config::load() {
+            Ok(config) => (config, None),
+            Err(e) => (Config::default(), Some(e)),
+        };
 
-        let log_file_path = Path::new("event_commander.log");
+        let instance = InstanceLock::acquire();
+        let log_file_path = instance.log_file_path();
         let log_file_result = OpenOptions::new()
             .create(true)
             .append(true)
-            .open(log_file_path);
+            .open(&log_file_path);
 
-        let log_file = match log_file_result {
-            Ok(file) => Some(BufWriter::new(file)),
+        let log_sink: Option<Box<dyn Write>> = match log_file_result {
+            Ok(file) => Some(Box::new(BufWriter::new(file))),
             Err(e) => {
                 eprintln!(
                     "Failed to open or create log file '{}': {}. Logging disabled.",
@@ -37,47 +69,245 @@ impl AppState {
             }
         };
 
-        let app_state = AppState {
+        let mut app_state = Self::with_source(
+            Box::new(PlatformSource::default()),
+            config,
+            instance,
+            log_sink,
+        );
+        // A secondary instance leaves the state files to the primary, which would otherwise
+        // lose its changes to whichever instance exits last.
+        let primary = app_state.instance.role() == InstanceRole::Primary;
+        app_state.persist_config = primary && config_error.is_none();
+        app_state.persist_bookmarks = primary;
+
+        if let Some(e) = config_error {
+            app_state.log(&format!("Configuration not loaded: {}", e));
+            app_state.show_error(
+                "Configuration Error",
+                &format!(
+                    "{}\n\nDefaults are in use and the file will not be overwritten.",
+                    e
+                ),
+            );
+        }
+
+        match bookmarks::load() {
+            Ok(saved) => app_state.bookmarks = saved,
+            Err(e) => app_state.log(&format!("Bookmarks not loaded: {}", e)),
+        }
+
+        match hosts::load() {
+            Ok(inventory) => app_state.hosts = inventory,
+            Err(e) => app_state.log(&format!("Host inventory not loaded: {}", e)),
+        }
+
+        match catalog::load_shared(app_state.config.message_catalog_url.as_deref()) {
+            Ok(shared) => app_state.shared_catalog = shared,
+            Err(e) => app_state.log(&format!("Shared message catalog not loaded: {}", e)),
+        }
+        app_state.load_sigma_rules();
+        #[cfg(target_os = "windows")]
+        {
+            app_state.hosts.saved_credentials =
+                crate::event_api::credential_targets(hosts::SAVED_CREDENTIAL_PREFIX);
+        }
+
+        if let InstanceRole::Secondary { primary_pid } = app_state.instance.role() {
+            let mut dialog = StatusDialog::new(
+                "Already Running",
+                &format!(
+                    "Event Commander is already running ({}).\n\nThis instance logs to {} and does not save settings or bookmarks.\n\n[a] Attach: open the current log in the running instance and exit\n[Enter] Continue separately",
+                    primary_pid.map_or("PID unknown".to_string(), |pid| format!("PID {}", pid)),
+                    app_state.instance.log_file_path().display()
+                ),
+                false,
+            );
+            dialog.offers_attach = true;
+            app_state.status_dialog = Some(dialog);
+        }
+
+        app_state
+    }
+
+    /// Creates a new instance of AppState reading its log tabs from `source`, without touching
+    /// the file system: the configuration, instance lock and log sink are passed in, and
+    /// nothing is saved until `persist_config` is set.
+    pub fn with_source(
+        source: Box<dyn EventSource>,
+        config: Config,
+        instance: InstanceLock,
+        log_sink: Option<Box<dyn Write>>,
+    ) -> Self {
+        let theme = Theme::resolve(&config.theme, &config.themes);
+        let channels = source.channels();
+        let initial_log_index = channels
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(&config.default_log))
+            .unwrap_or(0);
+        let initial_log_name = channels.get(initial_log_index).cloned().unwrap_or_default();
+
+        let mut app_state = AppState {
             focus: PanelFocus::Events,
-            selected_log_index: 0,
+            selected_log_index: initial_log_index,
             selected_log_name: initial_log_name,
+            host_name: helpers::local_host_name(),
             events: Vec::new(),
             table_state: TableState::default().with_selected(Some(0)),
             preview_scroll: 0,
+            preview_hscroll: 0,
+            preview_max_scroll: 0,
             status_dialog: None,
             preview_event_id: None,
             preview_content: None,
             preview_raw_xml: None,
             preview_view_mode: PreviewViewMode::default(),
-            log_file,
+            log_sink,
+            instance,
+            sort_descending: config.sort_descending,
+            auto_refresh: config.auto_refresh,
+            sort_key: config.sort_key,
+            active_filter: config.last_filter.clone().filter(|f| !f.is_empty()),
+            persist_config: false,
+            persist_bookmarks: false,
+            export_format: config.export_format,
+            theme: theme.unwrap_or_default(),
+            retention: None,
+            retention_warning_key: None,
+            config,
+            channel_access: vec![ChannelAccess::default(); channels.len()],
+            source,
+            channels,
             #[cfg(target_os = "windows")]
-            query_handle: None,
+            merged_queries: Vec::new(),
             #[cfg(target_os = "windows")]
-            publisher_metadata_cache: HashMap::new(),
+            deferred_events: HashMap::new(),
             is_loading: false,
+            load_progress: None,
+            load_tuning: HashMap::new(),
+            unloaded_before: None,
             no_more_events: false,
-            sort_descending: true,
-            active_filter: None,
             is_searching: false,
             search_term: String::new(),
             last_search_term: None,
+            search_origin: None,
+            search_previous_term: None,
             is_filter_dialog_visible: false,
             filter_dialog_focus: FilterFieldFocus::Source,
             filter_dialog_source_index: 0,
             filter_dialog_event_id: String::new(),
             filter_dialog_level: EventLevelFilter::default(),
             filter_dialog_time: TimeFilterOption::default(),
+            filter_dialog_keywords: KeywordFilter::default(),
+            filter_dialog_task: String::new(),
+            filter_dialog_opcode: String::new(),
+            filter_dialog_user: String::new(),
+            filter_dialog_computer: String::new(),
+            filter_dialog_excluded_sources: String::new(),
+            filter_dialog_recent: None,
+            filter_history: FilterHistory::default(),
             available_sources: None,
             filter_dialog_source_input: String::new(),
             filter_dialog_filtered_sources: Vec::new(),
             filter_dialog_filtered_source_selection: None,
             filter_event_id_cursor: 0,
             filter_source_cursor: 0,
+            filter_task_cursor: 0,
+            filter_opcode_cursor: 0,
+            filter_user_cursor: 0,
+            filter_computer_cursor: 0,
+            filter_excluded_sources_cursor: 0,
             search_cursor: 0,
+            local_filter: String::new(),
+            local_filter_cursor: 0,
+            is_local_filter_input: false,
+            local_filter_indices: None,
+            deep_search: None,
+            is_query_input_visible: false,
+            query_input: String::new(),
+            query_cursor: 0,
+            raw_query: None,
+            incident: None,
+            prompt: None,
+            prompt_input: String::new(),
+            prompt_cursor: 0,
+            pending_record_id: None,
+            pending_preview_scroll: 0,
+            is_elevated: elevation::is_elevated(),
+            elevation_prompt: None,
+            message_catalog: None,
+            shared_catalog: None,
+            #[cfg(target_os = "windows")]
+            publisher_templates: HashMap::new(),
             help_dialog_visible: false,
+            is_column_dialog_visible: false,
+            bookmarks: Vec::new(),
+            pending_action: None,
+            shutdown_analysis_cache: HashMap::new(),
+            detail_fields: None,
+            detail_selection: 0,
+            #[cfg(target_os = "windows")]
+            dns_analysis: None,
+            #[cfg(target_os = "windows")]
+            hyperv_view: None,
+            #[cfg(target_os = "windows")]
+            installer_view: None,
+            #[cfg(target_os = "windows")]
+            print_view: None,
+            #[cfg(target_os = "windows")]
+            device_view: None,
+            #[cfg(target_os = "windows")]
+            servicing_view: None,
+            audit_policy: None,
+            #[cfg(target_os = "windows")]
+            auth_flow: None,
+            #[cfg(target_os = "windows")]
+            object_access: None,
+            hosts: HostInventory::default(),
+            host_status: HashMap::new(),
+            host_picker: None,
+            #[cfg(target_os = "windows")]
+            wmi_analysis: None,
+            sigma_rules: Vec::new(),
+            sigma_skipped: 0,
+            sigma_view: None,
+            sigma_filter: None,
+            comparison: None,
+            split_pane: None,
+            key_count: None,
+            pending_mark: None,
+            marks: HashMap::new(),
+            events_visible_rows: 0,
+            trigger_runner: TriggerRunner::default(),
+            otlp_export: None,
+            footer_notice: None,
+            context_menu_selection: None,
+            admin_menu_selection: None,
+            clear_log_backup: None,
+            cluster_nodes: None,
+            histogram_buckets: 0,
+            web_logs_merged: false,
+            last_refresh: Instant::now(),
+            is_bookmarks_dialog_visible: false,
+            bookmarks_selection: 0,
+            column_dialog_selection: 0,
             help_scroll_position: 0,
         };
 
+        if theme.is_none() {
+            let message = format!(
+                "Unknown theme '{}' in configuration; using classic-blue.",
+                app_state.config.theme
+            );
+            app_state.log(&message);
+        }
+
+        let (trigger_runner, ignored_triggers) = TriggerRunner::load(&app_state.config.triggers);
+        app_state.trigger_runner = trigger_runner;
+        for line in ignored_triggers {
+            app_state.log(&line);
+        }
+        app_state.refresh_channel_access();
         app_state
     }
 
@@ -85,10 +315,10 @@ impl AppState {
     pub fn log(&mut self, message: &str) {
         let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S");
         let log_entry = format!("[{}]: {}\n", timestamp, message);
-        if let Some(ref mut writer) = self.log_file {
-            if let Err(e) = writer.write_all(log_entry.as_bytes()) {
-                eprintln!("Error writing to log file: {}", e);
-            }
+        if let Some(ref mut writer) = self.log_sink
+            && let Err(e) = writer.write_all(log_entry.as_bytes())
+        {
+            eprintln!("Error writing to log file: {}", e);
         }
     }
 
@@ -97,6 +327,1120 @@ impl AppState {
         self.status_dialog = Some(StatusDialog::new(title, message, true));
     }
 
+    /// Explains why a log cannot be read. When the Security log is denied to a process that
+    /// is not elevated, the elevation dialog offers to relaunch as administrator instead.
+    pub fn show_access_error(&mut self, log_name: &str, access: ChannelAccess) {
+        if access == ChannelAccess::Denied
+            && log_name.eq_ignore_ascii_case("Security")
+            && !self.is_elevated
+        {
+            self.elevation_prompt = Some(log_name.to_string());
+        } else {
+            self.show_error("Log Not Accessible", &access.explanation(log_name));
+        }
+    }
+
+    /// Turns auto-refresh on or off; the first refresh follows one interval later.
+    pub fn toggle_auto_refresh(&mut self) {
+        self.auto_refresh = !self.auto_refresh;
+        self.last_refresh = Instant::now();
+    }
+
+    /// Whether auto-refresh is on and its interval has passed.
+    pub fn auto_refresh_due(&self) -> bool {
+        self.auto_refresh
+            && self.last_refresh.elapsed().as_secs() >= self.config.auto_refresh_secs.max(1)
+    }
+
+    /// Writes the current preferences to the configuration file, unless it failed to load.
+    pub fn save_config(&mut self) {
+        if !self.persist_config {
+            return;
+        }
+        self.config.sort_descending = self.sort_descending;
+        self.config.sort_key = self.sort_key;
+        self.config.export_format = self.export_format;
+        self.config.auto_refresh = self.auto_refresh;
+        if let Err(e) = self.config.save() {
+            self.log(&format!("Failed to save configuration: {}", e));
+        }
+    }
+
+    /// Warns once per log and time range when the active time filter reaches further back
+    /// than the oldest event an overwriting log still holds.
+    pub fn check_retention_horizon(&mut self) {
+        let Some(retention) = self.retention.as_ref().filter(|r| r.is_losing_events()) else {
+            return;
+        };
+        let (Some(oldest), Some(horizon)) = (retention.oldest_event, retention.horizon()) else {
+            return;
+        };
+        let Some(time_filter) = self.active_filter.as_ref().map(|f| f.time_filter) else {
+            return;
+        };
+        if time_filter
+            .get_start_time()
+            .is_none_or(|start| start >= oldest)
+        {
+            return;
+        }
+        let warning_key = format!("{}|{}", self.selected_log_name, time_filter.display_name());
+        if self.retention_warning_key.as_deref() == Some(warning_key.as_str()) {
+            return;
+        }
+        let message = format!(
+            "'{}' overwrites old events when full and only holds about {} of history ({} records, max {} MB).\n\nEvents before {} are already gone, so the '{}' range is incomplete.\n\nTo keep older events, archive the log regularly (wevtutil epl {} <file>.evtx), enable 'Archive the log when full', or increase its maximum size.",
+            self.selected_log_name,
+            helpers::format_duration(horizon),
+            retention.record_count,
+            retention.max_size / (1024 * 1024),
+            oldest.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+            time_filter.display_name(),
+            self.selected_log_name
+        );
+        self.retention_warning_key = Some(warning_key);
+        self.show_confirmation("Older Events Overwritten", &message);
+    }
+
+    /// Orders the loaded events by the active sort key, keeping the selected event selected.
+    pub fn sort_events(&mut self) {
+        let selected_raw = self.selected_event().map(|event| event.raw_data.clone());
+        if self.sort_key != SortKey::DateTime {
+            let (sort_key, descending) = (self.sort_key, self.sort_descending);
+            self.events.sort_by(|a, b| {
+                let ordering = sort_key.compare(a, b);
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+        self.rebuild_local_filter();
+        if let Some(row) = selected_raw
+            .and_then(|raw| self.events.iter().position(|e| e.raw_data == raw))
+            .and_then(|idx| self.row_of_event(idx))
+        {
+            self.table_state.select(Some(row));
+        }
+    }
+
+    /// Drops the loaded events and the selection, e.g. before reloading the log.
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+        self.unloaded_before = None;
+        #[cfg(target_os = "windows")]
+        self.deferred_events.clear();
+        self.web_logs_merged = false;
+        self.table_state = TableState::default();
+        self.rebuild_local_filter();
+    }
+
+    /// Number of rows in the event list, after the local filter.
+    pub fn row_count(&self) -> usize {
+        self.local_filter_indices
+            .as_ref()
+            .map_or(self.events.len(), |indices| indices.len())
+    }
+
+    /// Maps an event list row to its index in `events`.
+    pub fn event_index(&self, row: usize) -> Option<usize> {
+        match &self.local_filter_indices {
+            Some(indices) => indices.get(row).copied(),
+            None => (row < self.events.len()).then_some(row),
+        }
+    }
+
+    /// Maps an index in `events` to its event list row, if the local filter shows it.
+    pub fn row_of_event(&self, index: usize) -> Option<usize> {
+        match &self.local_filter_indices {
+            Some(indices) => indices.binary_search(&index).ok(),
+            None => (index < self.events.len()).then_some(index),
+        }
+    }
+
+    /// Returns the event shown in the given event list row.
+    pub fn event_at_row(&self, row: usize) -> Option<&DisplayEvent> {
+        self.event_index(row)
+            .and_then(|index| self.events.get(index))
+    }
+
+    /// Recomputes the rows shown for the local filter after the loaded events changed.
+    pub fn rebuild_local_filter(&mut self) {
+        let term_lower = self.local_filter.to_lowercase();
+        self.local_filter_indices = if term_lower.is_empty() && self.sigma_filter.is_none() {
+            None
+        } else {
+            Some(
+                self.events
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, event)| {
+                        (term_lower.is_empty() || self.event_matches_search(event, &term_lower))
+                            && self.event_matches_sigma_filter(event)
+                    })
+                    .map(|(index, _)| index)
+                    .collect(),
+            )
+        };
+    }
+
+    /// Narrows the event list to the loaded events containing `local_filter`, keeping the
+    /// selected event selected when it is still shown.
+    pub fn apply_local_filter(&mut self) {
+        let selected_index = self
+            .table_state
+            .selected()
+            .and_then(|row| self.event_index(row));
+        self.rebuild_local_filter();
+        let row = selected_index.and_then(|index| self.row_of_event(index));
+        let row = row.or((self.row_count() > 0).then_some(0));
+        self.table_state.select(row);
+        self.update_preview_for_selection();
+    }
+
+    /// Narrows the event list to the loaded events mentioning the binary the selected
+    /// AppLocker or WDAC event audited or blocked.
+    pub fn filter_on_blocked_binary(&mut self) -> Result<(), String> {
+        let decision = self
+            .selected_event()
+            .and_then(AppControlDecision::decode)
+            .ok_or("The selected event is not an AppLocker or WDAC block or audit event.")?;
+        self.local_filter = decision.file_name().to_string();
+        self.local_filter_cursor = self.local_filter.chars().count();
+        self.apply_local_filter();
+        Ok(())
+    }
+
+    /// Adds the selected event's source to the active filter.
+    pub fn filter_on_source(&mut self) -> Result<(), String> {
+        self.narrow_filter(|filter, event| {
+            filter.source = Some(event.provider_name_original.clone());
+        })
+    }
+
+    /// Adds the selected event's source to the sources the active filter hides.
+    pub fn exclude_source(&mut self) -> Result<(), String> {
+        let source = self
+            .selected_event()
+            .ok_or("Please select an event first.")?
+            .provider_name_original
+            .clone();
+        let mut filter = self.active_filter.clone().unwrap_or_default();
+        if filter.source.as_ref() == Some(&source) {
+            return Err(format!(
+                "The filter shows only '{}'; clear the source filter instead.",
+                source
+            ));
+        }
+        if !filter.excluded_sources.contains(&source) {
+            filter.excluded_sources.push(source);
+        }
+        self.active_filter = Some(filter);
+        self.raw_query = None;
+        Ok(())
+    }
+
+    /// Replaces the Event ID condition of the active filter with the selected event's ID.
+    pub fn filter_on_event_id(&mut self) -> Result<(), String> {
+        self.narrow_filter(|filter, event| filter.event_id = Some(event.event_id.to_string()))
+    }
+
+    /// Updates the active filter, or a new one, from the selected event, dropping any XPath
+    /// query. The event stays selected after the reload.
+    fn narrow_filter(
+        &mut self,
+        update: impl FnOnce(&mut FilterCriteria, &DisplayEvent),
+    ) -> Result<(), String> {
+        let event = self
+            .selected_event()
+            .ok_or("Please select an event first.")?;
+        let mut filter = self.active_filter.clone().unwrap_or_default();
+        update(&mut filter, event);
+        self.pending_record_id = Some(event.record_id);
+        self.active_filter = Some(filter);
+        self.raw_query = None;
+        Ok(())
+    }
+
+    /// Scopes the log to the events of the selected event's activity, and of the activities
+    /// it started, with an XPath query over the whole channel.
+    pub fn show_related_activity(&mut self) -> Result<(), String> {
+        let event = self
+            .selected_event()
+            .ok_or("Please select an event first.")?;
+        let activity_id = event
+            .activity_id
+            .clone()
+            .or_else(|| event.related_activity_id.clone())
+            .filter(|id| !id.contains(['\'', '"']))
+            .ok_or("The selected event carries no activity ID.")?;
+        self.pending_record_id = Some(event.record_id);
+        self.raw_query = Some(format!(
+            "*[System[Correlation[@ActivityID='{0}' or @RelatedActivityID='{0}']]]",
+            activity_id
+        ));
+        Ok(())
+    }
+
+    /// Scopes the log to the events logged by the selected event's process, with an XPath
+    /// query over the whole channel. Process IDs are reused, so the list can include events
+    /// of other processes from before or after it ran.
+    pub fn filter_on_process(&mut self) -> Result<(), String> {
+        let event = self
+            .selected_event()
+            .ok_or("Please select an event first.")?;
+        let process_id = event
+            .process_id
+            .filter(|&id| id != 0)
+            .ok_or("The selected event does not record the process that logged it.")?;
+        self.pending_record_id = Some(event.record_id);
+        self.raw_query = Some(format!("*[System[Execution[@ProcessID={}]]]", process_id));
+        Ok(())
+    }
+
+    /// Scopes the log to the Service Control Manager events about the selected event's
+    /// service: its starts, stops, configuration changes and failures.
+    pub fn show_service_timeline(&mut self) -> Result<(), String> {
+        let event = self
+            .selected_event()
+            .ok_or("Please select an event first.")?;
+        let service = services::service_name(event)
+            .filter(|service| !service.contains(['\'', '"']))
+            .ok_or("The selected event is not a Service Control Manager event.")?;
+        self.pending_record_id = Some(event.record_id);
+        self.raw_query = Some(format!(
+            "*[System[Provider[@Name='{}']] and EventData[Data='{}']]",
+            services::SCM_PROVIDER,
+            service
+        ));
+        Ok(())
+    }
+
+    /// Removes the local filter and shows every loaded event again.
+    pub fn clear_local_filter(&mut self) {
+        self.local_filter.clear();
+        self.local_filter_cursor = 0;
+        self.sigma_filter = None;
+        self.apply_local_filter();
+    }
+
+    /// Reads the Sigma rules folder when it exists, logging the rule files left out.
+    pub fn load_sigma_rules(&mut self) {
+        let configured = self.config.sigma_rules_dir.clone();
+        let Some(dir) = sigma::rules_dir(configured.as_deref()) else {
+            return;
+        };
+        if !dir.is_dir() {
+            if configured.is_some() {
+                self.log(&format!("Sigma rules folder {} not found", dir.display()));
+            }
+            return;
+        }
+        let (rules, skipped) = sigma::load_rules(&dir);
+        for reason in &skipped {
+            self.log(&format!("Sigma rule skipped: {}", reason));
+        }
+        self.log(&format!(
+            "Loaded {} Sigma rules from {} ({} skipped)",
+            rules.len(),
+            dir.display(),
+            skipped.len()
+        ));
+        self.sigma_rules = rules;
+        self.sigma_skipped = skipped.len();
+    }
+
+    /// Records the Sigma rules an event matches, before it is added to the event list.
+    pub fn match_sigma_rules(&self, event: &mut DisplayEvent) {
+        if !self.sigma_rules.is_empty() {
+            event.sigma_hits = sigma::matching_titles(&self.sigma_rules, event);
+        }
+    }
+
+    /// Fires the triggers matched by events auto-refresh brought in, and notes the firings
+    /// and the failures reported since the last refresh in the log and the footer.
+    pub fn run_triggers(&mut self, events: &[DisplayEvent]) {
+        let mut notices = self.trigger_runner.failures();
+        notices.extend(self.trigger_runner.run(events));
+        for notice in notices {
+            self.log(&notice);
+            self.footer_notice = Some((notice, Instant::now()));
+        }
+    }
+
+    /// Starts sending the loaded events to the OTLP endpoint on a worker thread; the outcome is
+    /// shown once `continue_otlp_export` sees it finish.
+    pub fn start_otlp_export(&mut self) -> Result<(), String> {
+        if self.otlp_export.is_some() {
+            return Err("The previous OTLP export is still being sent.".to_string());
+        }
+        let config = OtlpConfig::resolve(&self.config)?;
+        if self.events.is_empty() {
+            return Err("No events loaded to export.".to_string());
+        }
+        let export = self.prepare_export(self.events.iter().collect())?;
+        self.footer_notice = Some((
+            format!(
+                "Sending {} log records to {}...",
+                export.events.len(),
+                config.endpoint
+            ),
+            Instant::now(),
+        ));
+        self.otlp_export = Some(OtlpExport::start(
+            config,
+            export,
+            self.selected_log_name.clone(),
+        ));
+        Ok(())
+    }
+
+    /// Shows the outcome of the OTLP export once its thread is done.
+    pub fn continue_otlp_export(&mut self) {
+        let Some(result) = self.otlp_export.as_ref().and_then(OtlpExport::finished) else {
+            return;
+        };
+        self.otlp_export = None;
+        match result {
+            Ok(message) => self.show_confirmation("OTLP Export Complete", &message),
+            Err(msg) => self.show_error("OTLP Export Failed", &msg),
+        }
+    }
+
+    /// Alerts about the error and critical events auto-refresh brought into a log listed in
+    /// `notify_channels`.
+    pub fn notify_new_errors(&mut self, events: &[DisplayEvent]) {
+        let watched = self
+            .config
+            .notify_channels
+            .iter()
+            .any(|channel| channel.eq_ignore_ascii_case(&self.selected_log_name));
+        if !watched {
+            return;
+        }
+        let Some(summary) = notify::error_summary(events, &self.selected_log_name) else {
+            return;
+        };
+        notify::alert("Event Commander", &summary, self.config.notify_toast);
+        self.log(&summary);
+        self.footer_notice = Some((summary, Instant::now()));
+    }
+
+    /// Whether an event is kept by `sigma_filter`.
+    fn event_matches_sigma_filter(&self, event: &DisplayEvent) -> bool {
+        match self.sigma_filter.as_deref() {
+            None => true,
+            Some("") => !event.sigma_hits.is_empty(),
+            Some(title) => event.sigma_hits.iter().any(|hit| hit == title),
+        }
+    }
+
+    /// Opens the Sigma hits panel on the first rule.
+    pub fn open_sigma_view(&mut self) {
+        self.sigma_view = Some(SigmaView::default());
+    }
+
+    /// Splits the event list into two panes, the new one showing the current log and events
+    /// until it is focused, or closes the pane beside the focused one.
+    pub fn toggle_split(&mut self) {
+        if self.split_pane.take().is_none() {
+            self.split_pane = Some(SplitPane {
+                log_index: self.selected_log_index,
+                log_name: self.selected_log_name.clone(),
+                filter: self.active_filter.clone(),
+                raw_query: self.raw_query.clone(),
+                events: self.events.clone(),
+                selection: self.selected_event_index(),
+                on_left: false,
+            });
+        }
+    }
+
+    /// Index in `events` of the selected event.
+    fn selected_event_index(&self) -> Option<usize> {
+        let selected = self.selected_event()?;
+        self.events
+            .iter()
+            .position(|event| std::ptr::eq(event, selected))
+    }
+
+    /// Moves the other pane's selection to the event logged closest to the selected one, and
+    /// reads its log around that time first when its events do not reach it.
+    pub fn sync_split_pane_time(&mut self) -> Result<(), String> {
+        let time = self
+            .selected_event()
+            .ok_or("Please select an event first.")?
+            .timestamp
+            .ok_or("The selected event's time is unknown.")?;
+        let mut pane = self
+            .split_pane
+            .take()
+            .ok_or("Split the event list with [|] to have a second pane to synchronize.")?;
+        let loaded = if pane.covers(time) || pane.raw_query.is_some() {
+            Ok(())
+        } else {
+            let mut source = self.source.fork();
+            let events = split::load_around(source.as_mut(), &pane.log_name, time, |start, end| {
+                source::window_query(pane.filter.as_ref(), start, end)
+            });
+            events.and_then(|mut events| {
+                if events.is_empty() {
+                    return Err(format!(
+                        "'{}' has no events within {} minutes of {}.",
+                        pane.log_name,
+                        split::SYNC_WINDOW_MINUTES,
+                        helpers::format_local_time(time)
+                    ));
+                }
+                if !self.sort_descending {
+                    events.reverse();
+                }
+                pane.events = events;
+                Ok(())
+            })
+        };
+        if loaded.is_ok() {
+            pane.selection = pane.nearest(time);
+        }
+        self.split_pane = Some(pane);
+        loaded
+    }
+
+    /// Moves focus to the other pane of split view. The focused pane keeps its events for
+    /// display and the other one is reloaded with its selection. Returns true when the log
+    /// needs reloading.
+    pub fn switch_split_pane(&mut self) -> bool {
+        let Some(pane) = self.split_pane.take() else {
+            return false;
+        };
+        self.pending_record_id = pane
+            .selection
+            .and_then(|index| pane.events.get(index))
+            .map(|event| event.record_id);
+        let selection = self.selected_event_index();
+        self.split_pane = Some(SplitPane {
+            log_index: self.selected_log_index,
+            log_name: std::mem::take(&mut self.selected_log_name),
+            filter: self.active_filter.take(),
+            raw_query: self.raw_query.take(),
+            events: std::mem::take(&mut self.events),
+            selection,
+            on_left: !pane.on_left,
+        });
+        self.selected_log_index = pane.log_index;
+        self.selected_log_name = pane.log_name;
+        self.active_filter = pane.filter;
+        self.raw_query = pane.raw_query;
+        self.focus = PanelFocus::Events;
+        true
+    }
+
+    /// Counts the events matching the current filter in the preset's two windows of the
+    /// selected log and opens the comparison.
+    pub fn open_comparison(&mut self, preset: ComparisonPreset) {
+        let selected = self.selected_event().and_then(|event| event.timestamp);
+        let mut source = self.source.fork();
+        let comparison = Comparison::run(
+            source.as_mut(),
+            preset,
+            &self.selected_log_name,
+            selected,
+            |start, end| source::window_query(self.active_filter.as_ref(), start, end),
+        );
+        self.comparison = Some(comparison);
+    }
+
+    /// Narrows the event list to the events matching the rule chosen in the hits panel, or to
+    /// those matching any rule for its first row, and closes the panel.
+    pub fn filter_on_sigma_selection(&mut self) {
+        let Some(view) = self.sigma_view.take() else {
+            return;
+        };
+        let hits = sigma::rule_hits(&self.sigma_rules, &self.events);
+        let title = match view.selection.checked_sub(1) {
+            Some(index) => match hits.get(index) {
+                Some(hit) => hit.title.clone(),
+                None => return,
+            },
+            None => String::new(),
+        };
+        self.sigma_filter = Some(title);
+        self.apply_local_filter();
+    }
+
+    /// Moves the divider between the event list and the preview by `steps` increments,
+    /// growing the event list for positive values.
+    pub fn resize_split(&mut self, steps: i16) {
+        let percent = self.config.split_percent as i16 + steps * SPLIT_STEP_PERCENT as i16;
+        self.config.split_percent =
+            percent.clamp(MIN_SPLIT_PERCENT as i16, MAX_SPLIT_PERCENT as i16) as u16;
+    }
+
+    /// Switches to the next built-in or user-defined theme.
+    pub fn cycle_theme(&mut self) {
+        let next_name = Theme::next_name(&self.config.theme, &self.config.themes);
+        if let Some(theme) = Theme::resolve(&next_name, &self.config.themes) {
+            self.theme = theme;
+            self.config.theme = next_name;
+        }
+    }
+
+    /// Writes the loaded events to a file in the selected export format, one record per line.
+    pub fn export_loaded_events(
+        &self,
+        path: PathBuf,
+    ) -> Result<(PathBuf, usize, Option<String>), String> {
+        if self.events.is_empty() {
+            return Err("No events loaded to export.".to_string());
+        }
+        self.write_export(path, self.events.iter().collect())
+    }
+
+    /// Writes every event matching the last search term to a file in the selected export
+    /// format. With `scan_whole_log`, the rest of the log is loaded first so matches beyond the
+    /// loaded batches are included.
+    pub fn export_search_matches(
+        &mut self,
+        scan_whole_log: bool,
+        path: PathBuf,
+    ) -> Result<(PathBuf, usize, Option<String>), String> {
+        let Some(term) = self.last_search_term.clone() else {
+            return Err("No search term. Press '/' to search first.".to_string());
+        };
+        if scan_whole_log {
+            self.load_remaining_events();
+        }
+        let matches = self.search_matches();
+        if matches.is_empty() {
+            return Err(format!("Search term '{}' not found.", term));
+        }
+        self.write_export(
+            path,
+            matches
+                .iter()
+                .filter_map(|&row| self.event_at_row(row))
+                .collect(),
+        )
+    }
+
+    /// Builds the redactor for exporting `events`, or `None` when redaction is off.
+    pub fn export_redactor(&self, events: &[&DisplayEvent]) -> Result<Option<Redactor>, String> {
+        if !self.config.redact_exports {
+            return Ok(None);
+        }
+        Redactor::new(&self.config.redaction_rules, events, &self.host_name).map(Some)
+    }
+
+    /// Copies `events`, each with the log it was read from as its channel, and the incident
+    /// markers for export, redacted when redaction is on.
+    pub fn prepare_export(&self, events: Vec<&DisplayEvent>) -> Result<PreparedExport, String> {
+        let redactor = self.export_redactor(&events)?;
+        let markers = self
+            .incident
+            .as_ref()
+            .map_or(&[][..], |incident| &incident.markers);
+        // Records name the log each event was read from, not the merged tab.
+        let channels: Vec<String> = events
+            .iter()
+            .map(|event| self.event_channel(event).to_string())
+            .collect();
+        let mut export = PreparedExport::new(events, markers, &self.host_name, redactor);
+        for (event, channel) in export.events.iter_mut().zip(channels) {
+            event.channel = channel;
+        }
+        Ok(export)
+    }
+
+    /// Returns a timestamped export file name for the current log and export format.
+    pub fn export_path(&self, suffix: &str) -> PathBuf {
+        PathBuf::from(format!(
+            "{}{}-{}.{}",
+            helpers::sanitize_filename(&self.selected_log_name),
+            suffix,
+            Local::now().format("%Y%m%d-%H%M%S"),
+            self.export_format.extension()
+        ))
+    }
+
+    /// Describes what `action` would overwrite and what replaces it, or `None` when its file
+    /// does not exist yet and it can run without asking.
+    pub fn overwrite_preview(&self, action: &PendingAction) -> Option<String> {
+        let path = action.path();
+        let existing = std::fs::metadata(path).ok()?;
+        let modified = existing
+            .modified()
+            .map(|time| {
+                chrono::DateTime::<Local>::from(time)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|_| "unknown".to_string());
+        let format = self.export_format.display_name();
+        let replacement = match action {
+            PendingAction::ExportEvents(_) => {
+                format!("{} loaded events as {}", self.events.len(), format)
+            }
+            PendingAction::ExportMatches { scan_whole_log, .. } => {
+                let term = self.last_search_term.as_deref().unwrap_or_default();
+                let count = self.search_matches().len();
+                if *scan_whole_log && !self.no_more_events {
+                    format!(
+                        "{} loaded events matching '{}' as {}, plus any matches in the rest of the log",
+                        count, term, format
+                    )
+                } else {
+                    format!("{} events matching '{}' as {}", count, term, format)
+                }
+            }
+            PendingAction::ExportCatalog(_) => match &self.message_catalog {
+                Some(catalog) => format!(
+                    "its {} templates plus those still missing for the loaded events",
+                    catalog.template_count()
+                ),
+                None => "templates for the loaded events only. Its current contents could not be read and will be lost".to_string(),
+            },
+            PendingAction::SaveEvent(_) => match self.selected_event() {
+                Some(event) => format!("event {} from {}", event.event_id, event.local_time()),
+                None => "the selected event".to_string(),
+            },
+        };
+        let mut notes = Vec::new();
+        let marker_count = self.incident.as_ref().map_or(0, |i| i.markers.len());
+        if marker_count > 0
+            && matches!(
+                action,
+                PendingAction::ExportEvents(_) | PendingAction::ExportMatches { .. }
+            )
+        {
+            notes.push(format!("{} incident markers are appended.", marker_count));
+        }
+        if self.config.redact_exports && !matches!(action, PendingAction::ExportCatalog(_)) {
+            notes.push("Redaction is on.".to_string());
+        }
+        Some(format!(
+            "{}\n\nThis file already exists ({} bytes, modified {}). It will be replaced with {}.{}",
+            path.display(),
+            existing.len(),
+            modified,
+            replacement,
+            notes
+                .iter()
+                .map(|note| format!(" {}", note))
+                .collect::<String>()
+        ))
+    }
+
+    /// Writes the given events, followed by any incident markers, to `path`. Returns the path,
+    /// the number of events and the redaction summary.
+    fn write_export(
+        &self,
+        path: PathBuf,
+        events: Vec<&DisplayEvent>,
+    ) -> Result<(PathBuf, usize, Option<String>), String> {
+        let export = self.prepare_export(events)?;
+        let mut content = String::new();
+        for event in &export.events {
+            content.push_str(&siem::format_event(
+                self.export_format,
+                event,
+                &event.channel,
+            ));
+            content.push('\n');
+        }
+        for marker in &export.markers {
+            content.push_str(&siem::format_marker(
+                self.export_format,
+                marker,
+                &self.selected_log_name,
+            ));
+            content.push('\n');
+        }
+        std::fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok((path, export.events.len(), export.redaction))
+    }
+
+    /// Returns the event selected in the event list.
+    pub fn selected_event(&self) -> Option<&DisplayEvent> {
+        self.table_state
+            .selected()
+            .and_then(|row| self.event_at_row(row))
+    }
+
+    /// Returns true if the event in the current log is bookmarked.
+    pub fn is_bookmarked(&self, event: &DisplayEvent) -> bool {
+        self.bookmarks
+            .iter()
+            .any(|b| b.matches(self.event_channel(event), event.record_id))
+    }
+
+    /// Returns the log an event was read from: the selected log, or the event's own channel
+    /// on the merged tab.
+    pub fn event_channel<'a>(&'a self, event: &'a DisplayEvent) -> &'a str {
+        if self.is_merged_tab() {
+            &event.channel
+        } else {
+            &self.selected_log_name
+        }
+    }
+
+    /// Index of the merged tab, after the backend's channels. Only the Windows Event Log
+    /// backend can interleave channels, so there is none elsewhere.
+    pub fn merged_tab_index(&self) -> Option<usize> {
+        cfg!(target_os = "windows").then_some(self.channels.len())
+    }
+
+    /// Number of log tabs, including the merged tab.
+    pub fn tab_count(&self) -> usize {
+        self.channels.len() + usize::from(self.merged_tab_index().is_some())
+    }
+
+    /// Returns the name of the log tab at `index`, including the merged tab.
+    pub fn tab_name(&self, index: usize) -> Option<&str> {
+        match self.channels.get(index) {
+            Some(name) => Some(name),
+            None => (Some(index) == self.merged_tab_index()).then_some(MERGED_TAB_NAME),
+        }
+    }
+
+    /// Returns the index of the log tab called `name`, ignoring case.
+    pub fn tab_index(&self, name: &str) -> Option<usize> {
+        (0..self.tab_count()).find(|&index| {
+            self.tab_name(index)
+                .is_some_and(|tab| tab.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Whether the merged tab is selected.
+    pub fn is_merged_tab(&self) -> bool {
+        Some(self.selected_log_index) == self.merged_tab_index()
+    }
+
+    /// Bookmarks the selected event, or removes its bookmark. Returns true if it was added.
+    pub fn toggle_bookmark(&mut self) -> Result<bool, String> {
+        let event = self.selected_event().ok_or("No event selected.")?;
+        let added = match self
+            .bookmarks
+            .iter()
+            .position(|b| b.matches(self.event_channel(event), event.record_id))
+        {
+            Some(index) => {
+                self.bookmarks.remove(index);
+                false
+            }
+            None => {
+                let bookmark = Bookmark::from_event(self.event_channel(event), event);
+                self.bookmarks.push(bookmark);
+                true
+            }
+        };
+        self.save_bookmarks()?;
+        Ok(added)
+    }
+
+    /// Writes the bookmarks file, unless another instance owns it.
+    fn save_bookmarks(&self) -> Result<(), String> {
+        if self.persist_bookmarks {
+            bookmarks::save(&self.bookmarks)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the bookmark at `index` in the bookmarks list.
+    pub fn remove_bookmark(&mut self, index: usize) -> Result<(), String> {
+        if index < self.bookmarks.len() {
+            self.bookmarks.remove(index);
+            self.bookmarks_selection = self
+                .bookmarks_selection
+                .min(self.bookmarks.len().saturating_sub(1));
+            self.save_bookmarks()?;
+        }
+        Ok(())
+    }
+
+    /// Selects the bookmarked event, switching logs and reloading without a filter when it is
+    /// not among the loaded events.
+    pub fn jump_to_bookmark(&mut self, index: usize) -> Result<(), String> {
+        let bookmark = self
+            .bookmarks
+            .get(index)
+            .cloned()
+            .ok_or("No bookmark selected.")?;
+        self.jump_to_event(&bookmark.channel, bookmark.record_id)
+            .map_err(|channel| {
+                format!(
+                    "The bookmark is for the '{}' log, which is not available here.",
+                    channel
+                )
+            })
+    }
+
+    /// Selects the event with the record ID in the channel, switching logs and reloading when
+    /// it is not among the loaded events. Fails with the channel when it has no tab.
+    fn jump_to_event(&mut self, channel: &str, record_id: u64) -> Result<(), String> {
+        let log_index = self
+            .channels
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(channel))
+            .ok_or_else(|| channel.to_string())?;
+        if log_index == self.selected_log_index {
+            let row = self
+                .events
+                .iter()
+                .position(|e| e.record_id == record_id)
+                .and_then(|index| self.row_of_event(index));
+            if let Some(row) = row {
+                self.table_state.select(Some(row));
+                self.update_preview_for_selection();
+                return Ok(());
+            }
+        }
+        self.pending_record_id = Some(record_id);
+        self.select_log_index(log_index);
+        Ok(())
+    }
+
+    /// Marks the selected event under a letter, replacing the event marked with it before.
+    pub fn set_mark(&mut self, name: char) -> Result<(), String> {
+        let event = self
+            .selected_event()
+            .ok_or("Please select an event first.")?;
+        let mark = (self.event_channel(event).to_string(), event.record_id);
+        self.marks.insert(name, mark);
+        Ok(())
+    }
+
+    /// Selects the event marked under a letter.
+    pub fn jump_to_mark(&mut self, name: char) -> Result<(), String> {
+        let (channel, record_id) =
+            self.marks.get(&name).cloned().ok_or_else(|| {
+                format!("No event is marked '{}'. Mark one with '{}.", name, name)
+            })?;
+        self.jump_to_event(&channel, record_id).map_err(|channel| {
+            format!(
+                "Mark '{}' is in the '{}' log, which is not available here.",
+                name, channel
+            )
+        })
+    }
+
+    /// Selects the loaded event with the given `EventRecordID`, clearing the local filter if
+    /// it hides the event. Returns false when the event is not loaded.
+    pub fn select_record(&mut self, record_id: u64) -> bool {
+        let Some(index) = self.events.iter().position(|e| e.record_id == record_id) else {
+            return false;
+        };
+        if self.row_of_event(index).is_none() {
+            self.clear_local_filter();
+        }
+        self.table_state.select(self.row_of_event(index));
+        self.update_preview_for_selection();
+        true
+    }
+
+    /// Opens the full-screen detail view for the selected event.
+    pub fn open_detail_view(&mut self) -> Result<(), String> {
+        let event = self
+            .selected_event()
+            .ok_or("Please select an event first.")?;
+        self.detail_fields = Some(detail::event_fields(event));
+        self.detail_selection = 0;
+        Ok(())
+    }
+
+    /// Copies the value of the selected detail view field to the clipboard.
+    pub fn copy_detail_field(&mut self) -> Result<String, String> {
+        let (name, value) = self
+            .detail_fields
+            .as_ref()
+            .and_then(|fields| fields.get(self.detail_selection))
+            .ok_or("No field selected.")?;
+        clipboard::copy_text(value)?;
+        Ok(name.clone())
+    }
+
+    /// Opens the record ID input used to jump to an event cited by another tool.
+    pub fn open_go_to_record_prompt(&mut self) {
+        self.prompt_input.clear();
+        self.prompt_cursor = 0;
+        self.prompt = Some(PromptKind::GoToRecord);
+    }
+
+    /// Captures the audit policy, checked against the Event IDs the filter asks for when the
+    /// Security log is shown.
+    pub fn open_audit_policy(&mut self) -> Result<(), String> {
+        let filter = self
+            .active_filter
+            .as_ref()
+            .filter(|_| self.selected_log_name == "Security")
+            .and_then(|filter| filter.event_id.as_deref());
+        self.audit_policy = Some(AuditPolicyView::capture(filter)?);
+        Ok(())
+    }
+
+    /// Opens the admin menu, which acts on the selected log itself rather than on a view of it.
+    pub fn open_admin_menu(&mut self) -> Result<(), String> {
+        if self.is_merged_tab() || self.cluster_nodes.is_some() {
+            return Err("Select a single log tab to back it up or clear it.".to_string());
+        }
+        self.admin_menu_selection = Some(0);
+        Ok(())
+    }
+
+    /// Opens an .evtx file input for the selected log, prefilled with a timestamped name.
+    pub fn open_log_backup_prompt(&mut self, prompt: PromptKind) {
+        self.prompt_input = format!(
+            "{}{}-{}.evtx",
+            helpers::sanitize_filename(&self.selected_log_name),
+            if prompt == PromptKind::ExportFilteredLog {
+                "-filtered"
+            } else {
+                ""
+            },
+            Local::now().format("%Y%m%d-%H%M%S")
+        );
+        self.prompt_cursor = self.prompt_input.chars().count();
+        self.prompt = Some(prompt);
+    }
+
+    /// Keeps the backup file chosen for clearing the log and asks for the log's name.
+    pub fn open_clear_log_confirm_prompt(&mut self, backup: &str) {
+        let backup = backup.trim();
+        self.clear_log_backup = (!backup.is_empty()).then(|| PathBuf::from(backup));
+        self.prompt_input.clear();
+        self.prompt_cursor = 0;
+        self.prompt = Some(PromptKind::ClearLogConfirm);
+    }
+
+    /// Opens the host picker on the groups of `hosts.toml`, or the node list input when it
+    /// defines none.
+    pub fn open_host_picker(&mut self) {
+        if self.hosts.is_empty() {
+            self.open_cluster_nodes_prompt();
+        } else {
+            self.host_picker = Some(HostPicker::default());
+        }
+    }
+
+    /// Starts entering a credential to save for the selected line of the host picker: the
+    /// user name first, then the password.
+    pub fn open_credential_prompt(&mut self) {
+        let Some(picker) = &mut self.host_picker else {
+            return;
+        };
+        let Some(&row) = self.hosts.rows().get(picker.selection) else {
+            return;
+        };
+        picker.credential_entry = Some((self.hosts.credential_name(row), None));
+        self.prompt_input.clear();
+        self.prompt_cursor = 0;
+        self.prompt = Some(PromptKind::CredentialUser);
+    }
+
+    /// Whether the host picker has connectivity checks waiting.
+    pub fn has_pending_host_checks(&self) -> bool {
+        self.host_picker
+            .as_ref()
+            .is_some_and(|picker| !picker.pending_checks.is_empty())
+    }
+
+    /// Opens the cluster node list input, prefilled with the nodes merged last.
+    pub fn open_cluster_nodes_prompt(&mut self) {
+        self.prompt_input = self.config.cluster_nodes.join(", ");
+        self.prompt_cursor = self.prompt_input.chars().count();
+        self.prompt = Some(PromptKind::ClusterNodes);
+    }
+
+    /// Opens the incident window input, prefilled with the current window or with the hour
+    /// around the selected event.
+    pub fn open_incident_window_prompt(&mut self) {
+        let (start, end) = match &self.incident {
+            Some(incident) => (incident.start, incident.end),
+            None => {
+                let center = self
+                    .selected_event()
+                    .and_then(|event| event.timestamp)
+                    .unwrap_or_else(Utc::now);
+                (
+                    center - Duration::minutes(30),
+                    center + Duration::minutes(30),
+                )
+            }
+        };
+        self.prompt_input = format!(
+            "{} .. {}",
+            helpers::format_local_time(start),
+            helpers::format_local_time(end)
+        );
+        self.prompt_cursor = self.prompt_input.chars().count();
+        self.prompt = Some(PromptKind::IncidentWindow);
+    }
+
+    /// Locks every log tab to the window in `input`; an empty input leaves incident mode.
+    /// Markers are kept when only the window changes.
+    pub fn apply_incident_window(&mut self, input: &str) -> Result<(), String> {
+        if input.trim().is_empty() {
+            self.incident = None;
+            return Ok(());
+        }
+        let (start, end) = helpers::parse_time_window(input)?;
+        let markers = self
+            .incident
+            .take()
+            .map(|incident| incident.markers)
+            .unwrap_or_default();
+        self.incident = Some(Incident {
+            start,
+            end,
+            markers,
+        });
+        Ok(())
+    }
+
+    /// Opens the marker input for the selected event, prefilled with the label already
+    /// placed at its time.
+    pub fn open_incident_marker_prompt(&mut self) -> Result<(), String> {
+        let incident = self
+            .incident
+            .as_ref()
+            .ok_or("Markers can only be placed in incident mode. Press [i] to choose a window.")?;
+        let time = self
+            .selected_event()
+            .ok_or("Select an event to mark its time.")?
+            .timestamp
+            .ok_or("The selected event's time is unknown.")?;
+        self.prompt_input = incident
+            .markers
+            .iter()
+            .find(|marker| marker.time == time)
+            .map(|marker| marker.label.clone())
+            .unwrap_or_default();
+        self.prompt_cursor = self.prompt_input.chars().count();
+        self.prompt = Some(PromptKind::IncidentMarker);
+        Ok(())
+    }
+
+    /// Labels the selected event's time on the incident timeline. An empty label removes the
+    /// marker placed there.
+    pub fn set_incident_marker(&mut self, label: &str) {
+        let Some(time) = self.selected_event().and_then(|event| event.timestamp) else {
+            return;
+        };
+        let Some(incident) = self.incident.as_mut() else {
+            return;
+        };
+        incident.markers.retain(|marker| marker.time != time);
+        let label = label.trim();
+        if !label.is_empty() {
+            incident.markers.push(IncidentMarker {
+                time,
+                label: label.to_string(),
+            });
+            incident.markers.sort_by_key(|marker| marker.time);
+        }
+    }
+
     /// Displays a confirmation message in a status dialog.
     pub fn show_confirmation(&mut self, title: &str, message: &str) {
         self.status_dialog = Some(StatusDialog::new(title, message, false));
@@ -113,16 +1457,65 @@ impl AppState {
 
     /// Gets a string indicating whether an advanced filter is active.
     pub fn get_filter_status(&self) -> &str {
-        if self.active_filter.is_some() {
+        if self.raw_query.is_some() {
+            "XPath"
+        } else if self.active_filter.is_some() {
             "On"
         } else {
             "Off"
         }
     }
 
+    /// Builds the terminal window title from the current channel, host, and filter status.
+    pub fn window_title(&self) -> String {
+        let mut title = format!(
+            "Event Commander - {} @ {}",
+            self.selected_log_name, self.host_name
+        );
+        if self.raw_query.is_some() {
+            title.push_str(" [XPath query]");
+        } else if self.active_filter.is_some() {
+            title.push_str(" [Filtered]");
+        }
+        title
+    }
+
     /// Updates the preview panel content based on the current table selection.
+    /// Returns the unexpected shutdown analysis for a Kernel-Power 41 event, gathering the
+    /// surrounding evidence on first use.
+    fn shutdown_analysis(&mut self, event: &DisplayEvent) -> Vec<String> {
+        if let Some(lines) = self.shutdown_analysis_cache.get(&event.record_id) {
+            return lines.clone();
+        }
+        #[cfg(target_os = "windows")]
+        let evidence = self.gather_shutdown_evidence(event);
+        #[cfg(not(target_os = "windows"))]
+        let evidence = analysis::ShutdownEvidence::default();
+        let lines = analysis::shutdown_analysis_lines(event, &evidence);
+        self.shutdown_analysis_cache
+            .insert(event.record_id, lines.clone());
+        lines
+    }
+
     pub fn update_preview_for_selection(&mut self) {
-        if let Some(selected_idx) = self.table_state.selected() {
+        #[cfg(target_os = "windows")]
+        {
+            self.render_deferred_selection();
+            self.reload_selected_raw_xml();
+        }
+        let analysis_lines = match self.selected_event() {
+            Some(event) if analysis::is_unexpected_shutdown(event) => {
+                let event = event.clone();
+                self.shutdown_analysis(&event)
+            }
+            Some(event) => analysis::decoded_lines(event),
+            None => Vec::new(),
+        };
+        if let Some(selected_idx) = self
+            .table_state
+            .selected()
+            .and_then(|row| self.event_index(row))
+        {
             if let Some(event) = self.events.get(selected_idx) {
                 const MS_PREFIX: &str = "Microsoft-Windows-";
                 let gray_style = Style::default().fg(Color::DarkGray);
@@ -144,22 +1537,41 @@ impl AppState {
                 };
                 let _source_line = Line::from(source_spans);
 
-                let header_lines: Vec<Line> = vec![
-                    Line::from(format!("Level:       {}", event.level)),
-                    Line::from(format!("DateTime:    {}", event.datetime)),
+                let mut header_lines: Vec<Line> = vec![
+                    Line::from(format!("Level:       {}", event.level.display_name())),
+                    Line::from(format!("DateTime:    {}", event.local_time())),
                     Line::from(format!("Source:      {}", event.source)),
-                    Line::from(format!("Event ID:    {}", event.id)),
-                    Line::from(String::new()),
-                    Line::from("--- Message ---".to_string()),
+                    Line::from(format!("Event ID:    {}", event.event_id)),
                 ];
+                if let Some(process_id) = event.process_id {
+                    header_lines.push(Line::from(match event.thread_id {
+                        Some(thread_id) => {
+                            format!("Process ID:  {} (thread {})", process_id, thread_id)
+                        }
+                        None => format!("Process ID:  {}", process_id),
+                    }));
+                }
+                if let Some(activity_id) = &event.activity_id {
+                    header_lines.push(Line::from(format!("Activity ID: {}", activity_id)));
+                }
+                if let Some(related_id) = &event.related_activity_id {
+                    header_lines.push(Line::from(format!("Related ID:  {}", related_id)));
+                }
+                header_lines.push(Line::from(String::new()));
+                header_lines.push(Line::from("--- Message ---".to_string()));
 
+                let has_named_fields = event.event_data.named().next().is_some();
                 let final_message_string = event
                     .formatted_message
                     .as_ref()
                     .filter(|fm| !fm.is_empty())
                     .cloned()
                     .unwrap_or_else(|| {
-                        if !event.message.is_empty() && !event.message.starts_with("<No") {
+                        // The fallback message only joins the values; the named fields below
+                        // say the same with their names.
+                        if has_named_fields {
+                            "<No message template; see the event data below>".to_string()
+                        } else if !event.message.is_empty() && !event.message.starts_with("<No") {
                             event.message.clone()
                         } else {
                             "<No message content found>".to_string()
@@ -172,13 +1584,36 @@ impl AppState {
                         .lines()
                         .map(|s| Line::from(s.to_string())),
                 );
+                if has_named_fields {
+                    content_lines.push(Line::from(String::new()));
+                    content_lines.push(Line::from("--- Event Data ---".to_string()));
+                    for (name, value) in event.event_data.named() {
+                        let mut value_lines = value.lines();
+                        content_lines.push(Line::from(format!(
+                            "{}: {}",
+                            name,
+                            value_lines.next().unwrap_or_default()
+                        )));
+                        content_lines
+                            .extend(value_lines.map(|line| Line::from(format!("    {}", line))));
+                    }
+                }
+                if let Some(template) = &self.config.preview_template {
+                    content_lines = event
+                        .fill_template(template, &final_message_string)
+                        .lines()
+                        .map(|line| Line::from(line.to_string()))
+                        .collect();
+                }
+                content_lines.extend(analysis_lines.into_iter().map(Line::from));
 
                 let content_text = Text::from(content_lines);
 
-                self.preview_event_id = Some(format!("{}_{}", event.source, event.id));
+                self.preview_event_id = Some(format!("{}_{}", event.source, event.event_id));
                 self.preview_content = Some(content_text);
                 self.preview_raw_xml = Some(event.raw_data.clone());
                 self.preview_scroll = 0;
+                self.preview_hscroll = 0;
             } else {
                 self.preview_event_id = None;
                 self.preview_content = Some(Text::from(
@@ -199,7 +1634,7 @@ impl AppState {
     pub fn scroll_down(&mut self) {
         let i = match self.table_state.selected() {
             Some(i) => {
-                if i >= self.events.len().saturating_sub(1) {
+                if i >= self.row_count().saturating_sub(1) {
                     0
                 } else {
                     i + 1
@@ -207,11 +1642,10 @@ impl AppState {
             }
             None => 0,
         };
-        if !self.events.is_empty() {
+        if self.row_count() > 0 {
             self.table_state.select(Some(i));
             self.update_preview_for_selection();
-            if i >= self.events.len().saturating_sub(20) {
-                #[cfg(target_os = "windows")]
+            if i >= self.row_count().saturating_sub(20) {
                 self.start_or_continue_log_load(false);
             }
         }
@@ -219,33 +1653,69 @@ impl AppState {
 
     /// Scrolls up one event in the event list.
     pub fn scroll_up(&mut self) {
+        if self.table_state.selected() == Some(0) {
+            self.load_unloaded_events();
+        }
         let i = match self.table_state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.events.len().saturating_sub(1)
+                    self.row_count().saturating_sub(1)
                 } else {
                     i - 1
                 }
             }
             None => 0,
         };
-        if !self.events.is_empty() {
+        if self.row_count() > 0 {
             self.table_state.select(Some(i));
             self.update_preview_for_selection();
         }
     }
 
+    /// Moves the selection by `rows`, stopping at either end rather than wrapping as the arrow
+    /// keys do, for vim motions with a count. Loads more events if near the end.
+    pub fn move_selection(&mut self, rows: isize) {
+        let current = self.table_state.selected().unwrap_or(0);
+        if rows < 0 && current < rows.unsigned_abs() {
+            self.load_unloaded_events();
+        }
+        if self.row_count() == 0 {
+            return;
+        }
+        let current = self.table_state.selected().unwrap_or(0);
+        let target = current
+            .saturating_add_signed(rows)
+            .min(self.row_count() - 1);
+        self.table_state.select(Some(target));
+        self.update_preview_for_selection();
+        if target >= self.row_count().saturating_sub(20) {
+            self.start_or_continue_log_load(false);
+        }
+    }
+
+    /// Half the rows of the event table, what Ctrl+D and Ctrl+U move by.
+    pub fn half_page(&self) -> usize {
+        (self.events_visible_rows / 2).max(1)
+    }
+
+    /// Selects the event list's row `row`, counted from 1, or the last loaded one.
+    pub fn go_to_row(&mut self, row: usize) {
+        if self.row_count() > 0 {
+            self.table_state
+                .select(Some(row.saturating_sub(1).min(self.row_count() - 1)));
+            self.update_preview_for_selection();
+        }
+    }
+
     /// Scrolls down one page in the event list; loads more events if near the end.
     pub fn page_down(&mut self) {
         let page_size = 10;
         let current_selection = self.table_state.selected().unwrap_or(0);
-        let new_selection =
-            (current_selection + page_size).min(self.events.len().saturating_sub(1));
-        if !self.events.is_empty() {
+        let new_selection = (current_selection + page_size).min(self.row_count().saturating_sub(1));
+        if self.row_count() > 0 {
             self.table_state.select(Some(new_selection));
             self.update_preview_for_selection();
-            if new_selection >= self.events.len().saturating_sub(20) {
-                #[cfg(target_os = "windows")]
+            if new_selection >= self.row_count().saturating_sub(20) {
                 self.start_or_continue_log_load(false);
             }
         }
@@ -254,17 +1724,137 @@ impl AppState {
     /// Scrolls up one page in the event list.
     pub fn page_up(&mut self) {
         let page_size = 10;
+        if self.table_state.selected().unwrap_or(0) < page_size {
+            self.load_unloaded_events();
+        }
         let current_selection = self.table_state.selected().unwrap_or(0);
         let new_selection = current_selection.saturating_sub(page_size);
-        if !self.events.is_empty() {
+        if self.row_count() > 0 {
             self.table_state.select(Some(new_selection));
             self.update_preview_for_selection();
         }
     }
 
+    /// Interleaves the IIS and HTTPERR log entries written within the loaded events' time span
+    /// with the events, or takes them out again when they are shown. Returns the summary shown
+    /// in the status dialog.
+    pub fn toggle_web_logs(&mut self) -> Result<String, String> {
+        let selected_raw = self.selected_event().map(|event| event.raw_data.clone());
+        let message = if self.web_logs_merged {
+            // Web log entries are the only events without a record ID.
+            self.events.retain(|event| event.record_id != 0);
+            self.web_logs_merged = false;
+            "Web log entries were removed from the event list.".to_string()
+        } else {
+            let start = self.events.iter().filter_map(|event| event.timestamp).min();
+            let end = self.events.iter().filter_map(|event| event.timestamp).max();
+            let (Some(start), Some(end)) = (start, end) else {
+                return Err(
+                    "Load some events first; web log entries are merged into their time span."
+                        .to_string(),
+                );
+            };
+            let mut entries = weblogs::read_web_logs(&self.config.web_log_dirs, start, end)?;
+            if entries.is_empty() {
+                return Err(format!(
+                    "No IIS or HTTPERR entries were logged between {} and {} in:\n{}",
+                    helpers::format_local_time(start),
+                    helpers::format_local_time(end),
+                    self.config.web_log_dirs.join("\n")
+                ));
+            }
+            let count = entries.len();
+            for entry in &mut entries {
+                self.match_sigma_rules(entry);
+            }
+            self.events.extend(entries);
+            self.web_logs_merged = true;
+            if self.sort_key == SortKey::DateTime {
+                if self.sort_descending {
+                    self.events
+                        .sort_by_key(|event| std::cmp::Reverse(event.timestamp));
+                } else {
+                    self.events.sort_by_key(|event| event.timestamp);
+                }
+            }
+            format!("Merged {} web log entries into the event list.", count)
+        };
+
+        self.table_state.select(None);
+        self.sort_events();
+        let row = selected_raw
+            .and_then(|raw| self.events.iter().position(|event| event.raw_data == raw))
+            .and_then(|index| self.row_of_event(index))
+            .or((self.row_count() > 0).then_some(0));
+        self.table_state.select(row);
+        self.update_preview_for_selection();
+        Ok(message)
+    }
+
+    /// Time span of the events in the event list, oldest to newest, spread over the histogram.
+    pub fn histogram_range(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let mut times = (0..self.row_count())
+            .filter_map(|row| self.event_at_row(row))
+            .filter_map(|event| event.timestamp);
+        let first = times.next()?;
+        Some(times.fold((first, first), |(start, end), time| {
+            (start.min(time), end.max(time))
+        }))
+    }
+
+    /// Number of listed events falling in each of `buckets` time slices of `histogram_range`.
+    pub fn histogram_counts(&self, buckets: usize) -> Vec<usize> {
+        let mut counts = vec![0; buckets];
+        let Some((start, end)) = self.histogram_range() else {
+            return counts;
+        };
+        for row in 0..self.row_count() {
+            if let Some(bucket) = self
+                .event_at_row(row)
+                .and_then(|event| helpers::time_bucket(event.timestamp?, start, end, buckets))
+            {
+                counts[bucket] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Moves the selection to the next time bucket of the histogram holding events, later
+    /// when `step` is positive, and selects the first row in it.
+    pub fn select_histogram_bucket(&mut self, step: isize) {
+        let buckets = self.histogram_buckets;
+        let Some((start, end)) = self.histogram_range() else {
+            return;
+        };
+        let counts = self.histogram_counts(buckets);
+        let current = self
+            .selected_event()
+            .and_then(|event| helpers::time_bucket(event.timestamp?, start, end, buckets));
+        let target = match current {
+            Some(current) if step > 0 => (current + 1..buckets).find(|&b| counts[b] > 0),
+            Some(current) => (0..current).rev().find(|&b| counts[b] > 0),
+            None => counts.iter().position(|&count| count > 0),
+        };
+        let Some(target) = target else {
+            return;
+        };
+        if let Some(row) = (0..self.row_count()).find(|&row| {
+            self.event_at_row(row)
+                .and_then(|event| helpers::time_bucket(event.timestamp?, start, end, buckets))
+                == Some(target)
+        }) {
+            self.table_state.select(Some(row));
+            self.update_preview_for_selection();
+        }
+    }
+
     /// Selects the top event in the event list.
     pub fn go_to_top(&mut self) {
-        if !self.events.is_empty() {
+        if self.unloaded_before.is_some() {
+            self.start_or_continue_log_load(true);
+            return;
+        }
+        if self.row_count() > 0 {
             self.table_state.select(Some(0));
             self.update_preview_for_selection();
         }
@@ -272,11 +1862,10 @@ impl AppState {
 
     /// Selects the bottom event in the event list and loads more events if necessary.
     pub fn go_to_bottom(&mut self) {
-        if !self.events.is_empty() {
-            let last_index = self.events.len().saturating_sub(1);
+        if self.row_count() > 0 {
+            let last_index = self.row_count().saturating_sub(1);
             self.table_state.select(Some(last_index));
             self.update_preview_for_selection();
-            #[cfg(target_os = "windows")]
             self.start_or_continue_log_load(false);
         }
     }
@@ -304,37 +1893,136 @@ impl AppState {
         self.preview_scroll = 0;
     }
 
-    /// Scrolls the preview panel to the bottom.
-    #[allow(dead_code)]
-    pub fn preview_scroll_to_bottom(&mut self, content_height: usize, view_height: usize) {
-        if content_height > view_height {
-            self.preview_scroll = content_height - view_height;
-        } else {
-            self.preview_scroll = 0;
-        }
+    /// Turns wrapping of the preview's long lines on or off.
+    pub fn toggle_preview_wrap(&mut self) {
+        self.config.preview_wrap = !self.config.preview_wrap;
+        self.preview_hscroll = 0;
+        self.preview_scroll = 0;
+    }
+
+    /// Scrolls the unwrapped preview sideways; the panel clamps it to the longest line.
+    pub fn preview_scroll_right(&mut self) {
+        self.preview_hscroll = self.preview_hscroll.saturating_add(PREVIEW_HSCROLL_STEP);
+    }
+
+    pub fn preview_scroll_left(&mut self) {
+        self.preview_hscroll = self.preview_hscroll.saturating_sub(PREVIEW_HSCROLL_STEP);
+    }
+
+    /// Scrolls the preview panel so that its last line is at the bottom.
+    pub fn preview_scroll_to_bottom(&mut self) {
+        self.preview_scroll = self.preview_max_scroll;
     }
 
     /// Determines if an event matches the provided search term.
     pub fn event_matches_search(&self, event: &DisplayEvent, term_lower: &str) -> bool {
         event.message.to_lowercase().contains(term_lower)
+            || event
+                .formatted_message
+                .as_ref()
+                .is_some_and(|message| message.to_lowercase().contains(term_lower))
             || event.source.to_lowercase().contains(term_lower)
-            || event.level.to_lowercase().contains(term_lower)
-            || event.id.to_lowercase().contains(term_lower)
-            || event.datetime.to_lowercase().contains(term_lower)
+            || event
+                .level
+                .display_name()
+                .to_lowercase()
+                .contains(term_lower)
+            || event.event_id.to_string().contains(term_lower)
+            || event.local_time().contains(term_lower)
+    }
+
+    /// Returns the event list rows matching the active search term.
+    pub fn search_matches(&self) -> Vec<usize> {
+        let Some(term_lower) = self.last_search_term.as_ref().map(|t| t.to_lowercase()) else {
+            return Vec::new();
+        };
+        (0..self.row_count())
+            .filter(|&row| {
+                self.event_at_row(row)
+                    .is_some_and(|event| self.event_matches_search(event, &term_lower))
+            })
+            .collect()
+    }
+
+    /// Opens the search bar with the last search term, remembering the selection and term to
+    /// go back to if the search is cancelled.
+    pub fn begin_search(&mut self) {
+        self.search_term = self.last_search_term.clone().unwrap_or_default();
+        self.search_cursor = self.search_term.chars().count();
+        self.search_origin = self.table_state.selected();
+        self.search_previous_term = self.last_search_term.clone();
+        self.is_searching = true;
+    }
+
+    /// Selects the first loaded event matching the search term as typed, from the row the
+    /// search started at down and then from the top. Without a match the selection stays
+    /// where the search started.
+    pub fn update_incremental_search(&mut self) {
+        self.restore_search_origin();
+        if self.search_term.is_empty() {
+            self.last_search_term = None;
+            return;
+        }
+        let term = self.search_term.clone();
+        self.last_search_term = Some(term.clone());
+        let start = self.table_state.selected().unwrap_or(0);
+        if !self.select_first_match(start..self.row_count(), &term) {
+            self.select_first_match(0..start, &term);
+        }
+    }
+
+    /// Closes the search bar, keeping the match it jumped to. When no loaded event matches,
+    /// the search goes on through the rest of the log as `n` does.
+    pub fn confirm_search(&mut self) -> Result<(), String> {
+        self.close_search_bar();
+        let Some(term_lower) = self.last_search_term.as_ref().map(|t| t.to_lowercase()) else {
+            return Ok(());
+        };
+        if self
+            .selected_event()
+            .is_some_and(|event| self.event_matches_search(event, &term_lower))
+        {
+            return Ok(());
+        }
+        self.find_next_match()
+    }
+
+    /// Closes the search bar, going back to the selection and search term from before it
+    /// opened.
+    pub fn cancel_search(&mut self) {
+        self.close_search_bar();
+        self.last_search_term = self.search_previous_term.take();
+        self.restore_search_origin();
+    }
+
+    fn close_search_bar(&mut self) {
+        self.is_searching = false;
+        self.search_term.clear();
+        self.search_cursor = 0;
+    }
+
+    fn restore_search_origin(&mut self) {
+        let origin = self.search_origin.filter(|&row| row < self.row_count());
+        if self.table_state.selected() != origin {
+            self.table_state.select(origin);
+            self.update_preview_for_selection();
+        }
     }
 
     /// Finds the next matching event based on the active search term.
+    /// When the loaded events run out, a deep search continues through the rest of the log
+    /// before wrapping around.
     pub fn find_next_match(&mut self) -> Result<(), String> {
         if let Some(term) = self.last_search_term.clone() {
-            let start_index = self.table_state.selected().map_or(0, |i| i + 1);
-            for i in (start_index..self.events.len()).chain(0..start_index) {
-                if let Some(event) = self.events.get(i) {
-                    if self.event_matches_search(event, &term.to_lowercase()) {
-                        self.table_state.select(Some(i));
-                        self.update_preview_for_selection();
-                        return Ok(());
-                    }
-                }
+            if self.select_match_after_selection(&term) {
+                return Ok(());
+            }
+            if !self.no_more_events {
+                self.deep_search = Some(crate::models::DeepSearch { term, scanned: 0 });
+                return Ok(());
+            }
+            if self.select_match_from_top(&term) {
+                return Ok(());
             }
             Err(format!("Search term '{}' not found.", term))
         } else {
@@ -342,24 +2030,49 @@ impl AppState {
         }
     }
 
+    /// Selects the first matching row below the selection.
+    pub fn select_match_after_selection(&mut self, term: &str) -> bool {
+        let start_index = self.table_state.selected().map_or(0, |i| i + 1);
+        self.select_first_match(start_index..self.row_count(), term)
+    }
+
+    /// Selects the first matching row from the top, up to and including the selection.
+    pub fn select_match_from_top(&mut self, term: &str) -> bool {
+        let start_index = self.table_state.selected().map_or(0, |i| i + 1);
+        self.select_first_match(0..start_index, term)
+    }
+
+    fn select_first_match(&mut self, rows: std::ops::Range<usize>, term: &str) -> bool {
+        let term_lower = term.to_lowercase();
+        let found = rows.into_iter().find(|&row| {
+            self.event_at_row(row)
+                .is_some_and(|event| self.event_matches_search(event, &term_lower))
+        });
+        if let Some(row) = found {
+            self.table_state.select(Some(row));
+            self.update_preview_for_selection();
+        }
+        found.is_some()
+    }
+
     /// Finds the previous matching event based on the active search term.
     pub fn find_previous_match(&mut self) -> Result<(), String> {
         if let Some(term) = self.last_search_term.clone() {
             let start_index = self
                 .table_state
                 .selected()
-                .map_or(self.events.len().saturating_sub(1), |i| i.saturating_sub(1));
-            let end_index = self.events.len();
+                .map_or(self.row_count().saturating_sub(1), |i| i.saturating_sub(1));
+            let end_index = self.row_count();
             for i in (0..=start_index)
                 .rev()
                 .chain((start_index + 1..end_index).rev())
             {
-                if let Some(event) = self.events.get(i) {
-                    if self.event_matches_search(event, &term.to_lowercase()) {
-                        self.table_state.select(Some(i));
-                        self.update_preview_for_selection();
-                        return Ok(());
-                    }
+                if let Some(event) = self.event_at_row(i)
+                    && self.event_matches_search(event, &term.to_lowercase())
+                {
+                    self.table_state.select(Some(i));
+                    self.update_preview_for_selection();
+                    return Ok(());
                 }
             }
             Err(format!("Search term '{}' not found.", term))
@@ -370,18 +2083,178 @@ impl AppState {
 
     /// Selects the selected log index and clears the active filter.
     pub fn select_log_index(&mut self, index: usize) {
-        if index < crate::models::LOG_NAMES.len() {
+        if let Some(name) = self.tab_name(index).map(str::to_string) {
             self.selected_log_index = index;
-            self.selected_log_name = crate::models::LOG_NAMES[index].to_string();
-            self.events.clear();
-            self.table_state.select(Some(0));
+            self.selected_log_name = name;
+            self.clear_events();
             self.no_more_events = false;
             self.active_filter = None;
-            #[cfg(target_os = "windows")]
+            self.raw_query = None;
             self.start_or_continue_log_load(true);
         }
     }
 
+    /// Captures the current view so it can be shared as a token.
+    pub fn current_view(&self) -> ViewState {
+        ViewState {
+            channel: self.selected_log_name.clone(),
+            filter: self.active_filter.clone(),
+            raw_query: self.raw_query.clone(),
+            sort_key: self.sort_key,
+            sort_descending: self.sort_descending,
+            record_id: self.selected_event().map(|event| event.record_id),
+            preview_scroll: self.preview_scroll,
+            incident: self.incident.clone(),
+        }
+    }
+
+    /// Switches to a shared view. The caller reloads the log; the view's event is selected
+    /// once it has been loaded.
+    pub fn apply_view(&mut self, view: ViewState) -> Result<(), String> {
+        let index = self.tab_index(&view.channel).ok_or_else(|| {
+            format!(
+                "The view is for the '{}' log, which is not available here.",
+                view.channel
+            )
+        })?;
+        self.selected_log_index = index;
+        self.selected_log_name = self.tab_name(index).unwrap_or_default().to_string();
+        self.active_filter = view.filter;
+        self.raw_query = view.raw_query;
+        self.sort_key = view.sort_key;
+        self.sort_descending = view.sort_descending;
+        self.incident = view.incident;
+        self.pending_record_id = view.record_id;
+        self.pending_preview_scroll = view.preview_scroll;
+        self.focus = PanelFocus::Events;
+        Ok(())
+    }
+
+    /// Opens the log with the filter and order given on the command line, on top of the saved
+    /// or shared view. A filter given there replaces the saved one. The caller reloads the log.
+    pub fn apply_launch_args(&mut self, cli: &Cli) -> Result<(), String> {
+        let mut view = self.current_view();
+        if let Some(path) = &cli.file {
+            view.channel = self.open_saved_log(path)?;
+        }
+        if let Some(log) = &cli.log {
+            if self.tab_index(log).is_none() {
+                return Err(format!(
+                    "There is no '{}' log. Available: {}.",
+                    log,
+                    self.channels.join(", ")
+                ));
+            }
+            view.channel = log.clone();
+        }
+        if cli.filter_source.is_some() || cli.event_id.is_some() || cli.last.is_some() {
+            view.filter = Some(FilterCriteria {
+                source: cli.filter_source.clone(),
+                event_id: cli.event_id.clone(),
+                time_filter: cli.last.unwrap_or_default(),
+                ..FilterCriteria::default()
+            });
+            view.raw_query = None;
+        }
+        if let Some(order) = cli.sort {
+            view.sort_key = SortKey::DateTime;
+            view.sort_descending = order == SortOrder::Desc;
+        }
+        self.apply_view(view)
+    }
+
+    /// Adds a tab for a saved `.evtx` log, named by its absolute path. Returns the tab name.
+    pub fn open_saved_log(&mut self, path: &Path) -> Result<String, String> {
+        if !cfg!(target_os = "windows") {
+            return Err("Saved .evtx logs can only be opened on Windows.".to_string());
+        }
+        let path = std::path::absolute(path)
+            .map_err(|e| format!("Invalid log file '{}': {}", path.display(), e))?;
+        let name = path.display().to_string();
+        if !helpers::is_saved_log(&name) {
+            return Err(format!("'{}' is not a saved .evtx log.", name));
+        }
+        if !path.is_file() {
+            return Err(format!("Log file '{}' does not exist.", name));
+        }
+        if !self.channels.contains(&name) {
+            self.channels.push(name.clone());
+            self.channel_access.push(self.source.check_access(&name));
+        }
+        Ok(name)
+    }
+
+    /// Opens the prompt to save the session to, or restore it from, a file, starting from the
+    /// default session file.
+    pub fn open_session_prompt(&mut self, prompt: PromptKind) {
+        self.prompt_input = session::default_session_path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        self.prompt_cursor = self.prompt_input.chars().count();
+        self.prompt = Some(prompt);
+    }
+
+    /// Saves the current view and the bookmarks to a session file.
+    pub fn save_session(&self, path: &Path) -> Result<(), String> {
+        session::save(
+            path,
+            &Session {
+                saved_at: Utc::now(),
+                view: self.current_view(),
+                bookmarks: self.bookmarks.clone(),
+            },
+        )
+    }
+
+    /// Switches to the view of a saved session and adds its bookmarks to the current ones.
+    /// The caller reloads the log. Returns the session with the number of bookmarks added.
+    pub fn restore_session(&mut self, path: &Path) -> Result<(Session, usize), String> {
+        let session = session::load(path)?;
+        self.apply_view(session.view.clone())?;
+        let before = self.bookmarks.len();
+        for bookmark in &session.bookmarks {
+            if !self
+                .bookmarks
+                .iter()
+                .any(|b| b.matches(&bookmark.channel, bookmark.record_id))
+            {
+                self.bookmarks.push(bookmark.clone());
+            }
+        }
+        let added = self.bookmarks.len() - before;
+        if added > 0 {
+            self.save_bookmarks()?;
+        }
+        Ok((session, added))
+    }
+
+    /// Opens the view token prompt with the token of the current view, ready to copy or to be
+    /// replaced by a token someone else shared.
+    pub fn open_view_token_prompt(&mut self) {
+        self.prompt_input = self.current_view().to_token();
+        self.prompt_cursor = self.prompt_input.chars().count();
+        self.prompt = Some(PromptKind::ViewToken);
+    }
+
+    /// Applies a request from another instance that attached to this one.
+    pub fn apply_attach_request(&mut self, request: AttachRequest) {
+        if let Some(index) = self
+            .channels
+            .iter()
+            .position(|name| *name == request.log_name)
+            .filter(|&index| index != self.selected_log_index)
+        {
+            self.select_log_index(index);
+        }
+        self.show_confirmation(
+            "Instance Attached",
+            &format!(
+                "Instance {} handed over to this window ({}).",
+                request.from_pid, request.log_name
+            ),
+        );
+    }
+
     /// Updates the filtered source list based on the filter dialog's input.
     pub fn update_filtered_sources(&mut self) {
         self.filter_dialog_filtered_sources.clear();
@@ -405,16 +2278,118 @@ impl AppState {
             } else if !self.filter_dialog_filtered_sources.is_empty() {
                 self.filter_dialog_filtered_source_selection = Some(0);
             }
-            if let Some(selected_pos) = self.filter_dialog_filtered_source_selection {
-                if let Some((original_index, _)) =
+            if let Some(selected_pos) = self.filter_dialog_filtered_source_selection
+                && let Some((original_index, _)) =
                     self.filter_dialog_filtered_sources.get(selected_pos)
+            {
+                self.filter_dialog_source_index = *original_index;
+            }
+        }
+    }
+
+    /// Resets the text cursors of every filter dialog input.
+    pub fn reset_filter_dialog_cursors(&mut self) {
+        self.filter_event_id_cursor = 0;
+        self.filter_source_cursor = 0;
+        self.filter_task_cursor = 0;
+        self.filter_opcode_cursor = 0;
+        self.filter_user_cursor = 0;
+        self.filter_computer_cursor = 0;
+        self.filter_excluded_sources_cursor = 0;
+    }
+
+    /// Fills the filter dialog's fields from a filter, or clears them.
+    pub fn fill_filter_dialog(&mut self, filter: Option<&FilterCriteria>) {
+        let filter = filter.cloned().unwrap_or_default();
+        self.filter_dialog_source_index = 0;
+        match &filter.source {
+            Some(source) => {
+                self.filter_dialog_source_input = source.clone();
+                if let Some(index) = self
+                    .available_sources
+                    .as_ref()
+                    .and_then(|sources| sources.iter().position(|s| s == source))
                 {
-                    self.filter_dialog_source_index = *original_index;
+                    self.filter_dialog_source_index = index;
                 }
             }
+            None => self.filter_dialog_source_input.clear(),
+        }
+        self.filter_dialog_event_id = filter.event_id.unwrap_or_default();
+        self.filter_dialog_level = filter.level;
+        self.filter_dialog_time = filter.time_filter;
+        self.filter_dialog_keywords = filter.keywords;
+        self.filter_dialog_task = filter.task.map(|t| t.to_string()).unwrap_or_default();
+        self.filter_dialog_opcode = filter.opcode.map(|o| o.to_string()).unwrap_or_default();
+        self.filter_dialog_user = filter.user_sid.unwrap_or_default();
+        self.filter_dialog_computer = filter.computer.unwrap_or_default();
+        self.filter_dialog_excluded_sources = filter.excluded_sources.join(", ");
+        self.update_filtered_sources();
+    }
+
+    /// Loads the next (or previous) recent filter into the filter dialog; stepping past either
+    /// end goes back to the active filter.
+    pub fn cycle_recent_filter(&mut self, forward: bool) {
+        let count = self.config.recent_filters.len();
+        if count == 0 {
+            return;
+        }
+        self.filter_dialog_recent = match (self.filter_dialog_recent, forward) {
+            (None, true) => Some(0),
+            (None, false) => Some(count - 1),
+            (Some(index), true) => Some(index + 1).filter(|&next| next < count),
+            (Some(index), false) => index.checked_sub(1),
+        };
+        let filter = match self.filter_dialog_recent {
+            Some(index) => self.config.recent_filters.get(index).cloned(),
+            None => self.active_filter.clone(),
+        };
+        self.fill_filter_dialog(filter.as_ref());
+        self.reset_filter_dialog_cursors();
+    }
+
+    /// Records the filter the log is being loaded with in the history and the recent filters.
+    /// Raw XPath queries are not filters and are left out.
+    pub fn record_filter_history(&mut self) {
+        if self.raw_query.is_some() {
+            return;
+        }
+        self.filter_history.record(self.active_filter.as_ref());
+        if let Some(filter) = &self.active_filter {
+            history::remember_recent(&mut self.config.recent_filters, filter);
         }
     }
 
+    /// Goes back (or forward) to the filter applied before (or after) the current one, like a
+    /// browser. Returns true when the filter changed and the log needs reloading.
+    pub fn step_filter_history(&mut self, forward: bool) -> bool {
+        let step = if forward {
+            self.filter_history.forward()
+        } else {
+            self.filter_history.back()
+        };
+        let Some(filter) = step else {
+            return false;
+        };
+        let (position, count) = self.filter_history.position();
+        self.footer_notice = Some((
+            format!(
+                "Filter {} of {}: {}",
+                position,
+                count,
+                filter
+                    .as_ref()
+                    .map(FilterCriteria::summary)
+                    .unwrap_or_else(|| "No filter".to_string())
+            ),
+            Instant::now(),
+        ));
+        self.config.last_filter = filter.clone();
+        self.active_filter = filter;
+        self.raw_query = None;
+        true
+    }
+
     /// Updates the level filter in the active filter or creates a new filter with just the level
     pub fn update_level_filter(&mut self) {
         let current_filter = self.active_filter.take().unwrap_or_default();
@@ -423,30 +2398,18 @@ impl AppState {
             level: new_level,
             ..current_filter
         });
-        #[cfg(target_os = "windows")]
+        self.config.last_filter = self.active_filter.clone();
+        self.raw_query = None;
         self.start_or_continue_log_load(true);
     }
 }
 
 impl Drop for AppState {
     fn drop(&mut self) {
-        #[cfg(target_os = "windows")]
+        if let Some(mut writer) = self.log_sink.take()
+            && let Err(e) = writer.flush()
         {
-            if let Some(handle) = self.query_handle.take() {
-                unsafe {
-                    let _ = EvtClose(handle);
-                }
-            }
-            for (_provider, handle) in self.publisher_metadata_cache.drain() {
-                unsafe {
-                    let _ = EvtClose(handle);
-                }
-            }
-        }
-        if let Some(mut writer) = self.log_file.take() {
-            if let Err(e) = writer.flush() {
-                eprintln!("Error flushing log file on drop: {}", e);
-            }
+            eprintln!("Error flushing log file on drop: {}", e);
         }
     }
 }
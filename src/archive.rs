@@ -0,0 +1,416 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Local, Utc};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{ArchiveArgs, ArchiveFormat};
+use crate::config::{self, Config};
+use crate::helpers;
+use crate::models::DisplayEvent;
+use crate::siem;
+use crate::source::{BatchTuner, EventSource, PlatformSource};
+
+#[cfg(target_os = "windows")]
+use crate::event_api;
+
+/// File in the archive folder holding the last record archived per channel.
+const STATE_FILE_NAME: &str = "archive_state.json";
+
+/// Last record archived per channel, so a restarted daemon continues where it stopped.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ArchiveState {
+    last_record: BTreeMap<String, u64>,
+}
+
+impl ArchiveState {
+    fn load(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(STATE_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| format!("Invalid archive state {}: {}", path.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn save(&self, dir: &Path) -> Result<(), String> {
+        let path = dir.join(STATE_FILE_NAME);
+        let text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(&path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// When a new archive file is started.
+struct Rotation {
+    max_bytes: u64,
+    max_age: Duration,
+}
+
+impl Rotation {
+    fn due(&self, segment: &Segment) -> bool {
+        Utc::now() - segment.started >= self.max_age || segment.size() >= self.max_bytes
+    }
+}
+
+/// The JSONL archive file a channel is written to.
+struct Segment {
+    path: PathBuf,
+    encoder: GzEncoder<File>,
+    started: DateTime<Utc>,
+}
+
+impl Segment {
+    fn create(dir: &Path, channel: &str) -> Result<Self, String> {
+        let path = new_archive_path(dir, channel, "jsonl.gz")?;
+        let file = File::create_new(&path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        Ok(Self {
+            path,
+            encoder: GzEncoder::new(file, Compression::default()),
+            started: Utc::now(),
+        })
+    }
+
+    /// Compressed bytes written so far.
+    fn size(&self) -> u64 {
+        self.encoder
+            .get_ref()
+            .metadata()
+            .map_or(0, |metadata| metadata.len())
+    }
+
+    fn finish(self) -> Result<(), String> {
+        self.encoder
+            .finish()
+            .map(|_| ())
+            .map_err(|e| format!("Failed to finish {}: {}", self.path.display(), e))
+    }
+}
+
+/// Archives one channel.
+struct ChannelArchiver {
+    channel: String,
+    /// Folder of the channel's archive files.
+    dir: PathBuf,
+    segment: Option<Segment>,
+    /// When the last .evtx archive was exported.
+    #[cfg(target_os = "windows")]
+    last_export: DateTime<Utc>,
+}
+
+impl ChannelArchiver {
+    /// Appends the events logged since `last` to the current JSONL archive, starting a new
+    /// one when it is due. Returns the newest record archived.
+    fn archive_jsonl(
+        &mut self,
+        last: Option<u64>,
+        rotation: &Rotation,
+        interval: Duration,
+    ) -> Result<Option<u64>, String> {
+        self.check_turnover(interval);
+        let events = self.pending_events(last)?;
+        let (Some(first), Some(newest)) = (events.first(), events.last()) else {
+            return Ok(None);
+        };
+        if let Some(last) = last {
+            self.check_overwritten(last, first.record_id);
+        }
+        for event in &events {
+            let line = siem::to_ndjson(event, &self.channel);
+            let segment = self.segment(rotation)?;
+            writeln!(segment.encoder, "{}", line)
+                .map_err(|e| format!("Failed to write {}: {}", segment.path.display(), e))?;
+        }
+        if let Some(segment) = &mut self.segment {
+            // Flushed after every pass, so the archive can be read while the daemon runs.
+            segment
+                .encoder
+                .flush()
+                .map_err(|e| format!("Failed to write {}: {}", segment.path.display(), e))?;
+            report(&format!(
+                "{}: archived {} events to {}",
+                self.channel,
+                events.len(),
+                segment.path.display()
+            ));
+        }
+        Ok(Some(newest.record_id))
+    }
+
+    /// The segment to write to, finishing the current one first when rotation is due.
+    fn segment(&mut self, rotation: &Rotation) -> Result<&mut Segment, String> {
+        let segment = match self.segment.take() {
+            Some(segment) if !rotation.due(&segment) => segment,
+            Some(segment) => {
+                segment.finish()?;
+                Segment::create(&self.dir, &self.channel)?
+            }
+            None => Segment::create(&self.dir, &self.channel)?,
+        };
+        Ok(self.segment.insert(segment))
+    }
+
+    /// Exports the events logged since `last` to a compressed .evtx file when the archive is
+    /// due: when `rotation.max_age` has passed since the last export, when the events waiting
+    /// would take up `rotation.max_bytes` of the log file, or when the channel overwrites
+    /// events and half of what it holds has not been archived yet. `force` exports whatever
+    /// is waiting. Returns the newest record archived.
+    #[cfg(target_os = "windows")]
+    fn archive_evtx(
+        &mut self,
+        last: Option<u64>,
+        rotation: &Rotation,
+        interval: Duration,
+        force: bool,
+    ) -> Result<Option<u64>, String> {
+        self.check_turnover(interval);
+        let Some(newest) = self.edge_record(true)? else {
+            return Ok(None);
+        };
+        if last.is_some_and(|last| newest <= last) {
+            return Ok(None);
+        }
+        let oldest = self.edge_record(false)?.unwrap_or(newest);
+        if let Some(last) = last {
+            self.check_overwritten(last, oldest);
+        }
+        let first = last.map_or(oldest, |last| (last + 1).max(oldest));
+        let pending = newest - first + 1;
+        let retention = event_api::load_retention_info(&self.channel);
+        let (held, log_size) = retention
+            .as_ref()
+            .map_or((0, 0), |info| (info.record_count, info.max_size));
+        let due = force
+            || Utc::now() - self.last_export >= rotation.max_age
+            || (held > 0 && log_size * pending / held >= rotation.max_bytes)
+            || (retention.is_some_and(|info| info.overwrites) && pending * 2 >= held);
+        if !due {
+            return Ok(None);
+        }
+
+        let path = new_archive_path(&self.dir, &self.channel, "evtx")?;
+        let query = format!(
+            "*[System[EventRecordID>={} and EventRecordID<={}]]",
+            first, newest
+        );
+        let path = event_api::export_log(&self.channel, &query, &path)?;
+        let compressed = path.with_extension("evtx.gz");
+        let result = compress(&path, &compressed);
+        let _ = fs::remove_file(&path);
+        result?;
+        self.last_export = Utc::now();
+        report(&format!(
+            "{}: archived {} events to {}",
+            self.channel,
+            pending,
+            compressed.display()
+        ));
+        Ok(Some(newest))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn archive_evtx(
+        &mut self,
+        _last: Option<u64>,
+        _rotation: &Rotation,
+        _interval: Duration,
+        _force: bool,
+    ) -> Result<Option<u64>, String> {
+        Err("evtx archives require the Windows Event Log service".to_string())
+    }
+
+    /// Reads the events after `last`, oldest first. They are read newest first, so backends
+    /// that cannot query by record number stop at `last` too.
+    fn pending_events(&self, last: Option<u64>) -> Result<Vec<DisplayEvent>, String> {
+        let mut source = PlatformSource::default();
+        source.query(&self.channel, &after_query(last), true)?;
+        let mut tuner = BatchTuner::bulk();
+        let mut events = Vec::new();
+        'read: loop {
+            let batch = tuner.next_batch(&mut source, usize::MAX)?;
+            if batch.is_empty() {
+                break;
+            }
+            for event in batch {
+                if last.is_some_and(|last| event.record_id <= last) {
+                    break 'read;
+                }
+                events.push(event);
+            }
+        }
+        events.reverse();
+        Ok(events)
+    }
+
+    /// The newest or oldest record number the channel holds.
+    #[cfg(target_os = "windows")]
+    fn edge_record(&self, newest: bool) -> Result<Option<u64>, String> {
+        let mut source = PlatformSource::default();
+        source.query(&self.channel, "*", newest)?;
+        Ok(source.next_batch(1)?.first().map(|event| event.record_id))
+    }
+
+    /// Reports events overwritten before they were archived. Record numbers are only
+    /// consecutive in the Windows Event Log.
+    fn check_overwritten(&self, last: u64, oldest_pending: u64) {
+        if cfg!(target_os = "windows") && oldest_pending > last + 1 {
+            report(&format!(
+                "{}: {} events were overwritten before they could be archived; shorten --interval or enlarge the log",
+                self.channel,
+                oldest_pending - last - 1
+            ));
+        }
+    }
+
+    /// Warns when the channel overwrites events faster than two passes of the daemon.
+    fn check_turnover(&self, interval: Duration) {
+        #[cfg(target_os = "windows")]
+        if let Some(horizon) = event_api::load_retention_info(&self.channel)
+            .filter(|info| info.is_losing_events())
+            .and_then(|info| info.horizon())
+            .filter(|horizon| *horizon < interval * 2)
+        {
+            report(&format!(
+                "{}: the log only holds the last {} minutes of events; shorten --interval",
+                self.channel,
+                horizon.num_minutes()
+            ));
+        }
+        #[cfg(not(target_os = "windows"))]
+        let _ = interval;
+    }
+}
+
+/// Archives the channels until the process is stopped, or once with `--once`.
+pub fn run(args: &ArchiveArgs) -> Result<(), Box<dyn Error>> {
+    if cfg!(not(target_os = "windows")) && args.format == ArchiveFormat::Evtx {
+        return Err("evtx archives require the Windows Event Log service".into());
+    }
+    let config = Config::load().unwrap_or_default();
+    let dir = match (&args.dir, &config.archive_dir) {
+        (Some(dir), _) => dir.clone(),
+        (None, Some(dir)) => PathBuf::from(dir),
+        (None, None) => config::config_dir()
+            .ok_or("Could not determine the archive folder; pass --dir.")?
+            .join("archive"),
+    };
+    let logs = if args.log.is_empty() {
+        config.archive_logs.clone()
+    } else {
+        args.log.clone()
+    };
+    if logs.is_empty() {
+        return Err("No channels to archive; pass --log or set archive_logs.".into());
+    }
+    let mut state = ArchiveState::load(&dir)?;
+    let mut archivers = Vec::new();
+    for channel in &logs {
+        let channel_dir = dir.join(helpers::sanitize_filename(channel));
+        fs::create_dir_all(&channel_dir)
+            .map_err(|e| format!("Failed to create {}: {}", channel_dir.display(), e))?;
+        archivers.push(ChannelArchiver {
+            channel: channel.clone(),
+            dir: channel_dir,
+            segment: None,
+            #[cfg(target_os = "windows")]
+            last_export: Utc::now(),
+        });
+    }
+    let rotation = Rotation {
+        max_bytes: args.max_size_mb * 1024 * 1024,
+        max_age: Duration::hours(args.rotate_hours as i64),
+    };
+    let interval = Duration::seconds(args.interval as i64);
+    report(&format!(
+        "Archiving {} to {} every {} seconds",
+        logs.join(", "),
+        dir.display(),
+        args.interval
+    ));
+
+    loop {
+        for archiver in &mut archivers {
+            let last = state.last_record.get(&archiver.channel).copied();
+            let result = match args.format {
+                ArchiveFormat::Jsonl => archiver.archive_jsonl(last, &rotation, interval),
+                ArchiveFormat::Evtx => archiver.archive_evtx(last, &rotation, interval, args.once),
+            };
+            match result {
+                Ok(Some(record)) => {
+                    state.last_record.insert(archiver.channel.clone(), record);
+                }
+                Ok(None) => {}
+                Err(msg) => report(&format!("{}: {}", archiver.channel, msg)),
+            }
+        }
+        state.save(&dir)?;
+        if args.once {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_secs(args.interval));
+    }
+    for segment in archivers
+        .into_iter()
+        .filter_map(|archiver| archiver.segment)
+    {
+        segment.finish()?;
+    }
+    Ok(())
+}
+
+/// The platform's query for the records after `last`, or all of them. Journal matches cannot
+/// compare record numbers, so there all entries are read back to `last`.
+fn after_query(last: Option<u64>) -> String {
+    #[cfg(target_os = "windows")]
+    return match last {
+        Some(last) => format!("*[System[EventRecordID>{}]]", last),
+        None => "*".to_string(),
+    };
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = last;
+        String::new()
+    }
+}
+
+/// A new archive file named after the channel and the local time, numbered when several are
+/// started within a second.
+fn new_archive_path(dir: &Path, channel: &str, extension: &str) -> Result<PathBuf, String> {
+    let stem = format!(
+        "{}-{}",
+        helpers::sanitize_filename(channel),
+        Local::now().format("%Y%m%d-%H%M%S")
+    );
+    (0..1000)
+        .map(|n| match n {
+            0 => dir.join(format!("{}.{}", stem, extension)),
+            n => dir.join(format!("{}-{}.{}", stem, n, extension)),
+        })
+        .find(|path| !path.exists() && !path.with_extension(format!("{}.gz", extension)).exists())
+        .ok_or_else(|| format!("Too many archives named {} in {}", stem, dir.display()))
+}
+
+/// Writes a gzip copy of `source` to `target`.
+#[cfg(target_os = "windows")]
+fn compress(source: &Path, target: &Path) -> Result<(), String> {
+    let mut input =
+        File::open(source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    let output = File::create_new(target)
+        .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let mut encoder = GzEncoder::new(output, Compression::default());
+    io::copy(&mut input, &mut encoder)
+        .and_then(|_| encoder.finish())
+        .map(|_| ())
+        .map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+}
+
+/// Writes a progress line to stderr, leaving stdout free.
+fn report(message: &str) {
+    eprintln!("{} {}", Local::now().format("%Y-%m-%d %H:%M:%S"), message);
+}
@@ -0,0 +1,165 @@
+use crate::helpers;
+
+/// Security event IDs and the audit subcategory (by GUID, as `auditpol /r` lists them) that
+/// has to be on for Windows to log them.
+const EVENT_SUBCATEGORIES: &[(&[u32], &str)] = &[
+    (&[4608, 4616], "{0CCE9210-69AE-11D9-BED3-505054503030}"),
+    (&[4697], "{0CCE9211-69AE-11D9-BED3-505054503030}"),
+    (
+        &[4624, 4625, 4648],
+        "{0CCE9215-69AE-11D9-BED3-505054503030}",
+    ),
+    (&[4634, 4647], "{0CCE9216-69AE-11D9-BED3-505054503030}"),
+    (&[4672], "{0CCE921B-69AE-11D9-BED3-505054503030}"),
+    (
+        &[4778, 4779, 4800, 4801],
+        "{0CCE921C-69AE-11D9-BED3-505054503030}",
+    ),
+    (
+        &[4656, 4658, 4660, 4663],
+        "{0CCE921D-69AE-11D9-BED3-505054503030}",
+    ),
+    (&[4657], "{0CCE921E-69AE-11D9-BED3-505054503030}"),
+    (&[5140], "{0CCE9224-69AE-11D9-BED3-505054503030}"),
+    (&[5152, 5153], "{0CCE9225-69AE-11D9-BED3-505054503030}"),
+    (
+        &[5156, 5157, 5158],
+        "{0CCE9226-69AE-11D9-BED3-505054503030}",
+    ),
+    (
+        &[4698, 4699, 4700, 4701, 4702],
+        "{0CCE9227-69AE-11D9-BED3-505054503030}",
+    ),
+    (&[4673, 4674], "{0CCE9228-69AE-11D9-BED3-505054503030}"),
+    (&[4688, 4696], "{0CCE922B-69AE-11D9-BED3-505054503030}"),
+    (&[4689], "{0CCE922C-69AE-11D9-BED3-505054503030}"),
+    (&[4719], "{0CCE922F-69AE-11D9-BED3-505054503030}"),
+    (
+        &[4670, 4703, 4704],
+        "{0CCE9231-69AE-11D9-BED3-505054503030}",
+    ),
+    (
+        &[4946, 4947, 4948, 4950],
+        "{0CCE9232-69AE-11D9-BED3-505054503030}",
+    ),
+    (
+        &[
+            4720, 4722, 4723, 4724, 4725, 4726, 4738, 4740, 4767, 4781, 4798,
+        ],
+        "{0CCE9235-69AE-11D9-BED3-505054503030}",
+    ),
+    (
+        &[4741, 4742, 4743],
+        "{0CCE9236-69AE-11D9-BED3-505054503030}",
+    ),
+    (
+        &[4727, 4728, 4731, 4732, 4735, 4737, 4756, 4799],
+        "{0CCE9237-69AE-11D9-BED3-505054503030}",
+    ),
+    (&[4662], "{0CCE923B-69AE-11D9-BED3-505054503030}"),
+    (&[5136], "{0CCE923C-69AE-11D9-BED3-505054503030}"),
+    (&[4776], "{0CCE923F-69AE-11D9-BED3-505054503030}"),
+    (&[4769, 4770], "{0CCE9240-69AE-11D9-BED3-505054503030}"),
+    (
+        &[4768, 4771, 4772],
+        "{0CCE9242-69AE-11D9-BED3-505054503030}",
+    ),
+    (&[5145], "{0CCE9244-69AE-11D9-BED3-505054503030}"),
+    (&[6416], "{0CCE9248-69AE-11D9-BED3-505054503030}"),
+    (&[4627], "{0CCE9249-69AE-11D9-BED3-505054503030}"),
+];
+
+/// One audit policy subcategory and its current setting.
+#[derive(Debug, Clone)]
+pub struct AuditSubcategory {
+    pub name: String,
+    /// Inclusion setting, such as "Success and Failure" or "No Auditing".
+    pub setting: String,
+    /// Event IDs of the current filter this subcategory logs.
+    pub needed_for: Vec<u32>,
+}
+
+impl AuditSubcategory {
+    /// auditpol writes the settings in the system language; only the English "No Auditing"
+    /// is recognized as off.
+    pub fn enabled(&self) -> bool {
+        !self.setting.eq_ignore_ascii_case("No Auditing")
+    }
+
+    /// Whether the current filter asks for events this subcategory does not log.
+    pub fn flagged(&self) -> bool {
+        !self.needed_for.is_empty() && !self.enabled()
+    }
+}
+
+/// State of the audit policy view.
+#[derive(Debug, Clone, Default)]
+pub struct AuditPolicyView {
+    /// Subcategories the filter needs but are off first, then the others the filter needs,
+    /// then the rest in auditpol's order.
+    pub subcategories: Vec<AuditSubcategory>,
+    pub selection: usize,
+}
+
+impl AuditPolicyView {
+    /// Reads the policy with `auditpol /get /category:* /r`, which needs an elevated prompt,
+    /// and marks the subcategories logging the event IDs `filter` includes.
+    pub fn capture(filter: Option<&str>) -> Result<Self, String> {
+        let output = std::process::Command::new("auditpol")
+            .args(["/get", "/category:*", "/r"])
+            .output()
+            .map_err(|e| format!("Failed to run auditpol: {}", e))?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() {
+            return Err(format!(
+                "auditpol could not read the audit policy; it needs an elevated prompt (Run as administrator).\n\n{}",
+                text.trim()
+            ));
+        }
+        let included = filter
+            .and_then(|spec| helpers::parse_event_id_spec(spec).ok())
+            .map(|spec| spec.include)
+            .unwrap_or_default();
+        let mut subcategories: Vec<AuditSubcategory> = text
+            .lines()
+            .filter_map(|line| {
+                // Machine Name, Policy Target, Subcategory, Subcategory GUID, Inclusion Setting,
+                // Exclusion Setting. The header and blank lines have no GUID.
+                let columns: Vec<&str> = line.split(',').map(str::trim).collect();
+                let guid = columns.get(3).filter(|guid| guid.starts_with('{'))?;
+                let guid = guid.to_uppercase();
+                let needed_for = EVENT_SUBCATEGORIES
+                    .iter()
+                    .filter(|(_, subcategory)| *subcategory == guid)
+                    .flat_map(|(ids, _)| ids.iter().copied())
+                    .filter(|id| {
+                        included
+                            .iter()
+                            .any(|&(start, end)| (start..=end).contains(id))
+                    })
+                    .collect();
+                Some(AuditSubcategory {
+                    name: columns.get(2)?.to_string(),
+                    setting: columns.get(4)?.to_string(),
+                    needed_for,
+                })
+            })
+            .collect();
+        if subcategories.is_empty() {
+            return Err("auditpol listed no audit subcategories.".to_string());
+        }
+        subcategories
+            .sort_by_key(|subcategory| (!subcategory.flagged(), subcategory.needed_for.is_empty()));
+        Ok(Self {
+            subcategories,
+            selection: 0,
+        })
+    }
+
+    pub fn flagged_count(&self) -> usize {
+        self.subcategories
+            .iter()
+            .filter(|subcategory| subcategory.flagged())
+            .count()
+    }
+}
@@ -0,0 +1,376 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::analysis;
+use crate::helpers;
+use crate::models::DisplayEvent;
+use crate::security;
+
+/// Kerberos TGT (4768) and service ticket (4769) requests, failed pre-authentication (4771),
+/// NTLM credential validation (4776) and the logons they lead to (4624, 4625).
+const AUTH_EVENT_IDS: &[u32] = &[4768, 4769, 4771, 4776, 4624, 4625];
+
+/// How far before and after the selected event the flow is read.
+pub const FLOW_WINDOW_MINUTES: i64 = 30;
+
+/// Most events read from the Security log.
+pub const MAX_AUTH_EVENTS: usize = 5000;
+
+/// Kerberos error codes in the Status field of 4768, 4769 and 4771, with what they usually
+/// mean.
+const KERBEROS_FAILURES: &[(u32, &str, &str)] = &[
+    (
+        0x6,
+        "KDC_ERR_C_PRINCIPAL_UNKNOWN",
+        "the user name does not exist",
+    ),
+    (
+        0x7,
+        "KDC_ERR_S_PRINCIPAL_UNKNOWN",
+        "no account has the requested service principal name",
+    ),
+    (
+        0xC,
+        "KDC_ERR_POLICY",
+        "a logon restriction (workstation or hours) denied the request",
+    ),
+    (
+        0xE,
+        "KDC_ERR_ETYPE_NOTSUPP",
+        "no encryption type is supported by both sides",
+    ),
+    (
+        0x10,
+        "KDC_ERR_PADATA_TYPE_NOSUPP",
+        "smart card logon failed or is not set up on the domain controller",
+    ),
+    (
+        0x12,
+        "KDC_ERR_CLIENT_REVOKED",
+        "the account is disabled, expired or locked out",
+    ),
+    (0x17, "KDC_ERR_KEY_EXPIRED", "the password has expired"),
+    (0x18, "KDC_ERR_PREAUTH_FAILED", "wrong password"),
+    (
+        0x1B,
+        "KDC_ERR_MUST_USE_USER2USER",
+        "the server principal only accepts user-to-user tickets",
+    ),
+    (
+        0x1F,
+        "KRB_AP_ERR_BAD_INTEGRITY",
+        "the ticket failed its integrity check",
+    ),
+    (0x20, "KRB_AP_ERR_TKT_EXPIRED", "the ticket has expired"),
+    (
+        0x25,
+        "KRB_AP_ERR_SKEW",
+        "the client clock differs too much from the domain controller",
+    ),
+    (
+        0x29,
+        "KRB_AP_ERR_MODIFIED",
+        "the ticket could not be decrypted, often a duplicate service principal name",
+    ),
+];
+
+/// Kerberos ticket option flags, numbered from the most significant bit as RFC 4120 does.
+const TICKET_OPTIONS: &[(u32, &str)] = &[
+    (0x4000_0000, "forwardable"),
+    (0x2000_0000, "forwarded"),
+    (0x1000_0000, "proxiable"),
+    (0x0800_0000, "proxy"),
+    (0x0400_0000, "allow-postdate"),
+    (0x0200_0000, "postdated"),
+    (0x0080_0000, "renewable"),
+    (0x0002_0000, "constrained-delegation"),
+    (0x0001_0000, "canonicalize"),
+    (0x0000_0020, "disable-transited-check"),
+    (0x0000_0010, "renewable-ok"),
+    (0x0000_0008, "enc-tkt-in-skey"),
+    (0x0000_0002, "renew"),
+    (0x0000_0001, "validate"),
+];
+
+/// Ticket encryption types; DES and RC4 are flagged as weak.
+const ENCRYPTION_TYPES: &[(u32, &str)] = &[
+    (0x1, "DES-CBC-CRC (weak)"),
+    (0x3, "DES-CBC-MD5 (weak)"),
+    (0x11, "AES128-CTS-HMAC-SHA1-96"),
+    (0x12, "AES256-CTS-HMAC-SHA1-96"),
+    (0x17, "RC4-HMAC (weak)"),
+    (0x18, "RC4-HMAC-EXP (weak)"),
+];
+
+/// One step of an authentication: a ticket request, a credential validation or a logon.
+#[derive(Debug, Clone)]
+pub struct AuthStep {
+    pub time: DateTime<Utc>,
+    /// "Kerberos", "NTLM" or "Logon".
+    pub protocol: &'static str,
+    pub step: String,
+    /// Domain controller or server that logged the step.
+    pub computer: String,
+    /// Address or workstation the request came from.
+    pub client: Option<String>,
+    /// Why the step failed.
+    pub failure: Option<String>,
+    /// Decoded fields for the detail pane.
+    pub details: Vec<String>,
+}
+
+impl AuthStep {
+    fn of(event: &DisplayEvent) -> Option<Self> {
+        let field = |name: &str| security::field(event, name).map(str::to_string);
+        let kerberos_details = || {
+            let mut details = Vec::new();
+            if let Some(options) = field("TicketOptions") {
+                details.push(format!("Ticket options: {}", ticket_options(&options)));
+            }
+            if let Some(encryption) = field("TicketEncryptionType") {
+                details.push(format!("Encryption:     {}", encryption_type(&encryption)));
+            }
+            if let Some(pre_auth) = field("PreAuthType") {
+                details.push(format!("Pre-auth type:  {}", pre_auth));
+            }
+            details
+        };
+        let kerberos_failure = || {
+            field("Status")
+                .and_then(|status| parse_code(&status))
+                .filter(|&code| code != 0)
+                .map(kerberos_failure)
+        };
+        let client = field("IpAddress")
+            .map(|address| address.trim_start_matches("::ffff:").to_string())
+            .filter(|address| address != "::1" && address != "127.0.0.1");
+        let (protocol, step, client, failure, details) = match event.event_id {
+            4768 => (
+                "Kerberos",
+                "AS-REQ: ticket-granting ticket".to_string(),
+                client,
+                kerberos_failure(),
+                kerberos_details(),
+            ),
+            4769 => (
+                "Kerberos",
+                format!(
+                    "TGS-REQ: service ticket for {}",
+                    field("ServiceName").unwrap_or_else(|| "(unknown service)".to_string())
+                ),
+                client,
+                kerberos_failure(),
+                kerberos_details(),
+            ),
+            4771 => (
+                "Kerberos",
+                "AS-REQ: pre-authentication".to_string(),
+                client,
+                kerberos_failure().or_else(|| Some("pre-authentication failed".to_string())),
+                kerberos_details(),
+            ),
+            4776 => (
+                "NTLM",
+                "Credential validation".to_string(),
+                field("Workstation"),
+                field("Status")
+                    .filter(|status| parse_code(status) != Some(0))
+                    .and_then(|_| security::failure_reason(event)),
+                field("PackageName")
+                    .map(|package| vec![format!("Package:        {}", package)])
+                    .unwrap_or_default(),
+            ),
+            4624 | 4625 => {
+                let mut details = Vec::new();
+                if let Some(logon_type) = security::logon_type(event) {
+                    details.push(format!("Logon type:     {}", logon_type));
+                }
+                for (label, name) in [
+                    ("Package:        ", "AuthenticationPackageName"),
+                    ("Logon process:  ", "LogonProcessName"),
+                    ("Workstation:    ", "WorkstationName"),
+                ] {
+                    if let Some(value) = field(name) {
+                        details.push(format!("{}{}", label, value));
+                    }
+                }
+                let step = format!(
+                    "{} via {}",
+                    if event.event_id == 4624 {
+                        "Logon"
+                    } else {
+                        "Failed logon"
+                    },
+                    field("AuthenticationPackageName").unwrap_or_else(|| "?".to_string())
+                );
+                let failure = (event.event_id == 4625).then(|| {
+                    security::failure_reason(event).unwrap_or_else(|| "logon failed".to_string())
+                });
+                (
+                    "Logon",
+                    step,
+                    security::source_address(event).or_else(|| field("WorkstationName")),
+                    failure,
+                    details,
+                )
+            }
+            _ => return None,
+        };
+        Some(Self {
+            time: event.timestamp?,
+            protocol,
+            step,
+            computer: event.computer.clone(),
+            client,
+            failure,
+            details,
+        })
+    }
+
+    /// Describes the step for the detail pane.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Time:           {}", helpers::format_local_time(self.time)),
+            format!("Protocol:       {}", self.protocol),
+            format!("Step:           {}", self.step),
+            format!("Logged by:      {}", self.computer),
+            format!(
+                "Client:         {}",
+                self.client.as_deref().unwrap_or("(not logged)")
+            ),
+            format!(
+                "Result:         {}",
+                self.failure
+                    .as_deref()
+                    .map_or("Succeeded".to_string(), |failure| format!(
+                        "Failed: {}",
+                        failure
+                    ))
+            ),
+        ];
+        lines.extend(self.details.iter().cloned());
+        lines
+    }
+}
+
+/// State of the authentication flow view.
+#[derive(Debug, Clone, Default)]
+pub struct AuthFlowView {
+    /// Account name without its domain or realm.
+    pub account: String,
+    /// Oldest first.
+    pub steps: Vec<AuthStep>,
+    /// Why the Security log could not be read.
+    pub unavailable: Option<String>,
+    pub selection: usize,
+    /// Step whose decoded fields are shown instead of the sequence.
+    pub open_step: Option<usize>,
+    pub detail_scroll: usize,
+}
+
+impl AuthFlowView {
+    /// Keeps the authentication events of `account` and orders them into a sequence.
+    pub fn new(account: String, events: &[DisplayEvent], unavailable: Option<String>) -> Self {
+        let mut steps: Vec<AuthStep> = events
+            .iter()
+            .filter(|event| {
+                security::field(event, "TargetUserName")
+                    .is_some_and(|user| bare_account(user).eq_ignore_ascii_case(&account))
+            })
+            .filter_map(AuthStep::of)
+            .collect();
+        steps.sort_by_key(|step| step.time);
+        Self {
+            account,
+            steps,
+            unavailable,
+            ..Self::default()
+        }
+    }
+
+    /// Time since the previous step, as "+0.042s" or "+5m".
+    pub fn delta(&self, index: usize) -> String {
+        let Some(previous) = index.checked_sub(1).and_then(|i| self.steps.get(i)) else {
+            return String::new();
+        };
+        let delta: Duration = self.steps[index].time - previous.time;
+        if delta < Duration::minutes(1) {
+            format!("+{:.3}s", delta.num_milliseconds() as f64 / 1000.0)
+        } else {
+            format!("+{}", helpers::format_duration(delta))
+        }
+    }
+}
+
+/// The account an authentication event is about, without its domain or realm.
+pub fn account_of(event: &DisplayEvent) -> Option<String> {
+    if !AUTH_EVENT_IDS.contains(&event.event_id) {
+        return None;
+    }
+    security::field(event, "TargetUserName")
+        .map(bare_account)
+        .filter(|account| !account.is_empty())
+}
+
+/// Reads the authentication events within `FLOW_WINDOW_MINUTES` of `time`. The account is
+/// matched after reading, since 4769 names it with its realm ("user@REALM").
+pub fn query(time: DateTime<Utc>) -> String {
+    let window = Duration::minutes(FLOW_WINDOW_MINUTES);
+    let ids: Vec<String> = AUTH_EVENT_IDS
+        .iter()
+        .map(|id| format!("EventID={}", id))
+        .collect();
+    format!(
+        "*[System[({}) and TimeCreated[@SystemTime>='{}' and @SystemTime<='{}']]]",
+        ids.join(" or "),
+        analysis::xpath_time(time - window),
+        analysis::xpath_time(time + window)
+    )
+}
+
+/// Strips "DOMAIN\" and "@REALM" from an account name.
+fn bare_account(name: &str) -> String {
+    let name = name.rsplit('\\').next().unwrap_or(name);
+    name.split('@').next().unwrap_or(name).to_string()
+}
+
+/// Reads a hex code such as "0x18".
+fn parse_code(value: &str) -> Option<u32> {
+    u32::from_str_radix(value.trim_start_matches("0x").trim_start_matches("0X"), 16).ok()
+}
+
+/// Names a Kerberos error and says what it usually means.
+fn kerberos_failure(code: u32) -> String {
+    match KERBEROS_FAILURES
+        .iter()
+        .find(|(known, _, _)| *known == code)
+    {
+        Some((_, name, meaning)) => format!("{} (0x{:X} {})", meaning, code, name),
+        None => format!("Kerberos error 0x{:X}", code),
+    }
+}
+
+/// Lists the flags set in a ticket options value such as "0x40810010".
+fn ticket_options(value: &str) -> String {
+    let Some(options) = parse_code(value) else {
+        return value.to_string();
+    };
+    let names: Vec<&str> = TICKET_OPTIONS
+        .iter()
+        .filter(|(flag, _)| options & flag != 0)
+        .map(|(_, name)| *name)
+        .collect();
+    if names.is_empty() {
+        value.to_string()
+    } else {
+        format!("{} ({})", value, names.join(", "))
+    }
+}
+
+fn encryption_type(value: &str) -> String {
+    parse_code(value)
+        .and_then(|code| ENCRYPTION_TYPES.iter().find(|(known, _)| *known == code))
+        .map_or_else(
+            || value.to_string(),
+            |(_, name)| format!("{} ({})", value, name),
+        )
+}
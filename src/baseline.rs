@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{BaselineAction, BaselineCaptureArgs, BaselineCompareArgs};
+use crate::digest::{self, Section, Table};
+use crate::helpers;
+use crate::models::{DisplayEvent, EventLevel};
+use crate::source::{BatchTuner, EventSource, PlatformSource};
+
+/// Rows in each table of the drift report.
+const MAX_DRIFT_ROWS: usize = 50;
+
+/// Events an event type needs in one of the two windows before a change of its frequency is
+/// reported, so that rare events do not show up as drift.
+const MIN_CHANGED_EVENTS: usize = 5;
+
+/// Counts per event type of one or more logs over a window, as saved by `baseline capture`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Baseline {
+    computer: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    logs: Vec<LogBaseline>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LogBaseline {
+    log: String,
+    /// Start of the window actually counted; later than the baseline's start when reading
+    /// stopped at `--max-events`.
+    since: DateTime<Utc>,
+    events: usize,
+    /// Why the log could not be read completely.
+    #[serde(default)]
+    error: Option<String>,
+    /// Most events first.
+    types: Vec<EventType>,
+}
+
+/// Events of one source and event ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EventType {
+    source: String,
+    event_id: u32,
+    level: EventLevel,
+    count: usize,
+    /// First line of the most recent event's message.
+    message: String,
+}
+
+impl LogBaseline {
+    fn days(&self, end: DateTime<Utc>) -> f64 {
+        (end - self.since).num_seconds().max(1) as f64 / 86400.0
+    }
+
+    fn find(&self, event_type: &EventType) -> Option<&EventType> {
+        self.types.iter().find(|other| {
+            other.event_id == event_type.event_id && other.source == event_type.source
+        })
+    }
+}
+
+/// Saves a baseline, or compares this machine or another baseline with one.
+pub fn run(action: &BaselineAction) -> Result<(), Box<dyn Error>> {
+    match action {
+        BaselineAction::Capture(args) => capture(args),
+        BaselineAction::Compare(args) => compare(args),
+    }
+}
+
+fn capture(args: &BaselineCaptureArgs) -> Result<(), Box<dyn Error>> {
+    let logs = if args.log.is_empty() {
+        digest::default_logs()
+    } else {
+        args.log.clone()
+    };
+    let baseline = count_logs(&logs, Duration::days(args.days as i64), args.max_events);
+    for log in &baseline.logs {
+        if let Some(error) = &log.error {
+            eprintln!("{}: not read completely: {}", log.log, error);
+        }
+    }
+    let json = serde_json::to_string_pretty(&baseline)?;
+    fs::write(&args.output, json)
+        .map_err(|e| format!("Failed to write '{}': {}", args.output.display(), e))?;
+    eprintln!(
+        "Saved {} event types of {} events to {}",
+        baseline
+            .logs
+            .iter()
+            .map(|log| log.types.len())
+            .sum::<usize>(),
+        baseline.logs.iter().map(|log| log.events).sum::<usize>(),
+        args.output.display()
+    );
+    Ok(())
+}
+
+fn compare(args: &BaselineCompareArgs) -> Result<(), Box<dyn Error>> {
+    let baseline = load(&args.baseline)?;
+    let current = match &args.against {
+        Some(path) => load(path)?,
+        None => {
+            let logs = if args.log.is_empty() {
+                baseline.logs.iter().map(|log| log.log.clone()).collect()
+            } else {
+                args.log.clone()
+            };
+            let window = args
+                .days
+                .map(|days| Duration::days(days as i64))
+                .unwrap_or(baseline.end - baseline.start);
+            count_logs(&logs, window, args.max_events)
+        }
+    };
+
+    let title = format!("Baseline Drift: {}", current.computer);
+    let intro = format!(
+        "{} from {} to {}, compared with the baseline of {} from {} to {}. Frequencies are \
+         compared per day.",
+        current.computer,
+        helpers::format_local_time(current.start),
+        helpers::format_local_time(current.end),
+        baseline.computer,
+        helpers::format_local_time(baseline.start),
+        helpers::format_local_time(baseline.end)
+    );
+    let sections: Vec<Section> = current
+        .logs
+        .iter()
+        .map(|log| {
+            let base = baseline.logs.iter().find(|base| base.log == log.log);
+            drift_section(log, current.end, base, baseline.end, args.factor)
+        })
+        .collect();
+    digest::write_report(
+        args.format,
+        args.output.as_deref(),
+        &title,
+        &intro,
+        &sections,
+    )
+}
+
+fn load(path: &Path) -> Result<Baseline, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+    serde_json::from_str(&text)
+        .map_err(|e| format!("'{}' is not a saved baseline: {}", path.display(), e))
+}
+
+/// Counts the event types of each log over the `window` up to now.
+fn count_logs(logs: &[String], window: Duration, max_events: usize) -> Baseline {
+    let end = Utc::now();
+    let start = end - window;
+    Baseline {
+        computer: helpers::local_host_name(),
+        start,
+        end,
+        logs: logs
+            .iter()
+            .map(|log| count_log(log, start, max_events))
+            .collect(),
+    }
+}
+
+/// Reads the log newest first back to `start`.
+fn count_log(log: &str, start: DateTime<Utc>, max_events: usize) -> LogBaseline {
+    let mut types: HashMap<(String, u32), EventType> = HashMap::new();
+    let mut result = LogBaseline {
+        log: log.to_string(),
+        since: start,
+        events: 0,
+        error: None,
+        types: Vec::new(),
+    };
+    let mut source = PlatformSource::default();
+    if let Err(e) = source.query(log, &digest::since_query(start), true) {
+        result.error = Some(e);
+        return result;
+    }
+    let mut tuner = BatchTuner::bulk();
+    let mut oldest = None;
+    'read: while result.events < max_events {
+        let batch = match tuner.next_batch(&mut source, max_events - result.events) {
+            Ok(batch) if batch.is_empty() => break,
+            Ok(batch) => batch,
+            Err(e) => {
+                result.error = Some(e);
+                break;
+            }
+        };
+        for event in batch {
+            let Some(time) = event.timestamp else {
+                continue;
+            };
+            if time < start {
+                break 'read;
+            }
+            result.events += 1;
+            oldest = Some(time);
+            add(&mut types, &event);
+        }
+        if result.events >= max_events {
+            result.since = oldest.unwrap_or(start);
+        }
+    }
+    result.types = types.into_values().collect();
+    result
+        .types
+        .sort_by(|a, b| b.count.cmp(&a.count).then(a.event_id.cmp(&b.event_id)));
+    result
+}
+
+/// Events are read newest first, so the first event of a type gives its message.
+fn add(types: &mut HashMap<(String, u32), EventType>, event: &DisplayEvent) {
+    let event_type = types
+        .entry((event.source.clone(), event.event_id))
+        .or_insert_with(|| EventType {
+            source: event.source.clone(),
+            event_id: event.event_id,
+            level: event.level,
+            count: 0,
+            message: event
+                .formatted_message
+                .as_deref()
+                .unwrap_or(&event.message)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+        });
+    event_type.count += 1;
+    if event.level.severity_rank() < event_type.level.severity_rank() {
+        event_type.level = event.level;
+    }
+}
+
+/// Builds the new, absent and changed event types tables of one log.
+fn drift_section(
+    log: &LogBaseline,
+    end: DateTime<Utc>,
+    base: Option<&LogBaseline>,
+    base_end: DateTime<Utc>,
+    factor: f64,
+) -> Section {
+    let mut notes = Vec::new();
+    if let Some(error) = &log.error {
+        notes.push(format!("Not read completely: {}", error));
+    }
+    let Some(base) = base else {
+        notes.push("The baseline does not cover this log.".to_string());
+        return (log.log.clone(), notes, Vec::new());
+    };
+    if let Some(error) = &base.error {
+        notes.push(format!("Not read completely for the baseline: {}", error));
+    }
+    let (days, base_days) = (log.days(end), base.days(base_end));
+    notes.push(format!(
+        "{} events ({:.1} per day), against {} ({:.1} per day) in the baseline.",
+        log.events,
+        log.events as f64 / days,
+        base.events,
+        base.events as f64 / base_days
+    ));
+    let by_severity = |a: &&EventType, b: &&EventType| {
+        a.level
+            .severity_rank()
+            .cmp(&b.level.severity_rank())
+            .then(b.count.cmp(&a.count))
+    };
+    let type_row = |event_type: &EventType, days: f64| {
+        vec![
+            event_type.source.clone(),
+            event_type.event_id.to_string(),
+            event_type.level.display_name(),
+            event_type.count.to_string(),
+            format!("{:.1}", event_type.count as f64 / days),
+            helpers::truncate_at_word(&event_type.message, 100),
+        ]
+    };
+
+    let mut new_types: Vec<&EventType> = log
+        .types
+        .iter()
+        .filter(|event_type| base.find(event_type).is_none())
+        .collect();
+    new_types.sort_by(by_severity);
+    let new_table = Table {
+        title: "New event types".to_string(),
+        header: vec!["Source", "Event ID", "Level", "Count", "Per day", "Message"],
+        rows: new_types
+            .into_iter()
+            .take(MAX_DRIFT_ROWS)
+            .map(|event_type| type_row(event_type, days))
+            .collect(),
+        empty: "No event types that are not in the baseline.",
+    };
+
+    let mut absent_types: Vec<&EventType> = base
+        .types
+        .iter()
+        .filter(|event_type| log.find(event_type).is_none())
+        .collect();
+    absent_types.sort_by(by_severity);
+    let absent_table = Table {
+        title: "Absent event types".to_string(),
+        header: vec![
+            "Source", "Event ID", "Level", "Baseline", "Per day", "Message",
+        ],
+        rows: absent_types
+            .into_iter()
+            .take(MAX_DRIFT_ROWS)
+            .map(|event_type| type_row(event_type, base_days))
+            .collect(),
+        empty: "Every event type of the baseline still occurs.",
+    };
+
+    // Ratio of the per-day rates, now over the baseline.
+    let mut changed: Vec<(&EventType, f64, f64)> = log
+        .types
+        .iter()
+        .filter_map(|event_type| {
+            let base_type = base.find(event_type)?;
+            if event_type.count.max(base_type.count) < MIN_CHANGED_EVENTS {
+                return None;
+            }
+            let rate = event_type.count as f64 / days;
+            let base_rate = base_type.count as f64 / base_days;
+            let ratio = rate / base_rate;
+            (ratio >= factor || ratio <= 1.0 / factor).then_some((event_type, base_rate, rate))
+        })
+        .collect();
+    changed.sort_by(|a, b| {
+        let spread = |(_, base_rate, rate): &(&EventType, f64, f64)| (rate / base_rate).ln().abs();
+        spread(b).total_cmp(&spread(a))
+    });
+    let changed_table = Table {
+        title: "Changed frequency".to_string(),
+        header: vec![
+            "Source",
+            "Event ID",
+            "Level",
+            "Baseline per day",
+            "Per day",
+            "Change",
+            "Message",
+        ],
+        rows: changed
+            .into_iter()
+            .take(MAX_DRIFT_ROWS)
+            .map(|(event_type, base_rate, rate)| {
+                let change = if rate >= base_rate {
+                    format!("x{:.1}", rate / base_rate)
+                } else {
+                    format!("/{:.1}", base_rate / rate)
+                };
+                vec![
+                    event_type.source.clone(),
+                    event_type.event_id.to_string(),
+                    event_type.level.display_name(),
+                    format!("{:.1}", base_rate),
+                    format!("{:.1}", rate),
+                    change,
+                    helpers::truncate_at_word(&event_type.message, 100),
+                ]
+            })
+            .collect(),
+        empty: "No event type changed in frequency by that factor.",
+    };
+
+    (
+        log.log.clone(),
+        notes,
+        vec![new_table, absent_table, changed_table],
+    )
+}
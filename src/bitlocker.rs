@@ -0,0 +1,174 @@
+use crate::models::{DisplayEvent, EventLevel};
+
+const BITLOCKER_PROVIDERS: [&str; 2] = [
+    "Microsoft-Windows-BitLocker-API",
+    "Microsoft-Windows-BitLocker-Driver",
+];
+const TPM_PROVIDERS: [&str; 2] = ["Microsoft-Windows-TPM-WMI", "TPM"];
+
+/// Phrases of BitLocker messages (lowercase, all must appear) with the state change they
+/// report and whether protection is on afterwards; the first match wins. The text is
+/// matched rather than event IDs because Windows releases number some of these events
+/// differently.
+const BITLOCKER_CHANGES: &[(&[&str], &str, Option<bool>)] = &[
+    (&["key protector", "creat"], "Key protector added", None),
+    (&["key protector", "add"], "Key protector added", None),
+    (&["key protector", "remov"], "Key protector removed", None),
+    (&["key protector", "delet"], "Key protector removed", None),
+    (
+        &["recovery", "backed up"],
+        "Recovery information backed up",
+        None,
+    ),
+    (&["recovery", "backup"], "Recovery information backup", None),
+    (&["recovery", "escrow"], "Recovery information backup", None),
+    (&["recovery"], "Recovery used", None),
+    (&["suspend"], "Protection suspended", Some(false)),
+    (&["resume"], "Protection resumed", Some(true)),
+    (&["decryption", "start"], "Decryption started", None),
+    (
+        &["decryption", "complete"],
+        "Decryption completed",
+        Some(false),
+    ),
+    (&["encryption", "start"], "Encryption started", None),
+    (&["encryption", "pause"], "Encryption paused", None),
+    (
+        &["encryption", "complete"],
+        "Encryption completed",
+        Some(true),
+    ),
+    (&["unlock"], "Volume unlocked", None),
+    (&[" locked"], "Volume locked", None),
+];
+
+/// Phrases of TPM messages with the state change they report, as for BitLocker.
+const TPM_CHANGES: &[(&[&str], &str, Option<bool>)] = &[
+    (&["lockout"], "TPM lockout", None),
+    (&["clear"], "TPM cleared", None),
+    (&["ownership"], "TPM ownership changed", None),
+    (&["provision"], "TPM provisioned", None),
+    (&["ready"], "TPM ready", None),
+    (&["secure boot"], "Secure Boot configuration", None),
+];
+
+/// Key protector types, as numbered by `Win32_EncryptableVolume`.
+const PROTECTOR_TYPES: &[(u32, &str)] = &[
+    (0, "Unknown"),
+    (1, "TPM"),
+    (2, "External key"),
+    (3, "Recovery password"),
+    (4, "TPM and PIN"),
+    (5, "TPM and startup key"),
+    (6, "TPM, PIN and startup key"),
+    (7, "Public key"),
+    (8, "Passphrase"),
+    (9, "TPM certificate"),
+    (10, "Active Directory account"),
+];
+
+/// What a BitLocker or TPM event says changed.
+#[derive(Debug, Clone)]
+pub struct EncryptionChange {
+    /// "BitLocker" or "TPM".
+    pub component: &'static str,
+    pub change: &'static str,
+    /// Whether BitLocker protection is on after the change, when the change decides it.
+    pub protection: Option<bool>,
+    pub failed: bool,
+    pub volume: Option<String>,
+    pub protector: Option<String>,
+    pub error: Option<String>,
+}
+
+impl EncryptionChange {
+    /// Decodes the event if it is a BitLocker or TPM event reporting a known state change.
+    pub fn decode(event: &DisplayEvent) -> Option<Self> {
+        let provider = event.provider_name_original.as_str();
+        let (component, changes) = if BITLOCKER_PROVIDERS.contains(&provider) {
+            ("BitLocker", BITLOCKER_CHANGES)
+        } else if TPM_PROVIDERS.contains(&provider) {
+            ("TPM", TPM_CHANGES)
+        } else {
+            return None;
+        };
+        let message = event
+            .formatted_message
+            .as_deref()
+            .unwrap_or(&event.message)
+            .to_lowercase();
+        let (_, change, protection) = changes
+            .iter()
+            .find(|(phrases, _, _)| phrases.iter().all(|phrase| message.contains(phrase)))?;
+        let field = |name: &str| {
+            event
+                .event_data
+                .named()
+                .find(|(field, value)| field.eq_ignore_ascii_case(name) && !value.is_empty())
+                .map(|(_, value)| value.to_string())
+        };
+        let failed = matches!(event.level, EventLevel::Error | EventLevel::Critical)
+            || message.contains("fail");
+        Some(Self {
+            component,
+            change,
+            protection: if failed { None } else { *protection },
+            failed,
+            volume: field("VolumeMountPoint").or_else(|| field("VolumeName")),
+            protector: field("ProtectorType").map(|value| protector_name(&value)),
+            error: field("ErrorCode").filter(|code| !matches!(code.as_str(), "0" | "0x0")),
+        })
+    }
+
+    pub fn is_recovery(&self) -> bool {
+        self.change == "Recovery used"
+    }
+
+    /// Labeled fields shown in the preview.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            String::new(),
+            format!("--- {} State Change ---", self.component),
+            format!(
+                "Change:      {}{}",
+                self.change,
+                if self.failed { " (failed)" } else { "" }
+            ),
+        ];
+        if let Some(protection) = self.protection {
+            lines.push(format!(
+                "Protection:  {}",
+                if protection { "On" } else { "Off" }
+            ));
+        }
+        if let Some(volume) = &self.volume {
+            lines.push(format!("Volume:      {}", volume));
+        }
+        if let Some(protector) = &self.protector {
+            lines.push(format!("Protector:   {}", protector));
+        }
+        if let Some(error) = &self.error {
+            lines.push(format!("Error:       {}", error));
+        }
+        if self.is_recovery() {
+            lines.push(
+                "The volume needed its recovery key; check who requested it and why the TPM did not unlock it."
+                    .to_string(),
+            );
+        }
+        lines
+    }
+}
+
+/// Whether the event reports a BitLocker recovery, for highlighting it in the event list.
+pub fn is_recovery(event: &DisplayEvent) -> bool {
+    EncryptionChange::decode(event).is_some_and(|change| change.is_recovery())
+}
+
+fn protector_name(value: &str) -> String {
+    value
+        .parse::<u32>()
+        .ok()
+        .and_then(|code| PROTECTOR_TYPES.iter().find(|(known, _)| *known == code))
+        .map_or_else(|| value.to_string(), |(_, name)| name.to_string())
+}
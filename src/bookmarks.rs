@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config;
+use crate::helpers;
+use crate::models::DisplayEvent;
+
+const BOOKMARKS_FILE_NAME: &str = "bookmarks.json";
+
+/// An event marked with [b], identified by its channel and `EventRecordID`. The remaining
+/// fields describe the event in the bookmarks list without loading it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub channel: String,
+    pub record_id: u64,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub event_id: u32,
+    pub source: String,
+    pub summary: String,
+}
+
+impl Bookmark {
+    pub fn from_event(channel: &str, event: &DisplayEvent) -> Self {
+        let body = event.formatted_message.as_deref().unwrap_or(&event.message);
+        Self {
+            channel: channel.to_string(),
+            record_id: event.record_id,
+            timestamp: event.timestamp,
+            event_id: event.event_id,
+            source: event.source.clone(),
+            summary: helpers::message_summary(body),
+        }
+    }
+
+    pub fn matches(&self, channel: &str, record_id: u64) -> bool {
+        self.record_id == record_id && self.channel.eq_ignore_ascii_case(channel)
+    }
+}
+
+/// Returns the path of the bookmarks file, next to the configuration file.
+pub fn bookmarks_path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join(BOOKMARKS_FILE_NAME))
+}
+
+/// Loads the saved bookmarks, or none when the file does not exist yet.
+pub fn load() -> Result<Vec<Bookmark>, String> {
+    let Some(path) = bookmarks_path().filter(|path| path.exists()) else {
+        return Ok(Vec::new());
+    };
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid bookmarks in {}: {}", path.display(), e))
+}
+
+/// Writes the bookmarks file, creating its directory if needed.
+pub fn save(bookmarks: &[Bookmark]) -> Result<(), String> {
+    let path = bookmarks_path().ok_or("Could not determine the configuration directory.")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(bookmarks)
+        .map_err(|e| format!("Failed to serialize bookmarks: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{config, helpers};
+
+/// Catalog consulted for every log after the log's own bundle, next to the configuration file.
+const SHARED_CATALOG_FILE_NAME: &str = "catalog.json";
+
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Message templates exported from provider metadata so events can be rendered on machines
+/// where the providers are not installed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageCatalog {
+    pub source: String,
+    pub created: String,
+    /// Provider name -> event ID -> message template with `%1`-style inserts.
+    pub providers: BTreeMap<String, BTreeMap<u32, String>>,
+}
+
+/// Returns the path of the catalog bundle that accompanies the given log or archive.
+pub fn catalog_path_for(source: &str) -> PathBuf {
+    let source_path = Path::new(source);
+    if source_path.extension().is_some() {
+        source_path.with_extension("catalog.json")
+    } else {
+        PathBuf::from(format!(
+            "{}.catalog.json",
+            helpers::sanitize_filename(source)
+        ))
+    }
+}
+
+/// Returns the full path of the shared catalog.
+pub fn shared_catalog_path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join(SHARED_CATALOG_FILE_NAME))
+}
+
+/// Loads the shared catalog, downloading it from `url` first when the file does not exist yet.
+/// Without the file or a URL there is no shared catalog.
+pub fn load_shared(url: Option<&str>) -> Result<Option<MessageCatalog>, String> {
+    let Some(path) = shared_catalog_path() else {
+        return Ok(None);
+    };
+    if path.exists() {
+        return MessageCatalog::load(&path).map(Some);
+    }
+    let Some(url) = url.filter(|url| !url.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let catalog = MessageCatalog::download(url)?;
+    catalog.save(&path)?;
+    Ok(Some(catalog))
+}
+
+impl MessageCatalog {
+    /// Creates an empty catalog for the given source.
+    #[cfg(target_os = "windows")]
+    pub fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            created: chrono::Utc::now().to_rfc3339(),
+            providers: BTreeMap::new(),
+        }
+    }
+
+    /// Loads a catalog bundle from disk.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid catalog {}: {}", path.display(), e))
+    }
+
+    /// Fetches a catalog bundle published over HTTP(S).
+    pub fn download(url: &str) -> Result<Self, String> {
+        let connector = native_tls::TlsConnector::new()
+            .map_err(|e| format!("Failed to initialize TLS: {}", e))?;
+        let agent = ureq::AgentBuilder::new()
+            .tls_connector(Arc::new(connector))
+            .timeout(DOWNLOAD_TIMEOUT)
+            .build();
+        let response = agent
+            .get(url)
+            .call()
+            .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+        let mut content = String::new();
+        response
+            .into_reader()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid catalog {}: {}", url, e))
+    }
+
+    /// Writes the catalog bundle to disk as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize catalog: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Returns true if the catalog already holds a template for the provider and event ID.
+    #[cfg(target_os = "windows")]
+    pub fn contains(&self, provider: &str, event_id: u32) -> bool {
+        self.providers
+            .get(provider)
+            .is_some_and(|templates| templates.contains_key(&event_id))
+    }
+
+    /// Total number of templates across all providers.
+    pub fn template_count(&self) -> usize {
+        self.providers.values().map(BTreeMap::len).sum()
+    }
+
+    /// Renders a message for the event from its template, substituting the event data values.
+    #[cfg(target_os = "windows")]
+    pub fn format(&self, provider: &str, event_id: u32, values: &[String]) -> Option<String> {
+        let template = self.providers.get(provider)?.get(&event_id)?;
+        format_template(template, values)
+    }
+}
+
+/// Renders a message from a template with `%1`-style inserts, `None` when nothing is left.
+#[cfg(target_os = "windows")]
+pub fn format_template(template: &str, values: &[String]) -> Option<String> {
+    let message = apply_template(template, values);
+    let trimmed = message.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Expands FormatMessage-style inserts: `%1`..`%99` (with optional `!fmt!`), `%n`, `%t` and `%%`.
+/// Parameter message references (`%%1234`) are left untouched.
+#[cfg(target_os = "windows")]
+fn apply_template(template: &str, values: &[String]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            output.push(c);
+            continue;
+        }
+        match chars.peek().copied() {
+            Some('n') => {
+                chars.next();
+                output.push('\n');
+            }
+            Some('t') => {
+                chars.next();
+                output.push('\t');
+            }
+            Some('%') => {
+                chars.next();
+                if chars.peek().is_some_and(char::is_ascii_digit) {
+                    output.push_str("%%");
+                } else {
+                    output.push('%');
+                }
+            }
+            Some(d) if d.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(d) = chars.peek().copied().filter(char::is_ascii_digit) {
+                    digits.push(d);
+                    chars.next();
+                }
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    for f in chars.by_ref() {
+                        if f == '!' {
+                            break;
+                        }
+                    }
+                }
+                let index = digits.parse::<usize>().unwrap_or(0);
+                match index.checked_sub(1).and_then(|i| values.get(i)) {
+                    Some(value) => output.push_str(value),
+                    None => {
+                        output.push('%');
+                        output.push_str(&digits);
+                    }
+                }
+            }
+            _ => output.push('%'),
+        }
+    }
+    output
+}
@@ -0,0 +1,234 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::helpers;
+use crate::models::{ExportFormat, SyslogFormat, TimeFilterOption};
+
+/// Command-line interface. Without a subcommand the interactive TUI is started.
+#[derive(Debug, Parser)]
+#[command(name = "event_commander", version, about = "Windows Event Log viewer")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Print events from a log as formatted text instead of starting the TUI.
+    #[arg(long)]
+    pub no_tui: bool,
+    /// Pipe the text output through $PAGER (falls back to less, then more).
+    #[arg(long, requires = "no_tui")]
+    pub pager: bool,
+    /// Log to read in --no-tui mode, or to open in the TUI (defaults to the configured default
+    /// log).
+    #[arg(long)]
+    pub log: Option<String>,
+    /// XPath or structured XML query used in --no-tui mode.
+    #[arg(long, requires = "no_tui")]
+    pub query: Option<String>,
+    /// Maximum number of events printed in --no-tui mode.
+    #[arg(long, default_value_t = 500, requires = "no_tui")]
+    pub max_events: usize,
+    /// Open a saved .evtx log as an extra tab. Messages its publishers cannot format here are
+    /// read from the catalog exported next to it (`<file>.catalog.json`), if there is one.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["no_tui", "log"])]
+    pub file: Option<PathBuf>,
+    /// Open a view shared with the [V] key (a token starting with "ec1.").
+    #[arg(long, value_name = "TOKEN", conflicts_with = "no_tui")]
+    pub view: Option<String>,
+    /// Resume a session saved with Ctrl+S: its log, filter, sort, selected event and bookmarks.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["no_tui", "view"])]
+    pub session: Option<PathBuf>,
+    /// Open the log filtered to events of this source.
+    #[arg(long, value_name = "SOURCE", conflicts_with = "no_tui")]
+    pub filter_source: Option<String>,
+    /// Open the log filtered to these Event IDs, e.g. 4625 or "4624,4700-4799,-4688".
+    #[arg(
+        long,
+        value_name = "IDS",
+        allow_hyphen_values = true,
+        value_parser = parse_event_ids,
+        conflicts_with = "no_tui"
+    )]
+    pub event_id: Option<String>,
+    /// Open the log filtered to the last 1h, 12h, 24h, 7d or 30d.
+    #[arg(long, value_name = "PERIOD", value_parser = parse_last, conflicts_with = "no_tui")]
+    pub last: Option<TimeFilterOption>,
+    /// Order the event list by time, oldest (asc) or newest (desc) first.
+    #[arg(long, value_enum, conflicts_with = "no_tui")]
+    pub sort: Option<SortOrder>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortOrder {
+    /// Oldest first.
+    Asc,
+    /// Newest first.
+    Desc,
+}
+
+/// Checks `--event-id` the way the filter dialog checks its Event ID field.
+fn parse_event_ids(value: &str) -> Result<String, String> {
+    helpers::parse_event_id_spec(value)?;
+    Ok(value.trim().to_string())
+}
+
+/// Reads `--last`, which offers the periods of the filter dialog's time filter.
+fn parse_last(value: &str) -> Result<TimeFilterOption, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1h" => Ok(TimeFilterOption::LastHour),
+        "12h" => Ok(TimeFilterOption::Last12Hours),
+        "24h" | "1d" => Ok(TimeFilterOption::Last24Hours),
+        "7d" | "1w" => Ok(TimeFilterOption::Last7Days),
+        "30d" => Ok(TimeFilterOption::Last30Days),
+        _ => Err("expected 1h, 12h, 24h, 7d or 30d".to_string()),
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Stream new events from a channel to stdout until interrupted.
+    Tail(TailArgs),
+    /// Summarize the last day or week of one or more logs, e.g. from a scheduled task.
+    Digest(DigestArgs),
+    /// Archive channels to compressed files before their retention overwrites events.
+    ArchiveDaemon(ArchiveArgs),
+    /// Save the event types of a healthy machine, or compare a machine or period with them.
+    Baseline {
+        #[command(subcommand)]
+        action: BaselineAction,
+    },
+    /// Check the Event Log service, log access, configuration and terminal, and report what
+    /// fails.
+    Doctor,
+}
+
+#[derive(Debug, Args)]
+pub struct TailArgs {
+    /// Channel to subscribe to.
+    #[arg(short, long, default_value = "System")]
+    pub log: String,
+    /// Output format, one event per line.
+    #[arg(short, long, value_enum, default_value_t = ExportFormat::Ndjson)]
+    pub format: ExportFormat,
+    /// Forward the events to a syslog collector (`host[:port]`, `udp://` or `tcp://`) instead
+    /// of printing them; without a value, `syslog_collector` from the configuration.
+    #[arg(long, value_name = "COLLECTOR", num_args = 0..=1, default_missing_value = "")]
+    pub syslog: Option<String>,
+    /// Record format when forwarding; `syslog_format` from the configuration by default.
+    #[arg(long, value_enum)]
+    pub syslog_format: Option<SyslogFormat>,
+}
+
+#[derive(Debug, Args)]
+pub struct DigestArgs {
+    /// Log to summarize; repeat for several (defaults to Application and System).
+    #[arg(short, long)]
+    pub log: Vec<String>,
+    /// Period summarized and compared with the one before it.
+    #[arg(short, long, value_enum, default_value_t = DigestPeriod::Daily)]
+    pub period: DigestPeriod,
+    #[arg(short, long, value_enum, default_value_t = DigestFormat::Markdown)]
+    pub format: DigestFormat,
+    /// Rows in each top list.
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+    /// Most events read from each log.
+    #[arg(long, default_value_t = 200_000)]
+    pub max_events: usize,
+    /// File to write the report to instead of stdout.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum DigestPeriod {
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum DigestFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Args)]
+pub struct ArchiveArgs {
+    /// Channel to archive; repeat for several (defaults to `archive_logs` in the config file).
+    #[arg(short, long)]
+    pub log: Vec<String>,
+    /// Folder the archives are written to (defaults to `archive_dir` in the config file).
+    #[arg(short, long)]
+    pub dir: Option<PathBuf>,
+    #[arg(short, long, value_enum, default_value_t = ArchiveFormat::Jsonl)]
+    pub format: ArchiveFormat,
+    /// Seconds between passes over the channels.
+    #[arg(long, default_value_t = 300)]
+    pub interval: u64,
+    /// Start a new archive file once the current one reaches this many megabytes.
+    #[arg(long, default_value_t = 100)]
+    pub max_size_mb: u64,
+    /// Start a new archive file once the current one is this many hours old.
+    #[arg(long, default_value_t = 24)]
+    pub rotate_hours: u64,
+    /// Make a single pass and exit, e.g. when started by Task Scheduler.
+    #[arg(long)]
+    pub once: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ArchiveFormat {
+    /// Gzip-compressed NDJSON, one event per line.
+    Jsonl,
+    /// Gzip-compressed .evtx files exported by the Event Log service.
+    Evtx,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BaselineAction {
+    /// Count the events of each source and event ID over a window and save them to a file.
+    Capture(BaselineCaptureArgs),
+    /// Report event types that are new, absent or much more frequent than in a baseline.
+    Compare(BaselineCompareArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct BaselineCaptureArgs {
+    /// Log to count; repeat for several (defaults to Application and System).
+    #[arg(short, long)]
+    pub log: Vec<String>,
+    /// Days back from now that are counted.
+    #[arg(long, default_value_t = 7)]
+    pub days: u32,
+    /// Most events read from each log.
+    #[arg(long, default_value_t = 200_000)]
+    pub max_events: usize,
+    /// File the baseline is saved to.
+    #[arg(short, long)]
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct BaselineCompareArgs {
+    /// Baseline saved by `baseline capture`.
+    pub baseline: PathBuf,
+    /// Baseline of another machine or period to compare instead of this machine's logs.
+    #[arg(long, value_name = "FILE")]
+    pub against: Option<PathBuf>,
+    /// Log to compare; repeat for several (defaults to the logs of the baseline).
+    #[arg(short, long, conflicts_with = "against")]
+    pub log: Vec<String>,
+    /// Days back from now that are counted (defaults to the window of the baseline).
+    #[arg(long, conflicts_with = "against")]
+    pub days: Option<u32>,
+    /// Most events read from each log.
+    #[arg(long, default_value_t = 200_000, conflicts_with = "against")]
+    pub max_events: usize,
+    /// Times more or less often per day an event type must occur to be reported as changed.
+    #[arg(long, default_value_t = 3.0)]
+    pub factor: f64,
+    #[arg(short, long, value_enum, default_value_t = DigestFormat::Markdown)]
+    pub format: DigestFormat,
+    /// File to write the report to instead of stdout.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
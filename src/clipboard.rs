@@ -0,0 +1,6 @@
+/// Places `text` on the system clipboard.
+pub fn copy_text(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| format!("Failed to copy to the clipboard: {}", e))
+}
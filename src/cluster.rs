@@ -0,0 +1,52 @@
+#[cfg(target_os = "windows")]
+use crate::models::DisplayEvent;
+
+/// Logs read on each node, with the query selecting the Failover Clustering events in them.
+/// The cluster service also logs its critical events (lost quorum, evicted nodes, failed
+/// resources) to the System log.
+#[cfg(target_os = "windows")]
+pub const CLUSTER_SOURCES: [(&str, &str); 2] = [
+    ("Microsoft-Windows-FailoverClustering/Operational", "*"),
+    (
+        "System",
+        "*[System[Provider[@Name='Microsoft-Windows-FailoverClustering']]]",
+    ),
+];
+
+/// Most events read from each log on each node.
+#[cfg(target_os = "windows")]
+pub const MAX_NODE_EVENTS: usize = 2000;
+
+/// Message of a node's event while only its System values are read, with
+/// `defer_remote_rendering` on.
+#[cfg(target_os = "windows")]
+pub const DEFERRED_MESSAGE: &str =
+    "Not read yet: select the event to read its message and data from the node.";
+
+/// Splits the node list typed into the prompt on commas and whitespace, dropping repeats.
+pub fn parse_nodes(input: &str) -> Result<Vec<String>, String> {
+    let mut nodes: Vec<String> = Vec::new();
+    for node in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        let node = node.trim().trim_start_matches('\\');
+        if !node.is_empty() && !nodes.iter().any(|known| known.eq_ignore_ascii_case(node)) {
+            nodes.push(node.to_string());
+        }
+    }
+    if nodes.is_empty() {
+        return Err("Enter at least one cluster node name.".to_string());
+    }
+    Ok(nodes)
+}
+
+/// Merges the events read from the nodes into one list ordered by time, newest first when
+/// `descending`.
+#[cfg(target_os = "windows")]
+pub fn merge(per_node: Vec<Vec<DisplayEvent>>, descending: bool) -> Vec<DisplayEvent> {
+    let mut events: Vec<DisplayEvent> = per_node.into_iter().flatten().collect();
+    if descending {
+        events.sort_by_key(|event| std::cmp::Reverse(event.timestamp));
+    } else {
+        events.sort_by_key(|event| event.timestamp);
+    }
+    events
+}
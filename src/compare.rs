@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::models::DisplayEvent;
+use crate::source::{BatchTuner, EventSource};
+
+/// Most events counted in each window.
+pub const MAX_WINDOW_EVENTS: usize = 200_000;
+/// An event type spiked when it was logged this many times as often as in the earlier
+/// window, and at least `SPIKE_MIN_INCREASE` times more.
+const SPIKE_FACTOR: usize = 3;
+const SPIKE_MIN_INCREASE: usize = 10;
+
+/// The two windows compared: the later one ends now, or starts at the selected event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ComparisonPreset {
+    #[default]
+    HourVsYesterday,
+    HourVsPreviousHour,
+    DayVsPreviousDay,
+    DayVsLastWeek,
+    /// From the selected event until now, against the same length of time before it, e.g.
+    /// after and before a deployment.
+    AroundSelected,
+}
+
+impl ComparisonPreset {
+    pub const ALL: [Self; 5] = [
+        Self::HourVsYesterday,
+        Self::HourVsPreviousHour,
+        Self::DayVsPreviousDay,
+        Self::DayVsLastWeek,
+        Self::AroundSelected,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::HourVsYesterday => "Last hour vs same hour yesterday",
+            Self::HourVsPreviousHour => "Last hour vs hour before",
+            Self::DayVsPreviousDay => "Last 24 hours vs day before",
+            Self::DayVsLastWeek => "Last 24 hours vs same day last week",
+            Self::AroundSelected => "Since selected event vs same span before it",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// The later and the earlier window as (start, end), or `None` for `AroundSelected`
+    /// without a selected event in the past.
+    pub fn windows(
+        self,
+        now: DateTime<Utc>,
+        selected: Option<DateTime<Utc>>,
+    ) -> Option<(Window, Window)> {
+        let (length, offset) = match self {
+            Self::HourVsYesterday => (Duration::hours(1), Duration::days(1)),
+            Self::HourVsPreviousHour => (Duration::hours(1), Duration::hours(1)),
+            Self::DayVsPreviousDay => (Duration::days(1), Duration::days(1)),
+            Self::DayVsLastWeek => (Duration::days(1), Duration::weeks(1)),
+            Self::AroundSelected => {
+                let pivot = selected.filter(|&time| time < now)?;
+                return Some(((pivot, now), (pivot - (now - pivot), pivot)));
+            }
+        };
+        let later = (now - length, now);
+        Some((later, (later.0 - offset, later.1 - offset)))
+    }
+}
+
+/// Start and end of a window; the end is excluded.
+pub type Window = (DateTime<Utc>, DateTime<Utc>);
+
+/// How an event type's count changed from the earlier window to the later one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    New,
+    Spike,
+    Up,
+    Down,
+    Gone,
+    Same,
+}
+
+impl Trend {
+    fn of(later: usize, earlier: usize) -> Self {
+        match (later, earlier) {
+            (_, 0) => Self::New,
+            (0, _) => Self::Gone,
+            _ if later >= earlier * SPIKE_FACTOR && later - earlier >= SPIKE_MIN_INCREASE => {
+                Self::Spike
+            }
+            _ if later > earlier => Self::Up,
+            _ if later < earlier => Self::Down,
+            _ => Self::Same,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::New => "NEW",
+            Self::Spike => "SPIKE",
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Gone => "gone",
+            Self::Same => "",
+        }
+    }
+
+    /// Whether the change hints at a regression.
+    pub fn is_notable(self) -> bool {
+        matches!(self, Self::New | Self::Spike)
+    }
+}
+
+/// Counts of one source and Event ID in both windows.
+#[derive(Debug, Clone)]
+pub struct CountDelta {
+    pub source: String,
+    pub event_id: u32,
+    pub later: usize,
+    pub earlier: usize,
+    pub trend: Trend,
+}
+
+impl CountDelta {
+    /// The change as "+12" or "-3".
+    pub fn change(&self) -> String {
+        if self.later >= self.earlier {
+            format!("+{}", self.later - self.earlier)
+        } else {
+            format!("-{}", self.earlier - self.later)
+        }
+    }
+}
+
+/// Result of the comparison view, while it is open.
+#[derive(Debug, Clone, Default)]
+pub struct Comparison {
+    pub preset: ComparisonPreset,
+    pub log: String,
+    pub later: Option<Window>,
+    pub earlier: Option<Window>,
+    /// New and spiking event types first, each group by the size of the change.
+    pub deltas: Vec<CountDelta>,
+    pub later_total: usize,
+    pub earlier_total: usize,
+    /// Why the windows could not be compared, or were only partly read.
+    pub notes: Vec<String>,
+    pub selection: usize,
+}
+
+impl Comparison {
+    /// Counts the events of `log` in both windows, read from `source` with a query per window
+    /// built by `query`, which applies the current filter.
+    pub fn run(
+        source: &mut dyn EventSource,
+        preset: ComparisonPreset,
+        log: &str,
+        selected: Option<DateTime<Utc>>,
+        query: impl Fn(DateTime<Utc>, DateTime<Utc>) -> String,
+    ) -> Self {
+        let mut comparison = Self {
+            preset,
+            log: log.to_string(),
+            ..Self::default()
+        };
+        let Some((later, earlier)) = preset.windows(Utc::now(), selected) else {
+            comparison
+                .notes
+                .push("Select an event in the past to compare the time since it.".to_string());
+            return comparison;
+        };
+        comparison.later = Some(later);
+        comparison.earlier = Some(earlier);
+
+        let later_counts = count_window(
+            source,
+            log,
+            &query(later.0, later.1),
+            later,
+            &mut comparison.notes,
+        );
+        let earlier_counts = count_window(
+            source,
+            log,
+            &query(earlier.0, earlier.1),
+            earlier,
+            &mut comparison.notes,
+        );
+        comparison.later_total = later_counts.values().sum();
+        comparison.earlier_total = earlier_counts.values().sum();
+
+        let mut keys: Vec<&(String, u32)> = later_counts.keys().collect();
+        keys.extend(
+            earlier_counts
+                .keys()
+                .filter(|key| !later_counts.contains_key(*key)),
+        );
+        comparison.deltas = keys
+            .into_iter()
+            .map(|key| {
+                let later = later_counts.get(key).copied().unwrap_or(0);
+                let earlier = earlier_counts.get(key).copied().unwrap_or(0);
+                CountDelta {
+                    source: key.0.clone(),
+                    event_id: key.1,
+                    later,
+                    earlier,
+                    trend: Trend::of(later, earlier),
+                }
+            })
+            .collect();
+        comparison.deltas.sort_by(|a, b| {
+            let size = |delta: &CountDelta| delta.later.abs_diff(delta.earlier);
+            b.trend
+                .is_notable()
+                .cmp(&a.trend.is_notable())
+                .then(size(b).cmp(&size(a)))
+                .then(a.source.cmp(&b.source))
+                .then(a.event_id.cmp(&b.event_id))
+        });
+        comparison
+    }
+}
+
+/// Counts the events of a window by source and Event ID, reading newest first. Events outside
+/// the window are skipped, for backends whose queries cannot bound the time.
+fn count_window(
+    source: &mut dyn EventSource,
+    log: &str,
+    query: &str,
+    (start, end): Window,
+    notes: &mut Vec<String>,
+) -> HashMap<(String, u32), usize> {
+    let mut counts = HashMap::new();
+    if let Err(e) = source.query(log, query, true) {
+        notes.push(e);
+        return counts;
+    }
+    let mut tuner = BatchTuner::bulk();
+    let mut read = 0;
+    'read: while read < MAX_WINDOW_EVENTS {
+        let batch = match tuner.next_batch(source, MAX_WINDOW_EVENTS - read) {
+            Ok(batch) if batch.is_empty() => break,
+            Ok(batch) => batch,
+            Err(e) => {
+                notes.push(e);
+                break;
+            }
+        };
+        for event in batch {
+            read += 1;
+            let Some(time) = event.timestamp else {
+                continue;
+            };
+            if time < start {
+                break 'read;
+            }
+            if time < end {
+                add(&mut counts, &event);
+            }
+        }
+        if read >= MAX_WINDOW_EVENTS {
+            notes.push(format!(
+                "Only the newest {} events of a window were counted.",
+                MAX_WINDOW_EVENTS
+            ));
+        }
+    }
+    counts
+}
+
+fn add(counts: &mut HashMap<(String, u32), usize>, event: &DisplayEvent) {
+    *counts
+        .entry((event.source.clone(), event.event_id))
+        .or_insert(0) += 1;
+}
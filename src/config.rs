@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::event_api::WINDOWS_CHANNELS;
+use crate::models::{
+    ColumnSettings, DEFAULT_MESSAGE_COLUMN_PERCENT, DEFAULT_SPLIT_PERCENT, ExportFormat,
+    FilterCriteria, MAX_MESSAGE_COLUMN_PERCENT, MAX_SPLIT_PERCENT, MIN_MESSAGE_COLUMN_PERCENT,
+    MIN_SPLIT_PERCENT, SortKey, SyslogFormat,
+};
+use crate::redact::RedactionRule;
+use crate::theme::Theme;
+use crate::triggers::Trigger;
+use crate::weblogs::DEFAULT_WEB_LOG_DIRS;
+
+const CONFIG_DIR_NAME: &str = "event_commander";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// User preferences persisted between sessions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Name of the color scheme used by the UI, either built in or from `themes`.
+    pub theme: String,
+    /// Log opened on startup.
+    pub default_log: String,
+    /// Whether events are listed newest first.
+    pub sort_descending: bool,
+    /// Column the event list is ordered by.
+    pub sort_key: SortKey,
+    /// Number of events fetched from the query per load; 0 sizes each load from the throughput
+    /// measured on the log.
+    pub batch_size: usize,
+    /// Whether loaded events keep their full XML. Off, it is dropped once the event is parsed
+    /// and read again when the event is selected, which takes much less memory.
+    pub retain_raw_xml: bool,
+    /// Most events kept loaded; those farthest from the selection are dropped and read again
+    /// when scrolled back to. 0 keeps every loaded event.
+    pub max_loaded_events: usize,
+    /// Format used when exporting loaded events.
+    pub export_format: ExportFormat,
+    /// Filter applied most recently, restored on startup.
+    pub last_filter: Option<FilterCriteria>,
+    /// Filters applied lately, most recent first, offered in the filter dialog.
+    pub recent_filters: Vec<FilterCriteria>,
+    /// Share of the middle area, in percent, given to the event list next to the preview.
+    pub split_percent: u16,
+    /// Whether the preview panel wraps long lines; off, it scrolls sideways.
+    pub preview_wrap: bool,
+    /// Layout of the formatted preview in place of the built-in one, with placeholders such
+    /// as `{level}`, `{datetime}`, `{message}` and `{data.TargetUserName}`.
+    pub preview_template: Option<String>,
+    /// Terminal width, in columns, below which the preview is stacked under the event list.
+    pub stack_below_width: u16,
+    /// Order, visibility and width of the event table columns.
+    pub columns: Vec<ColumnSettings>,
+    /// Whether the event density histogram is drawn above the event table.
+    pub show_histogram: bool,
+    /// Share of the event table, in percent, given to the message column when it is shown.
+    pub message_column_percent: u16,
+    /// Whether exported and saved events are redacted before they are written or sent.
+    pub redact_exports: bool,
+    /// Patterns replaced when redaction is on. Host and account names found in the exported
+    /// events are always redacted as well.
+    pub redaction_rules: Vec<RedactionRule>,
+    /// User-defined color schemes, keyed by name.
+    pub themes: BTreeMap<String, Theme>,
+    /// Channels interleaved on the merged tab.
+    pub merged_logs: Vec<String>,
+    /// Failover cluster nodes merged most recently, offered again by the node prompt.
+    pub cluster_nodes: Vec<String>,
+    /// Whether only the System values of events on remote hosts are read for the event list,
+    /// with the full event read when it is selected. Cuts the transfer over slow links.
+    pub defer_remote_rendering: bool,
+    /// Folders searched for IIS and HTTPERR logs, along with their immediate subfolders.
+    pub web_log_dirs: Vec<String>,
+    /// Whether the event list is auto-refreshed at startup.
+    pub auto_refresh: bool,
+    /// Seconds between auto-refreshes of the event list.
+    pub auto_refresh_secs: u64,
+    /// Channels `archive-daemon` archives when none are given on the command line.
+    pub archive_logs: Vec<String>,
+    /// Folder `archive-daemon` writes to; `archive` next to the config file when unset.
+    pub archive_dir: Option<String>,
+    /// Where to download the shared message catalog from when `catalog.json` does not exist
+    /// next to the config file yet.
+    pub message_catalog_url: Option<String>,
+    /// Folder of Sigma rules loaded events are matched against; `sigma` next to the config
+    /// file when unset.
+    pub sigma_rules_dir: Option<String>,
+    /// Syslog collector events are forwarded to, as `host[:port]` with an optional `udp://` or
+    /// `tcp://` prefix.
+    pub syslog_collector: Option<String>,
+    pub syslog_format: SyslogFormat,
+    /// OTLP/HTTP logs endpoint [O] sends events to, e.g. "http://localhost:4318/v1/logs";
+    /// the `OTEL_EXPORTER_OTLP_*` variables are used when unset.
+    pub otlp_endpoint: Option<String>,
+    /// Headers sent with every OTLP request, e.g. an API key; `OTEL_EXPORTER_OTLP_HEADERS`
+    /// is used when empty.
+    pub otlp_headers: BTreeMap<String, String>,
+    /// Rules run on the events auto-refresh brings in (`[[triggers]]`).
+    pub triggers: Vec<Trigger>,
+    /// Logs whose new error and critical events raise a notification during auto-refresh.
+    pub notify_channels: Vec<String>,
+    /// Whether notifications are Windows toasts; the terminal bell rings otherwise.
+    pub notify_toast: bool,
+    /// Whether the event list takes vim motions: `j`/`k`, `Ctrl+D`/`Ctrl+U`, marks, and digits
+    /// as counts, as in `20j`. Tabs are then switched with Alt and the digit, and the
+    /// comparison and time sync moved to Alt+k and Alt+j.
+    pub vim_keys: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: "classic-blue".to_string(),
+            default_log: WINDOWS_CHANNELS[0].to_string(),
+            sort_descending: true,
+            sort_key: SortKey::default(),
+            batch_size: 0,
+            retain_raw_xml: true,
+            max_loaded_events: 50_000,
+            export_format: ExportFormat::default(),
+            last_filter: None,
+            recent_filters: Vec::new(),
+            split_percent: DEFAULT_SPLIT_PERCENT,
+            preview_wrap: true,
+            preview_template: None,
+            stack_below_width: 120,
+            columns: ColumnSettings::defaults(),
+            show_histogram: true,
+            message_column_percent: DEFAULT_MESSAGE_COLUMN_PERCENT,
+            redact_exports: false,
+            redaction_rules: RedactionRule::defaults(),
+            themes: BTreeMap::new(),
+            merged_logs: vec!["System".to_string(), "Application".to_string()],
+            cluster_nodes: Vec::new(),
+            defer_remote_rendering: false,
+            web_log_dirs: DEFAULT_WEB_LOG_DIRS
+                .iter()
+                .map(|dir| dir.to_string())
+                .collect(),
+            auto_refresh: false,
+            auto_refresh_secs: 10,
+            archive_logs: vec!["Security".to_string(), "System".to_string()],
+            archive_dir: None,
+            message_catalog_url: None,
+            sigma_rules_dir: None,
+            syslog_collector: None,
+            syslog_format: SyslogFormat::default(),
+            otlp_endpoint: None,
+            otlp_headers: BTreeMap::new(),
+            triggers: Vec::new(),
+            notify_channels: Vec::new(),
+            notify_toast: true,
+            vim_keys: false,
+        }
+    }
+}
+
+/// Returns the directory holding the configuration file, `%APPDATA%\event_commander` on Windows.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = env::var_os("APPDATA")
+        .or_else(|| env::var_os("XDG_CONFIG_HOME"))
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join(CONFIG_DIR_NAME))
+}
+
+/// Returns the full path of the configuration file.
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(CONFIG_FILE_NAME))
+}
+
+impl Config {
+    /// Loads the configuration file, falling back to defaults when it does not exist yet.
+    pub fn load() -> Result<Self, String> {
+        let Some(path) = config_path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut config: Self = toml::from_str(&content)
+            .map_err(|e| format!("Invalid configuration in {}:\n\n{}", path.display(), e))?;
+        config.split_percent = config
+            .split_percent
+            .clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT);
+        ColumnSettings::normalize(&mut config.columns);
+        config.message_column_percent = config
+            .message_column_percent
+            .clamp(MIN_MESSAGE_COLUMN_PERCENT, MAX_MESSAGE_COLUMN_PERCENT);
+        Ok(config)
+    }
+
+    /// Writes the configuration file, creating its directory if needed.
+    pub fn save(&self) -> Result<PathBuf, String> {
+        let path = config_path().ok_or("Could not determine the configuration directory.")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize configuration: {}", e))?;
+        fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+}
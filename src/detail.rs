@@ -0,0 +1,82 @@
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::models::DisplayEvent;
+
+#[derive(PartialEq)]
+enum Section {
+    Other,
+    System,
+    UserData,
+}
+
+/// Builds the labeled rows of the detail view: every `System` element and attribute, then the
+/// `EventData` fields (by `Name` where the provider declares one) or `UserData` elements, then
+/// the message.
+pub fn event_fields(event: &DisplayEvent) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let mut reader = Reader::from_str(&event.raw_data);
+    let mut section = Section::Other;
+    let mut element = String::new();
+
+    let add_attributes = |fields: &mut Vec<(String, String)>, e: &BytesStart| {
+        let element = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+        for attr in e.attributes().flatten() {
+            let name = String::from_utf8_lossy(attr.key.local_name().as_ref()).to_string();
+            let value = attr.unescape_value().unwrap_or_default().to_string();
+            let value = if element == "TimeCreated" && name == "SystemTime" {
+                format!("{} ({})", event.local_time(), value)
+            } else {
+                value
+            };
+            fields.push((format!("{}.{}", element, name), value));
+        }
+    };
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                match (&section, name.as_str()) {
+                    (Section::Other, "System") => section = Section::System,
+                    (Section::Other, "UserData") => section = Section::UserData,
+                    (Section::System, _) => add_attributes(&mut fields, &e),
+                    _ => {}
+                }
+                element = name;
+            }
+            Ok(Event::Empty(e)) if section == Section::System => add_attributes(&mut fields, &e),
+            Ok(Event::Text(e)) if section != Section::Other => {
+                let text = e.unescape().unwrap_or_default().trim().to_string();
+                if text.is_empty() {
+                    continue;
+                }
+                let value = if section == Section::System && element == "Level" {
+                    format!("{} ({})", text, event.level.display_name())
+                } else {
+                    text
+                };
+                fields.push((element.clone(), value));
+            }
+            Ok(Event::End(e)) => {
+                let name = e.local_name();
+                if matches!(name.as_ref(), b"System" | b"UserData") {
+                    section = Section::Other;
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+
+    for (position, (name, value)) in event.event_data.fields.iter().enumerate() {
+        let label = name
+            .clone()
+            .unwrap_or_else(|| format!("Data {}", position + 1));
+        fields.push((label, value.clone()));
+    }
+
+    let message = event.formatted_message.as_deref().unwrap_or(&event.message);
+    fields.push(("Message".to_string(), message.trim_end().to_string()));
+    fields
+}
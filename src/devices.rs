@@ -0,0 +1,275 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::detail;
+use crate::helpers;
+use crate::models::DisplayEvent;
+
+/// Logs read for device activity, with their queries and whether the log is disabled by
+/// default (and so often missing or empty).
+pub const DEVICE_SOURCES: [(&str, &str, bool); 3] = [
+    (
+        "Microsoft-Windows-Kernel-PnP/Configuration",
+        "*[System[EventID=400 or EventID=410 or EventID=420]]",
+        false,
+    ),
+    (
+        "Microsoft-Windows-Partition/Diagnostic",
+        "*[System[EventID=1006]]",
+        false,
+    ),
+    (
+        "Microsoft-Windows-DriverFrameworks-UserMode/Operational",
+        "*[System[EventID=2003]]",
+        true,
+    ),
+];
+
+/// Most events read from each log.
+pub const MAX_DEVICE_EVENTS: usize = 5000;
+
+/// Connect or disconnect entries of one device logged within this many seconds of each
+/// other count as one, since a single plug-in is logged by several providers.
+const SAME_ACTION_SECONDS: i64 = 60;
+
+/// What happened to a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceAction {
+    Connected,
+    Disconnected,
+    Configured,
+    Removed,
+}
+
+impl DeviceAction {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Connected => "Connected",
+            Self::Disconnected => "Disconnected",
+            Self::Configured => "Driver configured",
+            Self::Removed => "Device removed",
+        }
+    }
+}
+
+/// One event of a device's timeline.
+#[derive(Debug, Clone)]
+pub struct DeviceEntry {
+    pub time: DateTime<Utc>,
+    pub action: DeviceAction,
+    /// Provider and event ID the entry was read from.
+    pub origin: String,
+    pub instance_id: String,
+}
+
+/// The activity of one device.
+#[derive(Debug, Clone)]
+pub struct Device {
+    /// Serial number, or the instance Windows made up for devices without one.
+    pub key: String,
+    pub name: String,
+    /// Oldest first.
+    pub entries: Vec<DeviceEntry>,
+}
+
+impl Device {
+    pub fn last_seen(&self) -> DateTime<Utc> {
+        self.entries
+            .last()
+            .map(|entry| entry.time)
+            .unwrap_or_default()
+    }
+
+    /// Number of separate connects or disconnects.
+    pub fn count(&self, action: DeviceAction) -> usize {
+        let mut count = 0;
+        let mut last: Option<DateTime<Utc>> = None;
+        for entry in self.entries.iter().filter(|entry| entry.action == action) {
+            if last.is_none_or(|last| entry.time - last > Duration::seconds(SAME_ACTION_SECONDS)) {
+                count += 1;
+            }
+            last = Some(entry.time);
+        }
+        count
+    }
+
+    /// Describes the device and its timeline for the detail pane.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Device:       {}", self.name),
+            format!("Serial/ID:    {}", self.key),
+            format!(
+                "Connects:     {}, disconnects: {}",
+                self.count(DeviceAction::Connected),
+                self.count(DeviceAction::Disconnected)
+            ),
+            String::new(),
+            "--- Timeline ---".to_string(),
+        ];
+        lines.extend(self.entries.iter().map(|entry| {
+            format!(
+                "{}  {:<18} {:<28} {}",
+                helpers::format_local_time(entry.time),
+                entry.action.display_name(),
+                entry.origin,
+                entry.instance_id
+            )
+        }));
+        lines
+    }
+}
+
+/// State of the device activity view.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceView {
+    /// Most recently seen first.
+    pub devices: Vec<Device>,
+    /// Logs that could not be read, with the reason.
+    pub unavailable: Vec<String>,
+    pub selection: usize,
+    /// Device whose timeline is shown instead of the list.
+    pub open_device: Option<usize>,
+    pub detail_scroll: usize,
+}
+
+impl DeviceView {
+    /// Groups the device events by serial number. Only hardware with a vendor in its ID
+    /// (`VID_` or `VEN_`) is kept, which leaves out software and root-enumerated devices.
+    pub fn new(events: &[DisplayEvent], unavailable: Vec<String>) -> Self {
+        let mut events: Vec<(DateTime<Utc>, &DisplayEvent)> = events
+            .iter()
+            .filter_map(|event| Some((event.timestamp?, event)))
+            .collect();
+        events.sort_by_key(|(time, _)| *time);
+
+        let mut devices: Vec<Device> = Vec::new();
+        for (time, event) in events {
+            let Some((instance_id, action, model)) = read_event(event) else {
+                continue;
+            };
+            let Some(key) = device_key(&instance_id) else {
+                continue;
+            };
+            let index = match devices.iter().position(|device| device.key == key) {
+                Some(index) => index,
+                None => {
+                    devices.push(Device {
+                        name: String::new(),
+                        key,
+                        entries: Vec::new(),
+                    });
+                    devices.len() - 1
+                }
+            };
+            let device = &mut devices[index];
+            // The disk's own model beats the vendor and product in the hardware ID.
+            if let Some(model) = model {
+                device.name = model;
+            } else if device.name.is_empty() {
+                device.name = hardware_name(&instance_id).unwrap_or_default();
+            }
+            device.entries.push(DeviceEntry {
+                time,
+                action,
+                origin: format!(
+                    "{} {}",
+                    event
+                        .provider_name_original
+                        .trim_start_matches("Microsoft-Windows-"),
+                    event.event_id
+                ),
+                instance_id,
+            });
+        }
+        for device in &mut devices {
+            if device.name.is_empty() {
+                device.name = device.key.clone();
+            }
+        }
+        devices.sort_by_key(|device| std::cmp::Reverse(device.last_seen()));
+        Self {
+            devices,
+            unavailable,
+            ..Self::default()
+        }
+    }
+}
+
+/// Reads the device instance ID, the action and, for disks, the model from a device event.
+fn read_event(event: &DisplayEvent) -> Option<(String, DeviceAction, Option<String>)> {
+    let fields = detail::event_fields(event);
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, value)| field == name && !value.is_empty())
+            .map(|(_, value)| value.trim().to_string())
+    };
+    match event.event_id {
+        400 => Some((field("DeviceInstanceId")?, DeviceAction::Configured, None)),
+        410 => Some((field("DeviceInstanceId")?, DeviceAction::Connected, None)),
+        420 => Some((field("DeviceInstanceId")?, DeviceAction::Removed, None)),
+        // Logged on arrival with the disk's capacity and again on removal with none.
+        1006 => {
+            let connected = field("Capacity").is_some_and(|capacity| capacity != "0");
+            let model = [field("Manufacturer"), field("Model")]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            let action = if connected {
+                DeviceAction::Connected
+            } else {
+                DeviceAction::Disconnected
+            };
+            Some((
+                field("ParentId")?,
+                action,
+                Some(model).filter(|model| !model.is_empty()),
+            ))
+        }
+        2003 => Some((field("InstanceId")?, DeviceAction::Connected, None)),
+        _ => None,
+    }
+}
+
+/// The segments of an instance ID, which user-mode driver events write with `#` instead
+/// of `\`.
+fn id_segments(instance_id: &str) -> Vec<String> {
+    instance_id
+        .to_uppercase()
+        .replace('#', "\\")
+        .split('\\')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The segment after the hardware ID: the serial number, or the instance Windows made up.
+/// Storage IDs append the logical unit ("&0"), which is dropped so the disk and its USB
+/// device share a key.
+fn device_key(instance_id: &str) -> Option<String> {
+    let segments = id_segments(instance_id);
+    let hardware = segments
+        .iter()
+        .position(|segment| segment.contains("VID_") || segment.contains("VEN_"))?;
+    let serial = segments.get(hardware + 1)?;
+    Some(serial.trim_end_matches("&0").to_string())
+}
+
+/// Names a device from the vendor and product in its hardware ID.
+fn hardware_name(instance_id: &str) -> Option<String> {
+    let segments = id_segments(instance_id);
+    let hardware = segments
+        .iter()
+        .find(|segment| segment.contains("VID_") || segment.contains("VEN_"))?;
+    let part = |prefix: &str| {
+        hardware
+            .split('&')
+            .find_map(|part| part.strip_prefix(prefix))
+            .map(|value| value.replace('_', " ").trim().to_string())
+    };
+    match (part("VEN_"), part("PROD_"), part("VID_"), part("PID_")) {
+        (Some(vendor), Some(product), _, _) => Some(format!("{} {}", vendor, product)),
+        (_, _, Some(vendor), Some(product)) => Some(format!("USB device {}:{}", vendor, product)),
+        _ => None,
+    }
+}
@@ -0,0 +1,465 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Duration, Utc};
+use quick_xml::escape::escape;
+
+use crate::cli::{DigestArgs, DigestFormat, DigestPeriod};
+use crate::helpers;
+use crate::models::{DisplayEvent, EventLevel};
+use crate::source::{BatchTuner, EventSource, PlatformSource};
+
+/// Level rows of the counts table; Verbose and unknown levels are counted as Information.
+const LEVEL_NAMES: [&str; 4] = ["Critical", "Error", "Warning", "Information"];
+
+/// Events of one source and event ID within a period.
+#[derive(Debug, Clone)]
+struct Signature {
+    source: String,
+    event_id: u32,
+    level: EventLevel,
+    count: usize,
+    first_seen: Option<DateTime<Utc>>,
+    last_seen: Option<DateTime<Utc>>,
+    /// First line of the most recent event's message.
+    message: String,
+}
+
+impl Signature {
+    fn is_error(&self) -> bool {
+        self.level.severity_rank() <= EventLevel::Error.severity_rank()
+    }
+}
+
+/// Counts of one period.
+#[derive(Debug, Default)]
+struct PeriodStats {
+    levels: [usize; 4],
+    signatures: HashMap<(String, u32), Signature>,
+}
+
+impl PeriodStats {
+    /// Events are read newest first, so the first event of a signature is its last seen.
+    fn add(&mut self, event: &DisplayEvent) {
+        let row = match event.level {
+            EventLevel::Critical => 0,
+            EventLevel::Error => 1,
+            EventLevel::Warning => 2,
+            _ => 3,
+        };
+        self.levels[row] += 1;
+        let signature = self
+            .signatures
+            .entry((event.source.clone(), event.event_id))
+            .or_insert_with(|| Signature {
+                source: event.source.clone(),
+                event_id: event.event_id,
+                level: event.level,
+                count: 0,
+                first_seen: event.timestamp,
+                last_seen: event.timestamp,
+                message: event
+                    .formatted_message
+                    .as_deref()
+                    .unwrap_or(&event.message)
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            });
+        signature.count += 1;
+        signature.first_seen = signature
+            .first_seen
+            .into_iter()
+            .chain(event.timestamp)
+            .min();
+        if event.level.severity_rank() < signature.level.severity_rank() {
+            signature.level = event.level;
+        }
+    }
+
+    /// Critical and error events by source.
+    fn error_sources(&self) -> HashMap<&str, usize> {
+        let mut sources = HashMap::new();
+        for signature in self.signatures.values().filter(|s| s.is_error()) {
+            *sources.entry(signature.source.as_str()).or_insert(0) += signature.count;
+        }
+        sources
+    }
+}
+
+/// What was read from one log.
+#[derive(Debug)]
+struct LogSummary {
+    log: String,
+    current: PeriodStats,
+    previous: PeriodStats,
+    /// Why the log could not be read completely.
+    error: Option<String>,
+    /// Whether reading stopped at `--max-events` before the previous period began.
+    truncated: bool,
+}
+
+/// A titled table of the report.
+pub struct Table {
+    pub title: String,
+    pub header: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+    /// Shown instead of the table when it has no rows.
+    pub empty: &'static str,
+}
+
+/// A section of the report: its heading, notes and tables.
+pub type Section = (String, Vec<String>, Vec<Table>);
+
+/// Writes a summary of the selected logs over the last day or week, compared with the period
+/// before it, as Markdown or HTML.
+pub fn run(args: &DigestArgs) -> Result<(), Box<dyn Error>> {
+    let end = Utc::now();
+    let length = match args.period {
+        DigestPeriod::Daily => Duration::days(1),
+        DigestPeriod::Weekly => Duration::weeks(1),
+    };
+    let start = end - length;
+    let previous_start = start - length;
+
+    let logs = if args.log.is_empty() {
+        default_logs()
+    } else {
+        args.log.clone()
+    };
+    let summaries: Vec<LogSummary> = logs
+        .iter()
+        .map(|log| summarize_log(log, previous_start, start, args.max_events))
+        .collect();
+
+    let period_name = match args.period {
+        DigestPeriod::Daily => "day",
+        DigestPeriod::Weekly => "week",
+    };
+    let title = format!("Event Digest: {}", helpers::local_host_name());
+    let intro = format!(
+        "{} to {}, compared with the {} before.",
+        helpers::format_local_time(start),
+        helpers::format_local_time(end),
+        period_name
+    );
+    let mut sections: Vec<Section> = Vec::new();
+    for summary in &summaries {
+        let mut notes = Vec::new();
+        if let Some(error) = &summary.error {
+            notes.push(format!("Not read completely: {}", error));
+        }
+        if summary.truncated {
+            notes.push(format!(
+                "Only the newest {} events were read; the previous {} is incomplete.",
+                args.max_events, period_name
+            ));
+        }
+        sections.push((summary.log.clone(), notes, tables(summary, args.top)));
+    }
+
+    write_report(
+        args.format,
+        args.output.as_deref(),
+        &title,
+        &intro,
+        &sections,
+    )
+}
+
+/// Writes the report as Markdown or HTML to `output`, or to stdout.
+pub fn write_report(
+    format: DigestFormat,
+    output: Option<&Path>,
+    title: &str,
+    intro: &str,
+    sections: &[Section],
+) -> Result<(), Box<dyn Error>> {
+    let report = match format {
+        DigestFormat::Markdown => markdown(title, intro, sections),
+        DigestFormat::Html => html(title, intro, sections),
+    };
+    match output {
+        Some(path) => fs::write(path, report)
+            .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))?,
+        None => io::stdout().lock().write_all(report.as_bytes())?,
+    }
+    Ok(())
+}
+
+/// Logs read when none are given.
+pub fn default_logs() -> Vec<String> {
+    #[cfg(target_os = "windows")]
+    return vec!["Application".to_string(), "System".to_string()];
+    #[cfg(not(target_os = "windows"))]
+    PlatformSource::default()
+        .channels()
+        .into_iter()
+        .take(1)
+        .collect()
+}
+
+/// The platform's query for events since `since`. Journal matches cannot compare times, so
+/// there all events are read until one is older.
+pub fn since_query(since: DateTime<Utc>) -> String {
+    #[cfg(target_os = "windows")]
+    return format!(
+        "*[System[TimeCreated[@SystemTime>='{}']]]",
+        crate::analysis::xpath_time(since)
+    );
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = since;
+        String::new()
+    }
+}
+
+/// Reads the log newest first back to `previous_start`, counting events before `start` in
+/// the previous period.
+fn summarize_log(
+    log: &str,
+    previous_start: DateTime<Utc>,
+    start: DateTime<Utc>,
+    max_events: usize,
+) -> LogSummary {
+    let mut summary = LogSummary {
+        log: log.to_string(),
+        current: PeriodStats::default(),
+        previous: PeriodStats::default(),
+        error: None,
+        truncated: false,
+    };
+    let mut source = PlatformSource::default();
+    if let Err(e) = source.query(log, &since_query(previous_start), true) {
+        summary.error = Some(e);
+        return summary;
+    }
+    let mut tuner = BatchTuner::bulk();
+    let mut read = 0;
+    'read: while read < max_events {
+        let batch = match tuner.next_batch(&mut source, max_events - read) {
+            Ok(batch) if batch.is_empty() => break,
+            Ok(batch) => batch,
+            Err(e) => {
+                summary.error = Some(e);
+                break;
+            }
+        };
+        for event in batch {
+            read += 1;
+            let Some(time) = event.timestamp else {
+                continue;
+            };
+            if time < previous_start {
+                break 'read;
+            }
+            if time >= start {
+                summary.current.add(&event);
+            } else {
+                summary.previous.add(&event);
+            }
+        }
+        summary.truncated = read >= max_events;
+    }
+    summary
+}
+
+/// Builds the counts, top error sources, top error signatures and new event IDs tables.
+fn tables(summary: &LogSummary, top: usize) -> Vec<Table> {
+    let counts = Table {
+        title: "Events".to_string(),
+        header: vec!["Level", "This period", "Previous", "Change"],
+        rows: LEVEL_NAMES
+            .iter()
+            .enumerate()
+            .map(|(row, name)| {
+                let (current, previous) =
+                    (summary.current.levels[row], summary.previous.levels[row]);
+                vec![
+                    name.to_string(),
+                    current.to_string(),
+                    previous.to_string(),
+                    change(current, previous),
+                ]
+            })
+            .collect(),
+        empty: "",
+    };
+
+    let previous_sources = summary.previous.error_sources();
+    let mut sources: Vec<(&str, usize)> = summary.current.error_sources().into_iter().collect();
+    sources.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    let error_sources = Table {
+        title: "Top error sources".to_string(),
+        header: vec!["Source", "Errors", "Previous", "Change"],
+        rows: sources
+            .into_iter()
+            .take(top)
+            .map(|(source, count)| {
+                let previous = previous_sources.get(source).copied().unwrap_or(0);
+                vec![
+                    source.to_string(),
+                    count.to_string(),
+                    previous.to_string(),
+                    change(count, previous),
+                ]
+            })
+            .collect(),
+        empty: "No critical or error events.",
+    };
+
+    let mut errors: Vec<&Signature> = summary
+        .current
+        .signatures
+        .values()
+        .filter(|signature| signature.is_error())
+        .collect();
+    errors.sort_by(|a, b| b.count.cmp(&a.count).then(b.last_seen.cmp(&a.last_seen)));
+    let error_signatures = Table {
+        title: "Top error signatures".to_string(),
+        header: vec![
+            "Source",
+            "Event ID",
+            "Level",
+            "Count",
+            "Last seen",
+            "Message",
+        ],
+        rows: errors
+            .into_iter()
+            .take(top)
+            .map(|signature| {
+                vec![
+                    signature.source.clone(),
+                    signature.event_id.to_string(),
+                    signature.level.display_name(),
+                    signature.count.to_string(),
+                    helpers::format_event_time(signature.last_seen),
+                    helpers::truncate_at_word(&signature.message, 100),
+                ]
+            })
+            .collect(),
+        empty: "No critical or error events.",
+    };
+
+    let mut new_ids: Vec<&Signature> = summary
+        .current
+        .signatures
+        .iter()
+        .filter(|(key, _)| !summary.previous.signatures.contains_key(*key))
+        .map(|(_, signature)| signature)
+        .collect();
+    new_ids.sort_by(|a, b| {
+        a.level
+            .severity_rank()
+            .cmp(&b.level.severity_rank())
+            .then(b.count.cmp(&a.count))
+    });
+    let new_event_ids = Table {
+        title: "New event IDs".to_string(),
+        header: vec![
+            "Source",
+            "Event ID",
+            "Level",
+            "Count",
+            "First seen",
+            "Message",
+        ],
+        rows: new_ids
+            .into_iter()
+            .take(top)
+            .map(|signature| {
+                vec![
+                    signature.source.clone(),
+                    signature.event_id.to_string(),
+                    signature.level.display_name(),
+                    signature.count.to_string(),
+                    helpers::format_event_time(signature.first_seen),
+                    helpers::truncate_at_word(&signature.message, 100),
+                ]
+            })
+            .collect(),
+        empty: "No event IDs that were not seen in the previous period.",
+    };
+
+    vec![counts, error_sources, error_signatures, new_event_ids]
+}
+
+/// Describes the change from the previous period, e.g. "+12" or "new".
+fn change(current: usize, previous: usize) -> String {
+    match (current, previous) {
+        (0, 0) => String::new(),
+        (_, 0) => "new".to_string(),
+        _ if current >= previous => format!("+{}", current - previous),
+        _ => format!("-{}", previous - current),
+    }
+}
+
+fn markdown(title: &str, intro: &str, sections: &[Section]) -> String {
+    let cell = |text: &str| text.replace('|', "\\|").replace('\n', " ");
+    let mut out = format!("# {}\n\n{}\n", cell(title), cell(intro));
+    for (log, notes, tables) in sections {
+        out.push_str(&format!("\n## {}\n", cell(log)));
+        for note in notes {
+            out.push_str(&format!("\n> {}\n", cell(note)));
+        }
+        for table in tables {
+            out.push_str(&format!("\n### {}\n\n", table.title));
+            if table.rows.is_empty() {
+                out.push_str(&format!("{}\n", table.empty));
+                continue;
+            }
+            out.push_str(&format!("| {} |\n", table.header.join(" | ")));
+            out.push_str(&format!("|{}\n", "---|".repeat(table.header.len())));
+            for row in &table.rows {
+                let cells: Vec<String> = row.iter().map(|value| cell(value)).collect();
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+        }
+    }
+    out
+}
+
+fn html(title: &str, intro: &str, sections: &[Section]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+         <style>body{{font-family:sans-serif}} table{{border-collapse:collapse}} \
+         th,td{{border:1px solid #999;padding:2px 6px;text-align:left}}</style>\n\
+         </head>\n<body>\n<h1>{0}</h1>\n<p>{1}</p>\n",
+        escape(title),
+        escape(intro)
+    );
+    for (log, notes, tables) in sections {
+        out.push_str(&format!("<h2>{}</h2>\n", escape(log.as_str())));
+        for note in notes {
+            out.push_str(&format!("<p><em>{}</em></p>\n", escape(note.as_str())));
+        }
+        for table in tables {
+            out.push_str(&format!("<h3>{}</h3>\n", escape(table.title.as_str())));
+            if table.rows.is_empty() {
+                out.push_str(&format!("<p>{}</p>\n", escape(table.empty)));
+                continue;
+            }
+            out.push_str("<table>\n<tr>");
+            for heading in &table.header {
+                out.push_str(&format!("<th>{}</th>", escape(heading)));
+            }
+            out.push_str("</tr>\n");
+            for row in &table.rows {
+                out.push_str("<tr>");
+                for value in row {
+                    out.push_str(&format!("<td>{}</td>", escape(value.as_str())));
+                }
+                out.push_str("</tr>\n");
+            }
+            out.push_str("</table>\n");
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
@@ -0,0 +1,154 @@
+use chrono::{DateTime, Utc};
+
+use crate::models::DisplayEvent;
+
+pub const DNS_CLIENT_CHANNEL: &str = "Microsoft-Windows-DNS-Client/Operational";
+/// Analytical log of the DNS Server role; only present (and enabled) on DNS servers.
+pub const DNS_SERVER_CHANNEL: &str = "Microsoft-Windows-DNSServer/Analytical";
+
+/// Failed lookups recorded by the DNS client: queries completed with a non-zero status.
+pub const CLIENT_FAILURES_QUERY: &str =
+    "*[System[EventID=3008] and EventData[Data[@Name='QueryStatus']!='0']]";
+/// Lookups that timed out, logged by the DNS client to the System log.
+pub const CLIENT_TIMEOUTS_QUERY: &str =
+    "*[System[Provider[@Name='Microsoft-Windows-DNS-Client'] and EventID=1014]]";
+/// Responses the DNS server answered with an error (RESPONSE_FAILURE).
+pub const SERVER_FAILURES_QUERY: &str = "*[System[EventID=258]]";
+
+/// Most failures read from each source.
+pub const MAX_FAILURE_EVENTS: usize = 5000;
+
+/// One failed resolution, reduced to what the analysis groups by.
+#[derive(Debug, Clone)]
+pub struct DnsFailure {
+    pub name: String,
+    pub rcode: String,
+    pub time: DateTime<Utc>,
+    /// "Client" or "Server".
+    pub origin: &'static str,
+}
+
+/// Failures of one name with one response code.
+#[derive(Debug, Clone)]
+pub struct FailureGroup {
+    pub name: String,
+    pub rcode: String,
+    pub origin: &'static str,
+    pub count: usize,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// State of the DNS failures view.
+#[derive(Debug, Clone, Default)]
+pub struct DnsAnalysis {
+    pub failures: Vec<DnsFailure>,
+    /// Sources that could not be read, with the reason.
+    pub unavailable: Vec<String>,
+    /// Domain the groups are narrowed to; matches the name and its subdomains.
+    pub domain: String,
+    pub domain_cursor: usize,
+    pub selection: usize,
+}
+
+impl DnsAnalysis {
+    /// Groups the failures under the domain filter by name and response code, most frequent
+    /// first.
+    pub fn groups(&self) -> Vec<FailureGroup> {
+        let domain = self.domain.trim().trim_start_matches('.').to_lowercase();
+        let mut groups: Vec<FailureGroup> = Vec::new();
+        for failure in &self.failures {
+            if !domain.is_empty() && !in_domain(&failure.name, &domain) {
+                continue;
+            }
+            match groups.iter_mut().find(|group| {
+                group.name == failure.name
+                    && group.rcode == failure.rcode
+                    && group.origin == failure.origin
+            }) {
+                Some(group) => {
+                    group.count += 1;
+                    group.last_seen = group.last_seen.max(failure.time);
+                }
+                None => groups.push(FailureGroup {
+                    name: failure.name.clone(),
+                    rcode: failure.rcode.clone(),
+                    origin: failure.origin,
+                    count: 1,
+                    last_seen: failure.time,
+                }),
+            }
+        }
+        groups.sort_by(|a, b| b.count.cmp(&a.count).then(b.last_seen.cmp(&a.last_seen)));
+        groups
+    }
+}
+
+/// Whether `name` is `domain` or one of its subdomains; `domain` is lowercase.
+fn in_domain(name: &str, domain: &str) -> bool {
+    let name = name.trim_end_matches('.').to_lowercase();
+    name == domain || name.ends_with(&format!(".{}", domain))
+}
+
+fn field<'a>(event: &'a DisplayEvent, name: &str) -> Option<&'a str> {
+    event
+        .event_data
+        .named()
+        .find(|(field, value)| *field == name && !value.is_empty())
+        .map(|(_, value)| value)
+}
+
+/// Reduces a DNS client or server failure event to its name and response code.
+pub fn failure_of(event: &DisplayEvent) -> Option<DnsFailure> {
+    let (name, rcode, origin) = match event.event_id {
+        3008 => {
+            let status: u32 = field(event, "QueryStatus")?.parse().ok()?;
+            (
+                field(event, "QueryName")?,
+                client_status_name(status),
+                "Client",
+            )
+        }
+        1014 => (field(event, "QueryName")?, "TIMEOUT".to_string(), "Client"),
+        258 => {
+            let rcode: u32 = field(event, "RCODE")?.parse().ok()?;
+            (field(event, "QNAME")?, rcode_name(rcode), "Server")
+        }
+        _ => return None,
+    };
+    Some(DnsFailure {
+        name: name.trim_end_matches('.').to_string(),
+        rcode,
+        time: event.timestamp?,
+        origin,
+    })
+}
+
+/// Names a DNS response code (RFC 1035 and RFC 2136).
+fn rcode_name(rcode: u32) -> String {
+    match rcode {
+        1 => "FORMERR".to_string(),
+        2 => "SERVFAIL".to_string(),
+        3 => "NXDOMAIN".to_string(),
+        4 => "NOTIMP".to_string(),
+        5 => "REFUSED".to_string(),
+        6 => "YXDOMAIN".to_string(),
+        7 => "YXRRSET".to_string(),
+        8 => "NXRRSET".to_string(),
+        9 => "NOTAUTH".to_string(),
+        10 => "NOTZONE".to_string(),
+        other => format!("RCODE {}", other),
+    }
+}
+
+/// Names the Win32 status the DNS client reports, using the response code it stands for
+/// where there is one.
+fn client_status_name(status: u32) -> String {
+    match status {
+        9001..=9010 => rcode_name(status - 9000),
+        9501 => "NODATA".to_string(),
+        1460 => "TIMEOUT".to_string(),
+        11001 => "HOST_NOT_FOUND".to_string(),
+        87 => "INVALID_PARAMETER".to_string(),
+        other => format!("status {}", other),
+    }
+}
@@ -0,0 +1,298 @@
+use std::error::Error;
+use std::io::{IsTerminal, stdout};
+
+use crate::catalog::{self, MessageCatalog};
+use crate::config::{self, Config};
+use crate::hosts;
+use crate::models::ChannelAccess;
+use crate::source::{EventSource, PlatformSource};
+use crate::theme::Theme;
+
+/// Smallest terminal the panels and dialogs are laid out for without clipping.
+const RECOMMENDED_COLUMNS: u16 = 80;
+const RECOMMENDED_ROWS: u16 = 24;
+
+/// Result of one check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    /// Works, but some feature will be limited.
+    Warn,
+    Fail,
+    /// Does not apply on this platform.
+    Skip,
+}
+
+impl Outcome {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+            Self::Skip => "SKIP",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Check {
+    name: String,
+    outcome: Outcome,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, outcome: Outcome, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            outcome,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Checks what the viewer needs and prints a report, failing when any check fails.
+pub fn run() -> Result<(), Box<dyn Error>> {
+    let mut checks = vec![event_log_service()];
+    checks.extend(channel_access());
+    checks.push(publishers());
+    checks.extend(configuration());
+    checks.extend(terminal());
+
+    for check in &checks {
+        println!(
+            "[{}] {}: {}",
+            check.outcome.label(),
+            check.name,
+            check.detail
+        );
+    }
+    let count = |outcome| {
+        checks
+            .iter()
+            .filter(|check| check.outcome == outcome)
+            .count()
+    };
+    let failed = count(Outcome::Fail);
+    println!();
+    println!(
+        "{} passed, {} with warnings, {} failed, {} skipped.",
+        count(Outcome::Pass),
+        count(Outcome::Warn),
+        failed,
+        count(Outcome::Skip)
+    );
+    if failed > 0 {
+        return Err(format!("{} of {} checks failed.", failed, checks.len()).into());
+    }
+    Ok(())
+}
+
+/// Reads the state of the Event Log service with `sc query`.
+#[cfg(target_os = "windows")]
+fn event_log_service() -> Check {
+    const NAME: &str = "Event Log service";
+    let output = match std::process::Command::new("sc")
+        .args(["query", "eventlog"])
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => return Check::new(NAME, Outcome::Fail, format!("sc query failed: {}", e)),
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    let state = text
+        .lines()
+        .find(|line| line.trim_start().starts_with("STATE"))
+        .and_then(|line| line.split(':').nth(1))
+        .and_then(|value| value.split_whitespace().nth(1))
+        .map(str::to_string);
+    match state {
+        Some(state) if state == "RUNNING" => Check::new(NAME, Outcome::Pass, "running"),
+        Some(state) => Check::new(
+            NAME,
+            Outcome::Fail,
+            format!(
+                "{}; start it with 'net start eventlog' from an elevated prompt",
+                state.to_lowercase()
+            ),
+        ),
+        None => Check::new(
+            NAME,
+            Outcome::Fail,
+            format!("state unknown: {}", text.trim().replace("\r\n", " ")),
+        ),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn event_log_service() -> Check {
+    Check::new(
+        "Event Log service",
+        Outcome::Skip,
+        "only checked on Windows",
+    )
+}
+
+/// Whether each log offered as a tab can be read.
+fn channel_access() -> Vec<Check> {
+    let source = PlatformSource::default();
+    source
+        .channels()
+        .into_iter()
+        .map(|channel| {
+            let name = format!("Log '{}'", channel);
+            match source.check_access(&channel) {
+                ChannelAccess::Readable => Check::new(name, Outcome::Pass, "readable"),
+                ChannelAccess::Unknown => {
+                    Check::new(name, Outcome::Skip, "access not checked on this platform")
+                }
+                ChannelAccess::Missing => {
+                    Check::new(name, Outcome::Warn, "does not exist on this computer")
+                }
+                access => Check::new(
+                    name,
+                    Outcome::Fail,
+                    access
+                        .explanation(&channel)
+                        .replace("\n\n", " ")
+                        .replace('\n', " "),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Whether the registered publishers can be listed, as the filter dialog's source list needs.
+#[cfg(target_os = "windows")]
+fn publishers() -> Check {
+    const NAME: &str = "Publishers";
+    match crate::event_api::publisher_ids() {
+        Ok(ids) if ids.is_empty() => Check::new(NAME, Outcome::Fail, "none are registered"),
+        Ok(ids) => Check::new(NAME, Outcome::Pass, format!("{} registered", ids.len())),
+        Err(e) => Check::new(NAME, Outcome::Fail, e),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn publishers() -> Check {
+    Check::new("Publishers", Outcome::Skip, "only checked on Windows")
+}
+
+/// Whether the files in the configuration folder can be read.
+fn configuration() -> Vec<Check> {
+    let mut checks = Vec::new();
+    let Some(dir) = config::config_dir() else {
+        checks.push(Check::new(
+            "Configuration",
+            Outcome::Fail,
+            "no configuration folder: none of APPDATA, XDG_CONFIG_HOME and HOME is set",
+        ));
+        return checks;
+    };
+
+    match Config::load() {
+        Ok(config) => {
+            let path = config::config_path().unwrap_or_default();
+            let detail = if path.exists() {
+                format!("{} is valid", path.display())
+            } else {
+                format!("no file at {}; defaults in use", path.display())
+            };
+            checks.push(Check::new("Configuration", Outcome::Pass, detail));
+            if Theme::resolve(&config.theme, &config.themes).is_none() {
+                checks.push(Check::new(
+                    "Theme",
+                    Outcome::Warn,
+                    format!("unknown theme '{}'; classic-blue is used", config.theme),
+                ));
+            }
+        }
+        Err(e) => checks.push(Check::new(
+            "Configuration",
+            Outcome::Fail,
+            e.replace("\n\n", " "),
+        )),
+    }
+
+    let hosts_path = hosts::hosts_path().unwrap_or_else(|| dir.join("hosts.toml"));
+    if hosts_path.exists() {
+        checks.push(match hosts::load() {
+            Ok(_) => Check::new(
+                "Host inventory",
+                Outcome::Pass,
+                format!("{} is valid", hosts_path.display()),
+            ),
+            Err(e) => Check::new("Host inventory", Outcome::Fail, e.replace("\n\n", " ")),
+        });
+    }
+
+    if let Some(catalog_path) = catalog::shared_catalog_path().filter(|path| path.exists()) {
+        checks.push(match MessageCatalog::load(&catalog_path) {
+            Ok(catalog) => Check::new(
+                "Shared message catalog",
+                Outcome::Pass,
+                format!(
+                    "{} templates in {}",
+                    catalog.template_count(),
+                    catalog_path.display()
+                ),
+            ),
+            Err(e) => Check::new("Shared message catalog", Outcome::Fail, e),
+        });
+    }
+    checks
+}
+
+/// Whether the TUI can take over this console.
+fn terminal() -> Vec<Check> {
+    let mut checks = Vec::new();
+    if !stdout().is_terminal() {
+        checks.push(Check::new(
+            "Terminal",
+            Outcome::Fail,
+            "output is redirected; the TUI needs a console (use --no-tui for text output)",
+        ));
+        return checks;
+    }
+
+    match crossterm::terminal::size() {
+        Ok((columns, rows)) if columns < RECOMMENDED_COLUMNS || rows < RECOMMENDED_ROWS => checks
+            .push(Check::new(
+                "Terminal size",
+                Outcome::Warn,
+                format!(
+                    "{}x{}; panels and dialogs are clipped below {}x{}",
+                    columns, rows, RECOMMENDED_COLUMNS, RECOMMENDED_ROWS
+                ),
+            )),
+        Ok((columns, rows)) => checks.push(Check::new(
+            "Terminal size",
+            Outcome::Pass,
+            format!("{}x{}", columns, rows),
+        )),
+        Err(e) => checks.push(Check::new("Terminal size", Outcome::Fail, e.to_string())),
+    }
+
+    let raw_mode = crossterm::terminal::enable_raw_mode()
+        .and_then(|()| crossterm::terminal::disable_raw_mode());
+    checks.push(match raw_mode {
+        Ok(()) => Check::new(
+            "Raw mode",
+            Outcome::Pass,
+            "key presses can be read directly",
+        ),
+        Err(e) => Check::new("Raw mode", Outcome::Fail, e.to_string()),
+    });
+
+    let colors = crossterm::style::available_color_count();
+    checks.push(if colors >= 16 {
+        Check::new("Colors", Outcome::Pass, format!("{} available", colors))
+    } else {
+        Check::new(
+            "Colors",
+            Outcome::Warn,
+            format!("{} available; the themes need 16", colors),
+        )
+    });
+    checks
+}
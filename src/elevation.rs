@@ -0,0 +1,111 @@
+#[cfg(target_os = "windows")]
+use windows::{
+    Win32::{
+        Foundation::{CloseHandle, HANDLE, HWND},
+        Security::{GetTokenInformation, TOKEN_ELEVATION, TOKEN_QUERY, TokenElevation},
+        System::Threading::{GetCurrentProcess, OpenProcessToken},
+        UI::{Shell::ShellExecuteW, WindowsAndMessaging::SW_SHOWNORMAL},
+    },
+    core::{PCWSTR, w},
+};
+
+#[cfg(target_os = "windows")]
+use crate::event_api::to_wide_string;
+
+/// Whether the process token is elevated (started with Run as administrator, or UAC is off
+/// for this account).
+#[cfg(target_os = "windows")]
+pub fn is_elevated() -> bool {
+    unsafe {
+        let mut token = HANDLE::default();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).is_err() {
+            return false;
+        }
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut length = 0;
+        let result = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut TOKEN_ELEVATION as *mut std::ffi::c_void),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut length,
+        );
+        let _ = CloseHandle(token);
+        result.is_ok() && elevation.TokenIsElevated != 0
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_elevated() -> bool {
+    false
+}
+
+/// Starts this executable again with the same arguments and working directory through the
+/// "runas" verb, which shows the UAC prompt. Returns once the new process has started.
+#[cfg(target_os = "windows")]
+pub fn relaunch_elevated() -> Result<(), String> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to find the Event Commander executable: {}", e))?;
+    let arguments = std::env::args()
+        .skip(1)
+        .map(|argument| quote_argument(&argument))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let exe_wide = to_wide_string(&exe.to_string_lossy());
+    let arguments_wide = to_wide_string(&arguments);
+    let directory_wide = std::env::current_dir()
+        .ok()
+        .map(|dir| to_wide_string(&dir.to_string_lossy()));
+    let instance = unsafe {
+        ShellExecuteW(
+            HWND::default(),
+            w!("runas"),
+            PCWSTR::from_raw(exe_wide.as_ptr()),
+            PCWSTR::from_raw(arguments_wide.as_ptr()),
+            directory_wide
+                .as_ref()
+                .map_or(PCWSTR::null(), |dir| PCWSTR::from_raw(dir.as_ptr())),
+            SW_SHOWNORMAL,
+        )
+    };
+    // ShellExecute returns a value of 32 or less on failure, including a declined UAC prompt.
+    if instance.0 <= 32 {
+        return Err(format!(
+            "Event Commander could not be restarted as administrator: {}",
+            windows::core::Error::from_win32().message()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn relaunch_elevated() -> Result<(), String> {
+    Err("Relaunching as administrator requires Windows.".to_string())
+}
+
+/// Quotes a command line argument the way the C runtime parses it back.
+#[cfg(target_os = "windows")]
+fn quote_argument(argument: &str) -> String {
+    if !argument.is_empty() && !argument.contains([' ', '\t', '"']) {
+        return argument.to_string();
+    }
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in argument.chars() {
+        if c == '\\' {
+            backslashes += 1;
+            continue;
+        }
+        let escapes = if c == '"' {
+            backslashes * 2 + 1
+        } else {
+            backslashes
+        };
+        quoted.push_str(&"\\".repeat(escapes));
+        quoted.push(c);
+        backslashes = 0;
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
@@ -1,17 +1,106 @@
-
 #[cfg(target_os = "windows")]
 use windows::{
-    Win32::Foundation::{ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS, GetLastError},
+    Win32::Foundation::{
+        CloseHandle, ERROR_ACCESS_DENIED, ERROR_EVT_CHANNEL_NOT_FOUND, ERROR_INSUFFICIENT_BUFFER,
+        ERROR_NO_MORE_ITEMS, GetLastError, HLOCAL, LocalFree, PSID,
+    },
+    Win32::Security::Authorization::ConvertSidToStringSidW,
+    Win32::Security::Credentials::{
+        CRED_ENUMERATE_FLAGS, CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_GENERIC, CREDENTIALW,
+        CredDeleteW, CredEnumerateW, CredFree, CredReadW, CredWriteW,
+    },
     Win32::System::EventLog::{
-        EVT_HANDLE, EVT_VARIANT, EvtClose, EvtFormatMessage, EvtFormatMessageXml, EvtNext,
-        EvtNextPublisherId, EvtOpenPublisherEnum, EvtOpenPublisherMetadata, EvtQuery,
-        EvtQueryChannelPath, EvtQueryReverseDirection, EvtRender, EvtRenderEventXml,
+        EVT_CHANNEL_CONFIG_PROPERTY_ID, EVT_EVENT_METADATA_PROPERTY_ID, EVT_HANDLE,
+        EVT_LOG_PROPERTY_ID, EVT_RPC_LOGIN, EVT_SYSTEM_PROPERTY_ID, EVT_VARIANT,
+        EVT_VARIANT_TYPE_ARRAY, EventMetadataEventID, EventMetadataEventMessageID,
+        EventMetadataEventTemplate, EvtChannelLoggingConfigAutoBackup,
+        EvtChannelLoggingConfigMaxSize, EvtChannelLoggingConfigRetention, EvtClearLog, EvtClose,
+        EvtCreateRenderContext, EvtExportLog, EvtExportLogChannelPath, EvtFormatMessage,
+        EvtFormatMessageId, EvtFormatMessageTask, EvtFormatMessageXml, EvtGetChannelConfigProperty,
+        EvtGetEventMetadataProperty, EvtGetLogInfo, EvtLogFull, EvtLogNumberOfLogRecords, EvtNext,
+        EvtNextEventMetadata, EvtNextPublisherId, EvtOpenChannelConfig, EvtOpenChannelEnum,
+        EvtOpenChannelPath, EvtOpenEventMetadataEnum, EvtOpenLog, EvtOpenPublisherEnum,
+        EvtOpenSession, EvtQuery, EvtQueryChannelPath, EvtQueryFilePath, EvtQueryReverseDirection,
+        EvtRender, EvtRenderContextSystem, EvtRenderContextUser, EvtRenderEventValues,
+        EvtRenderEventXml, EvtRpcLogin, EvtRpcLoginAuthDefault, EvtRpcLoginAuthKerberos,
+        EvtRpcLoginAuthNTLM, EvtRpcLoginAuthNegotiate, EvtSystemActivityID, EvtSystemChannel,
+        EvtSystemComputer, EvtSystemEventID, EvtSystemEventRecordId, EvtSystemKeywords,
+        EvtSystemLevel, EvtSystemProcessID, EvtSystemProviderName, EvtSystemRelatedActivityID,
+        EvtSystemTask, EvtSystemThreadID, EvtSystemTimeCreated, EvtSystemUserID,
+        EvtVarTypeAnsiString, EvtVarTypeBinary, EvtVarTypeBoolean, EvtVarTypeByte,
+        EvtVarTypeDouble, EvtVarTypeFileTime, EvtVarTypeGuid, EvtVarTypeHexInt32,
+        EvtVarTypeHexInt64, EvtVarTypeInt16, EvtVarTypeInt32, EvtVarTypeInt64, EvtVarTypeNull,
+        EvtVarTypeSByte, EvtVarTypeSid, EvtVarTypeSingle, EvtVarTypeSizeT, EvtVarTypeString,
+        EvtVarTypeSysTime, EvtVarTypeUInt16, EvtVarTypeUInt32, EvtVarTypeUInt64,
+    },
+    Win32::System::Threading::{
+        OpenProcess, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+        QueryFullProcessImageNameW,
     },
-    core::PCWSTR,
+    core::{PCWSTR, PWSTR},
+};
+
+#[cfg(target_os = "windows")]
+use crate::analysis::{self, ShutdownEvidence};
+#[cfg(target_os = "windows")]
+use crate::authflow::{self, AuthFlowView};
+#[cfg(target_os = "windows")]
+use crate::catalog::{self, MessageCatalog};
+#[cfg(target_os = "windows")]
+use crate::cluster;
+#[cfg(target_os = "windows")]
+use crate::devices::{self, DeviceView};
+#[cfg(target_os = "windows")]
+use crate::dns::{self, DnsAnalysis};
+#[cfg(target_os = "windows")]
+use crate::event_parser::{
+    event_data_message, parse_event_data, parse_event_xml, template_data_names,
+};
+#[cfg(target_os = "windows")]
+use crate::helpers::{self, parse_event_id_spec};
+#[cfg(target_os = "windows")]
+use crate::hosts::{self, HostStatus, Login, RemoteAuth};
+#[cfg(target_os = "windows")]
+use crate::hyperv::{self, HyperVView};
+use crate::models::{
+    AUTO_BATCH_RANGE, AUTO_BATCH_SECONDS, AppState, ChannelAccess, EVENT_BATCH_SIZE,
+    FIRST_PAINT_EVENTS, LoadProgress, SortKey,
+};
+#[cfg(target_os = "windows")]
+use crate::models::{
+    DisplayEvent, EventData, EventLevel, EventLevelFilter, FilterCriteria, RetentionInfo,
 };
+#[cfg(target_os = "windows")]
+use crate::msi::{self, InstallerView};
+#[cfg(target_os = "windows")]
+use crate::objectaccess::{self, ObjectAccess, ObjectAccessView};
+#[cfg(target_os = "windows")]
+use crate::printing::{self, PrintView};
+#[cfg(target_os = "windows")]
+use crate::publishers::PublisherCache;
+#[cfg(target_os = "windows")]
+use crate::servicing::{self, ServicingView};
+use crate::source::BatchTuner;
+#[cfg(target_os = "windows")]
+use crate::source::{EventSource, query_events};
+#[cfg(target_os = "windows")]
+use crate::wmi::{self, WmiAnalysis};
+#[cfg(target_os = "windows")]
+use chrono::{DateTime, Utc};
+#[cfg(target_os = "windows")]
+use std::cell::RefCell;
+#[cfg(target_os = "windows")]
+use std::collections::{BTreeSet, HashMap};
+#[cfg(target_os = "windows")]
+use std::path::{Path, PathBuf};
+#[cfg(target_os = "windows")]
+use std::rc::Rc;
+use std::time::Instant;
 
-use crate::event_parser::parse_event_xml;
-use crate::models::{AppState, EventLevelFilter, LOG_NAMES};
+#[cfg(target_os = "windows")]
+use crate::models::MergedQuery;
+#[cfg(target_os = "windows")]
+use std::collections::VecDeque;
 
 #[cfg(target_os = "windows")]
 pub fn to_wide_string(s: &str) -> Vec<u16> {
@@ -63,20 +152,424 @@ pub fn render_event_xml(event_handle: EVT_HANDLE) -> Option<String> {
     }
 }
 
+/// Renders an event's values through a render context. Returns the buffer, which also holds
+/// the strings the values point to, and the number of values at its start.
+#[cfg(target_os = "windows")]
+fn render_values(
+    context: EVT_HANDLE,
+    event_handle: EVT_HANDLE,
+) -> Option<(Vec<EVT_VARIANT>, usize)> {
+    const VARIANT_SIZE: usize = std::mem::size_of::<EVT_VARIANT>();
+    let mut buffer_used = 0;
+    let mut property_count = 0;
+    unsafe {
+        let _ = EvtRender(
+            context,
+            event_handle,
+            EvtRenderEventValues.0,
+            0,
+            None,
+            &mut buffer_used,
+            &mut property_count,
+        );
+    }
+    if buffer_used == 0 {
+        return None;
+    }
+    let mut buffer = vec![EVT_VARIANT::default(); (buffer_used as usize).div_ceil(VARIANT_SIZE)];
+    unsafe {
+        EvtRender(
+            context,
+            event_handle,
+            EvtRenderEventValues.0,
+            (buffer.len() * VARIANT_SIZE) as u32,
+            Some(buffer.as_mut_ptr() as *mut _),
+            &mut buffer_used,
+            &mut property_count,
+        )
+        .ok()?;
+    }
+    let count = (property_count as usize).min(buffer.len());
+    Some((buffer, count))
+}
+
+/// Converts a FILETIME, in 100 ns intervals since 1601, to a UTC time.
+#[cfg(target_os = "windows")]
+fn file_time_to_utc(file_time: u64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(
+        (file_time / 10_000_000) as i64 - 11_644_473_600,
+        (file_time % 10_000_000) as u32 * 100,
+    )
+}
+
+/// The string form of a SID, such as `S-1-5-18`.
+#[cfg(target_os = "windows")]
+fn sid_to_string(sid: PSID) -> Option<String> {
+    if sid.is_invalid() {
+        return None;
+    }
+    unsafe {
+        let mut string_sid = PWSTR::null();
+        ConvertSidToStringSidW(sid, &mut string_sid).ok()?;
+        let text = string_sid.to_string().ok();
+        LocalFree(HLOCAL(string_sid.0 as *mut _));
+        text
+    }
+}
+
+/// Reads the System values of an event through a render context made with
+/// `EvtRenderContextSystem`, without rendering or parsing its XML. The event data, message and
+/// raw XML are left empty; `raw_data` is a stub naming the record.
+#[cfg(target_os = "windows")]
+fn read_system_values(context: EVT_HANDLE, event_handle: EVT_HANDLE) -> Option<DisplayEvent> {
+    let (buffer, count) = render_values(context, event_handle)?;
+    let values = &buffer[..count];
+    let value = |id: EVT_SYSTEM_PROPERTY_ID| {
+        values
+            .get(id.0 as usize)
+            .filter(|variant| variant.Type != EvtVarTypeNull.0 as u32)
+            .map(|variant| variant.Anonymous)
+    };
+    let text = |id: EVT_SYSTEM_PROPERTY_ID| {
+        value(id)
+            .and_then(|value| unsafe { value.StringVal.to_string() }.ok())
+            .unwrap_or_default()
+    };
+    let guid = |id: EVT_SYSTEM_PROPERTY_ID| {
+        value(id)
+            .filter(|value| unsafe { !value.GuidVal.is_null() })
+            .map(|value| format!("{{{:?}}}", unsafe { *value.GuidVal }))
+    };
+    let timestamp = value(EvtSystemTimeCreated)
+        .and_then(|value| file_time_to_utc(unsafe { value.FileTimeVal }));
+    let user = value(EvtSystemUserID)
+        .and_then(|value| sid_to_string(unsafe { value.SidVal }))
+        .unwrap_or_default();
+    let provider = text(EvtSystemProviderName);
+    let mut event = DisplayEvent {
+        level: EventLevel::from_code(
+            value(EvtSystemLevel).map_or(0, |value| unsafe { value.ByteVal }),
+        ),
+        timestamp,
+        source: provider.clone(),
+        provider_name_original: provider,
+        event_id: value(EvtSystemEventID).map_or(0, |value| unsafe { value.UInt16Val } as u32),
+        channel: text(EvtSystemChannel),
+        computer: text(EvtSystemComputer),
+        user,
+        task: value(EvtSystemTask).map_or(0, |value| unsafe { value.UInt16Val }),
+        task_category: None,
+        keywords: value(EvtSystemKeywords).map_or(0, |value| unsafe { value.UInt64Val }),
+        record_id: value(EvtSystemEventRecordId).map_or(0, |value| unsafe { value.UInt64Val }),
+        activity_id: guid(EvtSystemActivityID),
+        related_activity_id: guid(EvtSystemRelatedActivityID),
+        process_id: value(EvtSystemProcessID).map(|value| unsafe { value.UInt32Val }),
+        thread_id: value(EvtSystemThreadID).map(|value| unsafe { value.UInt32Val }),
+        message: String::new(),
+        event_data: EventData::default(),
+        raw_data: String::new(),
+        raw_dropped: false,
+        formatted_message: None,
+        sigma_hits: Vec::new(),
+    };
+    event.raw_data = event.record_stub();
+    Some(event)
+}
+
+/// The text of one event data value, formatted as the event XML shows it. `None` for arrays
+/// and other types the XML renders in its own way.
+#[cfg(target_os = "windows")]
+fn variant_text(variant: &EVT_VARIANT) -> Option<String> {
+    if variant.Type & EVT_VARIANT_TYPE_ARRAY != 0 {
+        return None;
+    }
+    let value = variant.Anonymous;
+    let text = unsafe {
+        match variant.Type as i32 {
+            t if t == EvtVarTypeNull.0 => String::new(),
+            t if t == EvtVarTypeString.0 => value.StringVal.to_string().ok()?,
+            t if t == EvtVarTypeAnsiString.0 => value.AnsiStringVal.to_string().ok()?,
+            t if t == EvtVarTypeSByte.0 => value.SByteVal.to_string(),
+            t if t == EvtVarTypeByte.0 => value.ByteVal.to_string(),
+            t if t == EvtVarTypeInt16.0 => value.Int16Val.to_string(),
+            t if t == EvtVarTypeUInt16.0 => value.UInt16Val.to_string(),
+            t if t == EvtVarTypeInt32.0 => value.Int32Val.to_string(),
+            t if t == EvtVarTypeUInt32.0 => value.UInt32Val.to_string(),
+            t if t == EvtVarTypeInt64.0 => value.Int64Val.to_string(),
+            t if t == EvtVarTypeUInt64.0 => value.UInt64Val.to_string(),
+            t if t == EvtVarTypeHexInt32.0 => format!("0x{:x}", value.UInt32Val),
+            t if t == EvtVarTypeHexInt64.0 => format!("0x{:x}", value.UInt64Val),
+            t if t == EvtVarTypeSingle.0 => value.SingleVal.to_string(),
+            t if t == EvtVarTypeDouble.0 => value.DoubleVal.to_string(),
+            t if t == EvtVarTypeSizeT.0 => value.SizeTVal.to_string(),
+            t if t == EvtVarTypeBoolean.0 => value.BooleanVal.as_bool().to_string(),
+            t if t == EvtVarTypeGuid.0 && !value.GuidVal.is_null() => {
+                format!("{{{:?}}}", *value.GuidVal)
+            }
+            t if t == EvtVarTypeSid.0 => sid_to_string(value.SidVal)?,
+            t if t == EvtVarTypeFileTime.0 => file_time_to_utc(value.FileTimeVal)?
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            t if t == EvtVarTypeSysTime.0 && !value.SysTimeVal.is_null() => {
+                let time = *value.SysTimeVal;
+                chrono::NaiveDate::from_ymd_opt(
+                    time.wYear as i32,
+                    time.wMonth as u32,
+                    time.wDay as u32,
+                )?
+                .and_hms_milli_opt(
+                    time.wHour as u32,
+                    time.wMinute as u32,
+                    time.wSecond as u32,
+                    time.wMilliseconds as u32,
+                )?
+                .and_utc()
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+            }
+            t if t == EvtVarTypeBinary.0 && !value.BinaryVal.is_null() => {
+                std::slice::from_raw_parts(value.BinaryVal, variant.Count as usize)
+                    .iter()
+                    .map(|byte| format!("{:02X}", byte))
+                    .collect()
+            }
+            _ => return None,
+        }
+    };
+    Some(text)
+}
+
+/// Reads an event's data values through a render context made with `EvtRenderContextUser`.
+#[cfg(target_os = "windows")]
+fn read_user_values(context: EVT_HANDLE, event_handle: EVT_HANDLE) -> Option<Vec<String>> {
+    let (buffer, count) = render_values(context, event_handle)?;
+    buffer[..count].iter().map(variant_text).collect()
+}
+
+/// Render contexts for a batch of events, closed when dropped.
+#[cfg(target_os = "windows")]
+struct RenderContexts {
+    /// Reads the System values, for `read_system_values`.
+    system: EVT_HANDLE,
+    /// Reads the event data values, for `read_user_values`.
+    user: EVT_HANDLE,
+}
+
+#[cfg(target_os = "windows")]
+impl RenderContexts {
+    fn new() -> Option<Self> {
+        let system = unsafe { EvtCreateRenderContext(None, EvtRenderContextSystem.0) }.ok()?;
+        match unsafe { EvtCreateRenderContext(None, EvtRenderContextUser.0) } {
+            Ok(user) => Some(Self { system, user }),
+            Err(_) => {
+                unsafe {
+                    let _ = EvtClose(system);
+                }
+                None
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for RenderContexts {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = EvtClose(self.system);
+            let _ = EvtClose(self.user);
+        }
+    }
+}
+
+/// Reads an event's columns from its System values and its event data from the data values,
+/// named by the provider's template. The XML is rendered only to be kept as the raw record, and
+/// parsed for the event data when no template fits the values. Without render contexts the
+/// whole XML is parsed.
+#[cfg(target_os = "windows")]
+fn read_event(
+    publisher_metadata_cache: &mut PublisherCache,
+    contexts: Option<&RenderContexts>,
+    event_handle: EVT_HANDLE,
+) -> Option<DisplayEvent> {
+    let xml = render_event_xml(event_handle)?;
+    let Some(contexts) = contexts else {
+        return Some(parse_event_xml(&xml));
+    };
+    let Some(mut event) = read_system_values(contexts.system, event_handle) else {
+        return Some(parse_event_xml(&xml));
+    };
+    let named_values = read_user_values(contexts.user, event_handle).and_then(|values| {
+        let names = publisher_metadata_cache.data_names(
+            &event.provider_name_original,
+            event.event_id,
+            values.len(),
+        )?;
+        Some((names, values))
+    });
+    (event.event_data, event.message) = match named_values {
+        Some((names, values)) => {
+            let fields: Vec<(Option<String>, String)> = names
+                .into_iter()
+                .zip(values.iter().map(|value| value.trim().to_string()))
+                .collect();
+            let texts: Vec<String> = fields
+                .iter()
+                .map(|(_, value)| value.clone())
+                .filter(|value| !value.is_empty())
+                .collect();
+            let message = event_data_message(&event.provider_name_original, event.event_id, &texts);
+            (EventData { fields }, message)
+        }
+        None => parse_event_data(&xml, &event.provider_name_original, event.event_id),
+    };
+    event.raw_data = xml;
+    Some(event)
+}
+
+/// Checks whether the current user can read a channel by opening a query on it, which is
+/// where access is enforced.
+#[cfg(target_os = "windows")]
+pub fn check_channel_access(log_name: &str) -> ChannelAccess {
+    let channel_wide = to_wide_string(log_name);
+    let query_wide = to_wide_string("*");
+    unsafe {
+        match EvtQuery(
+            None,
+            PCWSTR::from_raw(channel_wide.as_ptr()),
+            PCWSTR::from_raw(query_wide.as_ptr()),
+            EvtQueryChannelPath.0,
+        ) {
+            Ok(handle) => {
+                let _ = EvtClose(handle);
+                ChannelAccess::Readable
+            }
+            Err(e) if e.code() == ERROR_ACCESS_DENIED.to_hresult() => ChannelAccess::Denied,
+            Err(e) if e.code() == ERROR_EVT_CHANNEL_NOT_FOUND.to_hresult() => {
+                ChannelAccess::Missing
+            }
+            Err(e) => ChannelAccess::Failed((e.code().0 & 0xFFFF) as u32),
+        }
+    }
+}
+
+/// Reads a channel's retention settings, current fill state and oldest event time.
+#[cfg(target_os = "windows")]
+pub fn load_retention_info(log_name: &str) -> Option<RetentionInfo> {
+    let channel_wide = to_wide_string(log_name);
+    let channel_path = PCWSTR::from_raw(channel_wide.as_ptr());
+    unsafe {
+        let config = EvtOpenChannelConfig(None, channel_path, 0).ok()?;
+        let retention = get_channel_config_variant(config, EvtChannelLoggingConfigRetention);
+        let auto_backup = get_channel_config_variant(config, EvtChannelLoggingConfigAutoBackup);
+        let max_size = get_channel_config_variant(config, EvtChannelLoggingConfigMaxSize);
+        let _ = EvtClose(config);
+
+        let log = EvtOpenLog(None, channel_path, EvtOpenChannelPath.0).ok()?;
+        let is_full = get_log_info_variant(log, EvtLogFull);
+        let record_count = get_log_info_variant(log, EvtLogNumberOfLogRecords);
+        let _ = EvtClose(log);
+
+        Some(RetentionInfo {
+            overwrites: !retention.is_some_and(|v| v.Anonymous.BooleanVal.as_bool())
+                && !auto_backup.is_some_and(|v| v.Anonymous.BooleanVal.as_bool()),
+            is_full: is_full.is_some_and(|v| v.Anonymous.BooleanVal.as_bool()),
+            max_size: max_size.map_or(0, |v| v.Anonymous.UInt64Val),
+            record_count: record_count.map_or(0, |v| v.Anonymous.UInt64Val),
+            oldest_event: oldest_event_time(channel_path),
+        })
+    }
+}
+
+/// Reads a fixed-size channel configuration property.
+#[cfg(target_os = "windows")]
+fn get_channel_config_variant(
+    config: EVT_HANDLE,
+    property_id: EVT_CHANNEL_CONFIG_PROPERTY_ID,
+) -> Option<EVT_VARIANT> {
+    let mut variant = EVT_VARIANT::default();
+    let mut buffer_used = 0;
+    unsafe {
+        EvtGetChannelConfigProperty(
+            config,
+            property_id,
+            0,
+            std::mem::size_of::<EVT_VARIANT>() as u32,
+            Some(&mut variant),
+            &mut buffer_used,
+        )
+        .ok()?;
+    }
+    Some(variant)
+}
+
+/// Reads a fixed-size log information property.
+#[cfg(target_os = "windows")]
+fn get_log_info_variant(log: EVT_HANDLE, property_id: EVT_LOG_PROPERTY_ID) -> Option<EVT_VARIANT> {
+    let mut variant = EVT_VARIANT::default();
+    let mut buffer_used = 0;
+    unsafe {
+        EvtGetLogInfo(
+            log,
+            property_id,
+            std::mem::size_of::<EVT_VARIANT>() as u32,
+            Some(&mut variant),
+            &mut buffer_used,
+        )
+        .ok()?;
+    }
+    Some(variant)
+}
+
+/// Returns the creation time of the oldest event still in the channel.
+#[cfg(target_os = "windows")]
+fn oldest_event_time(channel_path: PCWSTR) -> Option<chrono::DateTime<chrono::Utc>> {
+    let query_wide = to_wide_string("*");
+    unsafe {
+        let query = EvtQuery(
+            None,
+            channel_path,
+            PCWSTR::from_raw(query_wide.as_ptr()),
+            EvtQueryChannelPath.0,
+        )
+        .ok()?;
+        let mut event_handles = [0isize; 1];
+        let mut fetched = 0;
+        let next_result = EvtNext(query, &mut event_handles, 0, 0, &mut fetched);
+        let _ = EvtClose(query);
+        if next_result.is_err() || fetched == 0 {
+            return None;
+        }
+        let event_handle = EVT_HANDLE(event_handles[0]);
+        let xml = render_event_xml(event_handle);
+        let _ = EvtClose(event_handle);
+        helpers::event_system_time(&xml?).map(|time| time.with_timezone(&chrono::Utc))
+    }
+}
+
 /// Loads available event log sources using the Windows Event Log API.
 #[cfg(target_os = "windows")]
 pub fn load_available_sources(app: &mut AppState) -> Option<Vec<String>> {
+    match publisher_ids() {
+        Ok(sources) if sources.is_empty() => None,
+        Ok(sources) => Some(sources),
+        Err(e) => {
+            app.log(&e);
+            None
+        }
+    }
+}
+
+/// Names of the publishers registered on this computer, sorted case-insensitively.
+#[cfg(target_os = "windows")]
+pub fn publisher_ids() -> Result<Vec<String>, String> {
     let mut sources = Vec::new();
     let publisher_enum_handle = match unsafe { EvtOpenPublisherEnum(None, 0) } {
         Ok(handle) if !handle.is_invalid() => handle,
-        Ok(_handle) => return None,
-        Err(_e) => {
-            app.log(&format!(
+        Ok(_handle) => return Err("EvtOpenPublisherEnum returned an invalid handle.".to_string()),
+        Err(e) => {
+            return Err(format!(
                 "Error calling EvtOpenPublisherEnum: {} GetLastError: {:?}",
-                _e,
+                e,
                 unsafe { GetLastError() }
             ));
-            return None;
         }
     };
 
@@ -126,37 +619,259 @@ pub fn load_available_sources(app: &mut AppState) -> Option<Vec<String>> {
         let _ = EvtClose(publisher_enum_handle);
     }
 
-    if sources.is_empty() {
-        None
-    } else {
-        sources.sort_unstable_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()));
-        Some(sources)
+    sources.sort_unstable_by_key(|source| source.to_lowercase());
+    Ok(sources)
+}
+
+/// Events read ahead from each merged channel at a time.
+#[cfg(target_os = "windows")]
+const MERGED_READ_AHEAD: usize = 100;
+
+/// Logs offered as tabs by the Windows Event Log backend.
+pub const WINDOWS_CHANNELS: [&str; 5] = [
+    "Application",
+    "System",
+    "Security",
+    "Setup",
+    "ForwardedEvents",
+];
+
+/// The Windows Event Log API as an event source.
+#[cfg(target_os = "windows")]
+#[derive(Debug, Default)]
+pub struct WindowsEventLog {
+    /// Connection to the computer read; `None` reads this one.
+    session: Option<Rc<RemoteSession>>,
+    /// Whether only the System values of events are rendered.
+    system_only: bool,
+    query_handle: Option<EVT_HANDLE>,
+    /// Publisher metadata that messages are formatted with, shared by the sources forked or
+    /// connected from this one. Messages of remote events are formatted with the publishers
+    /// installed here.
+    publisher_metadata_cache: Rc<RefCell<PublisherCache>>,
+}
+
+/// An event log session on a remote computer, closed once no source uses it.
+#[cfg(target_os = "windows")]
+#[derive(Debug)]
+struct RemoteSession(EVT_HANDLE);
+
+#[cfg(target_os = "windows")]
+impl Drop for RemoteSession {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = EvtClose(self.0);
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for WindowsEventLog {
+    fn drop(&mut self) {
+        self.close_query();
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl EventSource for WindowsEventLog {
+    fn channels(&self) -> Vec<String> {
+        WINDOWS_CHANNELS
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    fn check_access(&self, channel: &str) -> ChannelAccess {
+        if helpers::is_saved_log(channel) {
+            return if Path::new(channel).is_file() {
+                ChannelAccess::Readable
+            } else {
+                ChannelAccess::Missing
+            };
+        }
+        check_channel_access(channel)
+    }
+
+    fn query(&mut self, channel: &str, query: &str, reverse: bool) -> Result<(), String> {
+        self.close_query();
+        let channel_wide = to_wide_string(channel);
+        let query_wide = to_wide_string(query);
+        // Structured XML queries name their own channels, so the path must be null.
+        let channel_path = if query.trim_start().starts_with('<') {
+            PCWSTR::null()
+        } else {
+            PCWSTR::from_raw(channel_wide.as_ptr())
+        };
+        let path_flag = if helpers::is_saved_log(channel) {
+            EvtQueryFilePath.0
+        } else {
+            EvtQueryChannelPath.0
+        };
+        let flags = if reverse {
+            path_flag | EvtQueryReverseDirection.0
+        } else {
+            path_flag
+        };
+        let session = self
+            .session
+            .as_ref()
+            .map_or(EVT_HANDLE::default(), |session| session.0);
+        let handle = unsafe {
+            EvtQuery(
+                session,
+                channel_path,
+                PCWSTR::from_raw(query_wide.as_ptr()),
+                flags,
+            )
+        }
+        .map_err(|e| e.to_string())?;
+        self.query_handle = Some(handle);
+        Ok(())
+    }
+
+    fn next_batch(&mut self, max: usize) -> Result<Vec<DisplayEvent>, String> {
+        let mut events = Vec::new();
+        let Some(query_handle) = self.query_handle else {
+            return Ok(events);
+        };
+        let mut event_handles = vec![0isize; max];
+        let mut fetched = 0;
+        unsafe {
+            if EvtNext(query_handle, &mut event_handles, 0, 0, &mut fetched).is_err() {
+                let error = GetLastError().0;
+                if error == ERROR_NO_MORE_ITEMS.0 {
+                    return Ok(events);
+                }
+                return Err(format!("WIN32_ERROR({})", error));
+            }
+            let contexts = RenderContexts::new();
+            let mut cache = self.publisher_metadata_cache.borrow_mut();
+            for &raw_handle in &event_handles[..fetched as usize] {
+                let event_handle = EVT_HANDLE(raw_handle);
+                let event = if self.system_only {
+                    contexts
+                        .as_ref()
+                        .and_then(|contexts| read_system_values(contexts.system, event_handle))
+                        .map(|mut event| {
+                            event.message = cluster::DEFERRED_MESSAGE.to_string();
+                            event
+                        })
+                } else {
+                    read_display_event(&mut cache, contexts.as_ref(), event_handle)
+                };
+                events.extend(event);
+                let _ = EvtClose(event_handle);
+            }
+        }
+        Ok(events)
+    }
+
+    fn close_query(&mut self) {
+        if let Some(handle) = self.query_handle.take() {
+            unsafe {
+                let _ = EvtClose(handle);
+            }
+        }
+    }
+
+    /// The event XML, pretty-printed when it parses.
+    fn render_detail(&self, event: &DisplayEvent) -> String {
+        match helpers::pretty_print_xml(&event.raw_data) {
+            Ok(pretty_xml) => pretty_xml,
+            Err(e) => format!(
+                "<Failed to pretty-print XML: {}. Displaying raw XML.>\n\n{}",
+                e, event.raw_data
+            ),
+        }
+    }
+
+    fn fork(&self) -> Box<dyn EventSource> {
+        Box::new(Self {
+            session: self.session.clone(),
+            system_only: self.system_only,
+            query_handle: None,
+            publisher_metadata_cache: Rc::clone(&self.publisher_metadata_cache),
+        })
+    }
+
+    fn connect(
+        &self,
+        host: &str,
+        login: &Login,
+        system_only: bool,
+    ) -> Result<Box<dyn EventSource>, String> {
+        let session = open_remote_session(host, login)?;
+        Ok(Box::new(Self {
+            session: Some(Rc::new(RemoteSession(session))),
+            system_only,
+            query_handle: None,
+            publisher_metadata_cache: Rc::clone(&self.publisher_metadata_cache),
+        }))
+    }
+
+    fn message_templates(
+        &mut self,
+        provider: &str,
+        event_ids: &BTreeSet<u32>,
+    ) -> HashMap<u32, String> {
+        load_message_templates(
+            &mut self.publisher_metadata_cache.borrow_mut(),
+            provider,
+            event_ids,
+        )
     }
 }
 
-/// Starts or continues loading event logs using the Windows Event Log API.
+/// Reads and formats one event from a query.
 #[cfg(target_os = "windows")]
+fn read_display_event(
+    publisher_metadata_cache: &mut PublisherCache,
+    contexts: Option<&RenderContexts>,
+    event_handle: EVT_HANDLE,
+) -> Option<DisplayEvent> {
+    let mut display_event = read_event(publisher_metadata_cache, contexts, event_handle)?;
+    display_event.formatted_message = format_event_message(
+        publisher_metadata_cache,
+        &display_event.provider_name_original,
+        event_handle,
+    );
+    if display_event.task != 0 {
+        display_event.task_category = format_event_task(
+            publisher_metadata_cache,
+            &display_event.provider_name_original,
+            event_handle,
+        );
+    }
+    Some(display_event)
+}
+
+/// Starts or continues loading the selected log through the platform's event source.
 impl AppState {
+    /// The batch is read in chunks from the event loop through `continue_log_load`, so the
+    /// footer can show its progress and Esc can stop it. A new initial load abandons a batch
+    /// still being read.
     pub fn start_or_continue_log_load(&mut self, initial_load: bool) {
+        if initial_load && self.load_progress.is_some() {
+            self.load_progress = None;
+            self.is_loading = false;
+        }
         if self.is_loading || (!initial_load && self.no_more_events) {
             return;
         }
         self.is_loading = true;
 
         if initial_load {
-            self.events.clear();
-            self.table_state = ratatui::widgets::TableState::default();
+            self.clear_events();
             self.no_more_events = false;
-            if let Some(handle) = self.query_handle.take() {
-                unsafe {
-                    let _ = EvtClose(handle);
-                }
-            }
+            self.cluster_nodes = None;
+            self.source.close_query();
+            #[cfg(target_os = "windows")]
+            self.close_merged_queries();
 
-            self.selected_log_name = LOG_NAMES
-                .get(self.selected_log_index)
-                .map(|s| s.to_string())
-                .unwrap_or_default();
+            self.selected_log_name = self
+                .tab_name(self.selected_log_index)
+                .unwrap_or_default()
+                .to_string();
 
             if self.selected_log_name.is_empty() {
                 self.show_error("Loading Error", "No log name selected.");
@@ -164,183 +879,867 @@ impl AppState {
                 return;
             }
 
-            let channel_wide = to_wide_string(&self.selected_log_name);
-            let query_str = self.build_xpath_from_filter();
-            let query_str_wide = to_wide_string(&query_str);
-
-            let flags = if self.sort_descending {
-                EvtQueryChannelPath.0 | EvtQueryReverseDirection.0
-            } else {
-                EvtQueryChannelPath.0
-            };
-
-            unsafe {
-                match EvtQuery(
-                    None,
-                    PCWSTR::from_raw(channel_wide.as_ptr()),
-                    PCWSTR::from_raw(query_str_wide.as_ptr()),
-                    flags,
-                ) {
-                    Ok(handle) => self.query_handle = Some(handle),
-                    Err(e) => {
-                        self.show_error(
-                            "Query Error",
-                            &format!("Failed to query log '{}': {}", self.selected_log_name, e),
-                        );
-                        self.is_loading = false;
-                        return;
-                    }
-                }
+            if !self.open_selected_log() {
+                self.is_loading = false;
+                return;
             }
+            self.record_filter_history();
         }
 
-        if let Some(query_handle) = self.query_handle {
-            let mut new_events_fetched = 0;
-            unsafe {
-                loop {
-                    let mut events_buffer: Vec<EVT_HANDLE> =
-                        vec![EVT_HANDLE::default(); crate::models::EVENT_BATCH_SIZE];
-                    let mut fetched = 0;
-                    let events_slice: &mut [isize] =
-                        std::mem::transmute(events_buffer.as_mut_slice());
-                    let next_result = EvtNext(query_handle, events_slice, 0, 0, &mut fetched);
-
-                    if !next_result.is_ok() {
-                        let error = GetLastError().0;
-                        if error == ERROR_NO_MORE_ITEMS.0 {
-                            self.no_more_events = true;
-                        } else {
-                            self.show_error(
-                                "Reading Error",
-                                &format!(
-                                    "Error reading event log '{}': WIN32_ERROR({})",
-                                    self.selected_log_name, error
-                                ),
-                            );
-                        }
-                        break;
-                    }
-
-                    if fetched == 0 {
-                        self.no_more_events = true;
-                        break;
-                    }
-
-                    for i in 0..(fetched as usize) {
-                        let event_handle = events_buffer[i];
-                        if let Some(xml) = render_event_xml(event_handle) {
-                            let mut display_event = parse_event_xml(&xml);
-
-                            display_event.formatted_message = format_event_message(
-                                self,
-                                &display_event.provider_name_original,
-                                event_handle,
-                            );
-                            self.events.push(display_event);
-                            new_events_fetched += 1;
-                        }
-                        let _ = EvtClose(event_handle);
-                    }
-                    break;
-                }
-            }
+        self.load_progress = Some(LoadProgress {
+            initial: initial_load,
+            batch_size: self.load_batch_size(),
+            fetched: 0,
+            started: Instant::now(),
+        });
+        self.continue_log_load();
+    }
 
-            if new_events_fetched > 0 && initial_load && !self.events.is_empty() {
-                self.table_state.select(Some(0));
+    /// Reads the next chunk of the batch being loaded, finishing the load once the batch is
+    /// complete or the log runs out. Called from the event loop between redraws; chunks are
+    /// sized so that reading one keeps the interface responsive on this log. The first chunk
+    /// of a newly opened log is small and shown and selected at once, while the rest loads.
+    pub fn continue_log_load(&mut self) {
+        let Some(progress) = self.load_progress else {
+            return;
+        };
+        let first_paint = progress.initial && progress.fetched == 0;
+        let mut wanted = self
+            .load_tuner()
+            .size()
+            .min(progress.batch_size - progress.fetched);
+        if first_paint {
+            wanted = wanted.min(FIRST_PAINT_EVENTS);
+        }
+        let started = Instant::now();
+        let fetched = self.read_next_batch(wanted);
+        self.load_tuner().record(fetched, started.elapsed());
+        if first_paint && fetched > 0 {
+            self.sort_events();
+            self.table_state.select(Some(0));
+            self.update_preview_for_selection();
+        }
+        if let Some(progress) = &mut self.load_progress {
+            progress.fetched += fetched;
+            if fetched == 0 || self.no_more_events || progress.fetched >= progress.batch_size {
+                self.finish_log_load();
             }
         }
+    }
 
-        self.update_preview_for_selection();
+    /// Chunk sizing of the selected log.
+    fn load_tuner(&mut self) -> &mut BatchTuner {
+        self.load_tuning
+            .entry(self.selected_log_name.clone())
+            .or_insert_with(BatchTuner::interactive)
+    }
 
-        self.is_loading = false;
+    /// Events a load reads: `batch_size`, or when it is 0 as many as the selected log gives in
+    /// about a second at the throughput measured on it.
+    pub fn load_batch_size(&self) -> usize {
+        if self.config.batch_size > 0 {
+            return self.config.batch_size;
+        }
+        let (min, max) = AUTO_BATCH_RANGE;
+        self.load_tuning
+            .get(&self.selected_log_name)
+            .and_then(|tuner| tuner.rate())
+            .map_or(EVENT_BATCH_SIZE, |rate| {
+                ((rate * AUTO_BATCH_SECONDS) as usize).clamp(min, max)
+            })
     }
 
-    pub fn build_xpath_from_filter(&self) -> String {
-        if let Some(filter) = &self.active_filter {
-            let mut conditions = Vec::new();
+    /// Stops the batch being loaded, keeping the events read so far.
+    pub fn cancel_log_load(&mut self) {
+        if let Some(progress) = self.load_progress {
+            self.log(&format!(
+                "Loading '{}' stopped after {} events.",
+                self.selected_log_name, progress.fetched
+            ));
+            self.finish_log_load();
+        }
+    }
 
-            if let Some(source) = &filter.source {
-                if !source.is_empty() {
-                    conditions.push(format!(
-                        "System/Provider[@Name='{}']",
-                        source.replace('\'', "&apos;").replace('"', "&quot;")
-                    ));
+    /// Loads the next batch before returning, for callers that need its events right away.
+    pub fn load_batch_now(&mut self) {
+        if self.load_progress.is_none() {
+            self.start_or_continue_log_load(false);
+        }
+        while self.load_progress.is_some() {
+            self.continue_log_load();
+        }
+    }
+
+    fn finish_log_load(&mut self) {
+        let Some(progress) = self.load_progress.take() else {
+            return;
+        };
+        self.is_loading = false;
+
+        if progress.fetched > 0 {
+            self.sort_events();
+        }
+        if progress.initial && self.table_state.selected().is_none() && self.row_count() > 0 {
+            self.table_state.select(Some(0));
+        }
+
+        self.update_preview_for_selection();
+
+        if progress.initial {
+            self.check_retention_horizon();
+            self.restore_pending_selection();
+        }
+    }
+
+    /// Runs the current query again, newest first, and merges the events logged since the
+    /// newest loaded one into the list at their place in the sort order, keeping the
+    /// selection. Skipped for the merged tab and while cluster or web log events are shown,
+    /// which are not read from one query, and in ascending order until the list has reached
+    /// the end of the log. Returns the number of events added.
+    pub fn refresh_new_events(&mut self) -> usize {
+        self.last_refresh = Instant::now();
+        if self.is_loading
+            || self.is_merged_tab()
+            || self.cluster_nodes.is_some()
+            || self.web_logs_merged
+            || (!self.sort_descending && !self.no_more_events)
+            || self.unloaded_before.is_some()
+        {
+            return 0;
+        }
+        let Some(newest) = self.events.iter().map(|event| event.record_id).max() else {
+            // Nothing matched the last time; read the log again if its query worked at all.
+            if self.no_more_events {
+                self.start_or_continue_log_load(true);
+            }
+            return self.events.len();
+        };
+
+        let query = self
+            .raw_query
+            .clone()
+            .unwrap_or_else(|| self.filter_query());
+        let mut source = self.source.fork();
+        let mut new_events = Vec::new();
+        let result = source
+            .query(&self.selected_log_name, &query, true)
+            .and_then(|_| {
+                loop {
+                    let batch = source.next_batch(self.load_batch_size())?;
+                    if batch.is_empty() {
+                        return Ok(());
+                    }
+                    for event in batch {
+                        if event.record_id <= newest {
+                            return Ok(());
+                        }
+                        new_events.push(event);
+                    }
+                }
+            });
+        if let Err(e) = result {
+            self.log(&format!(
+                "Auto-refresh of '{}' failed: {}",
+                self.selected_log_name, e
+            ));
+            return 0;
+        }
+        if new_events.is_empty() {
+            return 0;
+        }
+
+        let count = new_events.len();
+        for event in &mut new_events {
+            #[cfg(target_os = "windows")]
+            self.apply_message_fallback(event);
+            self.match_sigma_rules(event);
+            #[cfg(target_os = "windows")]
+            if !self.config.retain_raw_xml {
+                event.drop_raw_xml();
+            }
+        }
+        self.run_triggers(&new_events);
+        self.notify_new_errors(&new_events);
+        let selected_raw = self.selected_event().map(|event| event.raw_data.clone());
+        if self.sort_descending {
+            self.events.splice(0..0, new_events);
+        } else {
+            self.events.extend(new_events.into_iter().rev());
+        }
+        self.sort_events();
+        if let Some(row) = selected_raw
+            .and_then(|raw| self.events.iter().position(|e| e.raw_data == raw))
+            .and_then(|index| self.row_of_event(index))
+        {
+            self.table_state.select(Some(row));
+        }
+        self.update_preview_for_selection();
+        count
+    }
+
+    /// Opens the query of the selected tab, reporting why when it cannot be opened.
+    fn open_selected_log(&mut self) -> bool {
+        #[cfg(target_os = "windows")]
+        if self.is_merged_tab() {
+            self.message_catalog = None;
+            self.retention = None;
+            if let Err(msg) = self.open_merged_queries() {
+                self.show_error("Query Error", &msg);
+                return false;
+            }
+            return true;
+        }
+
+        let access = self
+            .channel_access
+            .get(self.selected_log_index)
+            .copied()
+            .unwrap_or_default();
+        if !access.is_readable() {
+            let log_name = self.selected_log_name.clone();
+            self.show_access_error(&log_name, access);
+            return false;
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let catalog_path = catalog::catalog_path_for(&self.selected_log_name);
+            self.message_catalog = if catalog_path.exists() {
+                match MessageCatalog::load(&catalog_path) {
+                    Ok(catalog) => Some(catalog),
+                    Err(e) => {
+                        self.log(&format!("Message catalog not loaded: {}", e));
+                        None
+                    }
                 }
+            } else {
+                None
+            };
+
+            // A saved log has no retention of its own.
+            self.retention = (!helpers::is_saved_log(&self.selected_log_name))
+                .then(|| load_retention_info(&self.selected_log_name))
+                .flatten();
+        }
+
+        let query_str = self
+            .raw_query
+            .clone()
+            .unwrap_or_else(|| self.filter_query());
+        if let Err(e) = self
+            .source
+            .query(&self.selected_log_name, &query_str, self.sort_descending)
+        {
+            let log_name = self.selected_log_name.clone();
+            if self.source.check_access(&log_name) == ChannelAccess::Denied {
+                self.show_access_error(&log_name, ChannelAccess::Denied);
+                return false;
             }
+            let message = if self.raw_query.is_some() {
+                format!(
+                    "Invalid query for log '{}': {}\n\n{}",
+                    log_name, e, query_str
+                )
+            } else {
+                format!("Failed to query log '{}': {}", log_name, e)
+            };
+            self.show_error("Query Error", &message);
+            return false;
+        }
+        true
+    }
 
-            if let Some(id) = &filter.event_id {
-                if !id.is_empty() && id.chars().all(char::is_numeric) {
-                    conditions.push(format!("System/EventID={}", id));
+    /// Moves up to `max` events of the open query into the event list. Returns the number of
+    /// events added.
+    fn read_next_batch(&mut self, max: usize) -> usize {
+        #[cfg(target_os = "windows")]
+        if self.is_merged_tab() {
+            return self.read_merged_batch(max);
+        }
+        match self.source.next_batch(max) {
+            Ok(batch) if batch.is_empty() => {
+                self.no_more_events = true;
+                0
+            }
+            Ok(batch) => {
+                let count = batch.len();
+                for mut event in batch {
+                    #[cfg(target_os = "windows")]
+                    self.apply_message_fallback(&mut event);
+                    self.match_sigma_rules(&mut event);
+                    #[cfg(target_os = "windows")]
+                    if !self.config.retain_raw_xml {
+                        event.drop_raw_xml();
+                    }
+                    self.events.push(event);
                 }
+                count
+            }
+            Err(e) => {
+                let message = format!(
+                    "Error reading event log '{}': {}",
+                    self.selected_log_name, e
+                );
+                self.show_error("Reading Error", &message);
+                0
+            }
+        }
+    }
+
+    /// Selects the event an imported view, bookmark or record jump pointed at, loading further
+    /// batches until it turns up or the log runs out.
+    fn restore_pending_selection(&mut self) {
+        let Some(record_id) = self.pending_record_id.take() else {
+            return;
+        };
+        let preview_scroll = std::mem::take(&mut self.pending_preview_scroll);
+        loop {
+            if self.select_record(record_id) {
+                self.preview_scroll = preview_scroll;
+                return;
+            }
+            if self.no_more_events {
+                break;
+            }
+            let loaded = self.events.len();
+            self.load_batch_now();
+            if self.events.len() == loaded {
+                break;
+            }
+        }
+        self.log(&format!(
+            "Event record {} was not found in '{}'.",
+            record_id, self.selected_log_name
+        ));
+    }
+
+    /// Loads the next batch for a running deep search and selects the first match below the
+    /// selection. Called from the event loop so the UI keeps drawing and Esc can cancel.
+    pub fn continue_deep_search(&mut self) {
+        let Some(term) = self.deep_search.as_ref().map(|search| search.term.clone()) else {
+            return;
+        };
+        let loaded = self.events.len();
+        self.load_batch_now();
+        if let Some(search) = &mut self.deep_search {
+            search.scanned += self.events.len() - loaded;
+        }
+        if self.select_match_after_selection(&term) {
+            self.deep_search = None;
+            return;
+        }
+        if self.no_more_events || self.events.len() == loaded {
+            self.deep_search = None;
+            if !self.select_match_from_top(&term) {
+                self.show_confirmation(
+                    "Search Failed",
+                    &format!("Search term '{}' not found in the whole log.", term),
+                );
+            }
+        }
+    }
+
+    /// Loads every remaining batch of the current query.
+    pub fn load_remaining_events(&mut self) {
+        while !self.no_more_events {
+            let loaded = self.events.len();
+            self.load_batch_now();
+            if self.events.len() == loaded {
+                break;
+            }
+        }
+    }
+
+    /// Whether loaded events can be dropped and read again later. That takes a query that can
+    /// be bounded by record ID: one Windows channel, filtered with the filter dialog and
+    /// listed in log order.
+    fn can_unload_events(&self) -> bool {
+        cfg!(target_os = "windows")
+            && self.raw_query.is_none()
+            && !self.is_merged_tab()
+            && self.cluster_nodes.is_none()
+            && !self.web_logs_merged
+            && self.sort_key == SortKey::DateTime
+    }
+
+    /// The filter's query narrowed to the events above (`above`) or below the event with
+    /// `record_id` in the list.
+    fn bounded_query(&self, record_id: u64, above: bool) -> Option<String> {
+        #[cfg(target_os = "windows")]
+        {
+            // Newest first, the events above have higher record IDs.
+            let operator = if above == self.sort_descending {
+                ">"
+            } else {
+                "<"
+            };
+            let mut conditions = self.filter_conditions();
+            conditions.extend(self.incident_condition());
+            conditions.push(format!("System/EventRecordID{}{}", operator, record_id));
+            Some(xpath_from_conditions(&conditions))
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (record_id, above);
+            None
+        }
+    }
+
+    /// Keeps the loaded events under `max_loaded_events` by dropping those at the end of the
+    /// list farther from the selection. Events dropped at the top are read again by
+    /// `load_unloaded_events` when the list is scrolled back up; for those at the bottom the
+    /// query is reopened below the last kept event, so scrolling down reads them again.
+    pub fn trim_loaded_events(&mut self) {
+        let max_events = self.config.max_loaded_events;
+        if max_events == 0 || self.load_progress.is_some() || !self.can_unload_events() {
+            return;
+        }
+        // Room for two batches, so that loading one does not drop the other right away.
+        let max_events = max_events.max(self.load_batch_size() * 2);
+        let excess = self.events.len().saturating_sub(max_events);
+        if excess == 0 {
+            return;
+        }
+        let selected_record = self.selected_event().map(|event| event.record_id);
+        let selected_index = self
+            .table_state
+            .selected()
+            .and_then(|row| self.event_index(row))
+            .unwrap_or(0);
+        if selected_index >= self.events.len() / 2 {
+            self.events.drain(..excess);
+            self.unloaded_before = Some(self.events[0].record_id);
+        } else {
+            let last = self.events[max_events - 1].record_id;
+            let Some(query) = self.bounded_query(last, false) else {
+                return;
+            };
+            if let Err(e) = self
+                .source
+                .query(&self.selected_log_name, &query, self.sort_descending)
+            {
+                self.log(&format!("Events not unloaded, the query failed: {}", e));
+                return;
+            }
+            self.events.truncate(max_events);
+            self.no_more_events = false;
+        }
+        self.rebuild_local_filter();
+        self.select_loaded_record(selected_record);
+        self.log(&format!(
+            "Unloaded {} events to stay under max_loaded_events ({}).",
+            excess, max_events
+        ));
+    }
+
+    /// Reads back a batch of the events dropped above the first loaded one, keeping the
+    /// selected event selected. Returns the number of events read.
+    pub fn load_unloaded_events(&mut self) -> usize {
+        let Some(query) = self
+            .unloaded_before
+            .and_then(|first| self.bounded_query(first, true))
+        else {
+            return 0;
+        };
+        // Read against the list order, so the events next to the first loaded one come first.
+        let batch_size = self.load_batch_size();
+        let mut source = self.source.fork();
+        let result = source
+            .query(&self.selected_log_name, &query, !self.sort_descending)
+            .and_then(|_| source.next_batch(batch_size));
+        let mut batch = match result {
+            Ok(batch) => batch,
+            Err(e) => {
+                self.log(&format!("Unloaded events not read again: {}", e));
+                return 0;
+            }
+        };
+        let count = batch.len();
+        for event in &mut batch {
+            #[cfg(target_os = "windows")]
+            self.apply_message_fallback(event);
+            self.match_sigma_rules(event);
+        }
+        batch.reverse();
+        let selected_record = self.selected_event().map(|event| event.record_id);
+        self.events.splice(0..0, batch);
+        self.unloaded_before = (count == batch_size)
+            .then(|| self.events.first().map(|event| event.record_id))
+            .flatten();
+        self.rebuild_local_filter();
+        self.select_loaded_record(selected_record);
+        count
+    }
+
+    /// Selects the loaded event with `record_id`, if it is shown.
+    fn select_loaded_record(&mut self, record_id: Option<u64>) {
+        if let Some(row) = record_id
+            .and_then(|id| self.events.iter().position(|event| event.record_id == id))
+            .and_then(|index| self.row_of_event(index))
+        {
+            self.table_state.select(Some(row));
+        }
+    }
+
+    /// Runs the read access pre-flight check for every log tab.
+    pub fn refresh_channel_access(&mut self) {
+        self.channel_access = self
+            .channels
+            .iter()
+            .map(|name| self.source.check_access(name))
+            .collect();
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Formats the message when the publisher could not, e.g. because it is not installed
+    /// here: from the log's message catalog, the shared catalog, or else the template in the
+    /// publisher's metadata with the event data substituted.
+    fn apply_message_fallback(&mut self, event: &mut DisplayEvent) {
+        if event.formatted_message.is_some() {
+            return;
+        }
+        let provider = &event.provider_name_original;
+        let values = event.event_data.values();
+        let from_catalog = [&self.message_catalog, &self.shared_catalog]
+            .into_iter()
+            .flatten()
+            .find_map(|catalog| catalog.format(provider, event.event_id, &values));
+        event.formatted_message = from_catalog.or_else(|| {
+            self.publisher_template(provider, event.event_id)
+                .and_then(|template| catalog::format_template(&template, &values))
+        });
+    }
+
+    /// The message template the publisher's metadata holds for an event ID, read once.
+    fn publisher_template(&mut self, provider: &str, event_id: u32) -> Option<String> {
+        let key = (provider.to_string(), event_id);
+        if let Some(template) = self.publisher_templates.get(&key) {
+            return template.clone();
+        }
+        let template = self
+            .source
+            .message_templates(provider, &BTreeSet::from([event_id]))
+            .remove(&event_id);
+        self.publisher_templates.insert(key, template.clone());
+        template
+    }
+
+    /// Opens a query with the current filter on every channel of the merged tab. Channels
+    /// that cannot be queried are reported and left out; it fails only when none can be.
+    fn open_merged_queries(&mut self) -> Result<(), String> {
+        let query_str = self
+            .raw_query
+            .clone()
+            .unwrap_or_else(|| self.build_xpath_from_filter());
+        if query_str.trim_start().starts_with('<') {
+            return Err(
+                "Structured queries name their own channels; run them from a log tab.".to_string(),
+            );
+        }
+
+        let mut failures = Vec::new();
+        for channel in self.config.merged_logs.clone() {
+            let mut source = self.source.fork();
+            match source.query(&channel, &query_str, self.sort_descending) {
+                Ok(()) => self.merged_queries.push(MergedQuery {
+                    channel,
+                    source,
+                    pending: VecDeque::new(),
+                    exhausted: false,
+                }),
+                Err(e) => failures.push(format!("'{}': {}", channel, e)),
             }
+        }
+        if self.merged_queries.is_empty() {
+            return Err(format!(
+                "None of the merged logs could be queried:\n{}",
+                failures.join("\n")
+            ));
+        }
+        if !failures.is_empty() {
+            self.show_error(
+                "Merged Logs Skipped",
+                &format!(
+                    "These logs are left out of the merged tab:\n{}",
+                    failures.join("\n")
+                ),
+            );
+        }
+        Ok(())
+    }
 
-            let level_condition = match filter.level {
-                EventLevelFilter::Information => {
-                    Some("(System/Level=0 or System/Level=4)".to_string())
+    /// Closes the merged tab's queries.
+    pub fn close_merged_queries(&mut self) {
+        self.merged_queries.clear();
+    }
+
+    /// Moves up to `max` events into the list, taking the next event by time from whichever
+    /// merged channel has it. Returns the number of events added.
+    fn read_merged_batch(&mut self, max: usize) -> usize {
+        let mut added = 0;
+        while added < max {
+            for index in 0..self.merged_queries.len() {
+                let query = &self.merged_queries[index];
+                if query.pending.is_empty() && !query.exhausted {
+                    self.read_ahead_merged_query(index);
                 }
-                EventLevelFilter::Warning => Some("System/Level=3".to_string()),
-                EventLevelFilter::Error => Some("(System/Level=1 or System/Level=2)".to_string()),
-                EventLevelFilter::All => None,
+            }
+            let heads = self
+                .merged_queries
+                .iter()
+                .enumerate()
+                .filter_map(|(index, query)| query.pending.front().map(|e| (e.timestamp, index)));
+            let next = if self.sort_descending {
+                heads.max_by_key(|&(time, _)| time)
+            } else {
+                heads.min_by_key(|&(time, _)| time)
+            };
+            let Some((_, index)) = next else {
+                self.no_more_events = true;
+                break;
             };
-            if let Some(cond) = level_condition {
-                conditions.push(cond);
+            if let Some(mut event) = self.merged_queries[index].pending.pop_front() {
+                self.match_sigma_rules(&mut event);
+                if !self.config.retain_raw_xml {
+                    event.drop_raw_xml();
+                }
+                self.events.push(event);
+                added += 1;
             }
+        }
+        added
+    }
 
-            if let Some(start_time_utc) = filter.time_filter.get_start_time() {
-                let timestamp_str =
-                    start_time_utc.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
-                conditions.push(format!(
-                    "System/TimeCreated[@SystemTime >= '{}']",
-                    timestamp_str
-                ));
+    /// Reads the next events of one merged channel into its pending queue.
+    fn read_ahead_merged_query(&mut self, index: usize) {
+        match self.merged_queries[index]
+            .source
+            .next_batch(MERGED_READ_AHEAD)
+        {
+            Ok(batch) if !batch.is_empty() => {
+                for mut event in batch {
+                    self.apply_message_fallback(&mut event);
+                    self.merged_queries[index].pending.push_back(event);
+                }
+            }
+            Ok(_) => self.merged_queries[index].exhausted = true,
+            Err(e) => {
+                let message = format!(
+                    "Stopped reading '{}' for the merged tab: {}",
+                    self.merged_queries[index].channel, e
+                );
+                self.log(&message);
+                self.merged_queries[index].exhausted = true;
             }
+        }
+    }
+
+    pub fn build_xpath_from_filter(&self) -> String {
+        let mut conditions = self.filter_conditions();
+        conditions.extend(self.incident_condition());
+        xpath_from_conditions(&conditions)
+    }
+
+    /// XPath conditions for the active filter dialog criteria.
+    fn filter_conditions(&self) -> Vec<String> {
+        let mut conditions = criteria_conditions(self.active_filter.as_ref());
+        conditions.extend(self.time_filter_condition());
+        conditions
+    }
+
+    /// XPath condition for the time range of the filter dialog, if one is set.
+    fn time_filter_condition(&self) -> Option<String> {
+        let start_time_utc = self.active_filter.as_ref()?.time_filter.get_start_time()?;
+        Some(format!(
+            "System/TimeCreated[@SystemTime >= '{}']",
+            start_time_utc.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+        ))
+    }
+
+    /// XPath condition restricting every tab to the incident window, if one is locked.
+    fn incident_condition(&self) -> Option<String> {
+        self.incident.as_ref().map(|incident| {
+            format!(
+                "System/TimeCreated[@SystemTime >= '{}' and @SystemTime <= '{}']",
+                incident
+                    .start
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                incident
+                    .end
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+            )
+        })
+    }
 
-            if conditions.is_empty() {
-                "*".to_string()
+    /// Selects the event with the given `EventRecordID`, loading batches until it turns up.
+    /// The record is looked up with an XPath query first; if the current filter hides it, the
+    /// filter is dropped and `true` is returned so the caller reloads the log.
+    pub fn go_to_record(&mut self, record_id: u64) -> Result<bool, String> {
+        if self.select_record(record_id) {
+            return Ok(false);
+        }
+        let record_condition = format!("System/EventRecordID={}", record_id);
+        let channel = self.selected_log_name.clone();
+        let mut conditions: Vec<String> = self.incident_condition().into_iter().collect();
+        conditions.push(record_condition.clone());
+        if self
+            .query_channel(&channel, &xpath_from_conditions(&conditions), false, 1)?
+            .is_empty()
+        {
+            return Err(if self.incident.is_some() {
+                format!(
+                    "Record {} is not in the '{}' log within the incident window.",
+                    record_id, channel
+                )
             } else {
-                format!("*[{}]", conditions.join(" and "))
+                format!("Record {} is not in the '{}' log.", record_id, channel)
+            });
+        }
+
+        self.pending_record_id = Some(record_id);
+        if self.raw_query.is_none() {
+            let mut conditions = self.filter_conditions();
+            conditions.extend(self.incident_condition());
+            conditions.push(record_condition);
+            if !self
+                .query_channel(&channel, &xpath_from_conditions(&conditions), false, 1)?
+                .is_empty()
+            {
+                self.restore_pending_selection();
+                return Ok(false);
             }
-        } else {
-            "*".to_string()
         }
+        self.active_filter = None;
+        self.raw_query = None;
+        Ok(true)
+    }
+}
+
+/// The XPath query of `filter` for the events logged from `start` to before `end`, in place
+/// of its own time range and the incident window.
+#[cfg(target_os = "windows")]
+pub fn xpath_for_window(
+    filter: Option<&FilterCriteria>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> String {
+    let mut conditions = criteria_conditions(filter);
+    conditions.push(format!(
+        "System/TimeCreated[@SystemTime >= '{}' and @SystemTime < '{}']",
+        start.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        end.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+    ));
+    xpath_from_conditions(&conditions)
+}
+
+/// XPath conditions for the criteria of `filter` other than the time range.
+#[cfg(target_os = "windows")]
+fn criteria_conditions(filter: Option<&FilterCriteria>) -> Vec<String> {
+    let mut conditions = Vec::new();
+    if let Some(filter) = filter {
+        if let Some(source) = &filter.source
+            && !source.is_empty()
+        {
+            conditions.push(format!(
+                "System/Provider[@Name='{}']",
+                helpers::escape_xpath_literal(source)
+            ));
+        }
+        for source in &filter.excluded_sources {
+            conditions.push(format!(
+                "System/Provider[@Name!='{}']",
+                helpers::escape_xpath_literal(source)
+            ));
+        }
+
+        if let Some(Ok(id_spec)) = filter.event_id.as_deref().map(parse_event_id_spec) {
+            let included: Vec<String> = id_spec
+                .include
+                .iter()
+                .map(|&(start, end)| {
+                    if start == end {
+                        format!("System/EventID={}", start)
+                    } else {
+                        format!("(System/EventID>={} and System/EventID<={})", start, end)
+                    }
+                })
+                .collect();
+            if !included.is_empty() {
+                conditions.push(format!("({})", included.join(" or ")));
+            }
+            // The Event Log XPath subset has no not(), so exclusions use != and < / >.
+            for (start, end) in id_spec.exclude {
+                if start == end {
+                    conditions.push(format!("System/EventID!={}", start));
+                } else {
+                    conditions.push(format!(
+                        "(System/EventID<{} or System/EventID>{})",
+                        start, end
+                    ));
+                }
+            }
+        }
+
+        let level_condition = match filter.level {
+            EventLevelFilter::Information => Some("(System/Level=0 or System/Level=4)".to_string()),
+            EventLevelFilter::Warning => Some("System/Level=3".to_string()),
+            EventLevelFilter::Error => Some("(System/Level=1 or System/Level=2)".to_string()),
+            EventLevelFilter::All => None,
+        };
+        if let Some(cond) = level_condition {
+            conditions.push(cond);
+        }
+
+        if let Some(mask) = filter.keywords.mask() {
+            conditions.push(format!("System[band(Keywords,{})]", mask));
+        }
+
+        if let Some(task) = filter.task {
+            conditions.push(format!("System/Task={}", task));
+        }
+
+        if let Some(opcode) = filter.opcode {
+            conditions.push(format!("System/Opcode={}", opcode));
+        }
+
+        if let Some(user_sid) = &filter.user_sid {
+            conditions.push(format!(
+                "System/Security[@UserID='{}']",
+                helpers::escape_xpath_literal(user_sid)
+            ));
+        }
+
+        if let Some(computer) = &filter.computer {
+            conditions.push(format!(
+                "System/Computer='{}'",
+                helpers::escape_xpath_literal(computer)
+            ));
+        }
+    }
+    conditions
+}
+
+#[cfg(target_os = "windows")]
+fn xpath_from_conditions(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        "*".to_string()
+    } else {
+        format!("*[{}]", conditions.join(" and "))
     }
 }
 
 #[cfg(target_os = "windows")]
 pub fn format_event_message(
-    app_state: &mut AppState,
+    publisher_metadata_cache: &mut PublisherCache,
     provider_name_original: &str,
     event_handle: EVT_HANDLE,
 ) -> Option<String> {
-    let provider_key = provider_name_original.to_string();
-    let mut publisher_metadata: Option<EVT_HANDLE> = None;
+    let publisher_metadata = publisher_metadata_cache.get(provider_name_original);
     let evt_variants_slice: Option<&[EVT_VARIANT]> = None;
 
     unsafe {
-        if let Some(cached_handle) = app_state.publisher_metadata_cache.get(&provider_key) {
-            publisher_metadata = Some(*cached_handle);
-        } else {
-            match EvtOpenPublisherMetadata(
-                None,
-                PCWSTR::from_raw(to_wide_string(provider_name_original).as_ptr()),
-                None,
-                0,
-                0,
-            ) {
-                Ok(handle) if !handle.is_invalid() => {
-                    publisher_metadata = Some(handle);
-                    app_state
-                        .publisher_metadata_cache
-                        .insert(provider_key.clone(), handle);
-                }
-                Ok(invalid_handle) => {
-                    if !invalid_handle.is_invalid() {
-                        let _ = EvtClose(invalid_handle);
-                    }
-                }
-                Err(_) => {}
-            }
-        }
         if let Some(handle_to_use) = publisher_metadata {
             let mut final_formatted_message: Option<String> = None;
             let mut buffer_size_needed: u32 = 0;
@@ -438,3 +1837,1092 @@ pub fn format_event_message(
         }
     }
 }
+
+/// Returns the provider's name for the event's task category, such as "Logon".
+#[cfg(target_os = "windows")]
+pub fn format_event_task(
+    publisher_metadata_cache: &mut PublisherCache,
+    provider_name_original: &str,
+    event_handle: EVT_HANDLE,
+) -> Option<String> {
+    let publisher_metadata = publisher_metadata_cache.get(provider_name_original)?;
+    format_message_string(publisher_metadata, event_handle, 0, EvtFormatMessageTask.0)
+}
+
+/// Calls EvtFormatMessage with a correctly sized buffer and returns the trimmed message.
+#[cfg(target_os = "windows")]
+fn format_message_string(
+    publisher_metadata: EVT_HANDLE,
+    event_handle: EVT_HANDLE,
+    message_id: u32,
+    flags: u32,
+) -> Option<String> {
+    let mut buffer_size_needed: u32 = 0;
+    unsafe {
+        match EvtFormatMessage(
+            publisher_metadata,
+            event_handle,
+            message_id,
+            None,
+            flags,
+            None,
+            &mut buffer_size_needed,
+        ) {
+            Err(e) if e.code() == ERROR_INSUFFICIENT_BUFFER.into() && buffer_size_needed > 0 => {}
+            _ => return None,
+        }
+        let mut buffer: Vec<u16> = vec![0; buffer_size_needed as usize];
+        EvtFormatMessage(
+            publisher_metadata,
+            event_handle,
+            message_id,
+            None,
+            flags,
+            Some(buffer.as_mut_slice()),
+            &mut buffer_size_needed,
+        )
+        .ok()?;
+        let null_pos = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        let msg = String::from_utf16_lossy(&buffer[..null_pos]);
+        let trimmed_msg = msg.trim();
+        (!trimmed_msg.is_empty()).then(|| trimmed_msg.to_string())
+    }
+}
+
+/// Reads a UInt32 property from an event metadata handle.
+#[cfg(target_os = "windows")]
+fn get_event_metadata_u32(
+    event_metadata: EVT_HANDLE,
+    property_id: EVT_EVENT_METADATA_PROPERTY_ID,
+) -> Option<u32> {
+    let mut variant = EVT_VARIANT::default();
+    let mut buffer_used = 0;
+    unsafe {
+        EvtGetEventMetadataProperty(
+            event_metadata,
+            property_id,
+            0,
+            std::mem::size_of::<EVT_VARIANT>() as u32,
+            Some(&mut variant),
+            &mut buffer_used,
+        )
+        .ok()?;
+        Some(variant.Anonymous.UInt32Val)
+    }
+}
+
+/// Reads a string property from an event metadata handle; empty when it has no value.
+#[cfg(target_os = "windows")]
+fn get_event_metadata_string(
+    event_metadata: EVT_HANDLE,
+    property_id: EVT_EVENT_METADATA_PROPERTY_ID,
+) -> Option<String> {
+    const VARIANT_SIZE: usize = std::mem::size_of::<EVT_VARIANT>();
+    let mut buffer_used = 0;
+    unsafe {
+        let _ =
+            EvtGetEventMetadataProperty(event_metadata, property_id, 0, 0, None, &mut buffer_used);
+    }
+    if buffer_used == 0 {
+        return None;
+    }
+    let mut buffer = vec![EVT_VARIANT::default(); (buffer_used as usize).div_ceil(VARIANT_SIZE)];
+    unsafe {
+        EvtGetEventMetadataProperty(
+            event_metadata,
+            property_id,
+            0,
+            (buffer.len() * VARIANT_SIZE) as u32,
+            Some(buffer.as_mut_ptr()),
+            &mut buffer_used,
+        )
+        .ok()?;
+    }
+    let variant = buffer[0];
+    if variant.Type != EvtVarTypeString.0 as u32 {
+        return Some(String::new());
+    }
+    unsafe { variant.Anonymous.StringVal.to_string() }.ok()
+}
+
+/// Field names of the templates a provider defines, by event ID, to name the values read
+/// through `EvtRenderContextUser`. An event ID has a template per version of the event; events
+/// without one get an empty list. Empty for classic providers.
+#[cfg(target_os = "windows")]
+pub fn load_data_names(publisher_metadata: EVT_HANDLE) -> HashMap<u32, Vec<Vec<String>>> {
+    let mut names: HashMap<u32, Vec<Vec<String>>> = HashMap::new();
+    let Ok(metadata_enum) = (unsafe { EvtOpenEventMetadataEnum(publisher_metadata, 0) }) else {
+        return names;
+    };
+    while let Ok(event_metadata) = unsafe { EvtNextEventMetadata(metadata_enum, 0) } {
+        let event_id = get_event_metadata_u32(event_metadata, EventMetadataEventID);
+        let template = get_event_metadata_string(event_metadata, EventMetadataEventTemplate);
+        unsafe {
+            let _ = EvtClose(event_metadata);
+        }
+        if let (Some(event_id), Some(template)) = (event_id, template) {
+            names
+                .entry(event_id)
+                .or_default()
+                .push(template_data_names(&template));
+        }
+    }
+    unsafe {
+        let _ = EvtClose(metadata_enum);
+    }
+    names
+}
+
+/// Collects the message templates a provider defines for the requested event IDs.
+/// Classic (non-manifest) providers have no event metadata, so their event ID is tried
+/// directly as the message ID.
+#[cfg(target_os = "windows")]
+fn load_message_templates(
+    publisher_metadata_cache: &mut PublisherCache,
+    provider_name: &str,
+    event_ids: &BTreeSet<u32>,
+) -> HashMap<u32, String> {
+    let mut templates = HashMap::new();
+    let Some(publisher_metadata) = publisher_metadata_cache.get(provider_name) else {
+        return templates;
+    };
+
+    if let Ok(metadata_enum) = unsafe { EvtOpenEventMetadataEnum(publisher_metadata, 0) } {
+        while let Ok(event_metadata) = unsafe { EvtNextEventMetadata(metadata_enum, 0) } {
+            let event_id = get_event_metadata_u32(event_metadata, EventMetadataEventID);
+            let message_id = get_event_metadata_u32(event_metadata, EventMetadataEventMessageID);
+            unsafe {
+                let _ = EvtClose(event_metadata);
+            }
+            let (Some(event_id), Some(message_id)) = (event_id, message_id) else {
+                continue;
+            };
+            if message_id == u32::MAX
+                || !event_ids.contains(&event_id)
+                || templates.contains_key(&event_id)
+            {
+                continue;
+            }
+            if let Some(template) = format_message_string(
+                publisher_metadata,
+                EVT_HANDLE::default(),
+                message_id,
+                EvtFormatMessageId.0,
+            ) {
+                templates.insert(event_id, template);
+            }
+        }
+        unsafe {
+            let _ = EvtClose(metadata_enum);
+        }
+    }
+
+    let unresolved: Vec<u32> = event_ids
+        .iter()
+        .copied()
+        .filter(|id| !templates.contains_key(id))
+        .collect();
+    for event_id in unresolved {
+        if let Some(template) = format_message_string(
+            publisher_metadata,
+            EVT_HANDLE::default(),
+            event_id,
+            EvtFormatMessageId.0,
+        ) {
+            templates.insert(event_id, template);
+        }
+    }
+    templates
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Exports the message templates used by the loaded events to the log's catalog bundle,
+    /// merging with any catalog already loaded. Returns the bundle path and template count.
+    pub fn export_message_catalog(&mut self) -> Result<(PathBuf, usize), String> {
+        if self.events.is_empty() {
+            return Err("No events loaded to build a message catalog from.".to_string());
+        }
+        let mut wanted: HashMap<String, BTreeSet<u32>> = HashMap::new();
+        for event in &self.events {
+            wanted
+                .entry(event.provider_name_original.clone())
+                .or_default()
+                .insert(event.event_id);
+        }
+
+        let mut bundle = self
+            .message_catalog
+            .clone()
+            .unwrap_or_else(|| MessageCatalog::new(&self.selected_log_name));
+        for (provider, event_ids) in wanted {
+            let missing: BTreeSet<u32> = event_ids
+                .into_iter()
+                .filter(|id| !bundle.contains(&provider, *id))
+                .collect();
+            if missing.is_empty() {
+                continue;
+            }
+            let templates = self.source.message_templates(&provider, &missing);
+            if !templates.is_empty() {
+                bundle
+                    .providers
+                    .entry(provider)
+                    .or_default()
+                    .extend(templates);
+            }
+        }
+
+        let path = catalog::catalog_path_for(&self.selected_log_name);
+        bundle.save(&path)?;
+        let count = bundle.template_count();
+        self.message_catalog = Some(bundle);
+        Ok((path, count))
+    }
+}
+
+/// Channel holding Kernel-Power 41 and the events around it.
+#[cfg(target_os = "windows")]
+const SHUTDOWN_EVIDENCE_CHANNEL: &str = "System";
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Runs a one-off XPath query against `channel` through a fork of the event source and
+    /// returns up to `max_events` events, newest first when `reverse` is set.
+    pub fn query_channel(
+        &mut self,
+        channel: &str,
+        xpath: &str,
+        reverse: bool,
+        max_events: usize,
+    ) -> Result<Vec<DisplayEvent>, String> {
+        let mut source = self.source.fork();
+        query_events(source.as_mut(), channel, xpath, reverse, max_events)
+    }
+
+    /// Gathers the System log events around a Kernel-Power 41: the boot it was logged at, the
+    /// last event before the machine went down, the previous clean shutdown and any bugcheck
+    /// reports logged after the boot. The evidence is best effort: a failed query leaves its
+    /// part empty, and an event whose time is unknown has none.
+    pub fn gather_shutdown_evidence(&mut self, event: &DisplayEvent) -> ShutdownEvidence {
+        let Some(time) = event.timestamp else {
+            return ShutdownEvidence::default();
+        };
+        let logged_at = analysis::xpath_time(time);
+        let (start_provider, start_id) = analysis::OS_START_EVENT;
+        let os_start = self
+            .query_channel(
+                SHUTDOWN_EVIDENCE_CHANNEL,
+                &format!(
+                    "*[System[Provider[@Name='{}'] and EventID={} and TimeCreated[@SystemTime <= '{}']]]",
+                    start_provider, start_id, logged_at
+                ),
+                true,
+                1,
+            )
+            .unwrap_or_default()
+            .pop();
+        let boot_time = os_start.and_then(|e| e.timestamp).unwrap_or(time);
+        let boot = analysis::xpath_time(boot_time);
+        let report_window_end = analysis::xpath_time(time + chrono::Duration::hours(1));
+
+        let last_before_crash = self
+            .query_channel(
+                SHUTDOWN_EVIDENCE_CHANNEL,
+                &format!("*[System[TimeCreated[@SystemTime < '{}']]]", boot),
+                true,
+                1,
+            )
+            .unwrap_or_default()
+            .pop();
+        let first_after_boot = self
+            .query_channel(
+                SHUTDOWN_EVIDENCE_CHANNEL,
+                &format!("*[System[TimeCreated[@SystemTime >= '{}']]]", boot),
+                false,
+                1,
+            )
+            .unwrap_or_default()
+            .pop();
+        let previous_clean_shutdown = self
+            .query_channel(
+                SHUTDOWN_EVIDENCE_CHANNEL,
+                &format!(
+                    "*[System[({}) and TimeCreated[@SystemTime < '{}']]]",
+                    analysis::provider_event_condition(analysis::CLEAN_SHUTDOWN_EVENTS),
+                    boot
+                ),
+                true,
+                1,
+            )
+            .unwrap_or_default()
+            .pop();
+        let crash_reports = self
+            .query_channel(
+                SHUTDOWN_EVIDENCE_CHANNEL,
+                &format!(
+                    "*[System[({}) and TimeCreated[@SystemTime >= '{}' and @SystemTime <= '{}']]]",
+                    analysis::provider_event_condition(analysis::CRASH_REPORT_EVENTS),
+                    boot,
+                    report_window_end
+                ),
+                false,
+                10,
+            )
+            .unwrap_or_default();
+
+        ShutdownEvidence {
+            last_before_crash,
+            previous_clean_shutdown,
+            first_after_boot,
+            crash_reports,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Reads failed resolutions from the DNS client logs and, where present, the DNS server's
+    /// analytical log.
+    pub fn load_dns_analysis(&mut self) -> DnsAnalysis {
+        let mut analysis = DnsAnalysis::default();
+        // Analytical logs can only be read oldest first.
+        let sources = [
+            (
+                dns::DNS_CLIENT_CHANNEL,
+                dns::CLIENT_FAILURES_QUERY,
+                true,
+                false,
+            ),
+            ("System", dns::CLIENT_TIMEOUTS_QUERY, true, false),
+            (
+                dns::DNS_SERVER_CHANNEL,
+                dns::SERVER_FAILURES_QUERY,
+                false,
+                true,
+            ),
+        ];
+        for (channel, query, reverse, optional) in sources {
+            match self.query_channel(channel, query, reverse, dns::MAX_FAILURE_EVENTS) {
+                Ok(events) => analysis
+                    .failures
+                    .extend(events.iter().filter_map(dns::failure_of)),
+                Err(msg) if !optional => analysis.unavailable.push(msg),
+                Err(_) => {}
+            }
+        }
+        analysis
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Reads the file and registry object access audits (4656, 4663) from the Security log.
+    pub fn load_object_access(&mut self) -> ObjectAccessView {
+        let mut view = ObjectAccessView::default();
+        match self.query_channel(
+            "Security",
+            objectaccess::OBJECT_ACCESS_QUERY,
+            true,
+            objectaccess::MAX_ACCESS_EVENTS,
+        ) {
+            Ok(events) => view.accesses = events.iter().filter_map(ObjectAccess::of).collect(),
+            Err(msg) => view.unavailable = Some(msg),
+        }
+        view
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Reads the Hyper-V admin channels and groups their events by virtual machine.
+    pub fn load_hyperv_view(&mut self) -> HyperVView {
+        let mut events = Vec::new();
+        let mut channels = Vec::new();
+        let mut unavailable = Vec::new();
+        for channel in hyperv::HYPERV_CHANNELS {
+            match self.query_channel(channel, "*", true, hyperv::MAX_GUEST_EVENTS) {
+                Ok(channel_events) => {
+                    events.extend(channel_events);
+                    channels.push(channel);
+                }
+                Err(msg) => unavailable.push(msg),
+            }
+        }
+        HyperVView::new(&events, channels, unavailable)
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Reads the Windows Installer events and groups them into install transactions.
+    pub fn load_installer_view(&mut self) -> InstallerView {
+        match self.query_channel(
+            msi::INSTALLER_CHANNEL,
+            msi::INSTALLER_QUERY,
+            true,
+            msi::MAX_INSTALLER_EVENTS,
+        ) {
+            Ok(events) => InstallerView::new(&events, None),
+            Err(msg) => InstallerView::new(&[], Some(msg)),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Reads the printed and failed documents from the PrintService logs and sums them up per
+    /// printer and user.
+    pub fn load_print_view(&mut self) -> PrintView {
+        let mut events = Vec::new();
+        let mut unavailable = Vec::new();
+        let sources = [
+            (printing::PRINT_OPERATIONAL_CHANNEL, printing::PRINTED_QUERY),
+            (printing::PRINT_ADMIN_CHANNEL, printing::FAILED_QUERY),
+        ];
+        for (channel, query) in sources {
+            match self.query_channel(channel, query, true, printing::MAX_PRINT_EVENTS) {
+                Ok(channel_events) => events.extend(channel_events),
+                Err(msg) => unavailable.push(msg),
+            }
+        }
+        PrintView::new(&events, unavailable)
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Reads the Plug and Play, partition and user-mode driver events and builds a timeline
+    /// per device.
+    pub fn load_device_view(&mut self) -> DeviceView {
+        let mut events = Vec::new();
+        let mut unavailable = Vec::new();
+        for (channel, query, disabled_by_default) in devices::DEVICE_SOURCES {
+            match self.query_channel(channel, query, true, devices::MAX_DEVICE_EVENTS) {
+                Ok(channel_events) => events.extend(channel_events),
+                Err(msg) if disabled_by_default => {
+                    unavailable.push(format!("{} (this log is disabled by default)", msg))
+                }
+                Err(msg) => unavailable.push(msg),
+            }
+        }
+        DeviceView::new(&events, unavailable)
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Reads the Kerberos, NTLM and logon events around the selected event and follows the
+    /// account it names through them.
+    pub fn load_auth_flow(&mut self) -> Result<AuthFlowView, String> {
+        let event = self
+            .selected_event()
+            .ok_or("Please select an event first.")?;
+        let account = authflow::account_of(event).ok_or(
+            "The selected event is not a Kerberos, NTLM or logon event (4768, 4769, 4771, 4776, 4624, 4625).",
+        )?;
+        let query = authflow::query(
+            event
+                .timestamp
+                .ok_or("The selected event's time is unknown.")?,
+        );
+        Ok(
+            match self.query_channel("Security", &query, false, authflow::MAX_AUTH_EVENTS) {
+                Ok(events) => AuthFlowView::new(account, &events, None),
+                Err(msg) => AuthFlowView::new(account, &[], Some(msg)),
+            },
+        )
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Reads the CBS package changes from the Setup log and the Windows Update installations
+    /// from the System log, and lists the updates that failed.
+    pub fn load_servicing_view(&mut self) -> ServicingView {
+        let mut events = Vec::new();
+        let mut unavailable = Vec::new();
+        let sources = [
+            (servicing::SETUP_CHANNEL, servicing::PACKAGE_QUERY),
+            ("System", servicing::UPDATE_QUERY),
+        ];
+        for (channel, query) in sources {
+            match self.query_channel(channel, query, true, servicing::MAX_SERVICING_EVENTS) {
+                Ok(channel_events) => events.extend(channel_events),
+                Err(msg) => unavailable.push(msg),
+            }
+        }
+        ServicingView::new(&events, unavailable)
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Reads the failed WMI client operations and names their client processes: from the
+    /// last process creation audit (4688) for the ID before the failure, or else from the
+    /// process running with that ID now.
+    pub fn load_wmi_analysis(&mut self) -> WmiAnalysis {
+        let mut analysis = WmiAnalysis::default();
+        match self.query_channel(
+            wmi::WMI_CHANNEL,
+            wmi::CLIENT_FAILURES_QUERY,
+            true,
+            wmi::MAX_FAILURE_EVENTS,
+        ) {
+            Ok(events) => analysis.failures = events.iter().filter_map(wmi::failure_of).collect(),
+            Err(msg) => analysis.unavailable = Some(msg),
+        }
+        for (process_id, last_failure) in analysis.client_processes() {
+            let query = format!(
+                "*[System[EventID=4688 and TimeCreated[@SystemTime<='{}']] and EventData[Data[@Name='NewProcessId']='0x{:x}']]",
+                analysis::xpath_time(last_failure),
+                process_id
+            );
+            let audited = self
+                .query_channel("Security", &query, true, 1)
+                .ok()
+                .and_then(|events| {
+                    events
+                        .first()?
+                        .event_data
+                        .named()
+                        .find_map(|(name, value)| {
+                            (name == "NewProcessName").then(|| value.to_string())
+                        })
+                });
+            let name = audited.or_else(|| {
+                process_image_name(process_id).map(|name| format!("{} (running now)", name))
+            });
+            if let Some(name) = name {
+                analysis.process_names.insert(process_id, name);
+            }
+        }
+        analysis
+    }
+}
+
+/// Full path of the executable of the running process `process_id`.
+#[cfg(target_os = "windows")]
+fn process_image_name(process_id: u32) -> Option<String> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id).ok()?;
+        let mut buffer = vec![0u16; 1024];
+        let mut size = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            handle,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut size,
+        );
+        let _ = CloseHandle(handle);
+        result.ok()?;
+        Some(String::from_utf16_lossy(&buffer[..size as usize]))
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Exports the events of the selected log matching `query` ("*" for all of them) to the
+    /// .evtx file `path`.
+    pub fn export_selected_log(&self, path: &Path, query: &str) -> Result<PathBuf, String> {
+        export_log(&self.selected_log_name, query, path)
+    }
+
+    /// Exports the selected log's events matching the XPath query, or else the filter, to
+    /// the .evtx file `path`, which Event Viewer opens as a saved log.
+    pub fn export_filtered_log(&self, path: &Path) -> Result<PathBuf, String> {
+        let query = self
+            .raw_query
+            .clone()
+            .unwrap_or_else(|| self.build_xpath_from_filter());
+        self.export_selected_log(path, &query)
+    }
+
+    /// Clears the selected log, backing it up to `backup` first when given. Nothing is
+    /// cleared if the backup fails.
+    pub fn clear_selected_log(&mut self, backup: Option<&Path>) -> Result<String, String> {
+        let backup = backup
+            .map(|path| self.export_selected_log(path, "*"))
+            .transpose()?;
+        let channel_wide = to_wide_string(&self.selected_log_name);
+        unsafe {
+            EvtClearLog(
+                None,
+                PCWSTR::from_raw(channel_wide.as_ptr()),
+                PCWSTR::null(),
+                0,
+            )
+        }
+        .map_err(|e| format!("Failed to clear '{}': {}", self.selected_log_name, e))?;
+        Ok(match backup {
+            Some(path) => format!(
+                "Cleared '{}' after backing it up to {}.",
+                self.selected_log_name,
+                path.display()
+            ),
+            None => format!("Cleared '{}' without a backup.", self.selected_log_name),
+        })
+    }
+}
+
+/// Exports the events of `channel` matching `query` to the .evtx file `path`. The Event Log
+/// service writes the file, so the path is made absolute first.
+#[cfg(target_os = "windows")]
+pub fn export_log(channel: &str, query: &str, path: &Path) -> Result<PathBuf, String> {
+    let path = std::path::absolute(path)
+        .map_err(|e| format!("Invalid export path '{}': {}", path.display(), e))?;
+    let channel_wide = to_wide_string(channel);
+    let query_wide = to_wide_string(query);
+    let path_wide = to_wide_string(&path.to_string_lossy());
+    // Structured XML queries name their own channels, so the path must be null.
+    let channel_path = if query.trim_start().starts_with('<') {
+        PCWSTR::null()
+    } else {
+        PCWSTR::from_raw(channel_wide.as_ptr())
+    };
+    unsafe {
+        EvtExportLog(
+            None,
+            channel_path,
+            PCWSTR::from_raw(query_wide.as_ptr()),
+            PCWSTR::from_raw(path_wide.as_ptr()),
+            EvtExportLogChannelPath.0,
+        )
+    }
+    .map_err(|e| {
+        format!(
+            "Failed to export '{}' to {}: {}",
+            channel,
+            path.display(),
+            e
+        )
+    })?;
+    Ok(path)
+}
+
+/// Reads the user name and password of a generic credential in Windows Credential Manager.
+#[cfg(target_os = "windows")]
+fn read_credential(target: &str) -> Result<(String, String), String> {
+    let target_wide = to_wide_string(target);
+    let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+    unsafe {
+        CredReadW(
+            PCWSTR::from_raw(target_wide.as_ptr()),
+            CRED_TYPE_GENERIC,
+            0,
+            &mut credential,
+        )
+        .map_err(|e| format!("Credential '{}' could not be read: {}", target, e))?;
+        let stored = &*credential;
+        let user = stored.UserName.to_string().unwrap_or_default();
+        // Passwords saved with cmdkey are UTF-16.
+        let blob = std::slice::from_raw_parts(
+            stored.CredentialBlob as *const u16,
+            stored.CredentialBlobSize as usize / 2,
+        );
+        let password = String::from_utf16_lossy(blob);
+        CredFree(credential as *const std::ffi::c_void);
+        Ok((user, password))
+    }
+}
+
+/// Saves a user name and password as a generic credential in Windows Credential Manager,
+/// replacing one of the same target name. It is kept for the current user on this machine.
+#[cfg(target_os = "windows")]
+pub fn save_credential(target: &str, user: &str, password: &str) -> Result<(), String> {
+    let mut target_wide = to_wide_string(target);
+    let mut user_wide = to_wide_string(user);
+    // Stored without the terminating null, as cmdkey does.
+    let mut blob: Vec<u8> = password
+        .encode_utf16()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect();
+    let credential = CREDENTIALW {
+        Type: CRED_TYPE_GENERIC,
+        TargetName: PWSTR(target_wide.as_mut_ptr()),
+        CredentialBlobSize: blob.len() as u32,
+        CredentialBlob: blob.as_mut_ptr(),
+        Persist: CRED_PERSIST_LOCAL_MACHINE,
+        UserName: PWSTR(user_wide.as_mut_ptr()),
+        ..Default::default()
+    };
+    let result = unsafe { CredWriteW(&credential, 0) }
+        .map_err(|e| format!("Credential '{}' could not be saved: {}", target, e));
+    blob.fill(0);
+    result
+}
+
+/// Removes a generic credential from Windows Credential Manager.
+#[cfg(target_os = "windows")]
+pub fn delete_credential(target: &str) -> Result<(), String> {
+    let target_wide = to_wide_string(target);
+    unsafe { CredDeleteW(PCWSTR::from_raw(target_wide.as_ptr()), CRED_TYPE_GENERIC, 0) }
+        .map_err(|e| format!("Credential '{}' could not be removed: {}", target, e))
+}
+
+/// Lists the target names, lowercase, of the generic credentials whose names start with
+/// `prefix`.
+#[cfg(target_os = "windows")]
+pub fn credential_targets(prefix: &str) -> BTreeSet<String> {
+    let filter_wide = to_wide_string(&format!("{}*", prefix));
+    let mut count = 0u32;
+    let mut credentials: *mut *mut CREDENTIALW = std::ptr::null_mut();
+    let mut targets = BTreeSet::new();
+    unsafe {
+        // Fails with ERROR_NOT_FOUND when there are none.
+        if CredEnumerateW(
+            PCWSTR::from_raw(filter_wide.as_ptr()),
+            CRED_ENUMERATE_FLAGS(0),
+            &mut count,
+            &mut credentials,
+        )
+        .is_err()
+        {
+            return targets;
+        }
+        targets.extend(
+            std::slice::from_raw_parts(credentials, count as usize)
+                .iter()
+                .map(|credential| &**credential)
+                .filter(|credential| credential.Type == CRED_TYPE_GENERIC)
+                .filter_map(|credential| credential.TargetName.to_string().ok())
+                .map(|target| target.to_lowercase()),
+        );
+        CredFree(credentials as *const std::ffi::c_void);
+    }
+    targets
+}
+
+/// Connects to the event log service of `server` as `login` says, with the current user's
+/// credentials when it names no credential.
+#[cfg(target_os = "windows")]
+fn open_remote_session(server: &str, login: &Login) -> Result<EVT_HANDLE, String> {
+    let (user, password) = match login.credential.as_deref() {
+        Some(target) => {
+            let (user, password) = read_credential(target)?;
+            (login.user.clone().or(Some(user)), Some(password))
+        }
+        None if login.user.is_some() => {
+            return Err(format!(
+                "A user name is set for '{}' but no credential holding its password.",
+                server
+            ));
+        }
+        None => (None, None),
+    };
+    // "DOMAIN\user" is split; "user@domain" is passed whole.
+    let (domain, user) = match user.as_deref().and_then(|user| user.split_once('\\')) {
+        Some((domain, name)) => (Some(domain.to_string()), Some(name.to_string())),
+        None => (None, user),
+    };
+    let mut server_wide = to_wide_string(server);
+    let mut user_wide = user.as_deref().map(to_wide_string);
+    let mut domain_wide = domain.as_deref().map(to_wide_string);
+    let mut password_wide = password.as_deref().map(to_wide_string);
+    let as_pwstr = |wide: &mut Option<Vec<u16>>| {
+        wide.as_mut()
+            .map_or(PWSTR::null(), |wide| PWSTR(wide.as_mut_ptr()))
+    };
+    let rpc_login = EVT_RPC_LOGIN {
+        Server: PWSTR(server_wide.as_mut_ptr()),
+        User: as_pwstr(&mut user_wide),
+        Domain: as_pwstr(&mut domain_wide),
+        Password: as_pwstr(&mut password_wide),
+        Flags: match login.auth {
+            RemoteAuth::Default => EvtRpcLoginAuthDefault.0,
+            RemoteAuth::Negotiate => EvtRpcLoginAuthNegotiate.0,
+            RemoteAuth::Kerberos => EvtRpcLoginAuthKerberos.0,
+            RemoteAuth::Ntlm => EvtRpcLoginAuthNTLM.0,
+        },
+    };
+    let session = unsafe {
+        EvtOpenSession(
+            EvtRpcLogin,
+            &rpc_login as *const _ as *const std::ffi::c_void,
+            0,
+            0,
+        )
+    }
+    .map_err(|e| connection_error(server, login.auth, &e))?;
+    // A session only connects once it is used, so the server's channels are listed.
+    let probe = unsafe { EvtOpenChannelEnum(session, 0) };
+    match probe {
+        Ok(channels) => {
+            unsafe {
+                let _ = EvtClose(channels);
+            }
+            Ok(session)
+        }
+        Err(e) => {
+            unsafe {
+                let _ = EvtClose(session);
+            }
+            Err(connection_error(server, login.auth, &e))
+        }
+    }
+}
+
+/// Explains why a remote event log session failed, for the errors firewalls, accounts and
+/// Kerberos commonly cause.
+#[cfg(target_os = "windows")]
+fn connection_error(server: &str, auth: RemoteAuth, error: &windows::core::Error) -> String {
+    const RPC_S_SERVER_UNAVAILABLE: u32 = 1722;
+    const EPT_S_NOT_REGISTERED: u32 = 1753;
+    const ERROR_LOGON_FAILURE: u32 = 1326;
+    const ERROR_NO_LOGON_SERVERS: u32 = 1311;
+    const SEC_E_TARGET_UNKNOWN: u32 = 0x8009_0303;
+    const SEC_E_NO_AUTHENTICATING_AUTHORITY: u32 = 0x8009_0311;
+    const SEC_E_WRONG_PRINCIPAL: u32 = 0x8009_0322;
+    let code = error.code().0 as u32;
+    let win32 = |value: u32| code == 0x8007_0000 | value;
+    if win32(RPC_S_SERVER_UNAVAILABLE) {
+        format!(
+            "'{}' did not answer ({}). Check that the name resolves and the host is running, \
+             and that its firewall allows the 'Remote Event Log Management' rules \
+             (Enable-NetFirewallRule -DisplayGroup \"Remote Event Log Management\").",
+            server, error
+        )
+    } else if win32(EPT_S_NOT_REGISTERED) {
+        format!(
+            "'{}' answered but does not offer the event log service ({}). The Windows Event \
+             Log service may be stopped, or a firewall passes the endpoint mapper but blocks the \
+             dynamic RPC ports the 'Remote Event Log Management' rules open.",
+            server, error
+        )
+    } else if code == ERROR_ACCESS_DENIED.to_hresult().0 as u32 {
+        format!(
+            "'{}' denied access to its event logs ({}). The account needs to be an \
+             administrator or in the Event Log Readers group there.",
+            server, error
+        )
+    } else if win32(ERROR_LOGON_FAILURE) {
+        format!(
+            "'{}' rejected the user name or password ({}). Check the credential, or save \
+             another with [p] in the host picker.",
+            server, error
+        )
+    } else if win32(ERROR_NO_LOGON_SERVERS)
+        || [
+            SEC_E_TARGET_UNKNOWN,
+            SEC_E_NO_AUTHENTICATING_AUTHORITY,
+            SEC_E_WRONG_PRINCIPAL,
+        ]
+        .contains(&code)
+    {
+        format!(
+            "Kerberos could not authenticate to '{}' ({}). It needs the host's DNS name \
+             rather than an address and a reachable domain controller; set auth = \"ntlm\" \
+             for hosts outside the domain.",
+            server, error
+        )
+    } else if auth == RemoteAuth::Default {
+        format!("Failed to connect to '{}': {}", server, error)
+    } else {
+        format!(
+            "Failed to connect to '{}' with {} authentication: {}",
+            server,
+            auth.name(),
+            error
+        )
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl AppState {
+    /// Saves a credential for a host or `@group` in Windows Credential Manager, which later
+    /// connections to the host or the group's hosts use unless `hosts.toml` names another.
+    pub fn save_host_credential(
+        &mut self,
+        name: &str,
+        user: &str,
+        password: &str,
+    ) -> Result<(), String> {
+        let target = hosts::saved_credential_target(name);
+        save_credential(&target, user, password)?;
+        self.hosts.saved_credentials.insert(target.clone());
+        self.host_status.clear();
+        self.log(&format!("Saved credential '{}' for {}.", target, user));
+        Ok(())
+    }
+
+    /// Reads the selected event's XML again by record ID when it was dropped after parsing. It
+    /// is kept from then on; an event no longer in the log keeps its stub.
+    pub fn reload_selected_raw_xml(&mut self) {
+        let Some(index) = self
+            .table_state
+            .selected()
+            .and_then(|row| self.event_index(row))
+            .filter(|&index| {
+                self.events
+                    .get(index)
+                    .is_some_and(|event| event.raw_dropped)
+            })
+        else {
+            return;
+        };
+        let event = &mut self.events[index];
+        event.raw_dropped = false;
+        let (channel, record_id) = (event.channel.clone(), event.record_id);
+        let xpath = format!("*[System[EventRecordID={}]]", record_id);
+        match self.query_channel(&channel, &xpath, false, 1) {
+            Ok(mut events) if !events.is_empty() => {
+                self.events[index].raw_data = events.remove(0).raw_data;
+            }
+            Ok(_) => self.log(&format!(
+                "Event record {} is no longer in '{}'; its XML cannot be read again.",
+                record_id, channel
+            )),
+            Err(msg) => self.log(&format!(
+                "XML of event record {} not read: {}",
+                record_id, msg
+            )),
+        }
+    }
+
+    /// Reads the selected event in full from its node when only its System values were read,
+    /// replacing it in the list. A failure is shown in place of the message and not retried.
+    pub fn render_deferred_selection(&mut self) {
+        let Some(index) = self
+            .table_state
+            .selected()
+            .and_then(|row| self.event_index(row))
+        else {
+            return;
+        };
+        let Some(event) = self.events.get(index) else {
+            return;
+        };
+        let key = (event.computer.clone(), event.record_id);
+        let Some(node) = self.deferred_events.remove(&key) else {
+            return;
+        };
+        let channel = event.channel.clone();
+        let xpath = format!("*[System[EventRecordID={}]]", event.record_id);
+        let result = self
+            .source
+            .connect(&node, &self.hosts.login_for(&node), false)
+            .and_then(|mut source| query_events(source.as_mut(), &channel, &xpath, false, 1));
+        match result {
+            Ok(mut events) if !events.is_empty() => {
+                let mut full = events.remove(0);
+                if full.computer.is_empty() {
+                    full.computer = node;
+                }
+                self.events[index] = full;
+            }
+            Ok(_) => {
+                self.events[index].message = format!(
+                    "The event is no longer in the '{}' log on {}.",
+                    channel, node
+                );
+            }
+            Err(msg) => self.events[index].message = format!("Not read from {}: {}", node, msg),
+        }
+    }
+
+    /// Removes the credential saved for a host or `@group`.
+    pub fn remove_host_credential(&mut self, name: &str) -> Result<(), String> {
+        let target = hosts::saved_credential_target(name);
+        if !self.hosts.saved_credentials.contains(&target) {
+            return Err(format!("No credential is saved for {}.", name));
+        }
+        delete_credential(&target)?;
+        self.hosts.saved_credentials.remove(&target);
+        self.host_status.clear();
+        self.log(&format!("Removed credential '{}'.", target));
+        Ok(())
+    }
+
+    /// Checks the next host queued in the host picker and records whether it answered.
+    pub fn continue_host_checks(&mut self) {
+        let Some(host) = self
+            .host_picker
+            .as_mut()
+            .and_then(|picker| picker.pending_checks.pop_front())
+        else {
+            return;
+        };
+        let status = match self
+            .source
+            .connect(&host, &self.hosts.login_for(&host), true)
+        {
+            Ok(_) => HostStatus::Reachable,
+            Err(msg) => HostStatus::Unreachable(msg),
+        };
+        self.host_status.insert(host.to_lowercase(), status);
+    }
+
+    /// Reads the Failover Clustering events of every node in `nodes` and shows them in the
+    /// event list as one time-ordered view. The Computer column tells the nodes apart. Nodes
+    /// that could not be read are left out and reported in a status dialog.
+    pub fn load_cluster_events(&mut self, nodes: Vec<String>) {
+        self.source.close_query();
+        self.close_merged_queries();
+        self.clear_events();
+        self.preview_scroll = 0;
+        self.retention = None;
+
+        let mut per_node = Vec::new();
+        let mut failures = Vec::new();
+        for node in &nodes {
+            let login = self.hosts.login_for(node);
+            let defer = self.config.defer_remote_rendering;
+            let mut source = match self.source.connect(node, &login, defer) {
+                Ok(source) => source,
+                Err(msg) => {
+                    self.host_status
+                        .insert(node.to_lowercase(), HostStatus::Unreachable(msg.clone()));
+                    failures.push(msg);
+                    continue;
+                }
+            };
+            let mut node_events = Vec::new();
+            let mut node_failures = Vec::new();
+            for (channel, query) in cluster::CLUSTER_SOURCES {
+                match query_events(
+                    source.as_mut(),
+                    channel,
+                    query,
+                    self.sort_descending,
+                    cluster::MAX_NODE_EVENTS,
+                ) {
+                    Ok(events) => node_events.extend(events),
+                    Err(msg) => node_failures.push(format!("{}: {}", node, msg)),
+                }
+            }
+            // A node is reachable when at least one of its logs could be read.
+            let status = if node_failures.len() < cluster::CLUSTER_SOURCES.len() {
+                HostStatus::Reachable
+            } else {
+                HostStatus::Unreachable(node_failures.join("\n"))
+            };
+            self.host_status.insert(node.to_lowercase(), status);
+            failures.extend(node_failures);
+            for event in &mut node_events {
+                if event.computer.is_empty() {
+                    event.computer = node.clone();
+                }
+                self.match_sigma_rules(event);
+                if self.config.defer_remote_rendering {
+                    self.deferred_events
+                        .insert((event.computer.clone(), event.record_id), node.clone());
+                }
+            }
+            per_node.push(node_events);
+        }
+
+        self.events = cluster::merge(per_node, self.sort_descending);
+        self.no_more_events = true;
+        self.config.cluster_nodes = nodes.clone();
+        self.save_config();
+        self.cluster_nodes = Some(nodes);
+        self.sort_events();
+        if self.row_count() > 0 {
+            self.table_state.select(Some(0));
+        }
+        self.update_preview_for_selection();
+        if !failures.is_empty() {
+            self.show_error("Cluster Nodes Not Read", &failures.join("\n"));
+        }
+    }
+}
@@ -1,18 +1,25 @@
-use chrono::Local;
-use quick_xml::{events::Event, Reader};
-use crate::models::DisplayEvent;
-
+use crate::models::{DisplayEvent, EventData, EventLevel};
+use chrono::{DateTime, Utc};
+use quick_xml::{Reader, events::Event};
 
 /// Parses an event XML string and returns a DisplayEvent struct with extracted data.
 #[cfg(target_os = "windows")]
 pub fn parse_event_xml(xml: &str) -> DisplayEvent {
     let mut source = "<Parse Error>".to_string();
     let mut provider_name_original = "<Parse Error>".to_string();
-    let mut id = "0".to_string();
-    let mut level = "Unknown".to_string();
-    let mut datetime = String::new();
-    let mut _system_data_end_pos: Option<usize> = None;
-    let _event_data_message = "<No event data found>".to_string();
+    let mut event_id: u32 = 0;
+    let mut level = EventLevel::default();
+    let mut timestamp = None;
+    let mut computer = String::new();
+    let mut channel = String::new();
+    let mut user = String::new();
+    let mut task: u16 = 0;
+    let mut keywords: u64 = 0;
+    let mut record_id: u64 = 0;
+    let mut activity_id: Option<String> = None;
+    let mut related_activity_id: Option<String> = None;
+    let mut process_id: Option<u32> = None;
+    let mut thread_id: Option<u32> = None;
 
     let mut reader = Reader::from_str(xml);
     reader.trim_text(true);
@@ -22,10 +29,11 @@ pub fn parse_event_xml(xml: &str) -> DisplayEvent {
     let mut inside_system = false;
     let mut inside_event_id = false;
     let mut inside_level = false;
-
-    let mut event_data_values = Vec::new();
-    let mut current_text_buffer = String::new();
-    let mut inside_event_or_user_data = false;
+    let mut inside_task = false;
+    let mut inside_keywords = false;
+    let mut inside_record_id = false;
+    let mut inside_computer = false;
+    let mut inside_channel = false;
 
     loop {
         match reader.read_event_into(&mut buf) {
@@ -39,9 +47,12 @@ pub fn parse_event_xml(xml: &str) -> DisplayEvent {
                     "Provider" if inside_system => {
                         for attr_result in e.attributes() {
                             if let Ok(attr) = attr_result {
-                                let attr_key = std::str::from_utf8(attr.key.local_name().into_inner()).unwrap_or("");
+                                let attr_key =
+                                    std::str::from_utf8(attr.key.local_name().into_inner())
+                                        .unwrap_or("");
                                 if attr_key == "Name" {
-                                    provider_name_original = attr.unescape_value().unwrap_or_default().to_string();
+                                    provider_name_original =
+                                        attr.unescape_value().unwrap_or_default().to_string();
                                     source = provider_name_original.clone();
                                 }
                             }
@@ -49,27 +60,54 @@ pub fn parse_event_xml(xml: &str) -> DisplayEvent {
                     }
                     "EventID" if inside_system => inside_event_id = true,
                     "Level" if inside_system => inside_level = true,
+                    "Task" if inside_system => inside_task = true,
+                    "Keywords" if inside_system => inside_keywords = true,
+                    "EventRecordID" if inside_system => inside_record_id = true,
+                    "Computer" if inside_system => inside_computer = true,
+                    "Channel" if inside_system => inside_channel = true,
+                    "Security" if inside_system => {
+                        for attr in e.attributes().flatten() {
+                            if attr.key.local_name().into_inner() == b"UserID" {
+                                user = attr.unescape_value().unwrap_or_default().to_string();
+                            }
+                        }
+                    }
+                    "Correlation" if inside_system => {
+                        for attr in e.attributes().flatten() {
+                            let value = attr.unescape_value().unwrap_or_default().to_string();
+                            match attr.key.local_name().into_inner() {
+                                b"ActivityID" => activity_id = Some(value),
+                                b"RelatedActivityID" => related_activity_id = Some(value),
+                                _ => {}
+                            }
+                        }
+                    }
+                    "Execution" if inside_system => {
+                        for attr in e.attributes().flatten() {
+                            let value = attr.unescape_value().unwrap_or_default().parse().ok();
+                            match attr.key.local_name().into_inner() {
+                                b"ProcessID" => process_id = value,
+                                b"ThreadID" => thread_id = value,
+                                _ => {}
+                            }
+                        }
+                    }
                     "TimeCreated" if inside_system => {
                         for attr_result in e.attributes() {
                             if let Ok(attr) = attr_result {
-                                let attr_key = std::str::from_utf8(attr.key.local_name().into_inner()).unwrap_or("");
+                                let attr_key =
+                                    std::str::from_utf8(attr.key.local_name().into_inner())
+                                        .unwrap_or("");
                                 if attr_key == "SystemTime" {
-                                    let time_str = attr.unescape_value().unwrap_or_default().to_string();
-                                    datetime = chrono::DateTime::parse_from_rfc3339(&time_str)
-                                        .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
-                                        .unwrap_or(time_str);
+                                    let time_str = attr.unescape_value().unwrap_or_default();
+                                    if let Ok(dt) = DateTime::parse_from_rfc3339(&time_str) {
+                                        timestamp = Some(dt.with_timezone(&Utc));
+                                    }
                                 }
                             }
                         }
                     }
-                    "EventData" | "UserData" => {
-                        inside_event_or_user_data = true;
-                        current_text_buffer.clear();
-                    }
-                    "Data" if inside_event_or_user_data => {
-                        current_text_buffer.clear();
-                    }
-                    _ => {},
+                    _ => {}
                 }
             }
             Ok(Event::End(ref e)) => {
@@ -77,47 +115,36 @@ pub fn parse_event_xml(xml: &str) -> DisplayEvent {
                     .unwrap_or("")
                     .to_string();
                 match local_name.as_str() {
-                    "System" => {
-                        inside_system = false;
-                        _system_data_end_pos = Some(reader.buffer_position());
-                    }
+                    "System" => inside_system = false,
                     "EventID" => inside_event_id = false,
                     "Level" => inside_level = false,
-                    "EventData" | "UserData" => {
-                        let trimmed_text = current_text_buffer.trim();
-                        if !trimmed_text.is_empty() && event_data_values.is_empty() {
-                            event_data_values.push(trimmed_text.to_string());
-                        }
-                        current_text_buffer.clear();
-                        inside_event_or_user_data = false;
-                    }
-                    "Data" if inside_event_or_user_data => {
-                        let trimmed_text = current_text_buffer.trim();
-                        if !trimmed_text.is_empty() {
-                            event_data_values.push(trimmed_text.to_string());
-                        }
-                        current_text_buffer.clear();
-                    }
-                    _ => {},
+                    "Task" => inside_task = false,
+                    "Keywords" => inside_keywords = false,
+                    "EventRecordID" => inside_record_id = false,
+                    "Computer" => inside_computer = false,
+                    "Channel" => inside_channel = false,
+                    _ => {}
                 }
             }
             Ok(Event::Text(ref e)) => {
                 let text_result = e.unescape();
                 if let Ok(text) = text_result {
-                     let text_str = text.to_string();
+                    let text_str = text.to_string();
                     if inside_event_id {
-                        id = text_str;
+                        event_id = text_str.parse().unwrap_or(0);
                     } else if inside_level {
-                        level = match text_str.as_str() {
-                            "1" => "Critical".to_string(),
-                            "2" => "Error".to_string(),
-                            "3" => "Warning".to_string(),
-                            "0" | "4" => "Information".to_string(),
-                            "5" => "Verbose".to_string(),
-                            _ => format!("Unknown({})", text_str),
-                        };
-                    } else if inside_event_or_user_data {
-                        current_text_buffer.push_str(&text_str);
+                        level = EventLevel::from_code(text_str.parse().unwrap_or(0));
+                    } else if inside_task {
+                        task = text_str.parse().unwrap_or(0);
+                    } else if inside_keywords {
+                        keywords =
+                            u64::from_str_radix(text_str.trim_start_matches("0x"), 16).unwrap_or(0);
+                    } else if inside_record_id {
+                        record_id = text_str.parse().unwrap_or(0);
+                    } else if inside_computer {
+                        computer = text_str;
+                    } else if inside_channel {
+                        channel = text_str;
                     }
                 }
             }
@@ -128,26 +155,141 @@ pub fn parse_event_xml(xml: &str) -> DisplayEvent {
         buf.clear();
     }
 
-    let final_message = if provider_name_original == "Microsoft-Windows-Windows Error Reporting" && id == "1001" {
-        if !event_data_values.is_empty() {
-             event_data_values.join("\n")
-        } else {
-            "<WER event data found but failed to parse/format>".to_string()
-        }
-    } else if !event_data_values.is_empty() {
-        event_data_values.join("\n")
-    } else {
-        "<No relevant event data found>".to_string()
-    };
+    let (event_data, final_message) = parse_event_data(xml, &provider_name_original, event_id);
 
     DisplayEvent {
         level,
-        datetime,
+        timestamp,
         source,
         provider_name_original,
-        id,
+        event_id,
+        channel,
+        computer,
+        user,
+        task,
+        task_category: None,
+        keywords,
+        record_id,
+        activity_id,
+        related_activity_id,
+        process_id,
+        thread_id,
         message: final_message,
+        event_data,
         raw_data: xml.to_string(),
+        raw_dropped: false,
         formatted_message: None,
+        sigma_hits: Vec::new(),
+    }
+}
+
+/// The message shown until the provider's message is formatted: the event data values, one
+/// per line.
+#[cfg(target_os = "windows")]
+pub fn event_data_message(provider_name: &str, event_id: u32, values: &[String]) -> String {
+    if !values.is_empty() {
+        values.join("\n")
+    } else if provider_name == "Microsoft-Windows-Windows Error Reporting" && event_id == 1001 {
+        "<WER event data found but failed to parse/format>".to_string()
+    } else {
+        "<No relevant event data found>".to_string()
+    }
+}
+
+/// Parses only the EventData or UserData of an event XML. Returns the fields and the message
+/// built from them.
+#[cfg(target_os = "windows")]
+pub fn parse_event_data(xml: &str, provider_name: &str, event_id: u32) -> (EventData, String) {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+    reader.expand_empty_elements(true);
+
+    let mut buf = Vec::new();
+    let mut values = Vec::new();
+    let mut event_data = EventData::default();
+    let mut current_data_name: Option<String> = None;
+    let mut current_text_buffer = String::new();
+    let mut inside_event_or_user_data = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => match e.name().local_name().into_inner() {
+                b"EventData" | b"UserData" => {
+                    inside_event_or_user_data = true;
+                    current_text_buffer.clear();
+                }
+                b"Data" if inside_event_or_user_data => {
+                    current_text_buffer.clear();
+                    current_data_name = e
+                        .try_get_attribute("Name")
+                        .ok()
+                        .flatten()
+                        .and_then(|attr| attr.unescape_value().ok())
+                        .map(|name| name.to_string());
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) => match e.name().local_name().into_inner() {
+                b"EventData" | b"UserData" => {
+                    let trimmed_text = current_text_buffer.trim();
+                    if !trimmed_text.is_empty() && values.is_empty() {
+                        values.push(trimmed_text.to_string());
+                    }
+                    current_text_buffer.clear();
+                    inside_event_or_user_data = false;
+                }
+                b"Data" if inside_event_or_user_data => {
+                    let trimmed_text = current_text_buffer.trim();
+                    if !trimmed_text.is_empty() {
+                        values.push(trimmed_text.to_string());
+                    }
+                    event_data
+                        .fields
+                        .push((current_data_name.take(), trimmed_text.to_string()));
+                    current_text_buffer.clear();
+                }
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) if inside_event_or_user_data => {
+                if let Ok(text) = e.unescape() {
+                    current_text_buffer.push_str(&text);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    let message = event_data_message(provider_name, event_id, &values);
+    (event_data, message)
+}
+
+/// Names of the fields an event template defines, in the order their values are rendered: the
+/// `name` of each `data` element.
+#[cfg(target_os = "windows")]
+pub fn template_data_names(template: &str) -> Vec<String> {
+    let mut reader = Reader::from_str(template);
+    let mut buf = Vec::new();
+    let mut names = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if e.name().local_name().into_inner() == b"data" =>
+            {
+                names.push(
+                    e.try_get_attribute("name")
+                        .ok()
+                        .flatten()
+                        .and_then(|attr| attr.unescape_value().ok())
+                        .map(|name| name.to_string())
+                        .unwrap_or_default(),
+                );
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
     }
-} 
\ No newline at end of file
+    names
+}
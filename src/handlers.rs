@@ -1,69 +1,1147 @@
+use crate::catalog;
+use crate::clipboard;
+use crate::cluster;
+use crate::compare::ComparisonPreset;
+use crate::elevation;
 use crate::helpers;
+#[cfg(target_os = "windows")]
+use crate::hosts::HostStatus;
+#[cfg(target_os = "windows")]
+use crate::hyperv;
+use crate::instance::InstanceRole;
 use crate::models::{
-    AppState, FilterFieldFocus, LOG_NAMES, PanelFocus, PostKeyPressAction, PreviewViewMode,
+    AdminAction, AppState, DisplayEvent, EventAction, EventColumn, FilterFieldFocus,
+    MAX_COLUMN_WIDTH, MAX_MESSAGE_COLUMN_PERCENT, MIN_COLUMN_WIDTH, MIN_MESSAGE_COLUMN_PERCENT,
+    MarkKey, PanelFocus, PendingAction, PostKeyPressAction, PreviewViewMode, PromptKind, SortKey,
 };
-use crossterm::event::{self, KeyCode};
+#[cfg(target_os = "windows")]
+use crate::objectaccess::ObjectAccess;
+use crate::pager;
+use crate::redact;
+use crate::security;
+use crate::sigma;
+use crate::syslog::SyslogForwarder;
+use crate::view::ViewState;
+use crossterm::event::{self, KeyCode, KeyModifiers};
 use std::fs;
+use std::path::PathBuf;
 
 /// Processes a key press event, updates the application state, and returns a PostKeyPressAction.
 pub fn handle_key_press(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    if app_state.deep_search.is_some() {
+        if key.code == KeyCode::Esc {
+            app_state.deep_search = None;
+        }
+        return PostKeyPressAction::None;
+    }
+    if app_state.load_progress.is_some() && key.code == KeyCode::Esc {
+        app_state.cancel_log_load();
+        return PostKeyPressAction::None;
+    }
+
     if app_state.help_dialog_visible {
         return handle_help_dialog_keys(key, app_state);
     }
 
-    if let Some(dialog) = &mut app_state.status_dialog {
-        if dialog.visible {
-            match key.code {
-                KeyCode::Enter | KeyCode::Esc => {
-                    dialog.dismiss();
+    if let Some(dialog) = &mut app_state.status_dialog
+        && dialog.visible
+    {
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                dialog.dismiss();
+                // Esc drops a pending overwrite along with its preview.
+                let pending = app_state.pending_action.take();
+                if let (Some(action), KeyCode::Enter) = (pending, key.code) {
+                    return run_file_action(app_state, action);
                 }
-                _ => {}
             }
-            return PostKeyPressAction::None;
+            KeyCode::Char('a') if dialog.offers_attach => {
+                return attach_to_running_instance(app_state);
+            }
+            _ => {}
         }
+        return PostKeyPressAction::None;
+    }
+
+    if app_state.elevation_prompt.is_some() {
+        return handle_elevation_prompt_keys(key, app_state);
     }
 
     if app_state.is_searching {
         return handle_search_keys(key, app_state);
     }
 
+    if app_state.is_local_filter_input {
+        return handle_local_filter_keys(key, app_state);
+    }
+
+    if app_state.is_query_input_visible {
+        return handle_query_input_keys(key, app_state);
+    }
+
+    if let Some(prompt) = app_state.prompt {
+        return handle_prompt_keys(key, prompt, app_state);
+    }
+
     if app_state.is_filter_dialog_visible {
         return handle_filter_dialog_keys(key, app_state);
     }
 
+    if app_state.is_column_dialog_visible {
+        return handle_column_dialog_keys(key, app_state);
+    }
+
+    if app_state.is_bookmarks_dialog_visible {
+        return handle_bookmarks_dialog_keys(key, app_state);
+    }
+
+    if app_state.detail_fields.is_some() {
+        return handle_detail_view_keys(key, app_state);
+    }
+
+    if let Some(selection) = app_state.context_menu_selection {
+        return handle_context_menu_keys(key, selection, app_state);
+    }
+
+    if let Some(selection) = app_state.admin_menu_selection {
+        return handle_admin_menu_keys(key, selection, app_state);
+    }
+
+    #[cfg(target_os = "windows")]
+    if app_state.dns_analysis.is_some() {
+        return handle_dns_analysis_keys(key, app_state);
+    }
+
+    #[cfg(target_os = "windows")]
+    if app_state.hyperv_view.is_some() {
+        return handle_hyperv_view_keys(key, app_state);
+    }
+
+    #[cfg(target_os = "windows")]
+    if app_state.installer_view.is_some() {
+        return handle_installer_view_keys(key, app_state);
+    }
+
+    #[cfg(target_os = "windows")]
+    if app_state.print_view.is_some() {
+        return handle_print_view_keys(key, app_state);
+    }
+
+    #[cfg(target_os = "windows")]
+    if app_state.device_view.is_some() {
+        return handle_device_view_keys(key, app_state);
+    }
+
+    #[cfg(target_os = "windows")]
+    if app_state.wmi_analysis.is_some() {
+        return handle_wmi_analysis_keys(key, app_state);
+    }
+
+    if app_state.sigma_view.is_some() {
+        return handle_sigma_view_keys(key, app_state);
+    }
+
+    if app_state.comparison.is_some() {
+        return handle_comparison_keys(key, app_state);
+    }
+
+    #[cfg(target_os = "windows")]
+    if app_state.servicing_view.is_some() {
+        return handle_servicing_view_keys(key, app_state);
+    }
+
+    if app_state.audit_policy.is_some() {
+        return handle_audit_policy_keys(key, app_state);
+    }
+
+    #[cfg(target_os = "windows")]
+    if app_state.auth_flow.is_some() {
+        return handle_auth_flow_keys(key, app_state);
+    }
+
+    #[cfg(target_os = "windows")]
+    if app_state.object_access.is_some() {
+        return handle_object_access_keys(key, app_state);
+    }
+
+    if app_state.host_picker.is_some() {
+        return handle_host_picker_keys(key, app_state);
+    }
+
     match key.code {
         KeyCode::Char('q') => return PostKeyPressAction::Quit,
         KeyCode::F(1) => {
             app_state.help_dialog_visible = true;
             return PostKeyPressAction::None;
         }
-        KeyCode::Char(c @ '1'..='5') => {
-            if let Some(index) = c.to_digit(10).map(|d| d as usize - 1) {
-                if index < LOG_NAMES.len() {
-                    app_state.select_log_index(index);
-                    return PostKeyPressAction::ReloadData;
+        KeyCode::Char('T') => {
+            app_state.cycle_theme();
+            return PostKeyPressAction::None;
+        }
+        KeyCode::F(5) => {
+            app_state.toggle_auto_refresh();
+            return PostKeyPressAction::None;
+        }
+        KeyCode::Left if key.modifiers.contains(KeyModifiers::ALT) => {
+            if app_state.step_filter_history(false) {
+                return PostKeyPressAction::ReloadData;
+            }
+            return PostKeyPressAction::None;
+        }
+        KeyCode::Right if key.modifiers.contains(KeyModifiers::ALT) => {
+            if app_state.step_filter_history(true) {
+                return PostKeyPressAction::ReloadData;
+            }
+            return PostKeyPressAction::None;
+        }
+        KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app_state.resize_split(-1);
+            return PostKeyPressAction::None;
+        }
+        KeyCode::Right if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app_state.resize_split(1);
+            return PostKeyPressAction::None;
+        }
+        KeyCode::Left if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            app_state.select_histogram_bucket(-1);
+            return PostKeyPressAction::None;
+        }
+        KeyCode::Right if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            app_state.select_histogram_bucket(1);
+            return PostKeyPressAction::None;
+        }
+        // With vim keys, digits typed in the event list are counts; Alt still switches tabs.
+        KeyCode::Char(c @ '1'..='9')
+            if !app_state.config.vim_keys
+                || app_state.focus != PanelFocus::Events
+                || key.modifiers.contains(KeyModifiers::ALT) =>
+        {
+            if let Some(index) = c.to_digit(10).map(|d| d as usize - 1)
+                && index < app_state.tab_count()
+            {
+                // The merged tab's channels are only checked when they are queried.
+                let access = app_state
+                    .channel_access
+                    .get(index)
+                    .copied()
+                    .unwrap_or_default();
+                if !access.is_readable() {
+                    let channel = app_state.channels[index].clone();
+                    app_state.show_access_error(&channel, access);
+                    return PostKeyPressAction::None;
                 }
+                app_state.select_log_index(index);
+                return PostKeyPressAction::ReloadData;
             }
         }
+        // Left and Right scroll the preview sideways while it does not wrap; Left returns
+        // to the event list once it is scrolled back.
+        KeyCode::Right
+            if app_state.focus == PanelFocus::Preview && !app_state.config.preview_wrap =>
+        {
+            app_state.preview_scroll_right();
+            return PostKeyPressAction::None;
+        }
+        KeyCode::Left
+            if app_state.focus == PanelFocus::Preview && app_state.preview_hscroll > 0 =>
+        {
+            app_state.preview_scroll_left();
+            return PostKeyPressAction::None;
+        }
         KeyCode::Tab | KeyCode::Right => {
             app_state.switch_focus();
             return PostKeyPressAction::None;
         }
-        KeyCode::BackTab | KeyCode::Left => {
-            if app_state.focus == PanelFocus::Preview {
-                app_state.focus = PanelFocus::Events;
+        KeyCode::BackTab | KeyCode::Left => {
+            if app_state.focus == PanelFocus::Preview {
+                app_state.focus = PanelFocus::Events;
+            } else {
+                app_state.switch_focus();
+            }
+            return PostKeyPressAction::None;
+        }
+        _ => {}
+    }
+
+    match app_state.focus {
+        PanelFocus::Events => handle_events_panel_keys(key, app_state),
+        PanelFocus::Preview => handle_preview_panel_keys(key, app_state),
+    }
+}
+
+/// Hands the current log over to an already running instance and quits this one.
+fn attach_to_running_instance(app_state: &mut AppState) -> PostKeyPressAction {
+    if app_state.instance.role() == InstanceRole::Primary {
+        return PostKeyPressAction::None;
+    }
+    match app_state
+        .instance
+        .send_attach_request(&app_state.selected_log_name)
+    {
+        Ok(_) => PostKeyPressAction::Quit,
+        Err(msg) => {
+            app_state.log(&format!("Attach failed: {}", msg));
+            app_state.show_error("Attach Failed", &msg);
+            PostKeyPressAction::None
+        }
+    }
+}
+
+/// Runs a file-writing action, or shows what it would overwrite and waits for confirmation.
+fn run_or_preview(app_state: &mut AppState, action: PendingAction) -> PostKeyPressAction {
+    match app_state.overwrite_preview(&action) {
+        Some(preview) => {
+            app_state.pending_action = Some(action);
+            PostKeyPressAction::ShowConfirmation("Overwrite File?".to_string(), preview)
+        }
+        None => run_file_action(app_state, action),
+    }
+}
+
+/// Writes the file for an export or save action and reports the outcome.
+fn run_file_action(app_state: &mut AppState, action: PendingAction) -> PostKeyPressAction {
+    let format = app_state.export_format.display_name().to_string();
+    match action {
+        PendingAction::ExportEvents(path) => match app_state.export_loaded_events(path) {
+            Ok((path, count, redaction)) => PostKeyPressAction::ShowConfirmation(
+                "Export Complete".to_string(),
+                redact::with_summary(
+                    format!(
+                        "{} events saved as {} to:\n\n{}",
+                        count,
+                        format,
+                        path.display()
+                    ),
+                    redaction.as_deref(),
+                ),
+            ),
+            Err(msg) => PostKeyPressAction::ShowConfirmation("Export Failed".to_string(), msg),
+        },
+        PendingAction::ExportMatches {
+            path,
+            scan_whole_log,
+        } => match app_state.export_search_matches(scan_whole_log, path) {
+            Ok((path, count, redaction)) => {
+                let scope = if scan_whole_log || app_state.no_more_events {
+                    "the whole log"
+                } else {
+                    "the loaded events (Ctrl+E scans the whole log)"
+                };
+                PostKeyPressAction::ShowConfirmation(
+                    "Export Complete".to_string(),
+                    redact::with_summary(
+                        format!(
+                            "{} matching events from {} saved as {} to:\n\n{}",
+                            count,
+                            scope,
+                            format,
+                            path.display()
+                        ),
+                        redaction.as_deref(),
+                    ),
+                )
+            }
+            Err(msg) => PostKeyPressAction::ShowConfirmation("Export Failed".to_string(), msg),
+        },
+        PendingAction::ExportCatalog(_) => {
+            #[cfg(target_os = "windows")]
+            return match app_state.export_message_catalog() {
+                Ok((path, count)) => PostKeyPressAction::ShowConfirmation(
+                    "Catalog Exported".to_string(),
+                    format!(
+                        "{} message templates saved to:\n\n{}\n\nKeep this file next to the log or archive to render messages offline.",
+                        count,
+                        path.display()
+                    ),
+                ),
+                Err(msg) => {
+                    PostKeyPressAction::ShowConfirmation("Catalog Export Failed".to_string(), msg)
+                }
+            };
+            #[cfg(not(target_os = "windows"))]
+            PostKeyPressAction::None
+        }
+        PendingAction::SaveEvent(path) => save_selected_event(app_state, path),
+    }
+}
+
+/// Copies the selected event to the clipboard as plain text, or as pretty-printed XML when
+/// `raw_xml` is set. Redaction applies as it does to exports.
+fn copy_selected_event(app_state: &mut AppState, raw_xml: bool) -> PostKeyPressAction {
+    let Some(event) = app_state.selected_event() else {
+        return PostKeyPressAction::ShowConfirmation(
+            "Copy Failed".to_string(),
+            "No event selected.".to_string(),
+        );
+    };
+    let (text, what) = if raw_xml {
+        (
+            helpers::pretty_print_xml(&event.raw_data).unwrap_or_else(|_| event.raw_data.clone()),
+            "XML",
+        )
+    } else {
+        (pager::format_event_text(event), "text")
+    };
+    let (text, redaction) = match app_state.export_redactor(&[event]) {
+        Ok(Some(mut redactor)) => (redactor.redact(&text), Some(redactor.summary())),
+        Ok(None) => (text, None),
+        Err(msg) => return PostKeyPressAction::ShowConfirmation("Copy Failed".to_string(), msg),
+    };
+    let message = format!(
+        "Event {} ({}, record {}) copied to the clipboard as {}.",
+        event.event_id, event.source, event.record_id, what
+    );
+    match clipboard::copy_text(&text) {
+        Ok(()) => PostKeyPressAction::ShowConfirmation(
+            "Copied".to_string(),
+            redact::with_summary(message, redaction.as_deref()),
+        ),
+        Err(msg) => PostKeyPressAction::ShowConfirmation("Copy Failed".to_string(), msg),
+    }
+}
+
+/// Copies an XPath query matching only the selected event to the clipboard.
+fn copy_selected_xpath(app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(event) = app_state.selected_event() else {
+        return PostKeyPressAction::ShowConfirmation(
+            "Copy Failed".to_string(),
+            "No event selected.".to_string(),
+        );
+    };
+    let xpath = event.xpath();
+    match clipboard::copy_text(&xpath) {
+        Ok(()) => PostKeyPressAction::ShowConfirmation(
+            "Copied".to_string(),
+            format!("XPath query copied to the clipboard:\n\n{}", xpath),
+        ),
+        Err(msg) => PostKeyPressAction::ShowConfirmation("Copy Failed".to_string(), msg),
+    }
+}
+
+/// Saves the selected event's XML, pretty-printed when possible, to `path`.
+fn save_selected_event(app_state: &mut AppState, path: PathBuf) -> PostKeyPressAction {
+    let (Some(raw_xml), Some(event)) = (&app_state.preview_raw_xml, app_state.selected_event())
+    else {
+        return PostKeyPressAction::ShowConfirmation(
+            "Save Failed".to_string(),
+            "No event selected or raw XML data unavailable to save.".to_string(),
+        );
+    };
+    let (xml_content, redaction) = match app_state.export_redactor(&[event]) {
+        Ok(Some(mut redactor)) => {
+            let xml = redactor.redact(raw_xml);
+            (xml, Some(redactor.summary()))
+        }
+        Ok(None) => (raw_xml.clone(), None),
+        Err(msg) => {
+            return PostKeyPressAction::ShowConfirmation("Save Failed".to_string(), msg);
+        }
+    };
+    let filename = path.display().to_string();
+
+    match helpers::pretty_print_xml(&xml_content) {
+        Ok(pretty_xml) => match fs::write(&path, &pretty_xml) {
+            Ok(_) => PostKeyPressAction::ShowConfirmation(
+                "Save Successful".to_string(),
+                redact::with_summary(
+                    format!("Event saved to:\n\n{}", filename),
+                    redaction.as_deref(),
+                ),
+            ),
+            Err(e) => {
+                let err_msg = format!("Failed to save event to {}: {}", filename, e);
+                app_state.log(&format!("Save error: {}", e));
+                PostKeyPressAction::ShowConfirmation("Save Failed".to_string(), err_msg)
+            }
+        },
+        Err(e) => {
+            app_state.log(&format!(
+                "Failed to pretty print XML for saving ({}). Saving raw.",
+                e
+            ));
+            match fs::write(&path, &xml_content) {
+                Ok(_) => PostKeyPressAction::ShowConfirmation(
+                    "Save Successful (Raw)".to_string(),
+                    redact::with_summary(
+                        format!("Event saved (raw XML) to:\\n{}", filename),
+                        redaction.as_deref(),
+                    ),
+                ),
+                Err(e) => {
+                    let err_msg = format!("Failed to save raw event to {}: {}", filename, e);
+                    app_state.log(&format!("Raw save error: {}", e));
+                    PostKeyPressAction::ShowConfirmation("Save Failed".to_string(), err_msg)
+                }
+            }
+        }
+    }
+}
+
+fn handle_bookmarks_dialog_keys(
+    key: event::KeyEvent,
+    app_state: &mut AppState,
+) -> PostKeyPressAction {
+    let selected = app_state.bookmarks_selection;
+    let result = match key.code {
+        KeyCode::Esc | KeyCode::Char('B') => {
+            app_state.is_bookmarks_dialog_visible = false;
+            Ok(())
+        }
+        KeyCode::Up => {
+            app_state.bookmarks_selection = selected.saturating_sub(1);
+            Ok(())
+        }
+        KeyCode::Down => {
+            app_state.bookmarks_selection =
+                (selected + 1).min(app_state.bookmarks.len().saturating_sub(1));
+            Ok(())
+        }
+        KeyCode::Enter if !app_state.bookmarks.is_empty() => {
+            app_state.is_bookmarks_dialog_visible = false;
+            app_state.focus = PanelFocus::Events;
+            app_state.jump_to_bookmark(selected)
+        }
+        KeyCode::Delete | KeyCode::Char('x') => app_state.remove_bookmark(selected),
+        _ => Ok(()),
+    };
+    match result {
+        Ok(()) => PostKeyPressAction::None,
+        Err(msg) => PostKeyPressAction::ShowConfirmation("Bookmarks".to_string(), msg),
+    }
+}
+
+fn handle_detail_view_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let field_count = app_state.detail_fields.as_ref().map_or(0, Vec::len);
+    let last = field_count.saturating_sub(1);
+    let selected = app_state.detail_selection;
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('d') | KeyCode::Char('q') => app_state.detail_fields = None,
+        KeyCode::Up => app_state.detail_selection = selected.saturating_sub(1),
+        KeyCode::Down => app_state.detail_selection = (selected + 1).min(last),
+        KeyCode::PageUp => app_state.detail_selection = selected.saturating_sub(10),
+        KeyCode::PageDown => app_state.detail_selection = (selected + 10).min(last),
+        KeyCode::Home | KeyCode::Char('g') => app_state.detail_selection = 0,
+        KeyCode::End | KeyCode::Char('G') => app_state.detail_selection = last,
+        KeyCode::Enter | KeyCode::Char('y') => {
+            return match app_state.copy_detail_field() {
+                Ok(name) => PostKeyPressAction::ShowConfirmation(
+                    "Copied".to_string(),
+                    format!("The value of '{}' was copied to the clipboard.", name),
+                ),
+                Err(msg) => PostKeyPressAction::ShowConfirmation("Copy Failed".to_string(), msg),
+            };
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+fn handle_context_menu_keys(
+    key: event::KeyEvent,
+    selection: usize,
+    app_state: &mut AppState,
+) -> PostKeyPressAction {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char(' ') | KeyCode::Char('m') => {
+            app_state.context_menu_selection = None;
+        }
+        KeyCode::Up => app_state.context_menu_selection = Some(selection.saturating_sub(1)),
+        KeyCode::Down => {
+            app_state.context_menu_selection =
+                Some((selection + 1).min(EventAction::ALL.len() - 1));
+        }
+        KeyCode::Enter => {
+            app_state.context_menu_selection = None;
+            return run_event_action(app_state, EventAction::ALL[selection]);
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+/// Runs an action of the context menu, or its shortcut, on the selected event.
+fn handle_admin_menu_keys(
+    key: event::KeyEvent,
+    selection: usize,
+    app_state: &mut AppState,
+) -> PostKeyPressAction {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('L') => app_state.admin_menu_selection = None,
+        KeyCode::Up => app_state.admin_menu_selection = Some(selection.saturating_sub(1)),
+        KeyCode::Down => {
+            app_state.admin_menu_selection = Some((selection + 1).min(AdminAction::ALL.len() - 1));
+        }
+        KeyCode::Enter => {
+            app_state.admin_menu_selection = None;
+            app_state.open_log_backup_prompt(match AdminAction::ALL[selection] {
+                AdminAction::BackUpLog => PromptKind::BackUpLog,
+                AdminAction::ExportFiltered => PromptKind::ExportFilteredLog,
+                AdminAction::ClearLog => PromptKind::ClearLogBackup,
+            });
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+fn handle_elevation_prompt_keys(
+    key: event::KeyEvent,
+    app_state: &mut AppState,
+) -> PostKeyPressAction {
+    match key.code {
+        KeyCode::Esc => app_state.elevation_prompt = None,
+        KeyCode::Enter => {
+            app_state.elevation_prompt = None;
+            match elevation::relaunch_elevated() {
+                Ok(()) => {
+                    app_state.log("Relaunched as administrator; exiting this instance.");
+                    return PostKeyPressAction::Quit;
+                }
+                Err(msg) => app_state.show_error("Run as Administrator", &msg),
+            }
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+/// Explains how to get the command line into process creation events, for a 4688 event
+/// logged without it.
+fn explain_missing_command_line(app_state: &AppState) -> PostKeyPressAction {
+    if !app_state
+        .selected_event()
+        .is_some_and(security::lacks_command_line)
+    {
+        return PostKeyPressAction::ShowConfirmation(
+            "Command Line".to_string(),
+            "Select a process creation event (4688) logged without its command line.".to_string(),
+        );
+    }
+    PostKeyPressAction::ShowConfirmation(
+        "Command Line Not Recorded".to_string(),
+        security::command_line_hint(),
+    )
+}
+
+fn run_event_action(app_state: &mut AppState, action: EventAction) -> PostKeyPressAction {
+    let result = match action {
+        EventAction::FilterSource => app_state.filter_on_source(),
+        EventAction::FilterEventId => app_state.filter_on_event_id(),
+        EventAction::FilterProcess => app_state.filter_on_process(),
+        EventAction::ExcludeSource => app_state.exclude_source(),
+        EventAction::ShowRelated => app_state.show_related_activity(),
+        EventAction::ServiceTimeline => app_state.show_service_timeline(),
+        EventAction::CopyText => return copy_selected_event(app_state, false),
+        EventAction::CopyXml => return copy_selected_event(app_state, true),
+        EventAction::CopyXPath => return copy_selected_xpath(app_state),
+        EventAction::ForwardSyslog => return forward_to_syslog(app_state, true),
+        EventAction::OpenDocs => {
+            let result = app_state
+                .selected_event()
+                .ok_or_else(|| "Please select an event first.".to_string())
+                .map(|event| helpers::event_docs_url(&event.provider_name_original, event.event_id))
+                .and_then(|url| helpers::open_url(&url));
+            return match result {
+                Ok(()) => PostKeyPressAction::None,
+                Err(msg) => PostKeyPressAction::ShowConfirmation(action.label().to_string(), msg),
+            };
+        }
+        EventAction::Details => {
+            return match app_state.open_detail_view() {
+                Ok(()) => PostKeyPressAction::None,
+                Err(msg) => PostKeyPressAction::ShowConfirmation("No Selection".to_string(), msg),
+            };
+        }
+    };
+    match result {
+        Ok(()) => {
+            app_state.focus = PanelFocus::Events;
+            PostKeyPressAction::ReloadData
+        }
+        Err(msg) => PostKeyPressAction::ShowConfirmation(action.label().to_string(), msg),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn handle_dns_analysis_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(analysis) = &mut app_state.dns_analysis else {
+        return PostKeyPressAction::None;
+    };
+    let group_count = analysis.groups().len();
+    match key.code {
+        KeyCode::Esc => app_state.dns_analysis = None,
+        KeyCode::Up => analysis.selection = analysis.selection.saturating_sub(1),
+        KeyCode::Down => {
+            analysis.selection = (analysis.selection + 1).min(group_count.saturating_sub(1));
+        }
+        KeyCode::Enter => {
+            // Narrow to the selected name, e.g. to see which of its subdomains fail.
+            if let Some(group) = analysis.groups().get(analysis.selection) {
+                analysis.domain = group.name.clone();
+                analysis.domain_cursor = analysis.domain.chars().count();
+                analysis.selection = 0;
+            }
+        }
+        _ => {
+            if edit_text_input(key, &mut analysis.domain, &mut analysis.domain_cursor) {
+                analysis.selection = 0;
+            }
+        }
+    }
+    PostKeyPressAction::None
+}
+
+#[cfg(target_os = "windows")]
+fn handle_hyperv_view_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(view) = &mut app_state.hyperv_view else {
+        return PostKeyPressAction::None;
+    };
+    let selected_guest = view.open_guest.unwrap_or(view.selection);
+    match (key.code, view.open_guest) {
+        (KeyCode::Esc, None) => app_state.hyperv_view = None,
+        (KeyCode::Esc | KeyCode::Backspace, Some(_)) => view.open_guest = None,
+        (KeyCode::Up, None) => view.selection = view.selection.saturating_sub(1),
+        (KeyCode::Down, None) => {
+            view.selection = (view.selection + 1).min(view.guests.len().saturating_sub(1));
+        }
+        (KeyCode::Enter, None) if !view.guests.is_empty() => {
+            view.open_guest = Some(view.selection);
+            view.timeline_selection = 0;
+        }
+        (KeyCode::Up, Some(_)) => {
+            view.timeline_selection = view.timeline_selection.saturating_sub(1);
+        }
+        (KeyCode::Down, Some(guest)) => {
+            let last = view.guests[guest].entries.len().saturating_sub(1);
+            view.timeline_selection = (view.timeline_selection + 1).min(last);
+        }
+        (KeyCode::Char('s'), _) => {
+            // Scope the event list to the guest with a structured query over its channels.
+            let Some(guest) = view.guests.get(selected_guest) else {
+                return PostKeyPressAction::None;
+            };
+            app_state.raw_query = Some(hyperv::guest_query(&guest.name, &view.channels));
+            app_state.hyperv_view = None;
+            app_state.focus = PanelFocus::Events;
+            return PostKeyPressAction::ReloadData;
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+#[cfg(target_os = "windows")]
+fn handle_installer_view_keys(
+    key: event::KeyEvent,
+    app_state: &mut AppState,
+) -> PostKeyPressAction {
+    let Some(view) = &mut app_state.installer_view else {
+        return PostKeyPressAction::None;
+    };
+    let selected = view.open_transaction.unwrap_or(view.selection);
+    match (key.code, view.open_transaction) {
+        (KeyCode::Esc, None) => app_state.installer_view = None,
+        (KeyCode::Esc | KeyCode::Backspace, Some(_)) => view.open_transaction = None,
+        (KeyCode::Up, None) => view.selection = view.selection.saturating_sub(1),
+        (KeyCode::Down, None) => {
+            view.selection = (view.selection + 1).min(view.transactions.len().saturating_sub(1));
+        }
+        (KeyCode::Enter, None) if !view.transactions.is_empty() => {
+            view.open_transaction = Some(view.selection);
+            view.detail_scroll = 0;
+        }
+        (KeyCode::Up, Some(_)) => view.detail_scroll = view.detail_scroll.saturating_sub(1),
+        (KeyCode::Down, Some(_)) => view.detail_scroll += 1,
+        (KeyCode::Char('s'), _) => {
+            // Show everything the Application log recorded while the transaction ran.
+            let Some(transaction) = view.transactions.get(selected) else {
+                return PostKeyPressAction::None;
+            };
+            app_state.raw_query = Some(transaction.events_query());
+            app_state.installer_view = None;
+            app_state.focus = PanelFocus::Events;
+            return PostKeyPressAction::ReloadData;
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+#[cfg(target_os = "windows")]
+fn handle_print_view_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(view) = &mut app_state.print_view else {
+        return PostKeyPressAction::None;
+    };
+    match (key.code, view.open_summary) {
+        (KeyCode::Esc, None) => app_state.print_view = None,
+        (KeyCode::Esc | KeyCode::Backspace, Some(_)) => view.open_summary = None,
+        (KeyCode::Up, None) => view.selection = view.selection.saturating_sub(1),
+        (KeyCode::Down, None) => {
+            view.selection = (view.selection + 1).min(view.summaries.len().saturating_sub(1));
+        }
+        (KeyCode::Enter, None) if !view.summaries.is_empty() => {
+            view.open_summary = Some(view.selection);
+            view.detail_scroll = 0;
+        }
+        (KeyCode::Up, Some(_)) => view.detail_scroll = view.detail_scroll.saturating_sub(1),
+        (KeyCode::Down, Some(_)) => view.detail_scroll += 1,
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+#[cfg(target_os = "windows")]
+fn handle_device_view_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(view) = &mut app_state.device_view else {
+        return PostKeyPressAction::None;
+    };
+    match (key.code, view.open_device) {
+        (KeyCode::Esc, None) => app_state.device_view = None,
+        (KeyCode::Esc | KeyCode::Backspace, Some(_)) => view.open_device = None,
+        (KeyCode::Up, None) => view.selection = view.selection.saturating_sub(1),
+        (KeyCode::Down, None) => {
+            view.selection = (view.selection + 1).min(view.devices.len().saturating_sub(1));
+        }
+        (KeyCode::Enter, None) if !view.devices.is_empty() => {
+            view.open_device = Some(view.selection);
+            view.detail_scroll = 0;
+        }
+        (KeyCode::Up, Some(_)) => view.detail_scroll = view.detail_scroll.saturating_sub(1),
+        (KeyCode::Down, Some(_)) => view.detail_scroll += 1,
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+#[cfg(target_os = "windows")]
+fn handle_servicing_view_keys(
+    key: event::KeyEvent,
+    app_state: &mut AppState,
+) -> PostKeyPressAction {
+    let Some(view) = &mut app_state.servicing_view else {
+        return PostKeyPressAction::None;
+    };
+    match (key.code, view.open_package) {
+        (KeyCode::Esc, None) => app_state.servicing_view = None,
+        (KeyCode::Esc | KeyCode::Backspace, Some(_)) => view.open_package = None,
+        (KeyCode::Up, None) => view.selection = view.selection.saturating_sub(1),
+        (KeyCode::Down, None) => {
+            view.selection = (view.selection + 1).min(view.packages.len().saturating_sub(1));
+        }
+        (KeyCode::Enter, None) if !view.packages.is_empty() => {
+            view.open_package = Some(view.selection);
+            view.detail_scroll = 0;
+        }
+        (KeyCode::Up, Some(_)) => view.detail_scroll = view.detail_scroll.saturating_sub(1),
+        (KeyCode::Down, Some(_)) => view.detail_scroll += 1,
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+#[cfg(target_os = "windows")]
+fn handle_auth_flow_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(view) = &mut app_state.auth_flow else {
+        return PostKeyPressAction::None;
+    };
+    match (key.code, view.open_step) {
+        (KeyCode::Esc, None) => app_state.auth_flow = None,
+        (KeyCode::Esc | KeyCode::Backspace, Some(_)) => view.open_step = None,
+        (KeyCode::Up, None) => view.selection = view.selection.saturating_sub(1),
+        (KeyCode::Down, None) => {
+            view.selection = (view.selection + 1).min(view.steps.len().saturating_sub(1));
+        }
+        (KeyCode::Enter, None) if !view.steps.is_empty() => {
+            view.open_step = Some(view.selection);
+            view.detail_scroll = 0;
+        }
+        (KeyCode::Up, Some(_)) => view.detail_scroll = view.detail_scroll.saturating_sub(1),
+        (KeyCode::Down, Some(_)) => view.detail_scroll += 1,
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+#[cfg(target_os = "windows")]
+fn handle_object_access_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(view) = &mut app_state.object_access else {
+        return PostKeyPressAction::None;
+    };
+    let object_count = view.objects().len();
+    match (key.code, view.open_object) {
+        (KeyCode::Esc, None) => app_state.object_access = None,
+        (KeyCode::Esc | KeyCode::Backspace, Some(_)) => view.open_object = None,
+        (KeyCode::Up, None) => view.selection = view.selection.saturating_sub(1),
+        (KeyCode::Down, None) => {
+            view.selection = (view.selection + 1).min(object_count.saturating_sub(1));
+        }
+        (KeyCode::Enter, None) if object_count > 0 => {
+            view.open_object = Some(view.selection);
+            view.detail_scroll = 0;
+        }
+        (KeyCode::Up, Some(_)) => view.detail_scroll = view.detail_scroll.saturating_sub(1),
+        (KeyCode::Down, Some(_)) => view.detail_scroll += 1,
+        (_, None) if edit_text_input(key, &mut view.prefix, &mut view.prefix_cursor) => {
+            view.selection = 0;
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+fn handle_host_picker_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(picker) = &mut app_state.host_picker else {
+        return PostKeyPressAction::None;
+    };
+    let rows = app_state.hosts.rows();
+    match key.code {
+        KeyCode::Esc => app_state.host_picker = None,
+        KeyCode::Up => picker.selection = picker.selection.saturating_sub(1),
+        KeyCode::Down => {
+            picker.selection = (picker.selection + 1).min(rows.len().saturating_sub(1))
+        }
+        KeyCode::Char(' ') => {
+            if let Some(&row) = rows.get(picker.selection) {
+                picker.toggle(&app_state.hosts, row);
+            }
+        }
+        KeyCode::Char('c') => {
+            #[cfg(target_os = "windows")]
+            for host in picker.chosen(&app_state.hosts) {
+                app_state
+                    .host_status
+                    .insert(host.to_lowercase(), HostStatus::Checking);
+                picker.pending_checks.push_back(host);
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Hosts".to_string(),
+                "Checking remote hosts requires the Windows Event Log service.".to_string(),
+            );
+        }
+        KeyCode::Char('e') => {
+            app_state.host_picker = None;
+            app_state.open_cluster_nodes_prompt();
+        }
+        KeyCode::Char('p') => app_state.open_credential_prompt(),
+        KeyCode::Char('x') => {
+            let Some(&row) = rows.get(picker.selection) else {
+                return PostKeyPressAction::None;
+            };
+            let name = app_state.hosts.credential_name(row);
+            #[cfg(target_os = "windows")]
+            if let Err(msg) = app_state.remove_host_credential(&name) {
+                app_state.show_error("Credential", &msg);
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Credential".to_string(),
+                format!(
+                    "Removing the credential of {} requires Windows Credential Manager.",
+                    name
+                ),
+            );
+        }
+        KeyCode::Enter => {
+            let hosts = picker.chosen(&app_state.hosts);
+            if hosts.is_empty() {
+                return PostKeyPressAction::None;
+            }
+            app_state.host_picker = None;
+            #[cfg(target_os = "windows")]
+            app_state.load_cluster_events(hosts);
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Cluster Nodes".to_string(),
+                "Reading remote event logs requires the Windows Event Log service.".to_string(),
+            );
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+fn handle_audit_policy_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(view) = &mut app_state.audit_policy else {
+        return PostKeyPressAction::None;
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('Y') => app_state.audit_policy = None,
+        KeyCode::Up => view.selection = view.selection.saturating_sub(1),
+        KeyCode::Down => {
+            view.selection = (view.selection + 1).min(view.subcategories.len().saturating_sub(1));
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+#[cfg(target_os = "windows")]
+fn handle_wmi_analysis_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(analysis) = &mut app_state.wmi_analysis else {
+        return PostKeyPressAction::None;
+    };
+    let group_count = analysis.groups().len();
+    match (key.code, analysis.open_group) {
+        (KeyCode::Esc, None) => app_state.wmi_analysis = None,
+        (KeyCode::Esc | KeyCode::Backspace, Some(_)) => analysis.open_group = None,
+        (KeyCode::Up, None) => analysis.selection = analysis.selection.saturating_sub(1),
+        (KeyCode::Down, None) => {
+            analysis.selection = (analysis.selection + 1).min(group_count.saturating_sub(1));
+        }
+        (KeyCode::Enter, None) if group_count > 0 => analysis.open_group = Some(analysis.selection),
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+/// Sends the selected event, or every event shown in the list, to the syslog collector,
+/// redacted as exports are.
+fn forward_to_syslog(app_state: &mut AppState, selected_only: bool) -> PostKeyPressAction {
+    let events: Vec<&DisplayEvent> = if selected_only {
+        app_state.selected_event().into_iter().collect()
+    } else {
+        (0..app_state.row_count())
+            .filter_map(|row| app_state.event_at_row(row))
+            .collect()
+    };
+    if events.is_empty() {
+        return PostKeyPressAction::ShowConfirmation(
+            "Syslog Forwarding".to_string(),
+            "No events to forward.".to_string(),
+        );
+    }
+    let collector = app_state
+        .config
+        .syslog_collector
+        .clone()
+        .unwrap_or_default();
+    let result = app_state.prepare_export(events).and_then(|export| {
+        let mut forwarder = SyslogForwarder::connect(&collector, app_state.config.syslog_format)?;
+        forwarder
+            .send_all(&export.events, &app_state.selected_log_name)
+            .map(|count| (forwarder.collector, count, export.redaction))
+    });
+    match result {
+        Ok((collector, count, redaction)) => {
+            app_state.log(&format!("Forwarded {} events to {}", count, collector));
+            PostKeyPressAction::ShowConfirmation(
+                "Syslog Forwarding Complete".to_string(),
+                redact::with_summary(
+                    format!("{} events sent to:\n\n{}", count, collector),
+                    redaction.as_deref(),
+                ),
+            )
+        }
+        Err(msg) => {
+            PostKeyPressAction::ShowConfirmation("Syslog Forwarding Failed".to_string(), msg)
+        }
+    }
+}
+
+fn handle_sigma_view_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    // The first row keeps the events matching any rule.
+    let row_count = sigma::rule_hits(&app_state.sigma_rules, &app_state.events).len() + 1;
+    let Some(view) = &mut app_state.sigma_view else {
+        return PostKeyPressAction::None;
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('a') => app_state.sigma_view = None,
+        KeyCode::Up => view.selection = view.selection.saturating_sub(1),
+        KeyCode::Down => view.selection = (view.selection + 1).min(row_count - 1),
+        KeyCode::Enter if !app_state.sigma_rules.is_empty() => {
+            app_state.filter_on_sigma_selection();
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+fn handle_comparison_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let Some(comparison) = &mut app_state.comparison else {
+        return PostKeyPressAction::None;
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('k') => app_state.comparison = None,
+        KeyCode::Up => comparison.selection = comparison.selection.saturating_sub(1),
+        KeyCode::Down => {
+            comparison.selection =
+                (comparison.selection + 1).min(comparison.deltas.len().saturating_sub(1));
+        }
+        KeyCode::Right | KeyCode::Tab => {
+            let preset = comparison.preset.next();
+            app_state.open_comparison(preset);
+        }
+        KeyCode::Left | KeyCode::BackTab => {
+            let preset = comparison.preset.previous();
+            app_state.open_comparison(preset);
+        }
+        _ => {}
+    }
+    PostKeyPressAction::None
+}
+
+fn handle_column_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    let columns = &mut app_state.config.columns;
+    let selected = app_state.column_dialog_selection.min(columns.len() - 1);
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+    // Width a column starts from when it stops filling the remaining space.
+    let fixed_width = |column: EventColumn| match column.default_width() {
+        0 => 30,
+        width => width,
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('K') => app_state.is_column_dialog_visible = false,
+        KeyCode::Up if shift && selected > 0 => {
+            columns.swap(selected, selected - 1);
+            app_state.column_dialog_selection = selected - 1;
+        }
+        KeyCode::Down if shift && selected + 1 < columns.len() => {
+            columns.swap(selected, selected + 1);
+            app_state.column_dialog_selection = selected + 1;
+        }
+        KeyCode::Up => app_state.column_dialog_selection = selected.saturating_sub(1),
+        KeyCode::Down => {
+            app_state.column_dialog_selection = (selected + 1).min(columns.len() - 1);
+        }
+        KeyCode::Char(' ') | KeyCode::Enter => {
+            // Keep at least one column so the table never renders empty rows.
+            let visible_count = columns.iter().filter(|c| c.visible).count();
+            if !columns[selected].visible || visible_count > 1 {
+                columns[selected].visible = !columns[selected].visible;
+            }
+        }
+        KeyCode::Left | KeyCode::Right if columns[selected].column == EventColumn::Message => {
+            let percent = &mut app_state.config.message_column_percent;
+            *percent = if key.code == KeyCode::Left {
+                percent.saturating_sub(5).max(MIN_MESSAGE_COLUMN_PERCENT)
+            } else {
+                (*percent + 5).min(MAX_MESSAGE_COLUMN_PERCENT)
+            };
+        }
+        KeyCode::Left | KeyCode::Right => {
+            let settings = &mut columns[selected];
+            let current = match settings.width {
+                0 => fixed_width(settings.column),
+                width => width,
+            };
+            settings.width = if key.code == KeyCode::Left {
+                current.saturating_sub(1).max(MIN_COLUMN_WIDTH)
+            } else {
+                (current + 1).min(MAX_COLUMN_WIDTH)
+            };
+        }
+        KeyCode::Char('a') if columns[selected].column != EventColumn::Message => {
+            let settings = &mut columns[selected];
+            settings.width = if settings.width == 0 {
+                fixed_width(settings.column)
             } else {
-                app_state.switch_focus();
-            }
-            return PostKeyPressAction::None;
+                0
+            };
         }
         _ => {}
     }
-
-    match app_state.focus {
-        PanelFocus::Events => handle_events_panel_keys(key, app_state),
-        PanelFocus::Preview => handle_preview_panel_keys(key, app_state),
-    }
+    PostKeyPressAction::None
 }
 
 fn handle_help_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
@@ -95,81 +1173,334 @@ fn handle_help_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) -> Po
     PostKeyPressAction::None
 }
 
+/// Edits the search term, jumping to the first loaded match on every keystroke.
 fn handle_search_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
-    let action = PostKeyPressAction::None;
-    let text = &mut app_state.search_term;
-    let cursor = &mut app_state.search_cursor;
-    let mut perform_search = false;
-
     match key.code {
-        KeyCode::Esc => {
-            app_state.is_searching = false;
-            text.clear();
-            *cursor = 0;
-            app_state.last_search_term = None;
-        }
+        KeyCode::Esc => app_state.cancel_search(),
         KeyCode::Enter => {
-            app_state.is_searching = false;
-            if !text.is_empty() {
-                app_state.last_search_term = Some(text.clone());
-                perform_search = true;
-            } else {
-                app_state.last_search_term = None;
+            if let Err(msg) = app_state.confirm_search() {
+                return PostKeyPressAction::ShowConfirmation("Search Failed".to_string(), msg);
             }
-            text.clear();
-            *cursor = 0;
         }
-        KeyCode::Char(c) => {
-            if text.is_empty() {
-                text.push(c);
-                *cursor = 1;
-            } else {
-                let byte_idx = text
-                    .char_indices()
-                    .nth(*cursor)
-                    .map(|(idx, _)| idx)
-                    .unwrap_or(text.len());
-                text.insert(byte_idx, c);
-                *cursor = cursor.saturating_add(1);
+        _ => {
+            if edit_text_input(
+                key,
+                &mut app_state.search_term,
+                &mut app_state.search_cursor,
+            ) {
+                app_state.update_incremental_search();
             }
         }
+    }
+    PostKeyPressAction::None
+}
+
+/// Applies a text-editing key to a single-line input, returning true if the key was consumed.
+fn edit_text_input(key: event::KeyEvent, text: &mut String, cursor: &mut usize) -> bool {
+    match key.code {
+        KeyCode::Char(c) => {
+            let byte_idx = text
+                .char_indices()
+                .nth(*cursor)
+                .map(|(idx, _)| idx)
+                .unwrap_or(text.len());
+            text.insert(byte_idx, c);
+            *cursor = cursor.saturating_add(1);
+        }
         KeyCode::Backspace => {
-            if *cursor > 0 {
-                let char_idx_to_remove = *cursor - 1;
-                if let Some((byte_idx, _)) = text.char_indices().nth(char_idx_to_remove) {
-                    text.remove(byte_idx);
-                    *cursor = cursor.saturating_sub(1);
-                }
+            if let Some((byte_idx, _)) = cursor
+                .checked_sub(1)
+                .and_then(|idx| text.char_indices().nth(idx))
+            {
+                text.remove(byte_idx);
+                *cursor -= 1;
             }
         }
         KeyCode::Delete => {
-            if *cursor < text.chars().count() {
-                if let Some((byte_idx, _)) = text.char_indices().nth(*cursor) {
-                    text.remove(byte_idx);
-                }
+            if let Some((byte_idx, _)) = text.char_indices().nth(*cursor) {
+                text.remove(byte_idx);
             }
         }
-        KeyCode::Left => {
-            *cursor = cursor.saturating_sub(1);
+        KeyCode::Left => *cursor = cursor.saturating_sub(1),
+        KeyCode::Right => *cursor = (*cursor + 1).min(text.chars().count()),
+        KeyCode::Home => *cursor = 0,
+        KeyCode::End => *cursor = text.chars().count(),
+        _ => return false,
+    }
+    true
+}
+
+/// Edits the local filter, narrowing the event list on every keystroke.
+fn handle_local_filter_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    match key.code {
+        KeyCode::Esc => {
+            app_state.is_local_filter_input = false;
+            app_state.clear_local_filter();
+        }
+        KeyCode::Enter => app_state.is_local_filter_input = false,
+        _ => {
+            if edit_text_input(
+                key,
+                &mut app_state.local_filter,
+                &mut app_state.local_filter_cursor,
+            ) {
+                app_state.apply_local_filter();
+            }
         }
-        KeyCode::Right => {
-            *cursor = (*cursor + 1).min(text.chars().count());
+    }
+    PostKeyPressAction::None
+}
+
+fn handle_query_input_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
+    match key.code {
+        KeyCode::Esc => {
+            app_state.is_query_input_visible = false;
+            app_state.query_input.clear();
+            app_state.query_cursor = 0;
         }
-        KeyCode::Home => {
-            *cursor = 0;
+        KeyCode::Enter => {
+            app_state.is_query_input_visible = false;
+            let query = app_state.query_input.trim().to_string();
+            app_state.raw_query = if query.is_empty() { None } else { Some(query) };
+            app_state.query_input.clear();
+            app_state.query_cursor = 0;
+            return PostKeyPressAction::ReloadData;
         }
-        KeyCode::End => {
-            *cursor = text.chars().count();
+        _ => {
+            edit_text_input(key, &mut app_state.query_input, &mut app_state.query_cursor);
         }
-
-        _ => {}
     }
+    PostKeyPressAction::None
+}
 
-    if perform_search {
-        let _result = app_state.find_next_match();
+fn handle_prompt_keys(
+    key: event::KeyEvent,
+    prompt: PromptKind,
+    app_state: &mut AppState,
+) -> PostKeyPressAction {
+    match key.code {
+        KeyCode::Esc => {
+            app_state.prompt = None;
+            app_state.prompt_input.clear();
+            app_state.prompt_cursor = 0;
+            if let Some(picker) = &mut app_state.host_picker {
+                picker.credential_entry = None;
+            }
+        }
+        KeyCode::Enter => {
+            app_state.prompt = None;
+            let input = std::mem::take(&mut app_state.prompt_input);
+            app_state.prompt_cursor = 0;
+            match prompt {
+                PromptKind::CredentialUser => {
+                    let user = input.trim();
+                    if let Some(picker) = &mut app_state.host_picker {
+                        match &mut picker.credential_entry {
+                            Some((_, entered)) if !user.is_empty() => {
+                                *entered = Some(user.to_string());
+                                app_state.prompt = Some(PromptKind::CredentialPassword);
+                            }
+                            _ => picker.credential_entry = None,
+                        }
+                    }
+                }
+                PromptKind::CredentialPassword => {
+                    let Some((name, Some(user))) = app_state
+                        .host_picker
+                        .as_mut()
+                        .and_then(|picker| picker.credential_entry.take())
+                    else {
+                        return PostKeyPressAction::None;
+                    };
+                    #[cfg(target_os = "windows")]
+                    if let Err(msg) = app_state.save_host_credential(&name, &user, &input) {
+                        app_state.show_error("Credential", &msg);
+                    }
+                    #[cfg(not(target_os = "windows"))]
+                    return PostKeyPressAction::ShowConfirmation(
+                        "Credential".to_string(),
+                        format!(
+                            "Saving a credential for {} ({}) requires Windows Credential Manager.",
+                            name, user
+                        ),
+                    );
+                }
+                PromptKind::IncidentWindow => match app_state.apply_incident_window(&input) {
+                    Ok(()) => return PostKeyPressAction::ReloadData,
+                    Err(msg) => app_state.show_error("Invalid Incident Window", &msg),
+                },
+                PromptKind::IncidentMarker => app_state.set_incident_marker(&input),
+                PromptKind::GoToRecord => {
+                    let input = input.trim();
+                    if input.is_empty() {
+                        return PostKeyPressAction::None;
+                    }
+                    let Ok(record_id) = input.parse::<u64>() else {
+                        app_state.show_error(
+                            "Invalid Record ID",
+                            &format!("'{}' is not an event record ID.", input),
+                        );
+                        return PostKeyPressAction::None;
+                    };
+                    #[cfg(target_os = "windows")]
+                    match app_state.go_to_record(record_id) {
+                        Ok(true) => return PostKeyPressAction::ReloadData,
+                        Ok(false) => {}
+                        Err(msg) => app_state.show_error("Record Not Found", &msg),
+                    }
+                    #[cfg(not(target_os = "windows"))]
+                    if !app_state.select_record(record_id) {
+                        app_state.show_error(
+                            "Record Not Found",
+                            &format!("Record {} is not loaded.", record_id),
+                        );
+                    }
+                }
+                PromptKind::ClusterNodes => match cluster::parse_nodes(&input)
+                    .and_then(|nodes| app_state.hosts.expand(nodes))
+                {
+                    #[cfg(target_os = "windows")]
+                    Ok(nodes) => app_state.load_cluster_events(nodes),
+                    #[cfg(not(target_os = "windows"))]
+                    Ok(_) => app_state.show_confirmation(
+                        "Cluster Nodes",
+                        "Reading remote event logs requires the Windows Event Log service.",
+                    ),
+                    Err(msg) => app_state.show_error("Invalid Node List", &msg),
+                },
+                PromptKind::BackUpLog => {
+                    let path = input.trim();
+                    if path.is_empty() {
+                        return PostKeyPressAction::None;
+                    }
+                    #[cfg(target_os = "windows")]
+                    match app_state.export_selected_log(std::path::Path::new(path), "*") {
+                        Ok(path) => app_state.show_confirmation(
+                            "Log Backed Up",
+                            &format!(
+                                "Saved '{}' to {}.",
+                                app_state.selected_log_name,
+                                path.display()
+                            ),
+                        ),
+                        Err(msg) => app_state.show_error("Backup Failed", &msg),
+                    }
+                    #[cfg(not(target_os = "windows"))]
+                    app_state.show_confirmation(
+                        "Back Up Log",
+                        "Backing up a log requires the Windows Event Log service.",
+                    );
+                }
+                PromptKind::ExportFilteredLog => {
+                    let path = input.trim();
+                    if path.is_empty() {
+                        return PostKeyPressAction::None;
+                    }
+                    #[cfg(target_os = "windows")]
+                    match app_state.export_filtered_log(std::path::Path::new(path)) {
+                        Ok(path) => app_state.show_confirmation(
+                            "Events Exported",
+                            &format!(
+                                "Saved the events of '{}' matching the current filter to {}.",
+                                app_state.selected_log_name,
+                                path.display()
+                            ),
+                        ),
+                        Err(msg) => app_state.show_error("Export Failed", &msg),
+                    }
+                    #[cfg(not(target_os = "windows"))]
+                    app_state.show_confirmation(
+                        "Export Filtered Events",
+                        "Exporting to .evtx requires the Windows Event Log service.",
+                    );
+                }
+                PromptKind::ClearLogBackup => app_state.open_clear_log_confirm_prompt(&input),
+                PromptKind::ClearLogConfirm => {
+                    let backup = app_state.clear_log_backup.take();
+                    if input.trim() != app_state.selected_log_name {
+                        app_state.show_error(
+                            "Log Not Cleared",
+                            &format!(
+                                "The name typed does not match '{}', so nothing was cleared.",
+                                app_state.selected_log_name
+                            ),
+                        );
+                        return PostKeyPressAction::None;
+                    }
+                    #[cfg(target_os = "windows")]
+                    match app_state.clear_selected_log(backup.as_deref()) {
+                        Ok(msg) => {
+                            app_state.show_confirmation("Log Cleared", &msg);
+                            return PostKeyPressAction::ReloadData;
+                        }
+                        Err(msg) => app_state.show_error("Clear Log Failed", &msg),
+                    }
+                    #[cfg(not(target_os = "windows"))]
+                    {
+                        let _ = backup;
+                        app_state.show_confirmation(
+                            "Clear Log",
+                            "Clearing a log requires the Windows Event Log service.",
+                        );
+                    }
+                }
+                PromptKind::SaveSession => {
+                    let path = input.trim();
+                    if path.is_empty() {
+                        return PostKeyPressAction::None;
+                    }
+                    match app_state.save_session(std::path::Path::new(path)) {
+                        Ok(()) => app_state.show_confirmation(
+                            "Session Saved",
+                            &format!(
+                                "Saved the view of '{}' and {} bookmarks to {}.\n\nRestore it with \
+                                 Ctrl+O or --session.",
+                                app_state.selected_log_name,
+                                app_state.bookmarks.len(),
+                                path
+                            ),
+                        ),
+                        Err(msg) => app_state.show_error("Session Not Saved", &msg),
+                    }
+                }
+                PromptKind::RestoreSession => {
+                    let path = input.trim();
+                    if path.is_empty() {
+                        return PostKeyPressAction::None;
+                    }
+                    match app_state.restore_session(std::path::Path::new(path)) {
+                        Ok((session, added)) => {
+                            app_state.log(&format!(
+                                "Restored the session saved {} ({} bookmarks added).",
+                                helpers::format_local_time(session.saved_at),
+                                added
+                            ));
+                            return PostKeyPressAction::ReloadData;
+                        }
+                        Err(msg) => app_state.show_error("Session Not Restored", &msg),
+                    }
+                }
+                PromptKind::ViewToken => {
+                    if input.trim() == app_state.current_view().to_token() {
+                        return PostKeyPressAction::None;
+                    }
+                    match ViewState::from_token(&input).and_then(|view| app_state.apply_view(view))
+                    {
+                        Ok(()) => return PostKeyPressAction::ReloadData,
+                        Err(msg) => app_state.show_error("Invalid View Token", &msg),
+                    }
+                }
+            }
+        }
+        _ => {
+            edit_text_input(
+                key,
+                &mut app_state.prompt_input,
+                &mut app_state.prompt_cursor,
+            );
+        }
     }
-
-    action
+    PostKeyPressAction::None
 }
 
 fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
@@ -182,6 +1513,26 @@ fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) ->
                 Some(&mut app_state.filter_dialog_event_id),
                 Some(&mut app_state.filter_event_id_cursor),
             ),
+            FilterFieldFocus::Task => (
+                Some(&mut app_state.filter_dialog_task),
+                Some(&mut app_state.filter_task_cursor),
+            ),
+            FilterFieldFocus::Opcode => (
+                Some(&mut app_state.filter_dialog_opcode),
+                Some(&mut app_state.filter_opcode_cursor),
+            ),
+            FilterFieldFocus::User => (
+                Some(&mut app_state.filter_dialog_user),
+                Some(&mut app_state.filter_user_cursor),
+            ),
+            FilterFieldFocus::Computer => (
+                Some(&mut app_state.filter_dialog_computer),
+                Some(&mut app_state.filter_computer_cursor),
+            ),
+            FilterFieldFocus::ExcludeSources => (
+                Some(&mut app_state.filter_dialog_excluded_sources),
+                Some(&mut app_state.filter_excluded_sources_cursor),
+            ),
             FilterFieldFocus::Source => (
                 Some(&mut app_state.filter_dialog_source_input),
                 Some(&mut app_state.filter_source_cursor),
@@ -192,7 +1543,13 @@ fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) ->
     if let (Some(text), Some(cursor)) = text_cursor_refs {
         match key.code {
             KeyCode::Char(c) => {
-                if app_state.filter_dialog_focus == FilterFieldFocus::EventId && !c.is_ascii_digit()
+                let is_numeric_field = matches!(
+                    app_state.filter_dialog_focus,
+                    FilterFieldFocus::Task | FilterFieldFocus::Opcode
+                );
+                if (app_state.filter_dialog_focus == FilterFieldFocus::EventId
+                    && !(c.is_ascii_digit() || c == ',' || c == '-' || c == ' '))
+                    || (is_numeric_field && !c.is_ascii_digit())
                 {
                 } else {
                     if text.is_empty() {
@@ -212,25 +1569,21 @@ fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) ->
                     }
                 }
             }
-            KeyCode::Backspace => {
-                if *cursor > 0 {
-                    let char_idx_to_remove = *cursor - 1;
-                    if let Some((byte_idx, _)) = text.char_indices().nth(char_idx_to_remove) {
-                        text.remove(byte_idx);
-                        *cursor = cursor.saturating_sub(1);
-                        if app_state.filter_dialog_focus == FilterFieldFocus::Source {
-                            app_state.update_filtered_sources();
-                        }
+            KeyCode::Backspace if *cursor > 0 => {
+                let char_idx_to_remove = *cursor - 1;
+                if let Some((byte_idx, _)) = text.char_indices().nth(char_idx_to_remove) {
+                    text.remove(byte_idx);
+                    *cursor = cursor.saturating_sub(1);
+                    if app_state.filter_dialog_focus == FilterFieldFocus::Source {
+                        app_state.update_filtered_sources();
                     }
                 }
             }
             KeyCode::Delete => {
-                if *cursor < text.chars().count() {
-                    if let Some((byte_idx, _)) = text.char_indices().nth(*cursor) {
-                        text.remove(byte_idx);
-                        if app_state.filter_dialog_focus == FilterFieldFocus::Source {
-                            app_state.update_filtered_sources();
-                        }
+                if let Some((byte_idx, _)) = text.char_indices().nth(*cursor) {
+                    text.remove(byte_idx);
+                    if app_state.filter_dialog_focus == FilterFieldFocus::Source {
+                        app_state.update_filtered_sources();
                     }
                 }
             }
@@ -254,8 +1607,7 @@ fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) ->
     match key.code {
         KeyCode::Esc => {
             app_state.is_filter_dialog_visible = false;
-            app_state.filter_event_id_cursor = 0;
-            app_state.filter_source_cursor = 0;
+            app_state.reset_filter_dialog_cursors();
             action = PostKeyPressAction::None;
         }
         KeyCode::Tab => {
@@ -298,8 +1650,15 @@ fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) ->
             FilterFieldFocus::Level => {
                 app_state.filter_dialog_focus = FilterFieldFocus::Time;
             }
-            FilterFieldFocus::Time => {
-                app_state.filter_dialog_focus = FilterFieldFocus::Source;
+            FilterFieldFocus::Time
+            | FilterFieldFocus::Keywords
+            | FilterFieldFocus::Task
+            | FilterFieldFocus::Opcode
+            | FilterFieldFocus::User
+            | FilterFieldFocus::Computer
+            | FilterFieldFocus::ExcludeSources
+            | FilterFieldFocus::Recent => {
+                app_state.filter_dialog_focus = app_state.filter_dialog_focus.next();
             }
             FilterFieldFocus::Apply => {
                 let source_input_trimmed = app_state.filter_dialog_source_input.trim();
@@ -309,37 +1668,93 @@ fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) ->
                     Some(source_input_trimmed.to_string())
                 };
                 let event_id_trimmed = app_state.filter_dialog_event_id.trim();
+                if let Err(msg) = helpers::parse_event_id_spec(event_id_trimmed) {
+                    app_state.filter_dialog_focus = FilterFieldFocus::EventId;
+                    app_state.show_error("Invalid Event ID", &msg);
+                    return PostKeyPressAction::None;
+                }
                 let selected_event_id = if event_id_trimmed.is_empty() {
                     None
                 } else {
                     Some(event_id_trimmed.to_string())
                 };
+                let task_trimmed = app_state.filter_dialog_task.trim();
+                let selected_task = if task_trimmed.is_empty() {
+                    None
+                } else if let Ok(task) = task_trimmed.parse::<u16>() {
+                    Some(task)
+                } else {
+                    app_state.filter_dialog_focus = FilterFieldFocus::Task;
+                    app_state.show_error("Invalid Task", "Task Category must be 0-65535.");
+                    return PostKeyPressAction::None;
+                };
+                let opcode_trimmed = app_state.filter_dialog_opcode.trim();
+                let selected_opcode = if opcode_trimmed.is_empty() {
+                    None
+                } else if let Ok(opcode) = opcode_trimmed.parse::<u8>() {
+                    Some(opcode)
+                } else {
+                    app_state.filter_dialog_focus = FilterFieldFocus::Opcode;
+                    app_state.show_error("Invalid Opcode", "Opcode must be 0-255.");
+                    return PostKeyPressAction::None;
+                };
+
+                let user_trimmed = app_state.filter_dialog_user.trim();
+                let selected_user = if user_trimmed.is_empty() {
+                    None
+                } else if user_trimmed.to_uppercase().starts_with("S-1-") {
+                    Some(user_trimmed.to_uppercase())
+                } else {
+                    app_state.filter_dialog_focus = FilterFieldFocus::User;
+                    app_state.show_error(
+                        "Invalid User SID",
+                        "User must be a SID such as S-1-5-21-...-1001.",
+                    );
+                    return PostKeyPressAction::None;
+                };
+                let computer_trimmed = app_state.filter_dialog_computer.trim();
+                let selected_computer = if computer_trimmed.is_empty() {
+                    None
+                } else {
+                    Some(computer_trimmed.to_string())
+                };
+                let excluded_sources: Vec<String> = app_state
+                    .filter_dialog_excluded_sources
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|source| !source.is_empty())
+                    .map(str::to_string)
+                    .collect();
 
                 let criteria = crate::models::FilterCriteria {
                     source: selected_source,
                     event_id: selected_event_id,
                     level: app_state.filter_dialog_level,
                     time_filter: app_state.filter_dialog_time,
+                    keywords: app_state.filter_dialog_keywords,
+                    task: selected_task,
+                    opcode: selected_opcode,
+                    user_sid: selected_user,
+                    computer: selected_computer,
+                    excluded_sources,
                 };
-                if criteria.source.is_none()
-                    && criteria.event_id.is_none()
-                    && criteria.level == crate::models::EventLevelFilter::All
-                    && criteria.time_filter == crate::models::TimeFilterOption::AnyTime
-                {
+                if criteria.is_empty() {
                     app_state.active_filter = None;
                 } else {
                     app_state.active_filter = Some(criteria);
                 }
+                app_state.config.last_filter = app_state.active_filter.clone();
+                app_state.raw_query = None;
                 app_state.is_filter_dialog_visible = false;
-                app_state.filter_event_id_cursor = 0;
-                app_state.filter_source_cursor = 0;
+                app_state.reset_filter_dialog_cursors();
                 perform_reload = true;
             }
             FilterFieldFocus::Clear => {
                 app_state.active_filter = None;
+                app_state.config.last_filter = None;
+                app_state.raw_query = None;
                 app_state.is_filter_dialog_visible = false;
-                app_state.filter_event_id_cursor = 0;
-                app_state.filter_source_cursor = 0;
+                app_state.reset_filter_dialog_cursors();
                 perform_reload = true;
             }
         },
@@ -350,6 +1765,10 @@ fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) ->
             FilterFieldFocus::Time => {
                 app_state.filter_dialog_time = app_state.filter_dialog_time.previous();
             }
+            FilterFieldFocus::Keywords => {
+                app_state.filter_dialog_keywords = app_state.filter_dialog_keywords.previous();
+            }
+            FilterFieldFocus::Recent => app_state.cycle_recent_filter(false),
             FilterFieldFocus::Apply | FilterFieldFocus::Clear => {
                 app_state.filter_dialog_focus = app_state.filter_dialog_focus.previous();
             }
@@ -362,59 +1781,57 @@ fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) ->
             FilterFieldFocus::Time => {
                 app_state.filter_dialog_time = app_state.filter_dialog_time.next();
             }
+            FilterFieldFocus::Keywords => {
+                app_state.filter_dialog_keywords = app_state.filter_dialog_keywords.next();
+            }
+            FilterFieldFocus::Recent => app_state.cycle_recent_filter(true),
             FilterFieldFocus::Apply | FilterFieldFocus::Clear => {
                 app_state.filter_dialog_focus = app_state.filter_dialog_focus.next();
             }
             _ => {}
         },
-        KeyCode::Up => match app_state.filter_dialog_focus {
-            FilterFieldFocus::Source => {
-                if !app_state.filter_dialog_filtered_sources.is_empty() {
-                    let count = app_state.filter_dialog_filtered_sources.len();
-                    let current_pos = app_state
-                        .filter_dialog_filtered_source_selection
-                        .unwrap_or(0);
-                    let new_pos = if current_pos == 0 {
-                        count - 1
-                    } else {
-                        current_pos - 1
-                    };
-                    app_state.filter_dialog_filtered_source_selection = Some(new_pos);
-                    if let Some((idx, name)) = app_state.filter_dialog_filtered_sources.get(new_pos)
-                    {
-                        app_state.filter_dialog_source_input = name.clone();
-                        app_state.filter_dialog_source_index = *idx;
-                        app_state.filter_source_cursor =
-                            app_state.filter_dialog_source_input.chars().count();
-                    }
-                }
+        KeyCode::Up
+            if app_state.filter_dialog_focus == FilterFieldFocus::Source
+                && !app_state.filter_dialog_filtered_sources.is_empty() =>
+        {
+            let count = app_state.filter_dialog_filtered_sources.len();
+            let current_pos = app_state
+                .filter_dialog_filtered_source_selection
+                .unwrap_or(0);
+            let new_pos = if current_pos == 0 {
+                count - 1
+            } else {
+                current_pos - 1
+            };
+            app_state.filter_dialog_filtered_source_selection = Some(new_pos);
+            if let Some((idx, name)) = app_state.filter_dialog_filtered_sources.get(new_pos) {
+                app_state.filter_dialog_source_input = name.clone();
+                app_state.filter_dialog_source_index = *idx;
+                app_state.filter_source_cursor =
+                    app_state.filter_dialog_source_input.chars().count();
             }
-            _ => {}
-        },
-        KeyCode::Down => match app_state.filter_dialog_focus {
-            FilterFieldFocus::Source => {
-                if !app_state.filter_dialog_filtered_sources.is_empty() {
-                    let count = app_state.filter_dialog_filtered_sources.len();
-                    let current_pos = app_state
-                        .filter_dialog_filtered_source_selection
-                        .unwrap_or(0);
-                    let new_pos = if current_pos >= count - 1 {
-                        0
-                    } else {
-                        current_pos + 1
-                    };
-                    app_state.filter_dialog_filtered_source_selection = Some(new_pos);
-                    if let Some((idx, name)) = app_state.filter_dialog_filtered_sources.get(new_pos)
-                    {
-                        app_state.filter_dialog_source_input = name.clone();
-                        app_state.filter_dialog_source_index = *idx;
-                        app_state.filter_source_cursor =
-                            app_state.filter_dialog_source_input.chars().count();
-                    }
-                }
+        }
+        KeyCode::Down
+            if app_state.filter_dialog_focus == FilterFieldFocus::Source
+                && !app_state.filter_dialog_filtered_sources.is_empty() =>
+        {
+            let count = app_state.filter_dialog_filtered_sources.len();
+            let current_pos = app_state
+                .filter_dialog_filtered_source_selection
+                .unwrap_or(0);
+            let new_pos = if current_pos >= count - 1 {
+                0
+            } else {
+                current_pos + 1
+            };
+            app_state.filter_dialog_filtered_source_selection = Some(new_pos);
+            if let Some((idx, name)) = app_state.filter_dialog_filtered_sources.get(new_pos) {
+                app_state.filter_dialog_source_input = name.clone();
+                app_state.filter_dialog_source_index = *idx;
+                app_state.filter_source_cursor =
+                    app_state.filter_dialog_source_input.chars().count();
             }
-            _ => {}
-        },
+        }
         _ => {}
     }
 
@@ -426,30 +1843,339 @@ fn handle_filter_dialog_keys(key: event::KeyEvent, app_state: &mut AppState) ->
 }
 
 fn handle_events_panel_keys(key: event::KeyEvent, app_state: &mut AppState) -> PostKeyPressAction {
-    match key.code {
+    if let Some(mark_key) = app_state.pending_mark.take() {
+        let KeyCode::Char(name) = key.code else {
+            return PostKeyPressAction::None;
+        };
+        let result = match mark_key {
+            MarkKey::Set => app_state.set_mark(name),
+            MarkKey::Jump => app_state.jump_to_mark(name),
+        };
+        if let Err(msg) = result {
+            return PostKeyPressAction::ShowConfirmation("Marks".to_string(), msg);
+        }
+        return PostKeyPressAction::None;
+    }
+    let count = app_state.key_count.take();
+    if let KeyCode::Char(digit @ '0'..='9') = key.code
+        && app_state.config.vim_keys
+        && (digit != '0' || count.is_some())
+    {
+        let digit = digit.to_digit(10).unwrap_or_default() as usize;
+        app_state.key_count = Some(count.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+        return PostKeyPressAction::None;
+    }
+    let rows = count.unwrap_or(1) as isize;
+    let vim_keys = app_state.config.vim_keys;
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    // With vim keys, j and k move like the arrow keys; Alt+j and Alt+k keep their own actions.
+    let code = match key.code {
+        KeyCode::Char('j') if vim_keys && !key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyCode::Down
+        }
+        KeyCode::Char('k') if vim_keys && !key.modifiers.contains(KeyModifiers::ALT) => KeyCode::Up,
+        code => code,
+    };
+    match code {
+        KeyCode::Down if count.is_some() => app_state.move_selection(rows),
+        KeyCode::Up if count.is_some() => app_state.move_selection(-rows),
         KeyCode::Down => app_state.scroll_down(),
         KeyCode::Up => app_state.scroll_up(),
+        KeyCode::Char('d') if vim_keys && control => {
+            app_state.move_selection(rows * app_state.half_page() as isize);
+        }
+        KeyCode::Char('u') if vim_keys && control => {
+            app_state.move_selection(-rows * app_state.half_page() as isize);
+        }
         KeyCode::PageDown => app_state.page_down(),
         KeyCode::PageUp => app_state.page_up(),
+        KeyCode::Char('g' | 'G') if count.is_some() => app_state.go_to_row(rows as usize),
         KeyCode::Home | KeyCode::Char('g') => app_state.go_to_top(),
         KeyCode::End | KeyCode::Char('G') => app_state.go_to_bottom(),
+        KeyCode::Char('\'') if vim_keys => app_state.pending_mark = Some(MarkKey::Set),
+        KeyCode::Char('`') if vim_keys => app_state.pending_mark = Some(MarkKey::Jump),
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app_state.open_session_prompt(PromptKind::SaveSession);
+        }
+        KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app_state.open_session_prompt(PromptKind::RestoreSession);
+        }
         KeyCode::Char('s') => {
             app_state.sort_descending = !app_state.sort_descending;
             return PostKeyPressAction::ReloadData;
         }
+        KeyCode::Char('o') => {
+            app_state.sort_key = app_state.sort_key.next();
+            if app_state.sort_key == SortKey::DateTime {
+                // Date and time order comes from the query, so fetch the events again.
+                return PostKeyPressAction::ReloadData;
+            }
+            app_state.sort_events();
+            app_state.go_to_top();
+        }
+        KeyCode::Char('i') => app_state.open_incident_window_prompt(),
+        KeyCode::Char('A') => {
+            if let Err(msg) = app_state.open_incident_marker_prompt() {
+                return PostKeyPressAction::ShowConfirmation("Incident Mode".to_string(), msg);
+            }
+        }
+        KeyCode::Char('V') => app_state.open_view_token_prompt(),
+        KeyCode::Char('J') => app_state.open_go_to_record_prompt(),
+        KeyCode::Char('N') => app_state.open_host_picker(),
+        KeyCode::Char('W') => match app_state.toggle_web_logs() {
+            Ok(msg) => return PostKeyPressAction::ShowConfirmation("Web Logs".to_string(), msg),
+            Err(msg) => app_state.show_error("Web Logs", &msg),
+        },
+        KeyCode::Char('H') => {
+            #[cfg(target_os = "windows")]
+            {
+                app_state.hyperv_view = Some(app_state.load_hyperv_view());
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Hyper-V Guests".to_string(),
+                "Reading the Hyper-V logs requires the Windows Event Log service.".to_string(),
+            );
+        }
+        KeyCode::Char('U') => {
+            #[cfg(target_os = "windows")]
+            {
+                app_state.installer_view = Some(app_state.load_installer_view());
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Installer Transactions".to_string(),
+                "Reading the installer events requires the Windows Event Log service.".to_string(),
+            );
+        }
+        KeyCode::Char('P') => {
+            #[cfg(target_os = "windows")]
+            {
+                app_state.print_view = Some(app_state.load_print_view());
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Print Jobs".to_string(),
+                "Reading the PrintService logs requires the Windows Event Log service.".to_string(),
+            );
+        }
+        KeyCode::Char('u') => {
+            #[cfg(target_os = "windows")]
+            {
+                app_state.device_view = Some(app_state.load_device_view());
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Device Activity".to_string(),
+                "Reading the device logs requires the Windows Event Log service.".to_string(),
+            );
+        }
+        KeyCode::Char('a') => app_state.open_sigma_view(),
+        KeyCode::Char('k') => app_state.open_comparison(ComparisonPreset::default()),
+        KeyCode::Char('|') => app_state.toggle_split(),
+        KeyCode::Char('j') => {
+            if let Err(msg) = app_state.sync_split_pane_time() {
+                app_state.show_error("Sync Time", &msg);
+            }
+        }
+        KeyCode::Char('\\') if app_state.switch_split_pane() => {
+            return PostKeyPressAction::ReloadData;
+        }
+        KeyCode::Char('z') => return forward_to_syslog(app_state, false),
+        KeyCode::Char('Q') => {
+            #[cfg(target_os = "windows")]
+            {
+                app_state.wmi_analysis = Some(app_state.load_wmi_analysis());
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "WMI Failures".to_string(),
+                "Reading the WMI-Activity log requires the Windows Event Log service.".to_string(),
+            );
+        }
+        KeyCode::Char('Y') => {
+            if let Err(msg) = app_state.open_audit_policy() {
+                return PostKeyPressAction::ShowConfirmation("Audit Policy".to_string(), msg);
+            }
+        }
+        KeyCode::Char('Z') => {
+            #[cfg(target_os = "windows")]
+            {
+                app_state.servicing_view = Some(app_state.load_servicing_view());
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Update Failures".to_string(),
+                "Reading the Setup and System logs requires the Windows Event Log service."
+                    .to_string(),
+            );
+        }
+        KeyCode::Char('t') => {
+            #[cfg(target_os = "windows")]
+            match app_state.load_auth_flow() {
+                Ok(view) => app_state.auth_flow = Some(view),
+                Err(msg) => {
+                    return PostKeyPressAction::ShowConfirmation(
+                        "Authentication Flow".to_string(),
+                        msg,
+                    );
+                }
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Authentication Flow".to_string(),
+                "Reading the Security log requires the Windows Event Log service.".to_string(),
+            );
+        }
+        KeyCode::Char('h') => {
+            #[cfg(target_os = "windows")]
+            {
+                let mut view = app_state.load_object_access();
+                // Start from the selected audit's object, if one is selected.
+                if let Some(access) = app_state.selected_event().and_then(ObjectAccess::of) {
+                    view.prefix = access.path;
+                    view.prefix_cursor = view.prefix.chars().count();
+                }
+                app_state.object_access = Some(view);
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "Object Access".to_string(),
+                "Reading the Security log requires the Windows Event Log service.".to_string(),
+            );
+        }
+        KeyCode::Char('D') => {
+            #[cfg(target_os = "windows")]
+            {
+                app_state.dns_analysis = Some(app_state.load_dns_analysis());
+            }
+            #[cfg(not(target_os = "windows"))]
+            return PostKeyPressAction::ShowConfirmation(
+                "DNS Failures".to_string(),
+                "Reading the DNS client logs requires the Windows Event Log service.".to_string(),
+            );
+        }
+        KeyCode::Char('y') => return copy_selected_event(app_state, false),
+        KeyCode::Char('c') => return copy_selected_event(app_state, true),
+        KeyCode::Char('d') => {
+            if let Err(msg) = app_state.open_detail_view() {
+                return PostKeyPressAction::ShowConfirmation("No Selection".to_string(), msg);
+            }
+        }
+        KeyCode::Char('F') => {
+            if let Err(msg) = app_state.filter_on_blocked_binary() {
+                return PostKeyPressAction::ShowConfirmation(
+                    "Application Control".to_string(),
+                    msg,
+                );
+            }
+        }
+        KeyCode::Char('w') => return explain_missing_command_line(app_state),
+        KeyCode::Char('S') => return run_event_action(app_state, EventAction::FilterSource),
+        KeyCode::Char('I') => return run_event_action(app_state, EventAction::FilterEventId),
+        KeyCode::Char('X') => return run_event_action(app_state, EventAction::FilterProcess),
+        KeyCode::Char('r') => return run_event_action(app_state, EventAction::ShowRelated),
+        KeyCode::Char(' ') | KeyCode::Char('m') => app_state.context_menu_selection = Some(0),
+        KeyCode::Char('L') => {
+            if let Err(msg) = app_state.open_admin_menu() {
+                return PostKeyPressAction::ShowConfirmation("Log Administration".to_string(), msg);
+            }
+        }
+        KeyCode::Char('b') => {
+            if let Err(msg) = app_state.toggle_bookmark() {
+                return PostKeyPressAction::ShowConfirmation("Bookmarks".to_string(), msg);
+            }
+        }
+        KeyCode::Char('B') => {
+            app_state.bookmarks_selection = 0;
+            app_state.is_bookmarks_dialog_visible = true;
+        }
+        KeyCode::Char('K') => {
+            app_state.column_dialog_selection = 0;
+            app_state.is_column_dialog_visible = true;
+        }
         KeyCode::Char('l') => {
             app_state.update_level_filter();
             return PostKeyPressAction::ReloadData;
         }
+        KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app_state.local_filter_cursor = app_state.local_filter.chars().count();
+            app_state.is_local_filter_input = true;
+        }
         KeyCode::Char('f') => {
             return PostKeyPressAction::OpenFilterDialog;
         }
-        KeyCode::Char('/') => {
-            if let Some(last_search) = &app_state.last_search_term {
-                app_state.search_term = last_search.clone();
+        KeyCode::Char('C') => {
+            let path = catalog::catalog_path_for(&app_state.selected_log_name);
+            return run_or_preview(app_state, PendingAction::ExportCatalog(path));
+        }
+        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let path = app_state.export_path("-matches");
+            return run_or_preview(
+                app_state,
+                PendingAction::ExportMatches {
+                    path,
+                    scan_whole_log: true,
+                },
+            );
+        }
+        KeyCode::Char('e') => {
+            let path = app_state.export_path("");
+            return run_or_preview(app_state, PendingAction::ExportEvents(path));
+        }
+        KeyCode::Char('M') => {
+            let path = app_state.export_path("-matches");
+            return run_or_preview(
+                app_state,
+                PendingAction::ExportMatches {
+                    path,
+                    scan_whole_log: false,
+                },
+            );
+        }
+        KeyCode::Char('E') => {
+            app_state.export_format = app_state.export_format.next();
+            return PostKeyPressAction::ShowConfirmation(
+                "Export Format".to_string(),
+                format!(
+                    "Loaded events will be exported as {}.",
+                    app_state.export_format.display_name()
+                ),
+            );
+        }
+        KeyCode::Char('O') => {
+            if let Err(msg) = app_state.start_otlp_export() {
+                return PostKeyPressAction::ShowConfirmation("OTLP Export Failed".to_string(), msg);
             }
-            app_state.is_searching = true;
         }
+        KeyCode::Char('R') => {
+            app_state.config.redact_exports = !app_state.config.redact_exports;
+            let message = if app_state.config.redact_exports {
+                let rules = app_state
+                    .config
+                    .redaction_rules
+                    .iter()
+                    .map(|rule| rule.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "Exports and saved events are now redacted.\n\nRules: {}, plus host and account names found in the events.",
+                    rules
+                )
+            } else {
+                "Exports and saved events are no longer redacted.".to_string()
+            };
+            return PostKeyPressAction::ShowConfirmation("Redaction".to_string(), message);
+        }
+        KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            return copy_selected_xpath(app_state);
+        }
+        KeyCode::Char('x') => {
+            app_state.query_input = app_state.raw_query.clone().unwrap_or_default();
+            app_state.query_cursor = app_state.query_input.chars().count();
+            app_state.is_query_input_visible = true;
+        }
+        KeyCode::Char('/') => app_state.begin_search(),
         KeyCode::Char('n') => match app_state.find_next_match() {
             Ok(_) => {}
             Err(msg) => {
@@ -479,86 +2205,58 @@ fn handle_preview_panel_keys(key: event::KeyEvent, app_state: &mut AppState) ->
         KeyCode::Esc | KeyCode::Left => {
             app_state.focus = PanelFocus::Events;
         }
+        KeyCode::Char('y') => return copy_selected_event(app_state, false),
+        KeyCode::Char('c') => return copy_selected_event(app_state, true),
+        KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            return copy_selected_xpath(app_state);
+        }
+        KeyCode::Enter | KeyCode::Char('d') => {
+            if let Err(msg) = app_state.open_detail_view() {
+                return PostKeyPressAction::ShowConfirmation("No Selection".to_string(), msg);
+            }
+        }
         KeyCode::Char('v') => {
             app_state.preview_view_mode = match app_state.preview_view_mode {
                 PreviewViewMode::Formatted => PreviewViewMode::RawXml,
                 PreviewViewMode::RawXml => PreviewViewMode::Formatted,
             };
             app_state.preview_scroll = 0;
+            app_state.preview_hscroll = 0;
         }
+        KeyCode::Char('w') => app_state.toggle_preview_wrap(),
         KeyCode::Char('s') => {
-            if let (Some(raw_xml), Some(event_id)) = (
-                &app_state.preview_raw_xml,
-                app_state
-                    .table_state
-                    .selected()
-                    .and_then(|idx| app_state.events.get(idx)),
-            ) {
-                let xml_content = raw_xml.clone();
-                let filename = format!(
-                    "{}-{}-[{}]-{}.xml",
-                    helpers::sanitize_filename(&app_state.selected_log_name),
-                    event_id.datetime.replace(':', "-").replace(' ', "_"),
-                    helpers::sanitize_filename(&event_id.id),
-                    helpers::sanitize_filename(&event_id.source)
-                );
-
-                match helpers::pretty_print_xml(&xml_content) {
-                    Ok(pretty_xml) => match fs::write(&filename, &pretty_xml) {
-                        Ok(_) => {
-                            return PostKeyPressAction::ShowConfirmation(
-                                "Save Successful".to_string(),
-                                format!("Event saved to:\n\n{}", filename),
-                            );
-                        }
-                        Err(e) => {
-                            let err_msg = format!("Failed to save event to {}: {}", filename, e);
-                            app_state.log(&format!("Save error: {}", e));
-                            return PostKeyPressAction::ShowConfirmation(
-                                "Save Failed".to_string(),
-                                err_msg,
-                            );
-                        }
-                    },
-                    Err(e) => {
-                        app_state.log(&format!(
-                            "Failed to pretty print XML for saving ({}). Saving raw.",
-                            e
-                        ));
-                        match fs::write(&filename, &xml_content) {
-                            Ok(_) => {
-                                return PostKeyPressAction::ShowConfirmation(
-                                    "Save Successful (Raw)".to_string(),
-                                    format!("Event saved (raw XML) to:\\n{}", filename),
-                                );
-                            }
-                            Err(e) => {
-                                let err_msg =
-                                    format!("Failed to save raw event to {}: {}", filename, e);
-                                app_state.log(&format!("Raw save error: {}", e));
-                                return PostKeyPressAction::ShowConfirmation(
-                                    "Save Failed".to_string(),
-                                    err_msg,
-                                );
-                            }
-                        }
-                    }
-                }
-            } else {
+            let Some(event) = app_state.selected_event() else {
                 return PostKeyPressAction::ShowConfirmation(
                     "Save Failed".to_string(),
                     "No event selected or raw XML data unavailable to save.".to_string(),
                 );
+            };
+            let path = PathBuf::from(format!(
+                "{}-{}-[{}]-{}.xml",
+                helpers::sanitize_filename(&app_state.selected_log_name),
+                event.local_time().replace(':', "-").replace(' ', "_"),
+                event.event_id,
+                helpers::sanitize_filename(&event.source)
+            ));
+            return run_or_preview(app_state, PendingAction::SaveEvent(path));
+        }
+        KeyCode::Char('F') => {
+            if let Err(msg) = app_state.filter_on_blocked_binary() {
+                return PostKeyPressAction::ShowConfirmation(
+                    "Application Control".to_string(),
+                    msg,
+                );
             }
         }
+        KeyCode::Char('X') => return run_event_action(app_state, EventAction::FilterProcess),
+        KeyCode::Char('r') => return run_event_action(app_state, EventAction::ShowRelated),
+        KeyCode::Char(' ') | KeyCode::Char('m') => app_state.context_menu_selection = Some(0),
         KeyCode::Down => app_state.preview_scroll_down(1),
         KeyCode::Up => app_state.preview_scroll_up(1),
         KeyCode::PageDown => app_state.preview_scroll_down(10),
         KeyCode::PageUp => app_state.preview_scroll_up(10),
         KeyCode::Home | KeyCode::Char('g') => app_state.preview_go_to_top(),
-        KeyCode::End | KeyCode::Char('G') => {
-            app_state.preview_scroll_down(u16::MAX);
-        }
+        KeyCode::End | KeyCode::Char('G') => app_state.preview_scroll_to_bottom(),
         _ => {}
     }
     PostKeyPressAction::None
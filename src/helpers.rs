@@ -9,6 +9,186 @@ pub fn sanitize_filename(filename: &str) -> String {
         .collect()
 }
 
+/// Whether a log tab names a saved `.evtx` file rather than a channel.
+pub fn is_saved_log(name: &str) -> bool {
+    std::path::Path::new(name)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("evtx"))
+}
+
+/// Returns the local computer name, falling back to "localhost".
+pub fn local_host_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "localhost".to_string())
+}
+
+/// Documentation page for an event: the reference page for Security audit events, a search
+/// of Microsoft Learn for the others.
+pub fn event_docs_url(provider: &str, event_id: u32) -> String {
+    if provider == "Microsoft-Windows-Security-Auditing" {
+        return format!(
+            "https://learn.microsoft.com/en-us/previous-versions/windows/it-pro/windows-10/security/threat-protection/auditing/event-{}",
+            event_id
+        );
+    }
+    let terms: String = format!("{} event {}", provider, event_id)
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => {
+                (byte as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect();
+    format!("https://learn.microsoft.com/en-us/search/?terms={}", terms)
+}
+
+/// Opens `url` in the default browser.
+pub fn open_url(url: &str) -> Result<(), String> {
+    let (program, args): (&str, &[&str]) = if cfg!(target_os = "windows") {
+        ("rundll32", &["url.dll,FileProtocolHandler"])
+    } else if cfg!(target_os = "macos") {
+        ("open", &[])
+    } else {
+        ("xdg-open", &[])
+    };
+    std::process::Command::new(program)
+        .args(args)
+        .arg(url)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open '{}': {}", url, e))
+}
+
+/// The system's text for a Win32 error code, from FormatMessage.
+#[cfg(target_os = "windows")]
+pub fn system_error_message(code: u32) -> Option<String> {
+    let message = windows::core::HRESULT::from_win32(code).message();
+    Some(message.trim().to_string()).filter(|message| !message.is_empty())
+}
+
+/// Without the Win32 API there is no system message table to read.
+#[cfg(not(target_os = "windows"))]
+pub fn system_error_message(_code: u32) -> Option<String> {
+    None
+}
+
+/// Escapes quotes in a value embedded in a single-quoted XPath literal.
+pub fn escape_xpath_literal(value: &str) -> String {
+    value.replace('\'', "&apos;").replace('"', "&quot;")
+}
+
+/// Extracts the `SystemTime` attribute of `TimeCreated` from raw event XML.
+#[cfg(target_os = "windows")]
+pub fn event_system_time(raw_xml: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    let start = raw_xml.find("SystemTime=")? + "SystemTime=".len();
+    let quote = raw_xml[start..].chars().next()?;
+    let value_start = start + quote.len_utf8();
+    let value_len = raw_xml[value_start..].find(quote)?;
+    chrono::DateTime::parse_from_rfc3339(&raw_xml[value_start..value_start + value_len]).ok()
+}
+
+/// Reduces an event message to its first non-empty line with whitespace collapsed, the part
+/// worth showing in a single table cell.
+pub fn message_summary(message: &str) -> String {
+    message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Shortens text to `max_chars`, breaking at a word boundary when one is close enough and
+/// marking the cut with an ellipsis.
+pub fn truncate_at_word(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+    let kept: String = text.chars().take(max_chars - 1).collect();
+    let cut = match kept.rfind(' ') {
+        // Only break at the space if that keeps most of the available width.
+        Some(space) if kept[..space].chars().count() * 3 >= (max_chars - 1) * 2 => {
+            kept[..space].trim_end_matches([',', ';', ':', '.', '-'])
+        }
+        _ => kept.as_str(),
+    };
+    format!("{}…", cut)
+}
+
+/// Formats a UTC time in the local time zone, e.g. "2024-05-01 13:45:00".
+pub fn format_local_time(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+/// Formats an event's creation time like `format_local_time`, or "Unknown" when it has none.
+pub fn format_event_time(time: Option<chrono::DateTime<chrono::Utc>>) -> String {
+    time.map_or_else(|| "Unknown".to_string(), format_local_time)
+}
+
+/// Parses a local time written as "YYYY-MM-DD HH:MM[:SS]".
+pub fn parse_local_time(input: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    let input = input.trim();
+    let naive = chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M"))
+        .map_err(|_| format!("'{}' is not a time like 2024-05-01 13:45:00.", input))?;
+    naive
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|time| time.with_timezone(&chrono::Utc))
+        .ok_or_else(|| format!("'{}' does not exist in the local time zone.", input))
+}
+
+/// Parses a time window written as "<start> .. <end>" in local time.
+pub fn parse_time_window(
+    input: &str,
+) -> Result<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>), String> {
+    let (start, end) = input
+        .split_once("..")
+        .ok_or("Separate the start and end of the window with '..'.")?;
+    let (start, end) = (parse_local_time(start)?, parse_local_time(end)?);
+    if start >= end {
+        return Err("The window must end after it starts.".to_string());
+    }
+    Ok((start, end))
+}
+
+/// Formats a duration as a short approximate span, e.g. "3d 4h", "5h 12m" or "12m".
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, mins) = (minutes / 1440, (minutes % 1440) / 60, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else {
+        format!("{}m", mins)
+    }
+}
+
+/// Returns which of `buckets` equal slices of `start..=end` holds `time`, or None outside it.
+pub fn time_bucket(
+    time: chrono::DateTime<chrono::Utc>,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+    buckets: usize,
+) -> Option<usize> {
+    if buckets == 0 || !(start..=end).contains(&time) {
+        return None;
+    }
+    let span_ms = (end - start).num_milliseconds().max(1);
+    Some(((time - start).num_milliseconds() * (buckets as i64 - 1) / span_ms) as usize)
+}
+
 /// Formats an XML string with indentation and returns the formatted XML or an error message.
 pub fn pretty_print_xml(xml_str: &str) -> Result<String, String> {
     let mut reader = Reader::from_str(xml_str);
@@ -76,3 +256,52 @@ pub fn centered_fixed_rect(
     let y = r.y + r.height.saturating_sub(height) / 2;
     Rect::new(x, y, width.min(r.width), height.min(r.height))
 }
+
+/// Inclusive (start, end) Event ID ranges parsed from a filter string.
+#[derive(Debug, Default)]
+pub struct EventIdSpec {
+    pub include: Vec<(u32, u32)>,
+    pub exclude: Vec<(u32, u32)>,
+}
+
+impl EventIdSpec {
+    /// Whether an Event ID is in an included range, or in none when only exclusions are
+    /// given, and in no excluded one.
+    pub fn matches(&self, event_id: u32) -> bool {
+        let in_ranges = |ranges: &[(u32, u32)]| {
+            ranges
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&event_id))
+        };
+        (self.include.is_empty() || in_ranges(&self.include)) && !in_ranges(&self.exclude)
+    }
+}
+
+/// Parses an Event ID filter such as "4624,4625,4700-4799,-4688" into ranges to include
+/// and to exclude. Exclusions are prefixed with '-'.
+pub fn parse_event_id_spec(spec: &str) -> Result<EventIdSpec, String> {
+    let mut parsed = EventIdSpec::default();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (target, body) = match part.strip_prefix('-') {
+            Some(rest) => (&mut parsed.exclude, rest.trim()),
+            None => (&mut parsed.include, part),
+        };
+        let parse_id = |s: &str| {
+            s.trim()
+                .parse::<u32>()
+                .map_err(|_| format!("'{}' is not a valid Event ID.", part))
+        };
+        let range = match body.split_once('-') {
+            Some((start, end)) => (parse_id(start)?, parse_id(end)?),
+            None => {
+                let id = parse_id(body)?;
+                (id, id)
+            }
+        };
+        if range.0 > range.1 {
+            return Err(format!("Range '{}' has its start after its end.", part));
+        }
+        target.push(range);
+    }
+    Ok(parsed)
+}
@@ -0,0 +1,60 @@
+use crate::models::FilterCriteria;
+
+/// Most filters kept to go back through.
+const MAX_HISTORY: usize = 50;
+/// Most filters offered in the filter dialog's recent list.
+pub const MAX_RECENT_FILTERS: usize = 10;
+
+/// Filters the event list was loaded with, oldest first, to go back and forward through like
+/// a browser's history. `None` stands for the unfiltered list.
+#[derive(Debug, Default)]
+pub struct FilterHistory {
+    entries: Vec<Option<FilterCriteria>>,
+    position: usize,
+}
+
+impl FilterHistory {
+    /// Records the filter the list was just loaded with, unless it is the current entry.
+    /// Filters gone back past are dropped, as a browser does after going back.
+    pub fn record(&mut self, filter: Option<&FilterCriteria>) {
+        if self.entries.get(self.position).map(Option::as_ref) == Some(filter) {
+            return;
+        }
+        self.entries.truncate(self.position + 1);
+        self.entries.push(filter.cloned());
+        if self.entries.len() > MAX_HISTORY {
+            self.entries.remove(0);
+        }
+        self.position = self.entries.len() - 1;
+    }
+
+    /// Steps back to the filter before the current one, if any.
+    pub fn back(&mut self) -> Option<Option<FilterCriteria>> {
+        self.position = self.position.checked_sub(1)?;
+        self.entries.get(self.position).cloned()
+    }
+
+    /// Steps forward to the filter gone back from, if any.
+    pub fn forward(&mut self) -> Option<Option<FilterCriteria>> {
+        if self.position + 1 >= self.entries.len() {
+            return None;
+        }
+        self.position += 1;
+        self.entries.get(self.position).cloned()
+    }
+
+    /// Position of the current filter as (1-based index, count), for the footer.
+    pub fn position(&self) -> (usize, usize) {
+        (self.position + 1, self.entries.len())
+    }
+}
+
+/// Moves the filter to the front of the recent filters, adding it when it is not among them.
+pub fn remember_recent(recent: &mut Vec<FilterCriteria>, filter: &FilterCriteria) {
+    if filter.is_empty() {
+        return;
+    }
+    recent.retain(|other| other != filter);
+    recent.insert(0, filter.clone());
+    recent.truncate(MAX_RECENT_FILTERS);
+}
@@ -0,0 +1,294 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+const HOSTS_FILE_NAME: &str = "hosts.toml";
+
+/// Prefix of the generic credentials saved from the host picker in Windows Credential
+/// Manager, followed by the host name, or by `@` and the group name.
+pub const SAVED_CREDENTIAL_PREFIX: &str = "event_commander:";
+
+/// Machines the remote features read from, in named groups.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HostInventory {
+    pub groups: Vec<HostGroup>,
+    /// Target names, lowercase, of the credentials saved from the host picker.
+    #[serde(skip)]
+    pub saved_credentials: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HostGroup {
+    pub name: String,
+    /// Credential used for the group's hosts that do not name their own.
+    pub credential: Option<String>,
+    /// User name for the group's hosts that do not set their own.
+    pub user: Option<String>,
+    /// Authentication package for the group's hosts that do not set their own.
+    pub auth: Option<RemoteAuth>,
+    pub hosts: Vec<Host>,
+}
+
+/// A machine, by DNS name or address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Host {
+    pub name: String,
+    /// Target name of a generic credential in Windows Credential Manager, as saved with
+    /// `cmdkey /generic:<name>`. Without one, a credential saved from the host picker is
+    /// used, and else the current user's credentials.
+    #[serde(default)]
+    pub credential: Option<String>,
+    /// User name to log on with instead of the credential's, as `DOMAIN\user` or
+    /// `user@domain`. The password is still taken from the credential.
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Authentication package to force: `negotiate`, `kerberos` or `ntlm`.
+    #[serde(default)]
+    pub auth: Option<RemoteAuth>,
+}
+
+/// Authentication package of a remote event log session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteAuth {
+    /// Left to the RPC runtime.
+    #[default]
+    Default,
+    Negotiate,
+    Kerberos,
+    Ntlm,
+}
+
+impl RemoteAuth {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Negotiate => "negotiate",
+            Self::Kerberos => "kerberos",
+            Self::Ntlm => "ntlm",
+        }
+    }
+}
+
+/// How to log on to a host's event log service. Hosts are only reached on Windows.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub struct Login {
+    pub credential: Option<String>,
+    pub user: Option<String>,
+    pub auth: RemoteAuth,
+}
+
+/// Whether a host's event log service could be reached, as last checked or read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub enum HostStatus {
+    Checking,
+    Reachable,
+    Unreachable(String),
+}
+
+/// Target name of the credential saved from the host picker for a host, or for a group when
+/// `name` is `@` and the group name.
+pub fn saved_credential_target(name: &str) -> String {
+    format!("{}{}", SAVED_CREDENTIAL_PREFIX, name.to_lowercase())
+}
+
+/// A line of the host picker: a group, or one of its hosts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerRow {
+    Group(usize),
+    Host(usize, usize),
+}
+
+/// State of the host picker.
+#[derive(Debug, Clone, Default)]
+pub struct HostPicker {
+    pub selection: usize,
+    /// Hosts ticked for the next merge, lowercase.
+    pub checked: BTreeSet<String>,
+    /// Hosts waiting for their connectivity check, one per pass of the event loop.
+    pub pending_checks: VecDeque<String>,
+    /// Host or `@group` a credential is being entered for, and the user name once entered.
+    pub credential_entry: Option<(String, Option<String>)>,
+}
+
+/// Returns the full path of the host inventory, next to the configuration file.
+pub fn hosts_path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join(HOSTS_FILE_NAME))
+}
+
+/// Loads the host inventory, or an empty one when the file does not exist.
+pub fn load() -> Result<HostInventory, String> {
+    let Some(path) = hosts_path().filter(|path| path.exists()) else {
+        return Ok(HostInventory::default());
+    };
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    toml::from_str(&content).map_err(|e| format!("Invalid hosts in {}:\n\n{}", path.display(), e))
+}
+
+impl HostInventory {
+    pub fn is_empty(&self) -> bool {
+        self.groups.iter().all(|group| group.hosts.is_empty())
+    }
+
+    /// The picker's lines: each group followed by its hosts.
+    pub fn rows(&self) -> Vec<PickerRow> {
+        let mut rows = Vec::new();
+        for (g, group) in self.groups.iter().enumerate() {
+            rows.push(PickerRow::Group(g));
+            rows.extend((0..group.hosts.len()).map(|h| PickerRow::Host(g, h)));
+        }
+        rows
+    }
+
+    /// The hosts a line stands for: all hosts of a group, or the one host.
+    pub fn hosts_of(&self, row: PickerRow) -> Vec<&Host> {
+        match row {
+            PickerRow::Group(g) => self.groups[g].hosts.iter().collect(),
+            PickerRow::Host(g, h) => vec![&self.groups[g].hosts[h]],
+        }
+    }
+
+    /// The credential to connect to `host` with: the one it names, the one saved for it, the
+    /// one its group names or the one saved for its group, in that order. Hosts outside the
+    /// inventory can have a saved credential too.
+    pub fn credential_for(&self, host: &str) -> Option<String> {
+        let group = self.groups.iter().find(|group| {
+            group
+                .hosts
+                .iter()
+                .any(|known| known.name.eq_ignore_ascii_case(host))
+        });
+        group
+            .and_then(|group| {
+                group
+                    .hosts
+                    .iter()
+                    .find(|known| known.name.eq_ignore_ascii_case(host))
+            })
+            .and_then(|known| known.credential.clone())
+            .or_else(|| self.saved_credential(host))
+            .or_else(|| group.and_then(|group| group.credential.clone()))
+            .or_else(|| group.and_then(|group| self.saved_credential(&format!("@{}", group.name))))
+    }
+
+    /// How to log on to `host`: its credential, and the user name and authentication package
+    /// it or else its group sets.
+    pub fn login_for(&self, host: &str) -> Login {
+        let (group, known) = self
+            .groups
+            .iter()
+            .find_map(|group| {
+                group
+                    .hosts
+                    .iter()
+                    .find(|known| known.name.eq_ignore_ascii_case(host))
+                    .map(|known| (Some(group), Some(known)))
+            })
+            .unwrap_or_default();
+        Login {
+            credential: self.credential_for(host),
+            user: known
+                .and_then(|known| known.user.clone())
+                .or_else(|| group.and_then(|group| group.user.clone())),
+            auth: known
+                .and_then(|known| known.auth)
+                .or_else(|| group.and_then(|group| group.auth))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Target name of the credential saved for a host or `@group`, if there is one.
+    fn saved_credential(&self, name: &str) -> Option<String> {
+        let target = saved_credential_target(name);
+        self.saved_credentials.contains(&target).then_some(target)
+    }
+
+    /// Name a credential is saved under for a line: the host, or `@` and the group.
+    pub fn credential_name(&self, row: PickerRow) -> String {
+        match row {
+            PickerRow::Group(g) => format!("@{}", self.groups[g].name),
+            PickerRow::Host(g, h) => self.groups[g].hosts[h].name.clone(),
+        }
+    }
+
+    /// Replaces each `@group` in a node list with the hosts of that group, dropping repeats.
+    pub fn expand(&self, nodes: Vec<String>) -> Result<Vec<String>, String> {
+        let mut expanded: Vec<String> = Vec::new();
+        for node in nodes {
+            let names = match node.strip_prefix('@') {
+                Some(name) => self
+                    .groups
+                    .iter()
+                    .find(|group| group.name.eq_ignore_ascii_case(name))
+                    .ok_or_else(|| format!("There is no host group '{}' in hosts.toml.", name))?
+                    .hosts
+                    .iter()
+                    .map(|host| host.name.clone())
+                    .collect(),
+                None => vec![node],
+            };
+            for name in names {
+                if !expanded
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(&name))
+                {
+                    expanded.push(name);
+                }
+            }
+        }
+        Ok(expanded)
+    }
+}
+
+impl HostPicker {
+    /// Ticks every host of the line, or unticks them when all are ticked already.
+    pub fn toggle(&mut self, inventory: &HostInventory, row: PickerRow) {
+        let names: Vec<String> = inventory
+            .hosts_of(row)
+            .iter()
+            .map(|host| host.name.to_lowercase())
+            .collect();
+        if names.iter().all(|name| self.checked.contains(name)) {
+            for name in &names {
+                self.checked.remove(name);
+            }
+        } else {
+            self.checked.extend(names);
+        }
+    }
+
+    /// The ticked hosts in inventory order, or the hosts of the selected line when none are.
+    pub fn chosen(&self, inventory: &HostInventory) -> Vec<String> {
+        let rows = inventory.rows();
+        let mut chosen: Vec<String> = Vec::new();
+        let candidates: Vec<&Host> = if self.checked.is_empty() {
+            rows.get(self.selection)
+                .map(|&row| inventory.hosts_of(row))
+                .unwrap_or_default()
+        } else {
+            rows.iter()
+                .filter(|row| matches!(row, PickerRow::Host(..)))
+                .flat_map(|&row| inventory.hosts_of(row))
+                .filter(|host| self.checked.contains(&host.name.to_lowercase()))
+                .collect()
+        };
+        for host in candidates {
+            if !chosen
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(&host.name))
+            {
+                chosen.push(host.name.clone());
+            }
+        }
+        chosen
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+
+use crate::analysis;
+use crate::helpers;
+use crate::models::DisplayEvent;
+
+/// Admin channels of the Hyper-V worker process, management service and compute service.
+pub const HYPERV_CHANNELS: [&str; 3] = [
+    "Microsoft-Windows-Hyper-V-Worker-Admin",
+    "Microsoft-Windows-Hyper-V-VMMS-Admin",
+    "Microsoft-Windows-Hyper-V-Compute-Admin",
+];
+
+/// Most events read from each channel.
+pub const MAX_GUEST_EVENTS: usize = 5000;
+
+/// Worker process events that mark a state change, by event ID.
+const STATE_EVENTS: &[(u32, &str)] = &[
+    (18500, "Started"),
+    (18502, "Turned off"),
+    (18504, "Shut down by guest"),
+    (18560, "Reset (triple fault)"),
+    (18590, "Guest crashed"),
+];
+
+/// Message phrases marking a state change for events missing from `STATE_EVENTS`.
+const STATE_PHRASES: &[(&str, &str)] = &[
+    ("started successfully", "Started"),
+    ("turned off", "Turned off"),
+    ("shut down", "Shut down"),
+    ("was saved", "Saved"),
+    ("restored successfully", "Restored"),
+    ("paused", "Paused"),
+    ("was reset", "Reset"),
+    ("fatal error", "Crashed"),
+];
+
+/// One event in a guest's timeline.
+#[derive(Debug, Clone)]
+pub struct TimelineEntry {
+    pub time: DateTime<Utc>,
+    pub event_id: u32,
+    /// State the guest entered, for state change events.
+    pub state: Option<&'static str>,
+    pub summary: String,
+}
+
+/// The events of one virtual machine, newest first.
+#[derive(Debug, Clone)]
+pub struct GuestTimeline {
+    pub name: String,
+    pub entries: Vec<TimelineEntry>,
+}
+
+impl GuestTimeline {
+    /// Most recent state change, if any was logged.
+    pub fn last_state(&self) -> Option<(&'static str, DateTime<Utc>)> {
+        self.entries
+            .iter()
+            .find_map(|entry| entry.state.map(|state| (state, entry.time)))
+    }
+}
+
+/// State of the Hyper-V guests view.
+#[derive(Debug, Clone, Default)]
+pub struct HyperVView {
+    pub guests: Vec<GuestTimeline>,
+    /// Channels that were read; the others are missing or could not be read.
+    pub channels: Vec<&'static str>,
+    /// Channels that could not be read, with the reason.
+    pub unavailable: Vec<String>,
+    pub selection: usize,
+    /// Guest whose timeline is shown instead of the guest list.
+    pub open_guest: Option<usize>,
+    pub timeline_selection: usize,
+}
+
+impl HyperVView {
+    /// Groups Hyper-V events by the virtual machine they name; events naming none are left out.
+    pub fn new(
+        events: &[DisplayEvent],
+        channels: Vec<&'static str>,
+        unavailable: Vec<String>,
+    ) -> Self {
+        let mut guests: Vec<GuestTimeline> = Vec::new();
+        for event in events {
+            let (Some(name), Some(time)) = (vm_name(event), event.timestamp) else {
+                continue;
+            };
+            let entry = timeline_entry(time, event);
+            match guests.iter_mut().find(|guest| guest.name == name) {
+                Some(guest) => guest.entries.push(entry),
+                None => guests.push(GuestTimeline {
+                    name,
+                    entries: vec![entry],
+                }),
+            }
+        }
+        for guest in &mut guests {
+            guest
+                .entries
+                .sort_by_key(|entry| std::cmp::Reverse(entry.time));
+        }
+        guests.sort_by_key(|guest| std::cmp::Reverse(guest.entries[0].time));
+        Self {
+            guests,
+            channels,
+            unavailable,
+            ..Self::default()
+        }
+    }
+}
+
+/// Returns the virtual machine an event is about. The worker process keeps it in
+/// `UserData`, other components in a named `EventData` field.
+pub fn vm_name(event: &DisplayEvent) -> Option<String> {
+    event
+        .event_data
+        .named()
+        .find(|(name, value)| matches!(*name, "VmName" | "VirtualMachineName") && !value.is_empty())
+        .map(|(_, value)| value.to_string())
+        .or_else(|| analysis::xml_element_text(&event.raw_data, "VmName"))
+}
+
+fn timeline_entry(time: DateTime<Utc>, event: &DisplayEvent) -> TimelineEntry {
+    let message = event.formatted_message.as_deref().unwrap_or(&event.message);
+    let message_lower = message.to_lowercase();
+    let state = STATE_EVENTS
+        .iter()
+        .find(|(id, _)| *id == event.event_id)
+        .map(|(_, state)| *state)
+        .or_else(|| {
+            STATE_PHRASES
+                .iter()
+                .find(|(phrase, _)| message_lower.contains(phrase))
+                .map(|(_, state)| *state)
+        });
+    TimelineEntry {
+        time,
+        event_id: event.event_id,
+        state,
+        summary: helpers::message_summary(message),
+    }
+}
+
+/// Builds a structured query over `channels` for the events naming `name`, for the raw query
+/// mode. Every channel must exist, or the whole query fails.
+pub fn guest_query(name: &str, channels: &[&str]) -> String {
+    let name = name
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('\'', "&apos;");
+    let condition = format!(
+        "*[UserData[VmlEventLog[VmName='{0}']] or EventData[Data[@Name='VmName']='{0}']]",
+        name
+    );
+    let queries: String = channels
+        .iter()
+        .map(|channel| format!("<Select Path=\"{}\">{}</Select>", channel, condition))
+        .collect();
+    format!("<QueryList><Query Id=\"0\">{}</Query></QueryList>", queries)
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+#[cfg(target_os = "windows")]
+use windows::Win32::{
+    Foundation::{CloseHandle, STILL_ACTIVE},
+    System::Threading::{GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION},
+};
+
+use crate::config;
+
+const LOCK_FILE: &str = "event_commander.lock";
+const PID_FILE: &str = "event_commander.pid";
+const LOG_FILE: &str = "event_commander.log";
+/// Times the PID of the instance holding the lock is waited for before it is given up on.
+const PID_WAITS: usize = 10;
+const PID_WAIT: Duration = Duration::from_millis(50);
+
+/// Whether this process owns the shared log file or runs alongside another instance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InstanceRole {
+    Primary,
+    /// `primary_pid` is `None` when the running instance did not write its PID in time.
+    Secondary {
+        primary_pid: Option<u32>,
+    },
+}
+
+/// Single-writer lock over the log and state files in the configuration directory. It is an
+/// OS file lock, so it goes away with the process that holds it however that process ends.
+#[derive(Debug)]
+pub struct InstanceLock {
+    role: InstanceRole,
+    pid: u32,
+    /// Directory the lock, PID, log and attach files live in.
+    dir: PathBuf,
+    /// The locked file, while this instance holds the lock.
+    lock_file: Option<File>,
+}
+
+/// A request handed from a newly started instance to the one already running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachRequest {
+    pub from_pid: u32,
+    pub log_name: String,
+}
+
+impl InstanceLock {
+    /// Takes the lock if no other instance holds it; otherwise becomes a secondary instance.
+    /// The holder writes its PID to a file of its own, since Windows does not let other
+    /// processes read a locked file.
+    pub fn acquire() -> Self {
+        let mut lock = Self::unlocked();
+        lock.dir = config::config_dir().unwrap_or_else(|| PathBuf::from("."));
+        // Without the lock file (e.g. read-only directory) or file locking, run without
+        // coordination.
+        if fs::create_dir_all(&lock.dir).is_err() {
+            return lock;
+        }
+        let Ok(file) = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(lock.path(LOCK_FILE))
+        else {
+            return lock;
+        };
+        match file.try_lock() {
+            Ok(()) => {
+                let own_path = lock.path(&format!("event_commander.{}.pid", lock.pid));
+                let _ = fs::write(&own_path, lock.pid.to_string())
+                    .and_then(|_| fs::rename(&own_path, lock.path(PID_FILE)));
+                lock.lock_file = Some(file);
+            }
+            Err(TryLockError::WouldBlock) => {
+                lock.role = InstanceRole::Secondary {
+                    primary_pid: lock.wait_for_primary_pid(),
+                };
+            }
+            Err(TryLockError::Error(_)) => {}
+        }
+        lock
+    }
+
+    /// A primary instance that coordinates with no other and leaves the lock file alone, for
+    /// state built without the configuration directory, such as in tests.
+    pub fn unlocked() -> Self {
+        Self {
+            role: InstanceRole::Primary,
+            pid: std::process::id(),
+            dir: PathBuf::from("."),
+            lock_file: None,
+        }
+    }
+
+    /// Reads the PID the lock holder writes right after taking the lock. A PID file left by
+    /// an instance that has exited is waited out, as the new holder is about to replace it.
+    fn wait_for_primary_pid(&self) -> Option<u32> {
+        for _ in 0..PID_WAITS {
+            let owner = read_pid(&self.path(PID_FILE));
+            if let Some(owner) =
+                owner.filter(|&owner| owner != self.pid && is_process_running(owner))
+            {
+                return Some(owner);
+            }
+            thread::sleep(PID_WAIT);
+        }
+        None
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Returns this instance's role.
+    pub fn role(&self) -> InstanceRole {
+        self.role
+    }
+
+    /// Returns the log file this instance should write to.
+    pub fn log_file_path(&self) -> PathBuf {
+        match self.role {
+            InstanceRole::Primary => self.path(LOG_FILE),
+            InstanceRole::Secondary { .. } => {
+                self.path(&format!("event_commander.{}.log", self.pid))
+            }
+        }
+    }
+
+    /// Hands a request to the primary instance, which picks it up on its next poll.
+    pub fn send_attach_request(&self, log_name: &str) -> Result<u32, String> {
+        let primary_pid = match self.role {
+            InstanceRole::Primary => return Err("This is the only running instance.".to_string()),
+            InstanceRole::Secondary { primary_pid: None } => {
+                return Err("The running instance did not report its process ID.".to_string());
+            }
+            InstanceRole::Secondary {
+                primary_pid: Some(primary_pid),
+            } => primary_pid,
+        };
+        let request = AttachRequest {
+            from_pid: self.pid,
+            log_name: log_name.to_string(),
+        };
+        let content = serde_json::to_string(&request)
+            .map_err(|e| format!("Failed to serialize attach request: {}", e))?;
+        let inbox = self.inbox_path(primary_pid);
+        let temp_path = inbox.with_extension("tmp");
+        fs::write(&temp_path, content)
+            .and_then(|_| fs::rename(&temp_path, &inbox))
+            .map_err(|e| format!("Failed to contact instance {}: {}", primary_pid, e))?;
+        Ok(primary_pid)
+    }
+
+    /// Takes a pending attach request addressed to this instance, if any.
+    pub fn take_attach_request(&self) -> Option<AttachRequest> {
+        self.lock_file.as_ref()?;
+        let inbox = self.inbox_path(self.pid);
+        if !inbox.exists() {
+            return None;
+        }
+        let content = fs::read_to_string(&inbox).ok();
+        let _ = fs::remove_file(&inbox);
+        serde_json::from_str(&content?).ok()
+    }
+
+    fn inbox_path(&self, pid: u32) -> PathBuf {
+        self.path(&format!("event_commander.{}.attach", pid))
+    }
+}
+
+impl Drop for InstanceLock {
+    /// Clears this instance's PID and inbox; the lock itself is released with the file.
+    fn drop(&mut self) {
+        if self.lock_file.is_some() {
+            if read_pid(&self.path(PID_FILE)) == Some(self.pid) {
+                let _ = fs::remove_file(self.path(PID_FILE));
+            }
+            let _ = fs::remove_file(self.inbox_path(self.pid));
+        }
+    }
+}
+
+fn read_pid(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Checks whether a process with the given ID is still alive.
+#[cfg(target_os = "windows")]
+fn is_process_running(pid: u32) -> bool {
+    unsafe {
+        let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return false;
+        };
+        let mut exit_code = 0u32;
+        let running = GetExitCodeProcess(handle, &mut exit_code).is_ok()
+            && exit_code == STILL_ACTIVE.0 as u32;
+        let _ = CloseHandle(handle);
+        running
+    }
+}
+
+/// Checks whether a process with the given ID is still alive.
+#[cfg(not(target_os = "windows"))]
+fn is_process_running(pid: u32) -> bool {
+    std::path::Path::new(&format!("/proc/{}", pid)).exists()
+}
@@ -0,0 +1,461 @@
+use std::ffi::{c_int, c_void};
+
+use chrono::{DateTime, Utc};
+use quick_xml::escape::escape;
+
+use crate::models::{
+    ChannelAccess, DisplayEvent, EventData, EventLevel, EventLevelFilter, FilterCriteria,
+};
+use crate::source::EventSource;
+
+/// Journals of this machine's system services and kernel, instead of every user's.
+const SD_JOURNAL_SYSTEM: c_int = 1 << 2;
+/// Journal of the current user's services.
+const SD_JOURNAL_CURRENT_USER: c_int = 1 << 3;
+const EACCES: i32 = 13;
+
+/// Channels offered as tabs, each with the journals it opens and the match that narrows them.
+const CHANNELS: &[(&str, c_int, Option<&str>)] = &[
+    ("System", SD_JOURNAL_SYSTEM, None),
+    ("Kernel", SD_JOURNAL_SYSTEM, Some("_TRANSPORT=kernel")),
+    ("Audit", SD_JOURNAL_SYSTEM, Some("_TRANSPORT=audit")),
+    ("User", SD_JOURNAL_CURRENT_USER, None),
+];
+
+#[repr(C)]
+struct SdJournal {
+    _private: [u8; 0],
+}
+
+#[link(name = "systemd")]
+unsafe extern "C" {
+    fn sd_journal_open(ret: *mut *mut SdJournal, flags: c_int) -> c_int;
+    fn sd_journal_close(journal: *mut SdJournal);
+    fn sd_journal_add_match(journal: *mut SdJournal, data: *const c_void, size: usize) -> c_int;
+    fn sd_journal_add_disjunction(journal: *mut SdJournal) -> c_int;
+    fn sd_journal_add_conjunction(journal: *mut SdJournal) -> c_int;
+    fn sd_journal_seek_head(journal: *mut SdJournal) -> c_int;
+    fn sd_journal_seek_tail(journal: *mut SdJournal) -> c_int;
+    fn sd_journal_seek_realtime_usec(journal: *mut SdJournal, usec: u64) -> c_int;
+    fn sd_journal_next(journal: *mut SdJournal) -> c_int;
+    fn sd_journal_previous(journal: *mut SdJournal) -> c_int;
+    fn sd_journal_get_realtime_usec(journal: *mut SdJournal, ret: *mut u64) -> c_int;
+    fn sd_journal_restart_data(journal: *mut SdJournal);
+    fn sd_journal_enumerate_data(
+        journal: *mut SdJournal,
+        data: *mut *const c_void,
+        length: *mut usize,
+    ) -> c_int;
+}
+
+/// systemd-journald, read through libsystemd's sd-journal API.
+#[derive(Debug)]
+pub struct JournalSource {
+    /// Journal of the open query; null when there is none.
+    journal: *mut SdJournal,
+    channel: String,
+    reverse: bool,
+    /// `since=` of the query: entries logged before it, in microseconds since 1970, are left
+    /// out.
+    since: Option<u64>,
+    /// `until=` of the query: entries logged at or after it are left out.
+    until: Option<u64>,
+}
+
+impl Default for JournalSource {
+    fn default() -> Self {
+        Self {
+            journal: std::ptr::null_mut(),
+            channel: String::new(),
+            reverse: false,
+            since: None,
+            until: None,
+        }
+    }
+}
+
+impl Drop for JournalSource {
+    fn drop(&mut self) {
+        self.close_query();
+    }
+}
+
+impl EventSource for JournalSource {
+    fn channels(&self) -> Vec<String> {
+        CHANNELS
+            .iter()
+            .map(|(name, _, _)| name.to_string())
+            .collect()
+    }
+
+    fn check_access(&self, channel: &str) -> ChannelAccess {
+        let Some(&(_, flags, _)) = CHANNELS.iter().find(|(name, _, _)| *name == channel) else {
+            return ChannelAccess::Missing;
+        };
+        match open_journal(flags) {
+            Ok(journal) => {
+                unsafe { sd_journal_close(journal) };
+                ChannelAccess::Readable
+            }
+            Err(EACCES) => ChannelAccess::Denied,
+            Err(errno) => ChannelAccess::Failed(errno as u32),
+        }
+    }
+
+    fn query(&mut self, channel: &str, query: &str, reverse: bool) -> Result<(), String> {
+        self.close_query();
+        let &(_, flags, channel_match) = CHANNELS
+            .iter()
+            .find(|(name, _, _)| *name == channel)
+            .ok_or_else(|| format!("There is no journal channel '{}'.", channel))?;
+        let journal = open_journal(flags).map_err(|errno| {
+            format!(
+                "Failed to open the journal: {}",
+                std::io::Error::from_raw_os_error(errno)
+            )
+        })?;
+        self.journal = journal;
+        self.channel = channel.to_string();
+        self.reverse = reverse;
+        self.since = None;
+        self.until = None;
+
+        // Matches on one field are ORed and on different fields ANDed; "+" ORs whole groups,
+        // as it does for journalctl. The channel's match is ANDed with all of them. `since=`
+        // and `until=` bound the whole query by time instead.
+        let result = (|| {
+            if let Some(channel_match) = channel_match {
+                self.add_match(channel_match)?;
+                unsafe { sd_journal_add_conjunction(journal) };
+            }
+            for term in query_terms(query)? {
+                if term == "+" {
+                    unsafe { sd_journal_add_disjunction(journal) };
+                } else if let Some(time) = term.strip_prefix("since=") {
+                    self.since = Some(parse_time(time)?);
+                } else if let Some(time) = term.strip_prefix("until=") {
+                    self.until = Some(parse_time(time)?);
+                } else if term.contains('=') {
+                    self.add_match(&term)?;
+                } else {
+                    return Err(format!(
+                        "Invalid journal match '{}'; matches are written FIELD=value.",
+                        term
+                    ));
+                }
+            }
+            let seeked = unsafe {
+                match (reverse, self.since, self.until) {
+                    (true, _, Some(until)) => sd_journal_seek_realtime_usec(journal, until),
+                    (true, _, None) => sd_journal_seek_tail(journal),
+                    (false, Some(since), _) => sd_journal_seek_realtime_usec(journal, since),
+                    (false, None, _) => sd_journal_seek_head(journal),
+                }
+            };
+            check(seeked)
+        })();
+        if result.is_err() {
+            self.close_query();
+        }
+        result
+    }
+
+    fn next_batch(&mut self, max: usize) -> Result<Vec<DisplayEvent>, String> {
+        let mut events = Vec::new();
+        if self.journal.is_null() {
+            return Ok(events);
+        }
+        while events.len() < max {
+            let moved = unsafe {
+                if self.reverse {
+                    sd_journal_previous(self.journal)
+                } else {
+                    sd_journal_next(self.journal)
+                }
+            };
+            check(moved)?;
+            if moved == 0 {
+                break;
+            }
+            let mut realtime_usec = 0u64;
+            unsafe { sd_journal_get_realtime_usec(self.journal, &mut realtime_usec) };
+            let before = self.since.is_some_and(|since| realtime_usec < since);
+            let after = self.until.is_some_and(|until| realtime_usec >= until);
+            // Past the far bound in the reading direction nothing more can match.
+            if (self.reverse && before) || (!self.reverse && after) {
+                self.close_query();
+                break;
+            }
+            if !before && !after {
+                events.push(self.read_entry());
+            }
+        }
+        Ok(events)
+    }
+
+    fn close_query(&mut self) {
+        if !self.journal.is_null() {
+            unsafe { sd_journal_close(self.journal) };
+            self.journal = std::ptr::null_mut();
+        }
+    }
+
+    /// The entry's fields one per line, as `journalctl -o export` writes them.
+    fn render_detail(&self, event: &DisplayEvent) -> String {
+        event
+            .event_data
+            .named()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn fork(&self) -> Box<dyn EventSource> {
+        Box::new(Self::default())
+    }
+}
+
+impl JournalSource {
+    fn add_match(&self, term: &str) -> Result<(), String> {
+        check(unsafe {
+            sd_journal_add_match(self.journal, term.as_ptr() as *const c_void, term.len())
+        })
+        .map_err(|msg| format!("Invalid journal match '{}': {}", term, msg))
+    }
+
+    /// Reads the entry the journal is positioned at.
+    fn read_entry(&self) -> DisplayEvent {
+        let mut fields: Vec<(String, String)> = Vec::new();
+        let mut realtime_usec = 0u64;
+        unsafe {
+            sd_journal_get_realtime_usec(self.journal, &mut realtime_usec);
+            sd_journal_restart_data(self.journal);
+            let mut data: *const c_void = std::ptr::null();
+            let mut length = 0usize;
+            while sd_journal_enumerate_data(self.journal, &mut data, &mut length) > 0 {
+                let bytes = std::slice::from_raw_parts(data as *const u8, length);
+                if let Some((name, value)) = String::from_utf8_lossy(bytes).split_once('=') {
+                    fields.push((name.to_string(), value.to_string()));
+                }
+            }
+        }
+        entry_to_event(&self.channel, realtime_usec, fields)
+    }
+}
+
+/// Maps a journal entry onto an event: the syslog priority gives the level, the syslog
+/// identifier the source, and every field is kept as named event data. Entries have no
+/// record number, so the realtime timestamp in microseconds stands in for it.
+fn entry_to_event(
+    channel: &str,
+    realtime_usec: u64,
+    fields: Vec<(String, String)>,
+) -> DisplayEvent {
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let level = match field("PRIORITY").and_then(|priority| priority.parse::<u8>().ok()) {
+        Some(0..=2) => EventLevel::Critical,
+        Some(3) => EventLevel::Error,
+        Some(4) => EventLevel::Warning,
+        Some(7) => EventLevel::Verbose,
+        _ => EventLevel::Information,
+    };
+    let source = field("SYSLOG_IDENTIFIER")
+        .or_else(|| field("_COMM"))
+        .or_else(|| (field("_TRANSPORT") == Some("kernel")).then_some("kernel"))
+        .unwrap_or("journal")
+        .to_string();
+    let timestamp = DateTime::<Utc>::from_timestamp_micros(realtime_usec as i64);
+    let computer = field("_HOSTNAME").unwrap_or_default().to_string();
+    let message = field("MESSAGE").unwrap_or_default().to_string();
+    let data_xml: String = fields
+        .iter()
+        .map(|(name, value)| format!("<Data Name=\"{}\">{}</Data>", escape(name), escape(value)))
+        .collect();
+    let raw_data = format!(
+        "<Event><System><Provider Name=\"{}\"/><Level>{}</Level>\
+         <TimeCreated SystemTime=\"{}\"/><Channel>{}</Channel><Computer>{}</Computer></System>\
+         <EventData>{}</EventData></Event>",
+        escape(&source),
+        level.code(),
+        timestamp
+            .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Micros, true))
+            .unwrap_or_default(),
+        escape(channel),
+        escape(&computer),
+        data_xml
+    );
+    DisplayEvent {
+        level,
+        timestamp,
+        source: source.clone(),
+        provider_name_original: source,
+        event_id: 0,
+        channel: channel.to_string(),
+        computer,
+        user: field("_UID").unwrap_or_default().to_string(),
+        task: 0,
+        task_category: None,
+        keywords: 0,
+        record_id: realtime_usec,
+        activity_id: field("_SYSTEMD_INVOCATION_ID").map(str::to_string),
+        related_activity_id: None,
+        process_id: field("_PID").and_then(|pid| pid.parse().ok()),
+        thread_id: field("TID").and_then(|tid| tid.parse().ok()),
+        message,
+        event_data: EventData {
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (Some(name), value))
+                .collect(),
+        },
+        raw_data,
+        formatted_message: None,
+        sigma_hits: Vec::new(),
+    }
+}
+
+/// Splits a query into its terms at whitespace. Double quotes keep whitespace in a term, as in
+/// `SYSLOG_IDENTIFIER="my app"`, and a backslash in them escapes a quote or backslash.
+fn query_terms(query: &str) -> Result<Vec<String>, String> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+    let mut in_term = false;
+    let mut chars = query.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_term = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => term.extend(chars.next()),
+                        Some(c) => term.push(c),
+                        None => {
+                            return Err("A quote in the journal query is not closed.".to_string());
+                        }
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_term {
+                    terms.push(std::mem::take(&mut term));
+                    in_term = false;
+                }
+            }
+            c => {
+                in_term = true;
+                term.push(c);
+            }
+        }
+    }
+    if in_term {
+        terms.push(term);
+    }
+    Ok(terms)
+}
+
+/// A `FIELD=value` term, quoted when the value would otherwise be split.
+fn match_term(field: &str, value: &str) -> String {
+    if value.contains(|c: char| c.is_whitespace() || c == '"') {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("{}=\"{}\"", field, escaped)
+    } else {
+        format!("{}={}", field, value)
+    }
+}
+
+/// Reads the time of a `since=` or `until=` term, in microseconds since 1970.
+fn parse_time(text: &str) -> Result<u64, String> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|time| time.timestamp_micros().max(0) as u64)
+        .map_err(|e| format!("Invalid journal time '{}': {}", text, e))
+}
+
+/// A `since=` or `until=` term for `time`.
+fn time_term(bound: &str, time: DateTime<Utc>) -> String {
+    format!(
+        "{}={}",
+        bound,
+        time.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+    )
+}
+
+/// Translates the filter dialog's criteria into journal matches, with its time range as a
+/// `since=` bound. Event IDs, keywords, tasks and excluded sources have no match equivalent
+/// and are left out.
+pub fn matches_from_filter(filter: Option<&FilterCriteria>) -> String {
+    let mut matches = filter_matches(filter);
+    if let Some(start) = filter.and_then(|filter| filter.time_filter.get_start_time()) {
+        matches.push(time_term("since", start));
+    }
+    matches.join(" ")
+}
+
+/// The matches of `filter` for the entries logged from `start` to before `end`, in place of
+/// its own time range.
+pub fn matches_for_window(
+    filter: Option<&FilterCriteria>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> String {
+    let mut matches = filter_matches(filter);
+    matches.push(time_term("since", start));
+    matches.push(time_term("until", end));
+    matches.join(" ")
+}
+
+fn filter_matches(filter: Option<&FilterCriteria>) -> Vec<String> {
+    let Some(filter) = filter else {
+        return Vec::new();
+    };
+    let mut matches = Vec::new();
+    if let Some(source) = filter.source.as_deref().filter(|source| !source.is_empty()) {
+        matches.push(match_term("SYSLOG_IDENTIFIER", source));
+    }
+    let priorities: &[u8] = match filter.level {
+        EventLevelFilter::Error => &[0, 1, 2, 3],
+        EventLevelFilter::Warning => &[4],
+        EventLevelFilter::Information => &[5, 6],
+        EventLevelFilter::All => &[],
+    };
+    matches.extend(
+        priorities
+            .iter()
+            .map(|priority| format!("PRIORITY={}", priority)),
+    );
+    if let Some(computer) = filter
+        .computer
+        .as_deref()
+        .filter(|computer| !computer.is_empty())
+    {
+        matches.push(match_term("_HOSTNAME", computer));
+    }
+    if let Some(uid) = filter.user_sid.as_deref().filter(|uid| !uid.is_empty()) {
+        matches.push(match_term("_UID", uid));
+    }
+    matches
+}
+
+fn open_journal(flags: c_int) -> Result<*mut SdJournal, i32> {
+    let mut journal = std::ptr::null_mut();
+    let result = unsafe { sd_journal_open(&mut journal, flags) };
+    if result < 0 {
+        Err(-result)
+    } else {
+        Ok(journal)
+    }
+}
+
+/// Turns a negative errno return into its message.
+fn check(result: c_int) -> Result<(), String> {
+    if result < 0 {
+        Err(std::io::Error::from_raw_os_error(-result).to_string())
+    } else {
+        Ok(())
+    }
+}
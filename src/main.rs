@@ -1,55 +1,165 @@
+mod analysis;
 mod app_state;
+mod archive;
+mod auditpol;
+#[cfg(target_os = "windows")]
+mod authflow;
+mod baseline;
+mod bitlocker;
+mod bookmarks;
+mod catalog;
+mod cli;
+mod clipboard;
+mod cluster;
+mod compare;
+mod config;
+mod detail;
+#[cfg(target_os = "windows")]
+mod devices;
+mod digest;
+#[cfg(target_os = "windows")]
+mod dns;
+mod doctor;
+mod elevation;
 mod event_api;
+#[cfg(target_os = "windows")]
 mod event_parser;
 mod handlers;
 mod helpers;
+mod history;
+mod hosts;
+#[cfg(target_os = "windows")]
+mod hyperv;
+mod instance;
+#[cfg(all(target_os = "linux", feature = "journald"))]
+mod journal;
+#[cfg(test)]
+mod mock;
 mod models;
+#[cfg(target_os = "windows")]
+mod msi;
+mod notify;
+#[cfg(target_os = "windows")]
+mod objectaccess;
+mod otlp;
+mod pager;
+#[cfg(target_os = "windows")]
+mod printing;
+#[cfg(target_os = "windows")]
+mod publishers;
+mod redact;
+mod security;
+mod services;
+#[cfg(target_os = "windows")]
+mod servicing;
+mod session;
+mod siem;
+mod sigma;
+mod source;
+mod split;
+mod syslog;
+mod tail;
 mod terminal;
+mod theme;
+mod triggers;
 mod ui;
+mod view;
+mod weblogs;
+#[cfg(target_os = "windows")]
+mod wmi;
 
+use clap::Parser;
 use crossterm::event::{self, Event, KeyEventKind};
 use models::PostKeyPressAction;
 use std::{error::Error, time::Duration};
 
-#[cfg(target_os = "windows")]
-use windows::Win32::System::EventLog::EvtClose;
-
 /// Application entry point; initializes the terminal and application state, and processes events.
 fn main() -> Result<(), Box<dyn Error>> {
+    let cli = cli::Cli::parse();
+    match &cli.command {
+        Some(cli::Command::Tail(args)) => return tail::run(args),
+        Some(cli::Command::Digest(args)) => return digest::run(args),
+        Some(cli::Command::ArchiveDaemon(args)) => return archive::run(args),
+        Some(cli::Command::Baseline { action }) => return baseline::run(action),
+        Some(cli::Command::Doctor) => return doctor::run(),
+        None => {}
+    }
+    if cli.no_tui {
+        return pager::run(&cli);
+    }
+
     let mut terminal = terminal::init_terminal()?;
     let mut app_state = models::AppState::new();
+    let shared_view = cli.view.as_deref().map(view::ViewState::from_token);
+    if let Some(Err(msg)) = shared_view.map(|view| view.and_then(|v| app_state.apply_view(v))) {
+        app_state.show_error("Invalid View Token", &msg);
+    }
+    if let Some(path) = &cli.session
+        && let Err(msg) = app_state.restore_session(path)
+    {
+        app_state.show_error("Session Not Restored", &msg);
+    }
+    if let Err(msg) = app_state.apply_launch_args(&cli) {
+        app_state.show_error("Invalid Arguments", &msg);
+    }
 
-    #[cfg(target_os = "windows")]
     app_state.start_or_continue_log_load(true);
 
+    let mut window_title = String::new();
     loop {
+        let current_title = app_state.window_title();
+        if current_title != window_title {
+            terminal::set_title(&current_title)?;
+            window_title = current_title;
+        }
+
         terminal.draw(|frame| ui::ui(frame, &mut app_state))?;
 
         let mut post_action = PostKeyPressAction::None;
 
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
+        if let Some(request) = app_state.instance.take_attach_request() {
+            app_state.apply_attach_request(request);
+        }
+
+        // Keep a deep search or host checks moving between keystrokes instead of waiting.
+        let poll_timeout = if app_state.deep_search.is_some()
+            || app_state.load_progress.is_some()
+            || app_state.has_pending_host_checks()
+        {
+            Duration::ZERO
+        } else {
+            Duration::from_millis(100)
+        };
+        if event::poll(poll_timeout)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
                     post_action = handlers::handle_key_press(key, &mut app_state);
                 }
+                // Pick up the new size before the next draw so the layout reflows immediately.
+                Event::Resize(_, _) => terminal.autoresize()?,
+                _ => {}
             }
         }
 
+        if app_state.deep_search.is_some() {
+            app_state.continue_deep_search();
+        }
+        app_state.continue_log_load();
+        app_state.trim_loaded_events();
+        app_state.continue_otlp_export();
+        #[cfg(target_os = "windows")]
+        app_state.continue_host_checks();
+        if app_state.auto_refresh_due() {
+            app_state.refresh_new_events();
+        }
+
         match post_action {
             PostKeyPressAction::ReloadData => {
-                #[cfg(target_os = "windows")]
-                {
-                    if let Some(handle) = app_state.query_handle.take() {
-                        unsafe {
-                            let _ = EvtClose(handle);
-                        }
-                    }
-                    app_state.events.clear();
-                    app_state.table_state.select(None);
-                    app_state.no_more_events = false;
-                    app_state.preview_scroll = 0;
-                    app_state.start_or_continue_log_load(true);
-                }
+                app_state.source.close_query();
+                app_state.clear_events();
+                app_state.no_more_events = false;
+                app_state.preview_scroll = 0;
+                app_state.start_or_continue_log_load(true);
             }
             PostKeyPressAction::ShowConfirmation(title, msg) => {
                 app_state.show_confirmation(&title, &msg);
@@ -62,28 +172,9 @@ fn main() -> Result<(), Box<dyn Error>> {
                             event_api::load_available_sources(&mut app_state);
                     }
                 }
-                app_state.filter_dialog_source_index = 0;
-                if let Some(active) = &app_state.active_filter {
-                    if let Some(ref source) = active.source {
-                        app_state.filter_dialog_source_input = source.clone();
-                        if let Some(ref sources) = app_state.available_sources {
-                            if let Some(idx) = sources.iter().position(|s| s == source) {
-                                app_state.filter_dialog_source_index = idx;
-                            }
-                        }
-                    } else {
-                        app_state.filter_dialog_source_input.clear();
-                    }
-                    app_state.filter_dialog_event_id = active.event_id.clone().unwrap_or_default();
-                    app_state.filter_dialog_level = active.level;
-                    app_state.filter_dialog_time = active.time_filter;
-                } else {
-                    app_state.filter_dialog_source_input.clear();
-                    app_state.filter_dialog_event_id.clear();
-                    app_state.filter_dialog_level = models::EventLevelFilter::default();
-                    app_state.filter_dialog_time = models::TimeFilterOption::default();
-                }
-                app_state.update_filtered_sources();
+                let active = app_state.active_filter.clone();
+                app_state.fill_filter_dialog(active.as_ref());
+                app_state.filter_dialog_recent = None;
                 app_state.filter_dialog_focus = models::FilterFieldFocus::EventId;
                 app_state.is_filter_dialog_visible = true;
             }
@@ -92,6 +183,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
+    app_state.save_config();
     terminal::restore_terminal()?;
     Ok(())
 }
@@ -0,0 +1,260 @@
+//! An in-memory event source, for exercising the interface without a log backend.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::models::{ChannelAccess, DisplayEvent, EventData, EventLevel};
+use crate::source::EventSource;
+
+/// Events of each channel, in the order they were appended.
+type Channels = Vec<(String, Vec<DisplayEvent>)>;
+
+/// Channels holding the events appended to them, read like a backend's queries. The query text
+/// is ignored. Clones and forks share the channels, so events appended after `AppState` took
+/// the source show up in its next reads.
+#[derive(Debug, Clone, Default)]
+pub struct MockSource {
+    logs: Rc<RefCell<Channels>>,
+    open: VecDeque<DisplayEvent>,
+}
+
+impl MockSource {
+    /// A source offering `channels` as log tabs, all empty.
+    pub fn new(channels: &[&str]) -> Self {
+        let source = Self::default();
+        source.logs.borrow_mut().extend(
+            channels
+                .iter()
+                .map(|channel| (channel.to_string(), Vec::new())),
+        );
+        source
+    }
+
+    /// Appends an event from `provider` to `channel`, logged `minutes_ago`, with the next
+    /// record ID of the channel. Returns the record ID.
+    pub fn append(&self, channel: &str, provider: &str, message: &str, minutes_ago: i64) -> u64 {
+        let mut logs = self.logs.borrow_mut();
+        let (_, events) = logs
+            .iter_mut()
+            .find(|(name, _)| name == channel)
+            .expect("channel of the mock source");
+        let record_id = events.len() as u64 + 1;
+        events.push(event(
+            channel,
+            provider,
+            message,
+            record_id,
+            Utc::now() - Duration::minutes(minutes_ago),
+        ));
+        record_id
+    }
+}
+
+impl EventSource for MockSource {
+    fn channels(&self) -> Vec<String> {
+        self.logs
+            .borrow()
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    fn check_access(&self, channel: &str) -> ChannelAccess {
+        if self.logs.borrow().iter().any(|(name, _)| name == channel) {
+            ChannelAccess::Readable
+        } else {
+            ChannelAccess::Missing
+        }
+    }
+
+    fn query(&mut self, channel: &str, _query: &str, reverse: bool) -> Result<(), String> {
+        let logs = self.logs.borrow();
+        let (_, events) = logs
+            .iter()
+            .find(|(name, _)| name == channel)
+            .ok_or_else(|| format!("No channel '{}'.", channel))?;
+        self.open = events.iter().cloned().collect();
+        if reverse {
+            self.open = self.open.drain(..).rev().collect();
+        }
+        Ok(())
+    }
+
+    fn next_batch(&mut self, max: usize) -> Result<Vec<DisplayEvent>, String> {
+        let count = max.min(self.open.len());
+        Ok(self.open.drain(..count).collect())
+    }
+
+    fn close_query(&mut self) {
+        self.open.clear();
+    }
+
+    fn render_detail(&self, event: &DisplayEvent) -> String {
+        event.raw_data.clone()
+    }
+
+    fn fork(&self) -> Box<dyn EventSource> {
+        Box::new(Self {
+            logs: Rc::clone(&self.logs),
+            open: VecDeque::new(),
+        })
+    }
+}
+
+/// An information event with ID 1000 from `provider` on `channel`, its message formatted.
+pub fn event(
+    channel: &str,
+    provider: &str,
+    message: &str,
+    record_id: u64,
+    timestamp: DateTime<Utc>,
+) -> DisplayEvent {
+    DisplayEvent {
+        level: EventLevel::Information,
+        timestamp: Some(timestamp),
+        source: provider.to_string(),
+        provider_name_original: provider.to_string(),
+        event_id: 1000,
+        channel: channel.to_string(),
+        computer: "mock-host".to_string(),
+        user: String::new(),
+        task: 0,
+        task_category: None,
+        keywords: 0,
+        record_id,
+        activity_id: None,
+        process_id: None,
+        thread_id: None,
+        related_activity_id: None,
+        message: message.to_string(),
+        event_data: EventData::default(),
+        raw_data: format!("{} record {}: {}", channel, record_id, message),
+        #[cfg(target_os = "windows")]
+        raw_dropped: false,
+        formatted_message: Some(message.to_string()),
+        sigma_hits: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::handlers::handle_key_press;
+    use crate::instance::InstanceLock;
+    use crate::models::AppState;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    /// An application reading `source`, with the default configuration and no files.
+    fn app_with(source: &MockSource) -> AppState {
+        let config = Config {
+            default_log: "Application".to_string(),
+            ..Config::default()
+        };
+        let mut app = AppState::with_source(
+            Box::new(source.clone()),
+            config,
+            InstanceLock::unlocked(),
+            None,
+        );
+        app.start_or_continue_log_load(true);
+        app.load_batch_now();
+        app
+    }
+
+    fn source_with_events() -> MockSource {
+        let source = MockSource::new(&["Application", "System"]);
+        source.append("Application", "Service Control", "service started", 30);
+        source.append("Application", "Disk", "disk is almost full", 20);
+        source.append("Application", "Backup", "backup finished", 10);
+        source.append("System", "Kernel", "system booted", 40);
+        source
+    }
+
+    fn press(app: &mut AppState, code: KeyCode) {
+        handle_key_press(KeyEvent::new(code, KeyModifiers::NONE), app);
+    }
+
+    #[test]
+    fn loads_the_default_tab_newest_first() {
+        let app = app_with(&source_with_events());
+        assert_eq!(app.channels, ["Application", "System"]);
+        assert_eq!(app.selected_log_name, "Application");
+        let records: Vec<u64> = app.events.iter().map(|event| event.record_id).collect();
+        assert_eq!(records, [3, 2, 1]);
+        assert!(app.status_dialog.is_none());
+    }
+
+    #[test]
+    fn search_jumps_to_the_match_while_typing() {
+        let mut app = app_with(&source_with_events());
+        press(&mut app, KeyCode::Char('/'));
+        for c in "disk".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(app.selected_event().map(|event| event.record_id), Some(2));
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.is_searching);
+        assert_eq!(app.selected_event().map(|event| event.record_id), Some(2));
+    }
+
+    #[test]
+    fn j_and_k_move_only_with_vim_keys() {
+        let mut app = app_with(&source_with_events());
+        // Without a split pane the time sync has nothing to synchronize.
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.table_state.selected(), Some(0));
+        assert!(
+            app.status_dialog
+                .as_ref()
+                .is_some_and(|d| d.title == "Sync Time")
+        );
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Char('k'));
+        assert!(app.comparison.is_some());
+        press(&mut app, KeyCode::Char('k'));
+        assert!(app.comparison.is_none());
+
+        app.config.vim_keys = true;
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.table_state.selected(), Some(2));
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!(app.table_state.selected(), Some(1));
+        assert!(app.comparison.is_none());
+    }
+
+    #[test]
+    fn renders_the_loaded_events() {
+        let mut app = app_with(&source_with_events());
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        terminal
+            .draw(|frame| crate::ui::ui(frame, &mut app))
+            .unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Service Control"));
+        assert!(screen.contains("Backup"));
+        assert!(!screen.contains("Kernel"));
+    }
+
+    #[test]
+    fn auto_refresh_reads_appended_events_through_the_source() {
+        let source = source_with_events();
+        let mut app = app_with(&source);
+        source.append("Application", "Updater", "update installed", 0);
+        assert_eq!(app.refresh_new_events(), 1);
+        assert_eq!(app.events.len(), 4);
+        assert_eq!(app.events[0].source, "Updater");
+    }
+}
@@ -1,26 +1,106 @@
-// use chrono::Local;
+use crate::analysis;
+use crate::auditpol::AuditPolicyView;
+#[cfg(target_os = "windows")]
+use crate::authflow::AuthFlowView;
+use crate::bookmarks::Bookmark;
+use crate::catalog::MessageCatalog;
+use crate::compare::Comparison;
+use crate::config::Config;
+#[cfg(target_os = "windows")]
+use crate::devices::DeviceView;
+#[cfg(target_os = "windows")]
+use crate::dns::DnsAnalysis;
+use crate::helpers;
+use crate::history::FilterHistory;
+use crate::hosts::{HostInventory, HostPicker, HostStatus};
+#[cfg(target_os = "windows")]
+use crate::hyperv::HyperVView;
+use crate::instance::InstanceLock;
+#[cfg(target_os = "windows")]
+use crate::msi::InstallerView;
+#[cfg(target_os = "windows")]
+use crate::objectaccess::ObjectAccessView;
+use crate::otlp::OtlpExport;
+#[cfg(target_os = "windows")]
+use crate::printing::PrintView;
+#[cfg(target_os = "windows")]
+use crate::servicing::ServicingView;
+use crate::sigma::{SigmaRule, SigmaView};
+use crate::source::{BatchTuner, EventSource};
+use crate::split::SplitPane;
+use crate::theme::Theme;
+use crate::triggers::TriggerRunner;
+#[cfg(target_os = "windows")]
+use crate::wmi::WmiAnalysis;
 use chrono::{DateTime, Duration, Utc};
 use ratatui::text::Text;
 use ratatui::widgets::TableState;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufWriter;
-
-#[cfg(target_os = "windows")]
-use windows::Win32::System::EventLog::EVT_HANDLE;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 /// Represents an event with displayable information.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DisplayEvent {
-    pub level: String,
-    pub datetime: String,
+    pub level: EventLevel,
+    /// `System/TimeCreated/@SystemTime`; formatted in local time when rendered. `None` when
+    /// the event has no creation time that could be read, which is shown as unknown.
+    pub timestamp: Option<DateTime<Utc>>,
     pub source: String,
     pub provider_name_original: String,
-    pub id: String,
+    pub event_id: u32,
+    /// `System/Channel`, the log the event was written to.
+    pub channel: String,
+    pub computer: String,
+    /// SID from `System/Security/@UserID`, empty when the event carries none.
+    pub user: String,
+    pub task: u16,
+    /// Task name from the provider's metadata, when it defines one for `task`.
+    pub task_category: Option<String>,
+    pub keywords: u64,
+    pub record_id: u64,
+    /// `System/Correlation/@ActivityID`, shared by the events of one operation.
+    pub activity_id: Option<String>,
+    /// `System/Execution/@ProcessID`, the process that logged the event.
+    pub process_id: Option<u32>,
+    /// `System/Execution/@ThreadID`.
+    pub thread_id: Option<u32>,
+    /// `System/Correlation/@RelatedActivityID`, the activity that started this one.
+    pub related_activity_id: Option<String>,
     pub message: String,
+    pub event_data: EventData,
     pub raw_data: String,
+    /// Whether `raw_data` is only a stub naming the record, the XML having been dropped after
+    /// parsing to save memory (`retain_raw_xml = false`).
+    #[serde(skip)]
+    #[cfg(target_os = "windows")]
+    pub raw_dropped: bool,
     pub formatted_message: Option<String>,
+    /// Titles of the Sigma rules the event matched when it was loaded.
+    #[serde(skip)]
+    pub sigma_hits: Vec<String>,
+}
+
+/// Severity of an event, from the `System/Level` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EventLevel {
+    Critical,
+    Error,
+    Warning,
+    /// Level 4, or 0 for classic events that predate levels.
+    #[default]
+    Information,
+    Verbose,
+    Unknown(u8),
+}
+
+/// Payload of `EventData`, in document order. Each value keeps its `Name` attribute when the
+/// provider declares one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventData {
+    pub fields: Vec<(Option<String>, String)>,
 }
 
 /// Represents a status dialog with a title, message, and state flags.
@@ -30,6 +110,30 @@ pub struct StatusDialog {
     pub message: String,
     pub visible: bool,
     pub is_error: bool,
+    /// Whether [a] attaches to the running instance, which only the dialog shown at startup
+    /// when another instance holds the lock offers.
+    pub offers_attach: bool,
+}
+
+/// A file write that would replace an existing file, held back until its preview is confirmed.
+#[derive(Debug, Clone)]
+pub enum PendingAction {
+    ExportEvents(PathBuf),
+    ExportMatches { path: PathBuf, scan_whole_log: bool },
+    ExportCatalog(PathBuf),
+    SaveEvent(PathBuf),
+}
+
+impl PendingAction {
+    /// File the action writes.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::ExportEvents(path)
+            | Self::ExportMatches { path, .. }
+            | Self::ExportCatalog(path)
+            | Self::SaveEvent(path) => path,
+        }
+    }
 }
 
 /// Represents the view mode for the preview panel when focused.
@@ -41,7 +145,7 @@ pub enum PreviewViewMode {
 }
 
 /// Represents an event level filter for displaying events.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Serialize, Deserialize)]
 pub enum EventLevelFilter {
     #[default]
     All,
@@ -51,7 +155,7 @@ pub enum EventLevelFilter {
 }
 
 /// Represents the time range options for filtering events.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Serialize, Deserialize)]
 pub enum TimeFilterOption {
     #[default]
     AnyTime,
@@ -62,6 +166,30 @@ pub enum TimeFilterOption {
     Last30Days,
 }
 
+/// Represents the standard keyword bits that events can be filtered on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Serialize, Deserialize)]
+pub enum KeywordFilter {
+    #[default]
+    Any,
+    AuditSuccess,
+    AuditFailure,
+    Classic,
+    CorrelationHint,
+    ResponseTime,
+    Sqm,
+    WdiContext,
+    WdiDiagnostic,
+}
+
+/// Key pressed in the event list that waits for a mark's letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkKey {
+    /// `'`: marks the selected event.
+    Set,
+    /// `` ` ``: jumps to the marked event.
+    Jump,
+}
+
 /// Represents which panel is currently focused in the TUI.
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum PanelFocus {
@@ -70,12 +198,270 @@ pub enum PanelFocus {
 }
 
 /// Represents criteria for filtering events.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct FilterCriteria {
     pub source: Option<String>,
     pub event_id: Option<String>,
     pub level: EventLevelFilter,
     pub time_filter: TimeFilterOption,
+    pub keywords: KeywordFilter,
+    pub task: Option<u16>,
+    pub opcode: Option<u8>,
+    pub user_sid: Option<String>,
+    pub computer: Option<String>,
+    /// Sources whose events are hidden, such as a provider flooding the log.
+    #[serde(default)]
+    pub excluded_sources: Vec<String>,
+}
+
+/// File format used when exporting or streaming events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Cef,
+    Leef,
+    Ndjson,
+}
+
+/// Message format of the records forwarded to a syslog collector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+pub enum SyslogFormat {
+    /// The event's message, with its System values and event data as structured data.
+    #[default]
+    Rfc5424,
+    /// A CEF record in the message.
+    Cef,
+}
+
+/// Whether the current user can read a channel, checked before its tab is opened. Only the
+/// backends construct the checked states, so builds without one only see `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    not(any(target_os = "windows", feature = "journald")),
+    allow(dead_code)
+)]
+pub enum ChannelAccess {
+    /// Not checked, as on platforms without the Event Log API.
+    #[default]
+    Unknown,
+    Readable,
+    Denied,
+    Missing,
+    /// Opening the channel failed with another Win32 error.
+    Failed(u32),
+}
+
+/// A labeled point on the incident timeline, such as "service restarted here".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentMarker {
+    pub time: DateTime<Utc>,
+    pub label: String,
+}
+
+/// Time window the view is locked to while incident mode is on. It applies to every log tab,
+/// and its markers are shown on the timeline ruler and written to exports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incident {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub markers: Vec<IncidentMarker>,
+}
+
+/// One channel of the merged tab: its open query and the events read from it that have not
+/// been merged into the list yet.
+#[cfg(target_os = "windows")]
+pub struct MergedQuery {
+    pub channel: String,
+    /// Fork of the event source holding the channel's query.
+    pub source: Box<dyn EventSource>,
+    /// Events read ahead, in query order.
+    pub pending: std::collections::VecDeque<DisplayEvent>,
+    pub exhausted: bool,
+}
+
+/// Single-line text input shown at the bottom of the screen, and what its text is for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    IncidentWindow,
+    IncidentMarker,
+    ViewToken,
+    GoToRecord,
+    ClusterNodes,
+    /// File to back the selected log up to.
+    BackUpLog,
+    /// File to save the selected log's events matching the current filter or query to.
+    ExportFilteredLog,
+    /// File to back the selected log up to before clearing it; empty skips the backup.
+    ClearLogBackup,
+    /// The selected log's name, typed to confirm clearing it.
+    ClearLogConfirm,
+    /// User name of a credential to save for the host picker's selected host or group.
+    CredentialUser,
+    /// Password of that credential, shown masked.
+    CredentialPassword,
+    /// File to save the session to.
+    SaveSession,
+    /// Session file to restore.
+    RestoreSession,
+}
+
+/// Actions offered by the admin menu for the selected log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminAction {
+    BackUpLog,
+    ExportFiltered,
+    ClearLog,
+}
+
+impl AdminAction {
+    /// Menu order.
+    pub const ALL: [Self; 3] = [Self::BackUpLog, Self::ExportFiltered, Self::ClearLog];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::BackUpLog => "Back up log to .evtx",
+            Self::ExportFiltered => "Export filtered events to .evtx",
+            Self::ClearLog => "Clear log...",
+        }
+    }
+}
+
+/// Actions offered by the context menu of the selected event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventAction {
+    FilterSource,
+    FilterEventId,
+    FilterProcess,
+    ExcludeSource,
+    ShowRelated,
+    ServiceTimeline,
+    CopyText,
+    CopyXml,
+    CopyXPath,
+    OpenDocs,
+    ForwardSyslog,
+    Details,
+}
+
+impl EventAction {
+    /// Menu order.
+    pub const ALL: [Self; 12] = [
+        Self::FilterSource,
+        Self::FilterEventId,
+        Self::FilterProcess,
+        Self::ExcludeSource,
+        Self::ShowRelated,
+        Self::ServiceTimeline,
+        Self::CopyText,
+        Self::CopyXml,
+        Self::CopyXPath,
+        Self::OpenDocs,
+        Self::ForwardSyslog,
+        Self::Details,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FilterSource => "Filter by this Source",
+            Self::FilterEventId => "Filter by this Event ID",
+            Self::FilterProcess => "Filter by this Process",
+            Self::ExcludeSource => "Exclude this Source",
+            Self::ShowRelated => "Show related events",
+            Self::ServiceTimeline => "Show service timeline",
+            Self::CopyText => "Copy as text",
+            Self::CopyXml => "Copy XML",
+            Self::CopyXPath => "Copy XPath query",
+            Self::OpenDocs => "Open provider docs",
+            Self::ForwardSyslog => "Forward to syslog",
+            Self::Details => "Show details",
+        }
+    }
+
+    /// Events panel key that runs the action directly, shown next to its label.
+    pub fn shortcut(self) -> &'static str {
+        match self {
+            Self::FilterSource => "S",
+            Self::FilterEventId => "I",
+            Self::FilterProcess => "X",
+            Self::ShowRelated => "r",
+            Self::CopyText => "y",
+            Self::CopyXml => "c",
+            Self::CopyXPath => "Ctrl+X",
+            Self::Details => "d",
+            Self::ExcludeSource | Self::ServiceTimeline | Self::OpenDocs | Self::ForwardSyslog => {
+                ""
+            }
+        }
+    }
+}
+
+/// A search that keeps loading batches of the log until the term turns up or the log runs out.
+#[derive(Debug, Clone)]
+pub struct DeepSearch {
+    pub term: String,
+    /// Events loaded since the search ran past the loaded ones.
+    pub scanned: usize,
+}
+
+/// A batch of events being read in chunks between redraws.
+#[derive(Debug, Clone, Copy)]
+pub struct LoadProgress {
+    /// Whether the log was opened for this batch; its first event is selected when done.
+    pub initial: bool,
+    /// Events the batch reads: `batch_size`, or as sized from the measured throughput.
+    pub batch_size: usize,
+    pub fetched: usize,
+    pub started: Instant,
+}
+
+/// Columns the event table can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventColumn {
+    Level,
+    DateTime,
+    Source,
+    EventId,
+    Computer,
+    Channel,
+    User,
+    TaskCategory,
+    Keywords,
+    RecordId,
+    /// Titles of the Sigma rules the event matched.
+    Rules,
+    Message,
+}
+
+/// Position, visibility and width of one event table column. A width of 0 lets the column
+/// take the space left over by the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnSettings {
+    pub column: EventColumn,
+    pub visible: bool,
+    pub width: u16,
+}
+
+/// Retention state of a channel, used to tell how far back its events still reach.
+#[derive(Debug, Clone)]
+pub struct RetentionInfo {
+    /// The channel overwrites old events when full and does not archive them first.
+    pub overwrites: bool,
+    /// The log file has reached its maximum size.
+    pub is_full: bool,
+    pub max_size: u64,
+    pub record_count: u64,
+    pub oldest_event: Option<DateTime<Utc>>,
+}
+
+/// Column the event list is ordered by. Date and time ordering comes from the query itself;
+/// the other keys are applied to the loaded events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SortKey {
+    #[default]
+    DateTime,
+    EventId,
+    Level,
+    Source,
 }
 
 /// Represents which field is focused in the filter dialog.
@@ -84,7 +470,15 @@ pub enum FilterFieldFocus {
     EventId,
     Level,
     Time,
+    Keywords,
+    Task,
+    Opcode,
+    User,
+    Computer,
+    ExcludeSources,
     Source,
+    /// Recently applied filters, loaded into the fields.
+    Recent,
     Apply,
     Clear,
 }
@@ -103,52 +497,242 @@ pub struct AppState {
     pub focus: PanelFocus,
     pub selected_log_index: usize,
     pub selected_log_name: String,
+    pub host_name: String,
     pub events: Vec<DisplayEvent>,
     pub table_state: TableState,
     pub preview_scroll: usize,
+    /// Columns the preview is scrolled right by while it does not wrap.
+    pub preview_hscroll: usize,
+    /// Furthest the preview can scroll down, as measured when it was last drawn.
+    pub preview_max_scroll: usize,
     pub status_dialog: Option<StatusDialog>,
     pub preview_event_id: Option<String>,
     pub preview_content: Option<Text<'static>>,
     pub preview_raw_xml: Option<String>,
     pub preview_view_mode: PreviewViewMode,
-    pub log_file: Option<BufWriter<File>>,
+    /// Where `log` writes; `None` disables logging.
+    pub log_sink: Option<Box<dyn Write>>,
+    pub instance: InstanceLock,
+    pub config: Config,
+    pub persist_config: bool,
+    /// Whether bookmark changes are written to the bookmarks file; off in secondary instances.
+    pub persist_bookmarks: bool,
+    pub export_format: ExportFormat,
+    pub theme: Theme,
+    pub retention: Option<RetentionInfo>,
+    pub retention_warning_key: Option<String>,
+    /// Backend the log tabs are read from.
+    pub source: Box<dyn EventSource>,
+    /// Channels the backend offers as log tabs.
+    pub channels: Vec<String>,
+    /// Open queries of the merged tab's channels, while it is selected.
     #[cfg(target_os = "windows")]
-    pub query_handle: Option<EVT_HANDLE>,
+    pub merged_queries: Vec<MergedQuery>,
+    /// Node each listed remote event was read from, by computer and record ID, while only its
+    /// System values are loaded.
     #[cfg(target_os = "windows")]
-    pub publisher_metadata_cache: HashMap<String, EVT_HANDLE>,
+    pub deferred_events: HashMap<(String, u64), String>,
     pub is_loading: bool,
+    /// Progress of the batch being read, until it is complete or cancelled.
+    pub load_progress: Option<LoadProgress>,
+    /// Chunk sizes measured while loading, by log, kept across loads of the log.
+    pub load_tuning: HashMap<String, BatchTuner>,
+    /// Record ID of the first loaded event when the events above it were unloaded to stay
+    /// under `max_loaded_events`. Scrolling up past it reads them again.
+    pub unloaded_before: Option<u64>,
     pub no_more_events: bool,
     pub sort_descending: bool,
+    pub sort_key: SortKey,
     pub active_filter: Option<FilterCriteria>,
     pub is_searching: bool,
     pub search_term: String,
     pub last_search_term: Option<String>,
+    /// Selected row and search term from before the search bar opened, restored when the
+    /// search is cancelled.
+    pub search_origin: Option<usize>,
+    pub search_previous_term: Option<String>,
     pub is_filter_dialog_visible: bool,
     pub filter_dialog_focus: FilterFieldFocus,
     pub filter_dialog_source_index: usize,
     pub filter_dialog_event_id: String,
     pub filter_dialog_level: EventLevelFilter,
     pub filter_dialog_time: TimeFilterOption,
+    pub filter_dialog_keywords: KeywordFilter,
+    pub filter_dialog_task: String,
+    pub filter_dialog_opcode: String,
+    pub filter_dialog_user: String,
+    pub filter_dialog_computer: String,
+    /// Comma-separated sources to hide.
+    pub filter_dialog_excluded_sources: String,
+    /// Recent filter loaded into the dialog, by index in `recent_filters`.
+    pub filter_dialog_recent: Option<usize>,
+    /// Filters the event list was loaded with, for going back and forward.
+    pub filter_history: FilterHistory,
     pub available_sources: Option<Vec<String>>,
     pub filter_dialog_source_input: String,
     pub filter_dialog_filtered_sources: Vec<(usize, String)>,
     pub filter_dialog_filtered_source_selection: Option<usize>,
     pub filter_event_id_cursor: usize,
     pub filter_source_cursor: usize,
+    pub filter_task_cursor: usize,
+    pub filter_opcode_cursor: usize,
+    pub filter_user_cursor: usize,
+    pub filter_computer_cursor: usize,
+    pub filter_excluded_sources_cursor: usize,
     pub search_cursor: usize,
+    /// Substring narrowing the loaded events without querying the log again.
+    pub local_filter: String,
+    pub local_filter_cursor: usize,
+    pub is_local_filter_input: bool,
+    /// Indices into `events` of the rows shown while the local filter is set. Table rows
+    /// index into this list instead of `events`.
+    pub local_filter_indices: Option<Vec<usize>>,
+    pub deep_search: Option<DeepSearch>,
+    pub is_query_input_visible: bool,
+    pub query_input: String,
+    pub query_cursor: usize,
+    pub raw_query: Option<String>,
+    pub incident: Option<Incident>,
+    pub prompt: Option<PromptKind>,
+    pub prompt_input: String,
+    pub prompt_cursor: usize,
+    /// Record ID to select once the log has reloaded, from an imported view.
+    pub pending_record_id: Option<u64>,
+    pub pending_preview_scroll: usize,
+    /// Read access to each of `channels`, by index.
+    pub channel_access: Vec<ChannelAccess>,
+    /// Whether the process runs elevated, checked at startup.
+    pub is_elevated: bool,
+    /// Log the elevation dialog is shown for, after reading it was denied.
+    pub elevation_prompt: Option<String>,
+    pub message_catalog: Option<MessageCatalog>,
+    /// Catalog used for every log when neither the publisher nor the log's own bundle has the
+    /// message (`catalog.json` next to the config file).
+    pub shared_catalog: Option<MessageCatalog>,
+    /// Message templates read from publisher metadata for events whose message could not be
+    /// formatted, by provider and event ID; `None` when the publisher has none.
+    #[cfg(target_os = "windows")]
+    pub publisher_templates: HashMap<(String, u32), Option<String>>,
     pub help_dialog_visible: bool,
+    pub is_column_dialog_visible: bool,
+    pub bookmarks: Vec<Bookmark>,
+    /// Action waiting for the overwrite preview in the status dialog to be confirmed.
+    pub pending_action: Option<PendingAction>,
+    /// Unexpected shutdown analysis lines, by `EventRecordID`, so the System log is only
+    /// queried once per Kernel-Power 41 event.
+    pub shutdown_analysis_cache: HashMap<u64, Vec<String>>,
+    /// Labeled fields of the event shown in the full-screen detail view, while it is open.
+    pub detail_fields: Option<Vec<(String, String)>>,
+    pub detail_selection: usize,
+    /// Failed DNS resolutions view, while it is open.
+    #[cfg(target_os = "windows")]
+    pub dns_analysis: Option<DnsAnalysis>,
+    /// Hyper-V guests view, while it is open.
+    #[cfg(target_os = "windows")]
+    pub hyperv_view: Option<HyperVView>,
+    /// Windows Installer transactions view, while it is open.
+    #[cfg(target_os = "windows")]
+    pub installer_view: Option<InstallerView>,
+    /// Print jobs view, while it is open.
+    #[cfg(target_os = "windows")]
+    pub print_view: Option<PrintView>,
+    /// Device activity view, while it is open.
+    #[cfg(target_os = "windows")]
+    pub device_view: Option<DeviceView>,
+    /// Update failures view, while it is open.
+    #[cfg(target_os = "windows")]
+    pub servicing_view: Option<ServicingView>,
+    /// Audit policy snapshot, while it is open.
+    pub audit_policy: Option<AuditPolicyView>,
+    /// Authentication flow of one account, while it is open.
+    #[cfg(target_os = "windows")]
+    pub auth_flow: Option<AuthFlowView>,
+    /// Object access view of file and registry auditing, while it is open.
+    #[cfg(target_os = "windows")]
+    pub object_access: Option<ObjectAccessView>,
+    /// Host groups from `hosts.toml`, offered by the host picker and giving the credentials
+    /// remote hosts are read with.
+    pub hosts: HostInventory,
+    /// Whether each host answered when it was last checked or read, by lowercase name.
+    pub host_status: HashMap<String, HostStatus>,
+    /// Host picker, while it is open.
+    pub host_picker: Option<HostPicker>,
+    /// WMI client failures view, while it is open.
+    #[cfg(target_os = "windows")]
+    pub wmi_analysis: Option<WmiAnalysis>,
+    /// Sigma rules loaded events are matched against, from `sigma_rules_dir`.
+    pub sigma_rules: Vec<SigmaRule>,
+    /// Number of rule files left out because they could not be used.
+    pub sigma_skipped: usize,
+    /// Sigma hits panel, while it is open.
+    pub sigma_view: Option<SigmaView>,
+    /// Rule the event list is narrowed to; an empty title keeps the events matching any rule.
+    pub sigma_filter: Option<String>,
+    /// Time window comparison, while it is open.
+    pub comparison: Option<Comparison>,
+    /// Pane shown beside the event list in split view.
+    pub split_pane: Option<SplitPane>,
+    /// Count typed before a vim motion, e.g. the 20 of `20j`.
+    pub key_count: Option<usize>,
+    pub pending_mark: Option<MarkKey>,
+    /// Events marked with `'` and a letter, by letter: their channel and record ID.
+    pub marks: HashMap<char, (String, u64)>,
+    /// Rows of the event table, as measured when it was last drawn.
+    pub events_visible_rows: usize,
+    pub trigger_runner: TriggerRunner,
+    /// OTLP export still being sent by [O].
+    pub otlp_export: Option<OtlpExport>,
+    /// Last trigger firing or failure, or new error events, and when it happened, shown in the
+    /// footer for a while.
+    pub footer_notice: Option<(String, Instant)>,
+    /// Highlighted action while the context menu of the selected event is open.
+    pub context_menu_selection: Option<usize>,
+    /// Highlighted action while the admin menu of the selected log is open.
+    pub admin_menu_selection: Option<usize>,
+    /// Backup file chosen for the log clear waiting for its typed confirmation.
+    pub clear_log_backup: Option<PathBuf>,
+    /// Cluster nodes whose Failover Clustering events fill the event list instead of the
+    /// selected log, while the merged view is shown.
+    pub cluster_nodes: Option<Vec<String>>,
+    /// Number of time buckets in the event histogram as last drawn, which the bucket keys
+    /// step through.
+    pub histogram_buckets: usize,
+    /// Whether IIS and HTTPERR log entries are interleaved with the loaded events.
+    pub web_logs_merged: bool,
+    /// Whether events logged since the newest loaded one are merged into the list every
+    /// `auto_refresh_secs` of the config.
+    pub auto_refresh: bool,
+    /// When new events were last merged, or auto-refresh was turned on.
+    pub last_refresh: Instant,
+    pub is_bookmarks_dialog_visible: bool,
+    pub bookmarks_selection: usize,
+    pub column_dialog_selection: usize,
     pub help_scroll_position: usize,
 }
 
 // Constants
+/// Events per load before the throughput of a log has been measured, with `batch_size = 0`.
 pub const EVENT_BATCH_SIZE: usize = 1000;
-pub const LOG_NAMES: [&str; 5] = [
-    "Application",
-    "System",
-    "Security",
-    "Setup",
-    "ForwardedEvents",
-];
+/// Reading time a load sized from the measured throughput aims at, and its fewest and most
+/// events.
+pub const AUTO_BATCH_SECONDS: f64 = 1.0;
+pub const AUTO_BATCH_RANGE: (usize, usize) = (200, 5000);
+/// Events the first chunk of a newly opened log reads, so the list shows them right away.
+pub const FIRST_PAINT_EVENTS: usize = 50;
+pub const DEFAULT_SPLIT_PERCENT: u16 = 65;
+pub const MIN_SPLIT_PERCENT: u16 = 30;
+pub const MAX_SPLIT_PERCENT: u16 = 85;
+pub const SPLIT_STEP_PERCENT: u16 = 5;
+/// Columns the preview scrolls sideways per Left/Right while it does not wrap.
+pub const PREVIEW_HSCROLL_STEP: usize = 8;
+pub const MIN_COLUMN_WIDTH: u16 = 4;
+pub const MAX_COLUMN_WIDTH: u16 = 80;
+pub const DEFAULT_MESSAGE_COLUMN_PERCENT: u16 = 40;
+pub const MIN_MESSAGE_COLUMN_PERCENT: u16 = 10;
+pub const MAX_MESSAGE_COLUMN_PERCENT: u16 = 80;
+/// Tab after the backend's channels interleaving the channels listed in
+/// `Config::merged_logs`.
+pub const MERGED_TAB_NAME: &str = "Merged";
 
 impl StatusDialog {
     /// Creates a new StatusDialog with the given title, message, and error flag.
@@ -158,6 +742,7 @@ impl StatusDialog {
             message: message.to_string(),
             visible: true,
             is_error,
+            offers_attach: false,
         }
     }
     /// Dismisses the status dialog.
@@ -248,14 +833,581 @@ impl TimeFilterOption {
     }
 }
 
+impl RetentionInfo {
+    /// Returns true if events are being overwritten, so the oldest retained event marks
+    /// the limit of what can still be queried.
+    pub fn is_losing_events(&self) -> bool {
+        self.overwrites && self.is_full
+    }
+
+    /// Time span between the oldest retained event and now.
+    pub fn horizon(&self) -> Option<Duration> {
+        self.oldest_event.map(|oldest| Utc::now() - oldest)
+    }
+}
+
+impl ChannelAccess {
+    /// Returns false only when the channel is known to be unreadable.
+    pub fn is_readable(&self) -> bool {
+        matches!(self, Self::Readable | Self::Unknown)
+    }
+
+    /// Explains why a channel cannot be read and what would allow it.
+    pub fn explanation(&self, channel: &str) -> String {
+        match self {
+            Self::Denied if channel.eq_ignore_ascii_case("Security") => format!(
+                "Your account cannot read the '{}' log.\n\nIt can only be read by administrators or accounts holding the 'Manage auditing and security log' right. Restart Event Commander from an elevated prompt (Run as administrator).",
+                channel
+            ),
+            Self::Denied if cfg!(target_os = "linux") => format!(
+                "Your account cannot read the '{}' journal.\n\nRun Event Commander as root, or ask for your account to be added to the 'systemd-journal' or 'adm' group and sign in again.",
+                channel
+            ),
+            Self::Denied => format!(
+                "Your account cannot read the '{}' log.\n\nRun Event Commander as administrator, or ask for your account to be added to the local 'Event Log Readers' group and sign in again.",
+                channel
+            ),
+            Self::Missing => format!("The '{}' log does not exist on this computer.", channel),
+            Self::Failed(code) => format!(
+                "The '{}' log could not be opened (WIN32_ERROR({})).",
+                channel, code
+            ),
+            Self::Readable | Self::Unknown => format!("The '{}' log can be read.", channel),
+        }
+    }
+}
+
+impl EventLevel {
+    /// Maps a `System/Level` value to a level, treating classic level 0 as informational.
+    #[cfg(target_os = "windows")]
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            1 => Self::Critical,
+            2 => Self::Error,
+            3 => Self::Warning,
+            0 | 4 => Self::Information,
+            5 => Self::Verbose,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// Returns the `System/Level` value for this level.
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Critical => 1,
+            Self::Error => 2,
+            Self::Warning => 3,
+            Self::Information => 4,
+            Self::Verbose => 5,
+            Self::Unknown(code) => *code,
+        }
+    }
+
+    /// Orders levels from most to least severe.
+    pub fn severity_rank(&self) -> u8 {
+        match self {
+            Self::Critical => 0,
+            Self::Error => 1,
+            Self::Warning => 2,
+            Self::Information => 3,
+            Self::Verbose => 4,
+            Self::Unknown(_) => 5,
+        }
+    }
+
+    /// Returns a displayable name for the level.
+    pub fn display_name(&self) -> String {
+        match self {
+            Self::Critical => "Critical".to_string(),
+            Self::Error => "Error".to_string(),
+            Self::Warning => "Warning".to_string(),
+            Self::Information => "Information".to_string(),
+            Self::Verbose => "Verbose".to_string(),
+            Self::Unknown(code) => format!("Unknown({})", code),
+        }
+    }
+}
+
+impl EventData {
+    /// Returns the fields that carry a name, in document order.
+    pub fn named(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_deref()?, value.as_str())))
+    }
+
+    /// Returns the values in document order, as used for `%n` message insertions.
+    pub fn values(&self) -> Vec<String> {
+        self.fields.iter().map(|(_, value)| value.clone()).collect()
+    }
+}
+
+impl DisplayEvent {
+    /// Replaces the event's XML with `record_stub`. The XML is read again by record ID when
+    /// the event is selected.
+    #[cfg(target_os = "windows")]
+    pub fn drop_raw_xml(&mut self) {
+        self.raw_data = self.record_stub();
+        self.raw_dropped = true;
+    }
+
+    /// XML naming only the event's record, which still tells events apart.
+    #[cfg(target_os = "windows")]
+    pub fn record_stub(&self) -> String {
+        format!(
+            "<Event><System><EventRecordID>{}</EventRecordID><Channel>{}</Channel>\
+             <Computer>{}</Computer></System></Event>",
+            self.record_id,
+            quick_xml::escape::escape(&self.channel),
+            quick_xml::escape::escape(&self.computer)
+        )
+    }
+
+    /// An XPath query matching only this event, for Event Viewer custom views, `wevtutil qe`
+    /// or `Get-WinEvent -FilterXPath`: its provider, Event ID and channel with its record ID,
+    /// or the millisecond it was logged for events without one.
+    pub fn xpath(&self) -> String {
+        let mut conditions = vec![
+            format!(
+                "Provider[@Name='{}']",
+                helpers::escape_xpath_literal(&self.provider_name_original)
+            ),
+            format!("EventID={}", self.event_id),
+        ];
+        if !self.channel.is_empty() {
+            conditions.push(format!(
+                "Channel='{}'",
+                helpers::escape_xpath_literal(&self.channel)
+            ));
+        }
+        if self.record_id > 0 {
+            conditions.push(format!("EventRecordID={}", self.record_id));
+        } else if let Some(time) = self.timestamp {
+            conditions.push(format!(
+                "TimeCreated[@SystemTime>='{}' and @SystemTime<'{}']",
+                analysis::xpath_time(time),
+                analysis::xpath_time(time + Duration::milliseconds(1))
+            ));
+        }
+        format!("*[System[{}]]", conditions.join(" and "))
+    }
+
+    /// Fills a `preview_template`: `{name}` is replaced by the event's field of that name,
+    /// `{data.Name}` by a named event data field (empty when the event has none) and `{data}`
+    /// by all named fields, one per line. `{{` and `}}` stand for braces; unknown placeholders
+    /// are left as they are.
+    pub fn fill_template(&self, template: &str, message: &str) -> String {
+        let mut filled = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find(['{', '}']) {
+            filled.push_str(&rest[..start]);
+            rest = &rest[start..];
+            if let Some(after) = rest.strip_prefix("{{").or_else(|| rest.strip_prefix("}}")) {
+                filled.push_str(&rest[..1]);
+                rest = after;
+                continue;
+            }
+            let Some(end) = rest.find('}').filter(|_| rest.starts_with('{')) else {
+                filled.push_str(&rest[..1]);
+                rest = &rest[1..];
+                continue;
+            };
+            let name = &rest[1..end];
+            match self.template_value(name, message) {
+                Some(value) => filled.push_str(&value),
+                None => filled.push_str(&rest[..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        filled.push_str(rest);
+        filled
+    }
+
+    fn template_value(&self, name: &str, message: &str) -> Option<String> {
+        let optional = |value: Option<String>| Some(value.unwrap_or_default());
+        if let Some(field) = name.strip_prefix("data.") {
+            return optional(
+                self.event_data
+                    .named()
+                    .find(|(data_name, _)| data_name.eq_ignore_ascii_case(field))
+                    .map(|(_, value)| value.to_string()),
+            );
+        }
+        match name {
+            "level" => Some(self.level.display_name()),
+            "datetime" => Some(self.local_time()),
+            "source" => Some(self.source.clone()),
+            "event_id" => Some(self.event_id.to_string()),
+            "computer" => Some(self.computer.clone()),
+            "channel" => Some(self.channel.clone()),
+            "user" => Some(self.user.clone()),
+            "record_id" => Some(self.record_id.to_string()),
+            "task" => optional(self.task_category.clone()),
+            "process_id" => optional(self.process_id.map(|id| id.to_string())),
+            "thread_id" => optional(self.thread_id.map(|id| id.to_string())),
+            "activity_id" => optional(self.activity_id.clone()),
+            "message" => Some(message.to_string()),
+            "data" => Some(
+                self.event_data
+                    .named()
+                    .map(|(data_name, value)| format!("{}: {}", data_name, value))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Formats the creation time in the local time zone, as shown in the event list.
+    pub fn local_time(&self) -> String {
+        helpers::format_event_time(self.timestamp)
+    }
+
+    /// Names of the standard keyword bits set on the event, comma separated.
+    pub fn keyword_names(&self) -> String {
+        let mut names = Vec::new();
+        let mut keyword = KeywordFilter::Any.next();
+        while keyword != KeywordFilter::Any {
+            if keyword.mask().is_some_and(|mask| self.keywords & mask != 0) {
+                names.push(keyword.display_name().to_string());
+            }
+            keyword = keyword.next();
+        }
+        names.join(", ")
+    }
+}
+
+impl EventColumn {
+    pub const ALL: [Self; 12] = [
+        Self::Level,
+        Self::DateTime,
+        Self::Source,
+        Self::EventId,
+        Self::Computer,
+        Self::Channel,
+        Self::User,
+        Self::TaskCategory,
+        Self::Keywords,
+        Self::RecordId,
+        Self::Rules,
+        Self::Message,
+    ];
+
+    /// Returns the column header.
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Level => "Level",
+            Self::DateTime => "Date and Time",
+            Self::Source => "Source",
+            Self::EventId => "Event ID",
+            Self::Computer => "Computer",
+            Self::Channel => "Channel",
+            Self::User => "User",
+            Self::TaskCategory => "Task Category",
+            Self::Keywords => "Keywords",
+            Self::RecordId => "Record ID",
+            Self::Rules => "Rules",
+            Self::Message => "Message",
+        }
+    }
+
+    /// Width used until the user changes it; 0 fills the remaining space. The message column
+    /// is sized by `Config::message_column_percent` instead.
+    pub fn default_width(&self) -> u16 {
+        match self {
+            Self::Level => 11,
+            Self::DateTime => 22,
+            Self::Source => 0,
+            Self::EventId => 10,
+            Self::Computer => 20,
+            Self::Channel => 16,
+            Self::User => 24,
+            Self::TaskCategory => 20,
+            Self::Keywords => 16,
+            Self::RecordId => 10,
+            Self::Rules => 24,
+            Self::Message => 0,
+        }
+    }
+
+    /// Sort key that orders the list by this column, if there is one.
+    pub fn sort_key(&self) -> Option<SortKey> {
+        match self {
+            Self::Level => Some(SortKey::Level),
+            Self::DateTime => Some(SortKey::DateTime),
+            Self::Source => Some(SortKey::Source),
+            Self::EventId => Some(SortKey::EventId),
+            _ => None,
+        }
+    }
+
+    /// Returns the cell text for an event.
+    pub fn text(&self, event: &DisplayEvent) -> String {
+        match self {
+            Self::Level => event.level.display_name(),
+            Self::DateTime => event.local_time(),
+            Self::Source => event.source.clone(),
+            Self::EventId => event.event_id.to_string(),
+            Self::Computer => event.computer.clone(),
+            Self::Channel => event.channel.clone(),
+            Self::User => {
+                if event.user.is_empty() {
+                    "N/A".to_string()
+                } else {
+                    event.user.clone()
+                }
+            }
+            Self::TaskCategory => match &event.task_category {
+                Some(name) => name.clone(),
+                None if event.task == 0 => "None".to_string(),
+                None => format!("({})", event.task),
+            },
+            Self::Keywords => event.keyword_names(),
+            Self::RecordId => event.record_id.to_string(),
+            Self::Rules => event.sigma_hits.join(", "),
+            Self::Message => helpers::message_summary(
+                event.formatted_message.as_deref().unwrap_or(&event.message),
+            ),
+        }
+    }
+}
+
+impl ColumnSettings {
+    /// The classic four columns followed by the optional ones, hidden.
+    pub fn defaults() -> Vec<Self> {
+        EventColumn::ALL
+            .iter()
+            .map(|&column| Self {
+                column,
+                visible: column.sort_key().is_some(),
+                width: column.default_width(),
+            })
+            .collect()
+    }
+
+    /// Drops duplicate entries and appends any column missing from `columns` as hidden, so a
+    /// hand-edited list always covers every column exactly once.
+    pub fn normalize(columns: &mut Vec<Self>) {
+        let mut seen = Vec::new();
+        columns.retain(|settings| {
+            let is_new = !seen.contains(&settings.column);
+            seen.push(settings.column);
+            is_new
+        });
+        for column in EventColumn::ALL {
+            if !seen.contains(&column) {
+                columns.push(Self {
+                    column,
+                    visible: false,
+                    width: column.default_width(),
+                });
+            }
+        }
+        for settings in columns.iter_mut().filter(|s| s.width != 0) {
+            settings.width = settings.width.clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+        }
+        if !columns.iter().any(|s| s.visible) {
+            columns[0].visible = true;
+        }
+    }
+}
+
+impl SortKey {
+    /// Cycles to the next sort key.
+    pub fn next(&self) -> Self {
+        match self {
+            Self::DateTime => Self::EventId,
+            Self::EventId => Self::Level,
+            Self::Level => Self::Source,
+            Self::Source => Self::DateTime,
+        }
+    }
+
+    /// Returns a displayable name for the sort key.
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::DateTime => "Date/Time",
+            Self::EventId => "Event ID",
+            Self::Level => "Level",
+            Self::Source => "Source",
+        }
+    }
+
+    /// Compares two events by this key in ascending order.
+    pub fn compare(&self, a: &DisplayEvent, b: &DisplayEvent) -> std::cmp::Ordering {
+        match self {
+            Self::DateTime => a.timestamp.cmp(&b.timestamp),
+            Self::EventId => a.event_id.cmp(&b.event_id),
+            Self::Level => b.level.severity_rank().cmp(&a.level.severity_rank()),
+            Self::Source => a.source.to_lowercase().cmp(&b.source.to_lowercase()),
+        }
+    }
+}
+
+impl ExportFormat {
+    /// Cycles to the next export format.
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Cef => Self::Leef,
+            Self::Leef => Self::Ndjson,
+            Self::Ndjson => Self::Cef,
+        }
+    }
+
+    /// Returns a displayable name for the export format.
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Cef => "CEF",
+            Self::Leef => "LEEF",
+            Self::Ndjson => "NDJSON",
+        }
+    }
+
+    /// Returns the file extension used for exports in this format.
+    pub fn extension(&self) -> &str {
+        match self {
+            Self::Cef => "cef",
+            Self::Leef => "leef",
+            Self::Ndjson => "ndjson",
+        }
+    }
+}
+
+impl KeywordFilter {
+    /// Cycles to the next keyword filter.
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Any => Self::AuditSuccess,
+            Self::AuditSuccess => Self::AuditFailure,
+            Self::AuditFailure => Self::Classic,
+            Self::Classic => Self::CorrelationHint,
+            Self::CorrelationHint => Self::ResponseTime,
+            Self::ResponseTime => Self::Sqm,
+            Self::Sqm => Self::WdiContext,
+            Self::WdiContext => Self::WdiDiagnostic,
+            Self::WdiDiagnostic => Self::Any,
+        }
+    }
+
+    /// Cycles to the previous keyword filter.
+    pub fn previous(&self) -> Self {
+        match self {
+            Self::Any => Self::WdiDiagnostic,
+            Self::AuditSuccess => Self::Any,
+            Self::AuditFailure => Self::AuditSuccess,
+            Self::Classic => Self::AuditFailure,
+            Self::CorrelationHint => Self::Classic,
+            Self::ResponseTime => Self::CorrelationHint,
+            Self::Sqm => Self::ResponseTime,
+            Self::WdiContext => Self::Sqm,
+            Self::WdiDiagnostic => Self::WdiContext,
+        }
+    }
+
+    /// Returns a displayable name for the keyword filter.
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Any => "Any",
+            Self::AuditSuccess => "Audit Success",
+            Self::AuditFailure => "Audit Failure",
+            Self::Classic => "Classic",
+            Self::CorrelationHint => "Correlation Hint",
+            Self::ResponseTime => "Response Time",
+            Self::Sqm => "SQM",
+            Self::WdiContext => "WDI Context",
+            Self::WdiDiagnostic => "WDI Diagnostic",
+        }
+    }
+
+    /// Returns the keyword bit mask matched by this filter, or None for Any.
+    pub fn mask(&self) -> Option<u64> {
+        match self {
+            Self::Any => None,
+            Self::ResponseTime => Some(0x0001_0000_0000_0000),
+            Self::WdiContext => Some(0x0002_0000_0000_0000),
+            Self::WdiDiagnostic => Some(0x0004_0000_0000_0000),
+            Self::Sqm => Some(0x0008_0000_0000_0000),
+            Self::AuditFailure => Some(0x0010_0000_0000_0000),
+            Self::AuditSuccess => Some(0x0020_0000_0000_0000),
+            Self::CorrelationHint => Some(0x0040_0000_0000_0000),
+            Self::Classic => Some(0x0080_0000_0000_0000),
+        }
+    }
+}
+
+impl FilterCriteria {
+    /// Returns true if no criteria are set, i.e. the filter matches every event.
+    pub fn is_empty(&self) -> bool {
+        self.source.is_none()
+            && self.event_id.is_none()
+            && self.level == EventLevelFilter::All
+            && self.time_filter == TimeFilterOption::AnyTime
+            && self.keywords == KeywordFilter::Any
+            && self.task.is_none()
+            && self.opcode.is_none()
+            && self.user_sid.is_none()
+            && self.computer.is_none()
+            && self.excluded_sources.is_empty()
+    }
+
+    /// Short description of the criteria set, e.g. "ID 4625, Error/Crit, Last 24 Hours".
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(event_id) = &self.event_id {
+            parts.push(format!("ID {}", event_id));
+        }
+        if let Some(source) = &self.source {
+            parts.push(source.clone());
+        }
+        if self.level != EventLevelFilter::All {
+            parts.push(self.level.display_name().to_string());
+        }
+        if self.time_filter != TimeFilterOption::AnyTime {
+            parts.push(self.time_filter.display_name().to_string());
+        }
+        if self.keywords != KeywordFilter::Any {
+            parts.push(self.keywords.display_name().to_string());
+        }
+        if let Some(task) = self.task {
+            parts.push(format!("Task {}", task));
+        }
+        if let Some(opcode) = self.opcode {
+            parts.push(format!("Opcode {}", opcode));
+        }
+        if let Some(user) = &self.user_sid {
+            parts.push(user.clone());
+        }
+        if let Some(computer) = &self.computer {
+            parts.push(computer.clone());
+        }
+        parts.extend(
+            self.excluded_sources
+                .iter()
+                .map(|source| format!("-{}", source)),
+        );
+        if parts.is_empty() {
+            "No filter".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
 impl FilterFieldFocus {
     /// Cycles to the next field in the filter dialog.
     pub fn next(&self) -> Self {
         match self {
             Self::EventId => Self::Level,
             Self::Level => Self::Time,
-            Self::Time => Self::Source,
-            Self::Source => Self::Apply,
+            Self::Time => Self::Keywords,
+            Self::Keywords => Self::Task,
+            Self::Task => Self::Opcode,
+            Self::Opcode => Self::User,
+            Self::User => Self::Computer,
+            Self::Computer => Self::ExcludeSources,
+            Self::ExcludeSources => Self::Source,
+            Self::Source => Self::Recent,
+            Self::Recent => Self::Apply,
             Self::Apply => Self::Clear,
             Self::Clear => Self::EventId,
         }
@@ -267,8 +1419,15 @@ impl FilterFieldFocus {
             Self::EventId => Self::Clear,
             Self::Level => Self::EventId,
             Self::Time => Self::Level,
-            Self::Source => Self::Time,
-            Self::Apply => Self::Source,
+            Self::Keywords => Self::Time,
+            Self::Task => Self::Keywords,
+            Self::Opcode => Self::Task,
+            Self::User => Self::Opcode,
+            Self::Computer => Self::User,
+            Self::ExcludeSources => Self::Computer,
+            Self::Source => Self::ExcludeSources,
+            Self::Recent => Self::Source,
+            Self::Apply => Self::Recent,
             Self::Clear => Self::Apply,
         }
     }
@@ -0,0 +1,315 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::analysis;
+use crate::helpers;
+use crate::models::DisplayEvent;
+
+/// Log the Windows Installer service writes to.
+pub const INSTALLER_CHANNEL: &str = "Application";
+pub const INSTALLER_QUERY: &str = "*[System[Provider[@Name='MsiInstaller']]]";
+
+/// Most installer events read.
+pub const MAX_INSTALLER_EVENTS: usize = 5000;
+
+/// Events starting and ending an installer transaction.
+const TRANSACTION_BEGIN: u32 = 1040;
+const TRANSACTION_END: u32 = 1042;
+
+/// Result events whose data is product name, version, language, status and manufacturer.
+const PRODUCT_RESULTS: &[(u32, &str)] = &[
+    (1033, "Install"),
+    (1034, "Removal"),
+    (1035, "Reconfiguration"),
+    (1036, "Update install"),
+    (1037, "Update removal"),
+];
+
+/// Result events whose data is a "Product: name -- outcome" line.
+const OUTCOME_RESULTS: &[(u32, &str, bool)] = &[
+    (11707, "Install", true),
+    (11708, "Install", false),
+    (11724, "Removal", true),
+    (11725, "Removal", false),
+    (11728, "Configuration", true),
+    (11729, "Configuration", false),
+];
+
+/// Windows Installer error codes, as returned by msiexec and logged as event 10000 + code.
+const INSTALLER_ERRORS: &[(u32, &str)] = &[
+    (0, "success"),
+    (1304, "error writing to a file"),
+    (
+        1310,
+        "error writing to a file; it may be locked or access is denied",
+    ),
+    (1311, "source file not found"),
+    (1316, "network error reading from a file"),
+    (1402, "registry key could not be opened"),
+    (1406, "registry value could not be written"),
+    (1500, "another installation is in progress"),
+    (1601, "the Windows Installer service could not be accessed"),
+    (1602, "cancelled by the user"),
+    (1603, "fatal error during installation"),
+    (1605, "the product is not installed"),
+    (1612, "the installation source is not available"),
+    (1618, "another installation is already in progress"),
+    (1619, "the package could not be opened"),
+    (1620, "the package is not a valid installer package"),
+    (1624, "a transform could not be applied"),
+    (1625, "the installation is forbidden by system policy"),
+    (1633, "the platform is not supported"),
+    (1638, "another version of the product is already installed"),
+    (1639, "invalid command line"),
+    (1641, "a restart was started to complete the installation"),
+    (1720, "a custom action script failed"),
+    (1721, "a custom action program could not be run"),
+    (1722, "a custom action program ended unexpectedly"),
+    (1723, "a custom action DLL could not be run"),
+    (1920, "a service failed to start"),
+    (1923, "a service could not be installed"),
+    (1935, "an assembly could not be installed"),
+    (3010, "a restart is required to complete the installation"),
+];
+
+/// Installer results that mean the product was changed.
+const SUCCESS_STATUSES: [u32; 3] = [0, 1641, 3010];
+
+/// Events not belonging to a begin/end pair are grouped with a result for the same product
+/// logged within this many minutes.
+const LOOSE_EVENT_MINUTES: i64 = 10;
+
+/// Names an installer error code.
+pub fn error_name(code: u32) -> String {
+    match INSTALLER_ERRORS.iter().find(|(known, _)| *known == code) {
+        Some((_, name)) => format!("{} ({})", code, name),
+        None => code.to_string(),
+    }
+}
+
+/// One event of a transaction, reduced to what the view lists.
+#[derive(Debug, Clone)]
+pub struct InstallerStep {
+    pub time: DateTime<Utc>,
+    pub event_id: u32,
+    pub summary: String,
+}
+
+/// The installer events of one install, removal or repair, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct Transaction {
+    pub started: Option<DateTime<Utc>>,
+    pub ended: Option<DateTime<Utc>>,
+    /// Package or product code the transaction was started for (event 1040).
+    pub package: Option<String>,
+    pub product: Option<String>,
+    pub version: Option<String>,
+    pub manufacturer: Option<String>,
+    pub action: Option<&'static str>,
+    /// Installer status of the result event; None when no result was logged.
+    pub status: Option<u32>,
+    pub succeeded: Option<bool>,
+    /// Decoded error events logged during the transaction.
+    pub errors: Vec<String>,
+    pub steps: Vec<InstallerStep>,
+}
+
+impl Transaction {
+    pub fn time(&self) -> DateTime<Utc> {
+        self.started
+            .or_else(|| self.steps.first().map(|step| step.time))
+            .unwrap_or_default()
+    }
+
+    fn last_time(&self) -> DateTime<Utc> {
+        self.steps
+            .last()
+            .map_or_else(|| self.time(), |step| step.time)
+    }
+
+    pub fn result(&self) -> &'static str {
+        match (self.succeeded, self.status) {
+            (Some(true), Some(1641 | 3010)) => "Restart needed",
+            (Some(true), _) => "Succeeded",
+            (Some(false), _) => "Failed",
+            (None, _) if !self.errors.is_empty() => "Errors",
+            (None, _) => "No result",
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.product
+            .as_deref()
+            .or(self.package.as_deref())
+            .unwrap_or("(unknown product)")
+    }
+
+    /// Describes the transaction for the detail pane.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("Product:      {}", self.name())];
+        let details = [
+            ("Version", self.version.clone()),
+            ("Manufacturer", self.manufacturer.clone()),
+            ("Package", self.package.clone()),
+            ("Action", self.action.map(str::to_string)),
+            (
+                "Result",
+                Some(match self.status {
+                    Some(status) => format!("{}, status {}", self.result(), error_name(status)),
+                    None => self.result().to_string(),
+                }),
+            ),
+            ("Started", Some(helpers::format_local_time(self.time()))),
+            ("Ended", self.ended.map(helpers::format_local_time)),
+        ];
+        for (label, value) in details {
+            if let Some(value) = value {
+                lines.push(format!("{:<14}{}", format!("{}:", label), value));
+            }
+        }
+        if !self.errors.is_empty() {
+            lines.push(String::new());
+            lines.push("--- Errors ---".to_string());
+            lines.extend(self.errors.iter().cloned());
+        }
+        lines.push(String::new());
+        lines.push("--- Events ---".to_string());
+        lines.extend(self.steps.iter().map(|step| {
+            format!(
+                "{}  {:>5}  {}",
+                helpers::format_local_time(step.time),
+                step.event_id,
+                step.summary
+            )
+        }));
+        lines
+    }
+
+    /// Structured query for every Application event logged during the transaction, for the
+    /// raw query mode.
+    pub fn events_query(&self) -> String {
+        let start = analysis::xpath_time(self.time() - Duration::seconds(1));
+        let end =
+            analysis::xpath_time(self.ended.unwrap_or(self.last_time()) + Duration::seconds(1));
+        format!(
+            "<QueryList><Query Id=\"0\"><Select Path=\"{}\">*[System[TimeCreated[@SystemTime &gt;= '{}' and @SystemTime &lt;= '{}']]]</Select></Query></QueryList>",
+            INSTALLER_CHANNEL, start, end
+        )
+    }
+
+    fn add(&mut self, time: DateTime<Utc>, event: &DisplayEvent) {
+        let message = event.formatted_message.as_deref().unwrap_or(&event.message);
+        let data = event.event_data.values();
+        if let Some((_, action)) = PRODUCT_RESULTS.iter().find(|(id, _)| *id == event.event_id) {
+            let field = |index: usize| data.get(index).filter(|value| !value.is_empty()).cloned();
+            self.product = field(0).or(self.product.take());
+            self.version = field(1).or(self.version.take());
+            self.manufacturer = field(4).or(self.manufacturer.take());
+            self.action = Some(action);
+            self.status = field(3).and_then(|status| status.parse().ok());
+            self.succeeded = self.status.map(|status| SUCCESS_STATUSES.contains(&status));
+        } else if let Some((_, action, succeeded)) = OUTCOME_RESULTS
+            .iter()
+            .find(|(id, _, _)| *id == event.event_id)
+        {
+            self.product = product_of(message).or(self.product.take());
+            self.action.get_or_insert(action);
+            self.succeeded.get_or_insert(*succeeded);
+        } else if (11000..12000).contains(&event.event_id) {
+            self.product = product_of(message).or(self.product.take());
+            self.errors.push(format!(
+                "Error {}: {}",
+                error_name(event.event_id - 10000),
+                helpers::message_summary(message)
+            ));
+        }
+        self.steps.push(InstallerStep {
+            time,
+            event_id: event.event_id,
+            summary: helpers::message_summary(message),
+        });
+    }
+}
+
+/// Reads the product name out of a "Product: name -- outcome" message.
+fn product_of(message: &str) -> Option<String> {
+    let rest = message.trim().strip_prefix("Product:")?;
+    let (name, _) = rest.split_once(" -- ")?;
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+/// State of the installer view.
+#[derive(Debug, Clone, Default)]
+pub struct InstallerView {
+    /// Newest first.
+    pub transactions: Vec<Transaction>,
+    /// Why the installer events could not be read.
+    pub unavailable: Option<String>,
+    pub selection: usize,
+    /// Transaction whose details are shown instead of the list.
+    pub open_transaction: Option<usize>,
+    pub detail_scroll: usize,
+}
+
+impl InstallerView {
+    /// Groups MsiInstaller events into transactions: the events between a begin (1040) and
+    /// end (1042) event, or a result with the events for the same product logged shortly
+    /// before it.
+    pub fn new(events: &[DisplayEvent], unavailable: Option<String>) -> Self {
+        // Events whose time could not be read have no place in a transaction.
+        let mut events: Vec<(DateTime<Utc>, &DisplayEvent)> = events
+            .iter()
+            .filter_map(|event| Some((event.timestamp?, event)))
+            .collect();
+        events.sort_by_key(|(time, _)| *time);
+
+        let mut transactions: Vec<Transaction> = Vec::new();
+        let mut open: Option<Transaction> = None;
+        for (time, event) in events {
+            match event.event_id {
+                TRANSACTION_BEGIN => {
+                    transactions.extend(open.take());
+                    let mut transaction = Transaction {
+                        started: Some(time),
+                        package: event.event_data.values().first().cloned(),
+                        ..Transaction::default()
+                    };
+                    transaction.add(time, event);
+                    open = Some(transaction);
+                }
+                TRANSACTION_END => {
+                    let mut transaction = open.take().unwrap_or_default();
+                    transaction.add(time, event);
+                    transaction.ended = Some(time);
+                    transactions.push(transaction);
+                }
+                _ => match open.as_mut() {
+                    Some(transaction) => transaction.add(time, event),
+                    None => {
+                        let message = event.formatted_message.as_deref().unwrap_or(&event.message);
+                        let product = product_of(message)
+                            .or_else(|| event.event_data.values().first().cloned());
+                        let joins_last = transactions.last().is_some_and(|last| {
+                            last.ended.is_none()
+                                && last.succeeded.is_none()
+                                && last.product == product
+                                && time - last.last_time() <= Duration::minutes(LOOSE_EVENT_MINUTES)
+                        });
+                        if !joins_last {
+                            transactions.push(Transaction::default());
+                        }
+                        if let Some(last) = transactions.last_mut() {
+                            last.add(time, event);
+                        }
+                    }
+                },
+            }
+        }
+        transactions.extend(open);
+        transactions.reverse();
+        Self {
+            transactions,
+            unavailable,
+            ..Self::default()
+        }
+    }
+}
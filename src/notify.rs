@@ -0,0 +1,82 @@
+use std::io::{Write, stdout};
+
+use crate::models::{DisplayEvent, EventLevel};
+
+/// Summary of the error and critical events among new ones, e.g. "3 new errors in System
+/// (latest: Disk 7)", or `None` when there are none.
+pub fn error_summary(events: &[DisplayEvent], channel: &str) -> Option<String> {
+    let errors: Vec<&DisplayEvent> = events
+        .iter()
+        .filter(|event| matches!(event.level, EventLevel::Error | EventLevel::Critical))
+        .collect();
+    let latest = errors.iter().max_by_key(|event| event.timestamp)?;
+    let noun = match (errors.len(), latest.level) {
+        (1, EventLevel::Critical) => "critical event",
+        (1, _) => "error",
+        _ => "errors",
+    };
+    Some(format!(
+        "{} new {} in {} (latest: {} {})",
+        errors.len(),
+        noun,
+        channel,
+        latest.source,
+        latest.event_id
+    ))
+}
+
+/// Raises a toast with the message, or rings the terminal bell where toasts are not
+/// available or fail to show.
+pub fn alert(title: &str, message: &str, toast: bool) {
+    if toast && show_toast(title, message).is_ok() {
+        return;
+    }
+    let mut out = stdout();
+    let _ = out.write_all(b"\x07").and_then(|()| out.flush());
+}
+
+/// Shows a Windows toast through PowerShell, which reaches the WinRT notification API
+/// without registering an app of our own; the toast is attributed to PowerShell.
+#[cfg(target_os = "windows")]
+fn show_toast(title: &str, message: &str) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+    use std::process::{Command, Stdio};
+
+    /// Keeps PowerShell from opening a console window.
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    const POWERSHELL_APP_ID: &str =
+        r"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe";
+
+    // XML text inside a single-quoted PowerShell string.
+    let text = |value: &str| {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('\'', "''")
+    };
+    let script = format!(
+        "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null; \
+         [Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] | Out-Null; \
+         $xml = New-Object Windows.Data.Xml.Dom.XmlDocument; \
+         $xml.LoadXml('<toast><visual><binding template=\"ToastGeneric\"><text>{}</text><text>{}</text></binding></visual></toast>'); \
+         [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{}').Show([Windows.UI.Notifications.ToastNotification]::new($xml))",
+        text(title),
+        text(message),
+        POWERSHELL_APP_ID
+    );
+    Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("PowerShell did not start: {}", e))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn show_toast(_title: &str, _message: &str) -> Result<(), String> {
+    Err("Toasts are only shown on Windows.".to_string())
+}
@@ -0,0 +1,287 @@
+use chrono::{DateTime, Utc};
+
+use crate::helpers;
+use crate::models::DisplayEvent;
+use crate::security;
+
+/// Handles requested (4656) and objects accessed (4663), logged for files and registry keys
+/// whose SACL audits the access.
+pub const OBJECT_ACCESS_QUERY: &str = "*[System[(EventID=4656 or EventID=4663)]]";
+
+/// Most audits read.
+pub const MAX_ACCESS_EVENTS: usize = 10000;
+
+/// Keyword bit of denied requests.
+const AUDIT_FAILURE: u64 = 0x0010_0000_0000_0000;
+
+/// Access rights of files and directories (FILE_*), named as the event message names them.
+const FILE_RIGHTS: &[(u32, &str)] = &[
+    (0x1, "ReadData (or ListDirectory)"),
+    (0x2, "WriteData (or AddFile)"),
+    (0x4, "AppendData (or AddSubdirectory)"),
+    (0x8, "ReadEA"),
+    (0x10, "WriteEA"),
+    (0x20, "Execute/Traverse"),
+    (0x40, "DeleteChild"),
+    (0x80, "ReadAttributes"),
+    (0x100, "WriteAttributes"),
+];
+
+/// Access rights of registry keys (KEY_*).
+const KEY_RIGHTS: &[(u32, &str)] = &[
+    (0x1, "Query key value"),
+    (0x2, "Set key value"),
+    (0x4, "Create sub-key"),
+    (0x8, "Enumerate sub-keys"),
+    (0x10, "Notify about changes"),
+    (0x20, "Create link"),
+    (0x100, "64-bit view"),
+    (0x200, "32-bit view"),
+];
+
+/// Standard and generic rights, which apply to every object type.
+const COMMON_RIGHTS: &[(u32, &str)] = &[
+    (0x10000, "DELETE"),
+    (0x20000, "READ_CONTROL"),
+    (0x40000, "WRITE_DAC"),
+    (0x80000, "WRITE_OWNER"),
+    (0x100000, "SYNCHRONIZE"),
+    (0x1000000, "ACCESS_SYS_SEC"),
+    (0x10000000, "GENERIC_ALL"),
+    (0x20000000, "GENERIC_EXECUTE"),
+    (0x40000000, "GENERIC_WRITE"),
+    (0x80000000, "GENERIC_READ"),
+];
+
+/// Rights that change a file or directory: write data, append, write EA, delete child and
+/// write attributes.
+const FILE_MODIFYING_RIGHTS: u32 = 0x2 | 0x4 | 0x10 | 0x40 | 0x100;
+/// Rights that change a registry key: set value, create sub-key and create link.
+const KEY_MODIFYING_RIGHTS: u32 = 0x2 | 0x4 | 0x20;
+/// DELETE, WRITE_DAC, WRITE_OWNER, GENERIC_ALL and GENERIC_WRITE.
+const COMMON_MODIFYING_RIGHTS: u32 = 0x10000 | 0x40000 | 0x80000 | 0x10000000 | 0x40000000;
+
+/// One handle request or access, reduced to what the view groups by.
+#[derive(Debug, Clone)]
+pub struct ObjectAccess {
+    pub time: DateTime<Utc>,
+    pub event_id: u32,
+    /// "File", "Key", or another object type such as "SAM_DOMAIN".
+    pub object_type: String,
+    pub path: String,
+    pub mask: u32,
+    /// Process image path.
+    pub process: String,
+    pub account: String,
+    /// Whether the handle request was denied.
+    pub failed: bool,
+}
+
+impl ObjectAccess {
+    /// Reads a 4656 or 4663 event.
+    pub fn of(event: &DisplayEvent) -> Option<Self> {
+        if !matches!(event.event_id, 4656 | 4663) {
+            return None;
+        }
+        let mask = security::field(event, "AccessMask")
+            .and_then(parse_mask)
+            .unwrap_or(0);
+        Some(Self {
+            time: event.timestamp?,
+            event_id: event.event_id,
+            object_type: security::field(event, "ObjectType")
+                .unwrap_or("Unknown")
+                .to_string(),
+            path: security::field(event, "ObjectName")?.to_string(),
+            mask,
+            process: security::field(event, "ProcessName")
+                .unwrap_or_default()
+                .to_string(),
+            account: security::account(event, "Subject").unwrap_or_default(),
+            failed: event.keywords & AUDIT_FAILURE != 0,
+        })
+    }
+}
+
+/// Every audited access to one object.
+#[derive(Debug, Clone)]
+pub struct ObjectSummary {
+    pub path: String,
+    pub object_type: String,
+    /// Rights of all its accesses together.
+    pub mask: u32,
+    pub failures: usize,
+    pub accounts: Vec<String>,
+    pub processes: Vec<String>,
+    /// Newest first.
+    pub accesses: Vec<ObjectAccess>,
+}
+
+impl ObjectSummary {
+    pub fn last_seen(&self) -> DateTime<Utc> {
+        self.accesses
+            .first()
+            .map(|access| access.time)
+            .unwrap_or_default()
+    }
+
+    /// Whether any access could change the object.
+    pub fn modified(&self) -> bool {
+        let specific = match self.object_type.as_str() {
+            "File" => FILE_MODIFYING_RIGHTS,
+            "Key" => KEY_MODIFYING_RIGHTS,
+            _ => 0,
+        };
+        self.mask & (specific | COMMON_MODIFYING_RIGHTS) != 0
+    }
+
+    /// Describes the object, who accessed it and how for the detail pane.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Object:       {}", self.path),
+            format!("Type:         {}", self.object_type),
+            format!(
+                "Accesses:     {} ({} denied)",
+                self.accesses.len(),
+                self.failures
+            ),
+            format!(
+                "Rights:       {}",
+                rights(&self.object_type, self.mask).join(", ")
+            ),
+            format!("Accounts:     {}", self.accounts.join(", ")),
+            format!("Processes:    {}", self.processes.join(", ")),
+            String::new(),
+            "--- Accesses ---".to_string(),
+        ];
+        lines.extend(self.accesses.iter().map(|access| {
+            format!(
+                "{}  {}  {:<7} {:<24} {:<20} {}",
+                helpers::format_local_time(access.time),
+                access.event_id,
+                if access.failed { "Denied" } else { "Granted" },
+                access.account,
+                access.process.rsplit('\\').next().unwrap_or_default(),
+                rights(&access.object_type, access.mask).join(", ")
+            )
+        }));
+        lines
+    }
+}
+
+/// State of the object access view.
+#[derive(Debug, Clone, Default)]
+pub struct ObjectAccessView {
+    pub accesses: Vec<ObjectAccess>,
+    /// Why the Security log could not be read.
+    pub unavailable: Option<String>,
+    /// Path prefix the objects are narrowed to.
+    pub prefix: String,
+    pub prefix_cursor: usize,
+    pub selection: usize,
+    /// Object whose accesses are shown instead of the list.
+    pub open_object: Option<usize>,
+    pub detail_scroll: usize,
+}
+
+impl ObjectAccessView {
+    /// Groups the accesses under the path prefix by object: objects with denied requests
+    /// first, then the most accessed.
+    pub fn objects(&self) -> Vec<ObjectSummary> {
+        let prefix = kernel_path(self.prefix.trim()).to_lowercase();
+        let mut objects: Vec<ObjectSummary> = Vec::new();
+        for access in &self.accesses {
+            if !access.path.to_lowercase().starts_with(&prefix) {
+                continue;
+            }
+            let index = match objects
+                .iter()
+                .position(|object| object.path.eq_ignore_ascii_case(&access.path))
+            {
+                Some(index) => index,
+                None => {
+                    objects.push(ObjectSummary {
+                        path: access.path.clone(),
+                        object_type: access.object_type.clone(),
+                        mask: 0,
+                        failures: 0,
+                        accounts: Vec::new(),
+                        processes: Vec::new(),
+                        accesses: Vec::new(),
+                    });
+                    objects.len() - 1
+                }
+            };
+            let object = &mut objects[index];
+            object.mask |= access.mask;
+            if access.failed {
+                object.failures += 1;
+            }
+            if !access.account.is_empty() && !object.accounts.contains(&access.account) {
+                object.accounts.push(access.account.clone());
+            }
+            if !access.process.is_empty() && !object.processes.contains(&access.process) {
+                object.processes.push(access.process.clone());
+            }
+            object.accesses.push(access.clone());
+        }
+        for object in &mut objects {
+            object
+                .accesses
+                .sort_by_key(|access| std::cmp::Reverse(access.time));
+        }
+        objects.sort_by(|a, b| {
+            (b.failures > 0)
+                .cmp(&(a.failures > 0))
+                .then(b.accesses.len().cmp(&a.accesses.len()))
+                .then(b.last_seen().cmp(&a.last_seen()))
+        });
+        objects
+    }
+}
+
+/// Names the rights in an access mask for the object type, keeping unknown bits in hex.
+pub fn rights(object_type: &str, mask: u32) -> Vec<String> {
+    let specific = match object_type {
+        "File" => FILE_RIGHTS,
+        "Key" => KEY_RIGHTS,
+        _ => &[],
+    };
+    let mut names = Vec::new();
+    let mut known = 0;
+    for &(bit, name) in specific.iter().chain(COMMON_RIGHTS) {
+        if mask & bit != 0 {
+            names.push(name.to_string());
+            known |= bit;
+        }
+    }
+    if mask & !known != 0 {
+        names.push(format!("0x{:x}", mask & !known));
+    }
+    names
+}
+
+/// Reads an access mask such as "0x120089".
+fn parse_mask(value: &str) -> Option<u32> {
+    let hex = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))?;
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// Registry paths are logged by their kernel names; accepts the usual hive abbreviations
+/// in a prefix as well.
+fn kernel_path(prefix: &str) -> String {
+    let upper = prefix.to_uppercase();
+    for (hive, kernel) in [
+        ("HKLM\\", "\\REGISTRY\\MACHINE\\"),
+        ("HKEY_LOCAL_MACHINE\\", "\\REGISTRY\\MACHINE\\"),
+        ("HKU\\", "\\REGISTRY\\USER\\"),
+        ("HKEY_USERS\\", "\\REGISTRY\\USER\\"),
+    ] {
+        if upper.starts_with(hive) {
+            return format!("{}{}", kernel, &prefix[hive.len()..]);
+        }
+    }
+    prefix.to_string()
+}
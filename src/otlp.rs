@@ -0,0 +1,257 @@
+use serde_json::{Value, json};
+use std::env;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::models::{DisplayEvent, EventLevel, IncidentMarker};
+use crate::redact::{self, PreparedExport};
+
+/// Maximum number of log records sent in a single OTLP request.
+const BATCH_SIZE: usize = 500;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where and how to ship OTLP log records, from `otlp_endpoint` and `otlp_headers` or the
+/// standard `OTEL_*` variables.
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    pub endpoint: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl OtlpConfig {
+    /// Takes the endpoint and headers from the configuration, and reads whichever of them is
+    /// not set there from the environment.
+    pub fn resolve(config: &Config) -> Result<Self, String> {
+        let endpoint = match config.otlp_endpoint.as_deref().map(str::trim) {
+            Some(endpoint) if !endpoint.is_empty() => endpoint.to_string(),
+            _ => Self::endpoint_from_env()?,
+        };
+        let headers = if config.otlp_headers.is_empty() {
+            env::var("OTEL_EXPORTER_OTLP_HEADERS")
+                .map(|value| parse_headers(&value))
+                .unwrap_or_default()
+        } else {
+            config
+                .otlp_headers
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        };
+        Ok(Self { endpoint, headers })
+    }
+
+    /// Reads the endpoint from `OTEL_EXPORTER_OTLP_LOGS_ENDPOINT`, falling back to
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT` with the `/v1/logs` path appended.
+    fn endpoint_from_env() -> Result<String, String> {
+        let endpoint = match env::var("OTEL_EXPORTER_OTLP_LOGS_ENDPOINT") {
+            Ok(endpoint) if !endpoint.trim().is_empty() => endpoint.trim().to_string(),
+            _ => match env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+                Ok(base) if !base.trim().is_empty() => {
+                    format!("{}/v1/logs", base.trim().trim_end_matches('/'))
+                }
+                _ => {
+                    return Err(
+                        "No OTLP endpoint configured.\n\nSet otlp_endpoint in the configuration (e.g. \"http://localhost:4318/v1/logs\"), or OTEL_EXPORTER_OTLP_ENDPOINT or OTEL_EXPORTER_OTLP_LOGS_ENDPOINT."
+                            .to_string(),
+                    );
+                }
+            },
+        };
+        Ok(endpoint)
+    }
+}
+
+/// An export sending on a thread of its own, so a slow or unreachable collector does not hold
+/// up the interface.
+pub struct OtlpExport {
+    outcome: Receiver<Result<String, String>>,
+}
+
+impl OtlpExport {
+    /// Starts sending the prepared events and markers to the endpoint. Each event is labelled
+    /// with its own channel; `channel`, the tab's, labels the markers.
+    pub fn start(config: OtlpConfig, export: PreparedExport, channel: String) -> Self {
+        let (sender, outcome) = mpsc::channel();
+        thread::spawn(move || {
+            let result = export_events(
+                &config,
+                &export.events,
+                &export.markers,
+                &channel,
+                &export.host_name,
+            )
+            .map(|count| {
+                redact::with_summary(
+                    format!("{} log records sent to:\n\n{}", count, config.endpoint),
+                    export.redaction.as_deref(),
+                )
+            });
+            let _ = sender.send(result);
+        });
+        Self { outcome }
+    }
+
+    /// The confirmation or the error once the thread is done.
+    pub fn finished(&self) -> Option<Result<String, String>> {
+        match self.outcome.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                Some(Err("The OTLP export stopped unexpectedly.".to_string()))
+            }
+        }
+    }
+}
+
+/// Parses the `key1=value1,key2=value2` header list used by the OTLP exporters.
+fn parse_headers(value: &str) -> Vec<(String, String)> {
+    value
+        .split(',')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() {
+                None
+            } else {
+                Some((key.to_string(), value.trim().to_string()))
+            }
+        })
+        .collect()
+}
+
+/// Maps an event level to the OTLP severity number.
+fn severity_number(level: EventLevel) -> u8 {
+    match level {
+        EventLevel::Critical => 21,
+        EventLevel::Error => 17,
+        EventLevel::Warning => 13,
+        EventLevel::Information => 9,
+        EventLevel::Verbose => 5,
+        EventLevel::Unknown(_) => 0,
+    }
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// Converts a single event into an OTLP log record.
+fn log_record(event: &DisplayEvent, channel: &str) -> Value {
+    let body = event.formatted_message.as_deref().unwrap_or(&event.message);
+    let mut attributes = vec![
+        string_attribute("winlog.provider_name", &event.provider_name_original),
+        string_attribute("winlog.channel", channel),
+    ];
+    attributes.push(
+        json!({ "key": "winlog.event_id", "value": { "intValue": event.event_id.to_string() } }),
+    );
+    // Zero marks the time as unknown.
+    let time = event
+        .timestamp
+        .and_then(|time| time.timestamp_nanos_opt())
+        .unwrap_or_default();
+    json!({
+        "timeUnixNano": time.to_string(),
+        "observedTimeUnixNano": chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default().to_string(),
+        "severityNumber": severity_number(event.level),
+        "severityText": event.level.display_name(),
+        "body": { "stringValue": body },
+        "attributes": attributes,
+    })
+}
+
+/// Converts an incident marker into a log record carrying its label as the body.
+fn marker_record(marker: &IncidentMarker, channel: &str) -> Value {
+    json!({
+        "timeUnixNano": marker.time.timestamp_nanos_opt().unwrap_or_default().to_string(),
+        "observedTimeUnixNano": chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default().to_string(),
+        "body": { "stringValue": marker.label },
+        "attributes": [
+            string_attribute("event.name", "incident.marker"),
+            string_attribute("winlog.channel", channel),
+        ],
+    })
+}
+
+/// Builds an OTLP/HTTP JSON `ExportLogsServiceRequest` for a batch of events and markers.
+fn build_request(
+    events: &[DisplayEvent],
+    markers: &[IncidentMarker],
+    channel: &str,
+    host_name: &str,
+) -> Value {
+    let records: Vec<Value> = events
+        .iter()
+        .map(|e| log_record(e, &e.channel))
+        .chain(markers.iter().map(|m| marker_record(m, channel)))
+        .collect();
+    json!({
+        "resourceLogs": [{
+            "resource": {
+                "attributes": [
+                    string_attribute("service.name", env!("CARGO_PKG_NAME")),
+                    string_attribute("host.name", host_name),
+                ]
+            },
+            "scopeLogs": [{
+                "scope": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+                "logRecords": records,
+            }]
+        }]
+    })
+}
+
+/// Sends the events to the configured OTLP endpoint in batches, with any incident markers in
+/// the first one. Returns the number of event records sent.
+fn export_events(
+    config: &OtlpConfig,
+    events: &[DisplayEvent],
+    markers: &[IncidentMarker],
+    channel: &str,
+    host_name: &str,
+) -> Result<usize, String> {
+    if events.is_empty() {
+        return Err("No events loaded to export.".to_string());
+    }
+    let connector =
+        native_tls::TlsConnector::new().map_err(|e| format!("Failed to initialize TLS: {}", e))?;
+    let agent = ureq::AgentBuilder::new()
+        .tls_connector(Arc::new(connector))
+        .timeout(REQUEST_TIMEOUT)
+        .build();
+
+    let mut sent = 0;
+    for (index, batch) in events.chunks(BATCH_SIZE).enumerate() {
+        let batch_markers = if index == 0 { markers } else { &[] };
+        let body = build_request(batch, batch_markers, channel, host_name).to_string();
+        let mut request = agent
+            .post(&config.endpoint)
+            .set("Content-Type", "application/json");
+        for (key, value) in &config.headers {
+            request = request.set(key, value);
+        }
+        match request.send_string(&body) {
+            Ok(_) => sent += batch.len(),
+            Err(ureq::Error::Status(code, response)) => {
+                let detail = response.into_string().unwrap_or_default();
+                return Err(format!(
+                    "Collector at {} rejected the export after {} records (HTTP {}).\n\n{}",
+                    config.endpoint,
+                    sent,
+                    code,
+                    detail.trim()
+                ));
+            }
+            Err(e) => {
+                return Err(format!(
+                    "Failed to reach {} after {} records: {}",
+                    config.endpoint, sent, e
+                ));
+            }
+        }
+    }
+    Ok(sent)
+}
@@ -0,0 +1,165 @@
+use std::env;
+use std::error::Error;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::models::DisplayEvent;
+
+#[cfg(target_os = "windows")]
+use crate::publishers::PublisherCache;
+#[cfg(target_os = "windows")]
+use crate::{event_api, event_parser};
+#[cfg(target_os = "windows")]
+use windows::{
+    Win32::System::EventLog::{
+        EVT_HANDLE, EvtClose, EvtNext, EvtQuery, EvtQueryChannelPath, EvtQueryReverseDirection,
+    },
+    core::PCWSTR,
+};
+
+/// Pagers tried in order when $PAGER is not set.
+const FALLBACK_PAGERS: [&str; 2] = ["less", "more"];
+
+/// Formats an event as a plain-text block: a header line followed by the indented message.
+pub fn format_event_text(event: &DisplayEvent) -> String {
+    let mut text = format!(
+        "{}  {:<11}  {}  [{}]\n",
+        event.local_time(),
+        event.level.display_name(),
+        event.source,
+        event.event_id
+    );
+    let message = event.formatted_message.as_deref().unwrap_or(&event.message);
+    for line in message.lines() {
+        text.push_str("    ");
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Queries the requested log and prints the events as text, optionally through a pager.
+pub fn run(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    let log_name = match &cli.log {
+        Some(log) => log.clone(),
+        None => Config::load().unwrap_or_default().default_log,
+    };
+    let events = query_events(&log_name, cli.query.as_deref(), cli.max_events)?;
+    let mut output = String::new();
+    for event in &events {
+        output.push_str(&format_event_text(event));
+        output.push('\n');
+    }
+    if output.is_empty() {
+        output = format!("No events found in '{}'.\n", log_name);
+    }
+
+    if cli.pager {
+        page(&output)
+    } else {
+        let mut stdout = io::stdout().lock();
+        match stdout.write_all(output.as_bytes()) {
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            result => result.map_err(Into::into),
+        }
+    }
+}
+
+/// Writes the text to the stdin of $PAGER, or the first fallback pager that can be started.
+fn page(text: &str) -> Result<(), Box<dyn Error>> {
+    let mut candidates: Vec<String> = env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .into_iter()
+        .collect();
+    candidates.extend(FALLBACK_PAGERS.iter().map(|p| p.to_string()));
+
+    for candidate in &candidates {
+        let mut parts = candidate.split_whitespace();
+        let Some(program) = parts.next() else {
+            continue;
+        };
+        let Ok(mut child) = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            // The pager may exit before reading everything; that is not an error.
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        child.wait()?;
+        return Ok(());
+    }
+
+    io::stdout().write_all(text.as_bytes())?;
+    Ok(())
+}
+
+/// Reads up to `max_events` events from the log, newest first.
+#[cfg(target_os = "windows")]
+fn query_events(
+    log_name: &str,
+    query: Option<&str>,
+    max_events: usize,
+) -> Result<Vec<DisplayEvent>, Box<dyn Error>> {
+    let query_str = query.unwrap_or("*");
+    let channel_wide = event_api::to_wide_string(log_name);
+    let query_wide = event_api::to_wide_string(query_str);
+    // Structured XML queries name their own channels, so the path must be null.
+    let channel_path = if query_str.trim_start().starts_with('<') {
+        PCWSTR::null()
+    } else {
+        PCWSTR::from_raw(channel_wide.as_ptr())
+    };
+    let mut publisher_metadata_cache = PublisherCache::default();
+    let mut events = Vec::new();
+
+    unsafe {
+        let query_handle = EvtQuery(
+            None,
+            channel_path,
+            PCWSTR::from_raw(query_wide.as_ptr()),
+            EvtQueryChannelPath.0 | EvtQueryReverseDirection.0,
+        )
+        .map_err(|e| format!("Failed to query log '{}': {}", log_name, e))?;
+
+        while events.len() < max_events {
+            let mut events_buffer =
+                vec![EVT_HANDLE::default(); (max_events - events.len()).min(100)];
+            let events_slice: &mut [isize] = std::mem::transmute(events_buffer.as_mut_slice());
+            let mut fetched = 0;
+            if EvtNext(query_handle, events_slice, 0, 0, &mut fetched).is_err() || fetched == 0 {
+                break;
+            }
+            for &event_handle in &events_buffer[..fetched as usize] {
+                if let Some(xml) = event_api::render_event_xml(event_handle) {
+                    let mut display_event = event_parser::parse_event_xml(&xml);
+                    display_event.formatted_message = event_api::format_event_message(
+                        &mut publisher_metadata_cache,
+                        &display_event.provider_name_original,
+                        event_handle,
+                    );
+                    events.push(display_event);
+                }
+                let _ = EvtClose(event_handle);
+            }
+        }
+
+        let _ = EvtClose(query_handle);
+    }
+    Ok(events)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn query_events(
+    _log_name: &str,
+    _query: Option<&str>,
+    _max_events: usize,
+) -> Result<Vec<DisplayEvent>, Box<dyn Error>> {
+    Err("--no-tui requires the Windows Event Log service".into())
+}
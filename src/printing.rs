@@ -0,0 +1,192 @@
+use chrono::{DateTime, Utc};
+
+use crate::detail;
+use crate::helpers;
+use crate::models::DisplayEvent;
+
+/// Log of printed documents. It is disabled by default, so it is often empty.
+pub const PRINT_OPERATIONAL_CHANNEL: &str = "Microsoft-Windows-PrintService/Operational";
+/// Log the spooler writes failed jobs to.
+pub const PRINT_ADMIN_CHANNEL: &str = "Microsoft-Windows-PrintService/Admin";
+pub const PRINTED_QUERY: &str = "*[System[EventID=307]]";
+pub const FAILED_QUERY: &str = "*[System[EventID=372 or EventID=6161]]";
+
+/// Most events read from each log.
+pub const MAX_PRINT_EVENTS: usize = 5000;
+
+/// Jobs listed in the details of a printer and user.
+const DETAIL_JOBS: usize = 100;
+
+/// One printed or failed document.
+#[derive(Debug, Clone)]
+pub struct PrintJob {
+    pub time: DateTime<Utc>,
+    pub document: String,
+    pub client: String,
+    pub pages: Option<u64>,
+    /// Win32 error the print processor returned; None for printed documents.
+    pub error: Option<String>,
+}
+
+impl PrintJob {
+    /// Reads a printed (307) or failed (372, 6161) document event into the printer, the
+    /// owner and the job.
+    fn of(event: &DisplayEvent) -> Option<(String, String, PrintJob)> {
+        let fields = detail::event_fields(event);
+        let param = |index: usize| {
+            fields
+                .iter()
+                .find(|(name, _)| *name == format!("Param{}", index))
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default()
+        };
+        // 307: job, document, owner, client, printer, port, bytes, pages.
+        // 372 and 6161: job, document, owner, printer, data type, spool size, bytes,
+        // total bytes, client, error.
+        let (printer, job) = match event.event_id {
+            307 => (
+                param(5),
+                PrintJob {
+                    time: event.timestamp?,
+                    document: param(2),
+                    client: param(4),
+                    pages: param(8).parse().ok(),
+                    error: None,
+                },
+            ),
+            372 | 6161 => (
+                param(4),
+                PrintJob {
+                    time: event.timestamp?,
+                    document: param(2),
+                    client: param(9),
+                    pages: None,
+                    error: Some(param(10)).filter(|error| !error.is_empty()),
+                },
+            ),
+            _ => return None,
+        };
+        if printer.is_empty() {
+            return None;
+        }
+        Some((printer, param(3), job))
+    }
+
+    pub fn failed(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// The jobs one user sent to one printer.
+#[derive(Debug, Clone)]
+pub struct PrinterSummary {
+    pub printer: String,
+    pub user: String,
+    pub printed: usize,
+    pub failed: usize,
+    pub pages: u64,
+    pub last_time: DateTime<Utc>,
+    /// Newest first.
+    pub jobs: Vec<PrintJob>,
+}
+
+impl PrinterSummary {
+    /// Describes the printer and user for the detail pane.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Printer:      {}", self.printer),
+            format!("User:         {}", self.user),
+            format!("Printed:      {} jobs, {} pages", self.printed, self.pages),
+            format!("Failed:       {} jobs", self.failed),
+            format!(
+                "Last job:     {}",
+                helpers::format_local_time(self.last_time)
+            ),
+            String::new(),
+            "--- Jobs ---".to_string(),
+        ];
+        lines.extend(self.jobs.iter().take(DETAIL_JOBS).map(|job| {
+            let outcome = match (&job.error, job.pages) {
+                (Some(error), _) => format!("FAILED, error {}", error),
+                (None, Some(pages)) => format!("{} pages", pages),
+                (None, None) => "printed".to_string(),
+            };
+            let mut line = format!(
+                "{}  {:<24} {}",
+                helpers::format_local_time(job.time),
+                outcome,
+                job.document
+            );
+            if !job.client.is_empty() {
+                line.push_str(&format!(" (from {})", job.client));
+            }
+            line
+        }));
+        if self.jobs.len() > DETAIL_JOBS {
+            lines.push(format!("... {} older jobs", self.jobs.len() - DETAIL_JOBS));
+        }
+        lines
+    }
+}
+
+/// State of the print jobs view.
+#[derive(Debug, Clone, Default)]
+pub struct PrintView {
+    /// Printers and users with failures first, then the most recently used.
+    pub summaries: Vec<PrinterSummary>,
+    /// Logs that could not be read, with the reason.
+    pub unavailable: Vec<String>,
+    pub selection: usize,
+    /// Summary whose jobs are shown instead of the list.
+    pub open_summary: Option<usize>,
+    pub detail_scroll: usize,
+}
+
+impl PrintView {
+    /// Groups printed and failed document events by printer and owner.
+    pub fn new(events: &[DisplayEvent], unavailable: Vec<String>) -> Self {
+        let mut summaries: Vec<PrinterSummary> = Vec::new();
+        for (printer, user, job) in events.iter().filter_map(PrintJob::of) {
+            let index = match summaries.iter().position(|summary| {
+                summary.printer.eq_ignore_ascii_case(&printer)
+                    && summary.user.eq_ignore_ascii_case(&user)
+            }) {
+                Some(index) => index,
+                None => {
+                    summaries.push(PrinterSummary {
+                        printer,
+                        user,
+                        printed: 0,
+                        failed: 0,
+                        pages: 0,
+                        last_time: job.time,
+                        jobs: Vec::new(),
+                    });
+                    summaries.len() - 1
+                }
+            };
+            let summary = &mut summaries[index];
+            if job.failed() {
+                summary.failed += 1;
+            } else {
+                summary.printed += 1;
+                summary.pages += job.pages.unwrap_or(0);
+            }
+            summary.last_time = summary.last_time.max(job.time);
+            summary.jobs.push(job);
+        }
+        for summary in &mut summaries {
+            summary.jobs.sort_by_key(|job| std::cmp::Reverse(job.time));
+        }
+        summaries.sort_by(|a, b| {
+            (b.failed > 0)
+                .cmp(&(a.failed > 0))
+                .then(b.last_time.cmp(&a.last_time))
+        });
+        Self {
+            summaries,
+            unavailable,
+            ..Self::default()
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::{HashMap, HashSet};
+
+use windows::{
+    Win32::System::EventLog::{EVT_HANDLE, EvtClose, EvtOpenPublisherMetadata},
+    core::PCWSTR,
+};
+
+use crate::event_api::{load_data_names, to_wide_string};
+
+/// Field names of a provider's templates by event ID, one list per template.
+type DataNames = HashMap<u32, Vec<Vec<String>>>;
+
+/// Publishers kept open at most; the least recently used one is closed to open another.
+const MAX_OPEN_PUBLISHERS: usize = 64;
+
+/// A publisher metadata handle, closed when dropped.
+#[derive(Debug)]
+struct PublisherHandle(EVT_HANDLE);
+
+impl Drop for PublisherHandle {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = EvtClose(self.0);
+        }
+    }
+}
+
+/// Publisher metadata opened to format messages, by provider name.
+#[derive(Debug, Default)]
+pub struct PublisherCache {
+    /// Open handles, with the lookup they were last used by.
+    open: HashMap<String, (PublisherHandle, u64)>,
+    /// Providers whose metadata could not be opened, which are not tried again for every
+    /// event they logged.
+    missing: HashSet<String>,
+    lookups: u64,
+    /// Template field names by provider; `None` when its metadata could not be opened. Empty
+    /// for classic providers, which define no templates.
+    data_names: HashMap<String, Option<DataNames>>,
+}
+
+impl PublisherCache {
+    /// The metadata of a provider, opened on first use. The handle stays valid until the next
+    /// lookup, which may close it to make room for another publisher.
+    pub fn get(&mut self, provider_name: &str) -> Option<EVT_HANDLE> {
+        self.lookups += 1;
+        if let Some((handle, last_used)) = self.open.get_mut(provider_name) {
+            *last_used = self.lookups;
+            return Some(handle.0);
+        }
+        if self.missing.contains(provider_name) {
+            return None;
+        }
+        let provider_wide = to_wide_string(provider_name);
+        match unsafe {
+            EvtOpenPublisherMetadata(None, PCWSTR::from_raw(provider_wide.as_ptr()), None, 0, 0)
+        } {
+            Ok(handle) if !handle.is_invalid() => {
+                if self.open.len() >= MAX_OPEN_PUBLISHERS {
+                    self.close_least_recently_used();
+                }
+                self.open.insert(
+                    provider_name.to_string(),
+                    (PublisherHandle(handle), self.lookups),
+                );
+                Some(handle)
+            }
+            _ => {
+                self.missing.insert(provider_name.to_string());
+                None
+            }
+        }
+    }
+
+    /// Names for the `count` event data values of an event, from the provider's template for
+    /// its ID with as many fields; all unnamed for a classic provider. `None` when no template
+    /// fits, as when the event was logged by another version of the provider or one not
+    /// installed here.
+    pub fn data_names(
+        &mut self,
+        provider_name: &str,
+        event_id: u32,
+        count: usize,
+    ) -> Option<Vec<Option<String>>> {
+        if !self.data_names.contains_key(provider_name) {
+            let names = self.get(provider_name).map(load_data_names);
+            self.data_names.insert(provider_name.to_string(), names);
+        }
+        let templates = self.data_names[provider_name].as_ref()?;
+        if templates.is_empty() {
+            return Some(vec![None; count]);
+        }
+        templates
+            .get(&event_id)?
+            .iter()
+            .find(|names| names.len() == count)
+            .map(|names| names.iter().cloned().map(Some).collect())
+    }
+
+    fn close_least_recently_used(&mut self) {
+        let oldest = self
+            .open
+            .iter()
+            .min_by_key(|(_, (_, last_used))| *last_used)
+            .map(|(provider_name, _)| provider_name.clone());
+        if let Some(provider_name) = oldest {
+            self.open.remove(&provider_name);
+        }
+    }
+}
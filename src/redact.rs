@@ -0,0 +1,250 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{DisplayEvent, IncidentMarker};
+
+/// A pattern whose matches are replaced before events leave the machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionRule {
+    /// Label used in the substitution summary.
+    pub name: String,
+    /// Regular expression, matched case-insensitively.
+    pub pattern: String,
+    pub replacement: String,
+}
+
+impl RedactionRule {
+    fn new(name: &str, pattern: &str, replacement: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    /// Rules for the identifying data most often found in Windows events.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("Account", r"\b[\w.-]+\\[\w.$-]+", "[ACCOUNT]"),
+            Self::new("User SID", r"\bS-1-5-21(-\d+){3,4}\b", "[SID]"),
+            Self::new("Email", r"\b[\w.+-]+@[\w-]+(\.[\w-]+)+\b", "[EMAIL]"),
+            Self::new("IPv4", r"\b(\d{1,3}\.){3}\d{1,3}\b", "[IP]"),
+            Self::new(
+                "IPv6",
+                r"\b([0-9a-f]{1,4}:){7}[0-9a-f]{1,4}\b|\b([0-9a-f]{1,4}:){1,6}:([0-9a-f]{1,4}:?){1,6}\b",
+                "[IP]",
+            ),
+        ]
+    }
+}
+
+/// Event data fields whose values name a user or machine wherever else they appear.
+const IDENTIFYING_FIELDS: &[(&str, &str)] = &[
+    ("SubjectUserName", "Account"),
+    ("TargetUserName", "Account"),
+    ("SubjectDomainName", "Domain"),
+    ("TargetDomainName", "Domain"),
+    ("WorkstationName", "Host name"),
+];
+
+/// Values too generic to redact on their own.
+const IGNORED_VALUES: &[&str] = &[
+    "-",
+    "SYSTEM",
+    "LOCAL SERVICE",
+    "NETWORK SERVICE",
+    "NT AUTHORITY",
+];
+
+struct CompiledRule {
+    name: String,
+    regex: Regex,
+    replacement: String,
+    count: usize,
+}
+
+/// Applies redaction rules and counts the substitutions made.
+pub struct Redactor {
+    rules: Vec<CompiledRule>,
+}
+
+impl Redactor {
+    /// Compiles the configured rules. Host and account names taken from `events` are redacted
+    /// literally as well, since they rarely follow a pattern.
+    pub fn new(
+        rules: &[RedactionRule],
+        events: &[&DisplayEvent],
+        host_name: &str,
+    ) -> Result<Self, String> {
+        let mut literals: Vec<(&str, String)> = Vec::new();
+        let mut add_literal = |kind: &'static str, value: &str| {
+            let value = value.trim();
+            let ignored = IGNORED_VALUES.iter().any(|v| v.eq_ignore_ascii_case(value));
+            if value.len() >= 2
+                && !ignored
+                && !literals.iter().any(|(_, v)| v.eq_ignore_ascii_case(value))
+            {
+                literals.push((kind, value.to_string()));
+            }
+        };
+        add_literal("Host name", host_name);
+        for event in events {
+            add_literal("Host name", &event.computer);
+            // Short names turn up in messages as often as the FQDN recorded as the computer.
+            if let Some((short, _)) = event.computer.split_once('.') {
+                add_literal("Host name", short);
+            }
+            for (name, value) in event.event_data.named() {
+                if let Some((_, kind)) = IDENTIFYING_FIELDS.iter().find(|(field, _)| *field == name)
+                {
+                    add_literal(kind, value);
+                }
+            }
+        }
+        // Longer values first, so an FQDN is replaced before the short name inside it.
+        literals.sort_by_key(|(_, value)| std::cmp::Reverse(value.len()));
+
+        let mut compiled = Vec::new();
+        for rule in rules {
+            let regex = RegexBuilder::new(&rule.pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| format!("Invalid redaction rule '{}': {}", rule.name, e))?;
+            compiled.push(CompiledRule {
+                name: rule.name.clone(),
+                regex,
+                replacement: rule.replacement.clone(),
+                count: 0,
+            });
+        }
+        for (kind, value) in literals {
+            // Word boundaries only hold next to word characters, e.g. not after "PC01$".
+            let boundary = |c: Option<char>| match c {
+                Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+                _ => "",
+            };
+            let pattern = format!(
+                "{}{}{}",
+                boundary(value.chars().next()),
+                regex::escape(&value),
+                boundary(value.chars().last())
+            );
+            let regex = RegexBuilder::new(&pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| format!("Failed to build redaction for '{}': {}", value, e))?;
+            compiled.push(CompiledRule {
+                name: kind.to_string(),
+                regex,
+                replacement: format!("[{}]", kind.to_uppercase().replace(' ', "_")),
+                count: 0,
+            });
+        }
+        Ok(Self { rules: compiled })
+    }
+
+    /// Replaces every match of every rule in `text`.
+    pub fn redact(&mut self, text: &str) -> String {
+        let mut text = text.to_string();
+        for rule in &mut self.rules {
+            let count = rule.regex.find_iter(&text).count();
+            if count > 0 {
+                rule.count += count;
+                text = rule
+                    .regex
+                    .replace_all(&text, regex::NoExpand(&rule.replacement))
+                    .into_owned();
+            }
+        }
+        text
+    }
+
+    /// Returns a copy of the event with identifying data replaced in every text field.
+    pub fn redact_event(&mut self, event: &DisplayEvent) -> DisplayEvent {
+        let mut redacted = event.clone();
+        redacted.computer = self.redact(&event.computer);
+        redacted.user = self.redact(&event.user);
+        redacted.message = self.redact(&event.message);
+        redacted.formatted_message = event.formatted_message.as_deref().map(|m| self.redact(m));
+        redacted.raw_data = self.redact(&event.raw_data);
+        for (_, value) in &mut redacted.event_data.fields {
+            *value = self.redact(value);
+        }
+        redacted
+    }
+
+    pub fn redact_marker(&mut self, marker: &IncidentMarker) -> IncidentMarker {
+        IncidentMarker {
+            time: marker.time,
+            label: self.redact(&marker.label),
+        }
+    }
+
+    /// Describes the substitutions made so far, per rule.
+    pub fn summary(&self) -> String {
+        let mut totals: Vec<(&str, usize)> = Vec::new();
+        for rule in self.rules.iter().filter(|r| r.count > 0) {
+            match totals.iter_mut().find(|(name, _)| *name == rule.name) {
+                Some((_, count)) => *count += rule.count,
+                None => totals.push((&rule.name, rule.count)),
+            }
+        }
+        if totals.is_empty() {
+            return "Redaction: no identifying data found.".to_string();
+        }
+        let total: usize = totals.iter().map(|(_, count)| count).sum();
+        let details = totals
+            .iter()
+            .map(|(name, count)| format!("{}: {}", name, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("Redaction: {} substitutions ({}).", total, details)
+    }
+}
+
+/// Events, markers and host name ready to leave the machine, redacted if redaction is on.
+pub struct PreparedExport {
+    pub events: Vec<DisplayEvent>,
+    pub markers: Vec<IncidentMarker>,
+    pub host_name: String,
+    /// Substitution summary, when redaction was applied.
+    pub redaction: Option<String>,
+}
+
+impl PreparedExport {
+    pub fn new(
+        events: Vec<&DisplayEvent>,
+        markers: &[IncidentMarker],
+        host_name: &str,
+        redactor: Option<Redactor>,
+    ) -> Self {
+        let Some(mut redactor) = redactor else {
+            return Self {
+                events: events.into_iter().cloned().collect(),
+                markers: markers.to_vec(),
+                host_name: host_name.to_string(),
+                redaction: None,
+            };
+        };
+        let events = events
+            .into_iter()
+            .map(|event| redactor.redact_event(event))
+            .collect();
+        let markers = markers.iter().map(|m| redactor.redact_marker(m)).collect();
+        let host_name = redactor.redact(host_name);
+        Self {
+            events,
+            markers,
+            host_name,
+            redaction: Some(redactor.summary()),
+        }
+    }
+}
+
+/// Appends a redaction summary, if any, to a confirmation message.
+pub fn with_summary(message: String, summary: Option<&str>) -> String {
+    match summary {
+        Some(summary) => format!("{}\n\n{}", message, summary),
+        None => message,
+    }
+}
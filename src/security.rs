@@ -0,0 +1,245 @@
+use crate::models::DisplayEvent;
+use crate::siem;
+
+const SECURITY_PROVIDER: &str = "Microsoft-Windows-Security-Auditing";
+
+/// Registry key and value of the "Include command line in process creation events" policy.
+const COMMAND_LINE_POLICY_KEY: &str =
+    r"HKLM\Software\Microsoft\Windows\CurrentVersion\Policies\System\Audit";
+const COMMAND_LINE_POLICY_VALUE: &str = "ProcessCreationIncludeCmdLine_Enabled";
+
+/// Logon types of 4624, 4625 and 4634.
+const LOGON_TYPES: &[(&str, &str)] = &[
+    ("2", "Interactive (console)"),
+    ("3", "Network (e.g. file share)"),
+    ("4", "Batch (scheduled task)"),
+    ("5", "Service"),
+    ("7", "Unlock"),
+    ("8", "Network cleartext"),
+    ("9", "New credentials (runas /netonly)"),
+    ("10", "Remote interactive (RDP)"),
+    ("11", "Cached interactive"),
+    ("12", "Cached remote interactive"),
+    ("13", "Cached unlock"),
+];
+
+/// NTSTATUS codes explaining a failed logon (4625).
+const LOGON_FAILURES: &[(&str, &str)] = &[
+    ("0xc0000064", "user name does not exist"),
+    ("0xc000006a", "wrong password"),
+    ("0xc000006d", "bad user name or password"),
+    ("0xc000006f", "outside the allowed logon hours"),
+    ("0xc0000070", "not allowed from this workstation"),
+    ("0xc0000071", "password expired"),
+    ("0xc0000072", "account disabled"),
+    ("0xc0000133", "clock out of sync with the domain controller"),
+    ("0xc000015b", "logon type not granted to the account"),
+    ("0xc0000193", "account expired"),
+    ("0xc0000224", "password must be changed at next logon"),
+    ("0xc0000234", "account locked out"),
+    ("0xc000005e", "no logon servers available"),
+];
+
+/// Message insertion strings (`%%n`) found in Security event data.
+const INSERTION_STRINGS: &[(&str, &str)] = &[
+    ("%%1842", "Yes"),
+    ("%%1843", "No"),
+    ("%%1936", "Full token (UAC off or built-in administrator)"),
+    ("%%1937", "Elevated"),
+    ("%%1938", "Limited (not elevated)"),
+    ("%%2313", "Unknown user name or bad password"),
+];
+
+fn lookup<'a>(table: &[(&str, &'a str)], key: &str) -> Option<&'a str> {
+    table
+        .iter()
+        .find(|(code, _)| code.eq_ignore_ascii_case(key))
+        .map(|(_, text)| *text)
+}
+
+/// Returns a named field, leaving out the "-" placeholders Windows uses for empty values.
+pub fn field<'a>(event: &'a DisplayEvent, name: &str) -> Option<&'a str> {
+    event
+        .event_data
+        .named()
+        .find(|(field, _)| *field == name)
+        .map(|(_, value)| value.trim())
+        .filter(|value| !value.is_empty() && *value != "-")
+}
+
+/// Formats the account in `{prefix}DomainName` and `{prefix}UserName` as DOMAIN\user.
+pub fn account(event: &DisplayEvent, prefix: &str) -> Option<String> {
+    let user = field(event, &format!("{}UserName", prefix))?;
+    Some(match field(event, &format!("{}DomainName", prefix)) {
+        Some(domain) => format!("{}\\{}", domain, user),
+        None => user.to_string(),
+    })
+}
+
+pub fn logon_type(event: &DisplayEvent) -> Option<String> {
+    let code = field(event, "LogonType")?;
+    Some(match lookup(LOGON_TYPES, code) {
+        Some(name) => format!("{} - {}", code, name),
+        None => code.to_string(),
+    })
+}
+
+/// Explains a logon failure from its substatus, falling back to the status.
+pub fn failure_reason(event: &DisplayEvent) -> Option<String> {
+    let code = field(event, "SubStatus")
+        .filter(|code| *code != "0x0")
+        .or_else(|| field(event, "Status"))?;
+    Some(match lookup(LOGON_FAILURES, code) {
+        Some(reason) => format!("{} ({})", reason, code),
+        None => code.to_string(),
+    })
+}
+
+pub fn source_address(event: &DisplayEvent) -> Option<String> {
+    let address = field(event, "IpAddress")?;
+    Some(match field(event, "IpPort").filter(|port| *port != "0") {
+        Some(port) => format!("{}:{}", address, port),
+        None => address.to_string(),
+    })
+}
+
+fn text(event: &DisplayEvent, name: &str) -> Option<String> {
+    field(event, name).map(|value| {
+        lookup(INSERTION_STRINGS, value)
+            .unwrap_or(value)
+            .to_string()
+    })
+}
+
+/// Summarizes common Security audit events as labeled fields, or returns None for other
+/// events.
+pub fn summary_lines(event: &DisplayEvent) -> Option<Vec<String>> {
+    if event.provider_name_original != SECURITY_PROVIDER {
+        return None;
+    }
+    let title = siem::known_event_name(&event.provider_name_original, event.event_id)?;
+    let rows: Vec<(&str, Option<String>)> = match event.event_id {
+        4624 => vec![
+            ("Account", account(event, "Target")),
+            ("Logon type", logon_type(event)),
+            ("Source", source_address(event)),
+            ("Workstation", text(event, "WorkstationName")),
+            ("Process", text(event, "ProcessName")),
+            ("Auth package", text(event, "AuthenticationPackageName")),
+            ("Elevated", text(event, "ElevatedToken")),
+            ("Logon ID", text(event, "TargetLogonId")),
+            ("Requested by", account(event, "Subject")),
+        ],
+        4625 => vec![
+            ("Account", account(event, "Target")),
+            ("Logon type", logon_type(event)),
+            ("Reason", failure_reason(event)),
+            ("Source", source_address(event)),
+            ("Workstation", text(event, "WorkstationName")),
+            ("Process", text(event, "ProcessName")),
+            ("Auth package", text(event, "AuthenticationPackageName")),
+        ],
+        4634 | 4647 => vec![
+            ("Account", account(event, "Target")),
+            ("Logon type", logon_type(event)),
+            ("Logon ID", text(event, "TargetLogonId")),
+        ],
+        4648 => vec![
+            ("Account", account(event, "Subject")),
+            ("Credentials of", account(event, "Target")),
+            ("Target server", text(event, "TargetServerName")),
+            ("Process", text(event, "ProcessName")),
+            ("Source", source_address(event)),
+        ],
+        4672 => vec![
+            ("Account", account(event, "Subject")),
+            ("Logon ID", text(event, "SubjectLogonId")),
+            ("Privileges", text(event, "PrivilegeList")),
+        ],
+        4688 => vec![
+            ("Process", text(event, "NewProcessName")),
+            (
+                "Command line",
+                text(event, "CommandLine")
+                    .or_else(|| Some("not recorded, press [w] for why".to_string())),
+            ),
+            ("Parent", text(event, "ParentProcessName")),
+            ("Account", account(event, "Subject")),
+            ("Token", text(event, "TokenElevationType")),
+            ("Process ID", text(event, "NewProcessId")),
+        ],
+        // 4740 keeps the computer the lockout came from in TargetDomainName.
+        4740 => vec![
+            ("Account", text(event, "TargetUserName")),
+            ("Caller", text(event, "TargetDomainName")),
+            ("Reported by", account(event, "Subject")),
+        ],
+        4720 | 4722 | 4724 | 4726 => vec![
+            ("Account", account(event, "Target")),
+            ("Changed by", account(event, "Subject")),
+            ("UPN", text(event, "UserPrincipalName")),
+        ],
+        4728 | 4732 => vec![
+            (
+                "Member",
+                text(event, "MemberName").or_else(|| text(event, "MemberSid")),
+            ),
+            ("Group", account(event, "Target")),
+            ("Changed by", account(event, "Subject")),
+        ],
+        _ => Vec::new(),
+    };
+
+    let mut lines = vec![String::new(), format!("--- Security Audit: {} ---", title)];
+    for (label, value) in rows {
+        if let Some(value) = value {
+            lines.push(format!("{:<15}{}", format!("{}:", label), value));
+        }
+    }
+    Some(lines)
+}
+
+/// Whether the event is a process creation (4688) logged without the command line, as it is
+/// while the command line policy is off.
+pub fn lacks_command_line(event: &DisplayEvent) -> bool {
+    event.provider_name_original == SECURITY_PROVIDER
+        && event.event_id == 4688
+        && field(event, "CommandLine").is_none()
+}
+
+/// Reads the command line policy with `reg query`: Some(false) when it is off or not set,
+/// None when the registry could not be read.
+fn command_line_policy_enabled() -> Option<bool> {
+    let output = std::process::Command::new("reg")
+        .args([
+            "query",
+            COMMAND_LINE_POLICY_KEY,
+            "/v",
+            COMMAND_LINE_POLICY_VALUE,
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return Some(false);
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let value = text
+        .lines()
+        .find(|line| line.contains(COMMAND_LINE_POLICY_VALUE))?
+        .split_whitespace()
+        .last()?;
+    Some(value != "0x0")
+}
+
+/// Explains why process creation events lack the command line and how to turn it on.
+pub fn command_line_hint() -> String {
+    let current = match command_line_policy_enabled() {
+        Some(true) => "enabled. Events logged before it was enabled still lack the command line.",
+        Some(false) => "not enabled.",
+        None => "unknown (the registry could not be read).",
+    };
+    format!(
+        "Windows logs process creation (4688) without the command line unless the policy \"Include command line in process creation events\" is enabled.\n\nGroup Policy: Computer Configuration > Administrative Templates > System > Audit Process Creation > Include command line in process creation events = Enabled\n\nRegistry: {}\\{} = 1 (DWORD)\n\nOnly processes started after the change are logged with it. Command lines can contain passwords and tokens, so keep read access to the Security log restricted.\n\nThe policy on this computer is {}",
+        COMMAND_LINE_POLICY_KEY, COMMAND_LINE_POLICY_VALUE, current
+    )
+}
@@ -0,0 +1,175 @@
+use crate::helpers;
+use crate::models::DisplayEvent;
+
+pub const SCM_PROVIDER: &str = "Service Control Manager";
+
+/// Win32 errors services commonly fail with, used when the system cannot describe the code.
+const SERVICE_ERRORS: &[(u32, &str)] = &[
+    (2, "The system cannot find the file specified."),
+    (3, "The system cannot find the path specified."),
+    (5, "Access is denied."),
+    (193, "The service binary is not a valid Win32 application."),
+    (
+        1053,
+        "The service did not respond to the start or control request in a timely fashion.",
+    ),
+    (1058, "The service is disabled or has no enabled devices."),
+    (
+        1060,
+        "The specified service does not exist as an installed service.",
+    ),
+    (1067, "The process terminated unexpectedly."),
+    (1068, "The dependency service or group failed to start."),
+    (1069, "The service did not start due to a logon failure."),
+    (
+        1075,
+        "The dependency service does not exist or has been marked for deletion.",
+    ),
+    (
+        1079,
+        "The account specified for this service differs from the account specified for other services running in the same process.",
+    ),
+    (
+        1297,
+        "A privilege that the service requires to function properly does not exist in the service account configuration.",
+    ),
+];
+
+/// A start failure, timeout or crash reported by the Service Control Manager (7000, 7001,
+/// 7009, 7011, 7031 and 7034).
+#[derive(Debug, Clone)]
+pub struct ServiceFailure {
+    pub service: String,
+    pub failure: &'static str,
+    /// Service the failed one depends on (7001).
+    pub dependency: Option<String>,
+    /// Win32 error the start failed with.
+    pub error: Option<u32>,
+    pub timeout_ms: Option<u64>,
+    /// Times the service has terminated unexpectedly (7031, 7034).
+    pub crashes: Option<u32>,
+    /// Recovery action the SCM takes next (7031).
+    pub recovery: Option<String>,
+}
+
+impl ServiceFailure {
+    /// Decodes the event if it is one of the SCM failure events. The insertion strings are
+    /// read by position, since older systems log them without names.
+    pub fn decode(event: &DisplayEvent) -> Option<Self> {
+        if event.provider_name_original != SCM_PROVIDER {
+            return None;
+        }
+        let values = event.event_data.values();
+        let value = |index: usize| {
+            values
+                .get(index)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let service = service_name(event)?;
+        let base = |failure| Self {
+            service: service.clone(),
+            failure,
+            dependency: None,
+            error: None,
+            timeout_ms: None,
+            crashes: None,
+            recovery: None,
+        };
+        Some(match event.event_id {
+            7000 => Self {
+                error: value(1).and_then(|code| error_code(&code)),
+                ..base("Failed to start")
+            },
+            7001 => Self {
+                dependency: value(1),
+                error: value(2).and_then(|code| error_code(&code)),
+                ..base("Dependency failed to start")
+            },
+            7009 => Self {
+                timeout_ms: value(0).and_then(|ms| ms.parse().ok()),
+                ..base("Timed out connecting")
+            },
+            7011 => Self {
+                timeout_ms: value(0).and_then(|ms| ms.parse().ok()),
+                ..base("Timed out responding to a control")
+            },
+            7031 => Self {
+                crashes: value(1).and_then(|count| count.parse().ok()),
+                recovery: value(4),
+                ..base("Terminated unexpectedly")
+            },
+            7034 => Self {
+                crashes: value(1).and_then(|count| count.parse().ok()),
+                ..base("Terminated unexpectedly")
+            },
+            _ => return None,
+        })
+    }
+
+    /// Labeled fields shown in the preview.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            String::new(),
+            "--- Service Failure ---".to_string(),
+            format!("Service:     {}", self.service),
+            format!("Failure:     {}", self.failure),
+        ];
+        if let Some(dependency) = &self.dependency {
+            lines.push(format!("Dependency:  {}", dependency));
+        }
+        if let Some(error) = self.error {
+            lines.push(format!(
+                "Error:       {} {}",
+                error,
+                error_description(error)
+            ));
+        }
+        if let Some(timeout_ms) = self.timeout_ms {
+            lines.push(format!("Timeout:     {} ms", timeout_ms));
+        }
+        if let Some(crashes) = self.crashes {
+            lines.push(format!("Crashes:     {}", crashes));
+        }
+        if let Some(recovery) = &self.recovery {
+            lines.push(format!("Recovery:    {}", recovery));
+        }
+        lines
+    }
+}
+
+/// The service an SCM event is about: the second insertion string of the timeout events
+/// (7009, 7011), which start with the timeout, and the first of the others.
+pub fn service_name(event: &DisplayEvent) -> Option<String> {
+    if event.provider_name_original != SCM_PROVIDER {
+        return None;
+    }
+    let index = if matches!(event.event_id, 7009 | 7011) {
+        1
+    } else {
+        0
+    };
+    event
+        .event_data
+        .values()
+        .get(index)
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Reads an error inserted as a parameter message (`%%1053`) or a plain number.
+fn error_code(value: &str) -> Option<u32> {
+    value.trim_start_matches("%%").parse().ok()
+}
+
+/// The system's text for a Win32 error, or the built-in one where the system has none.
+fn error_description(code: u32) -> String {
+    helpers::system_error_message(code)
+        .or_else(|| {
+            SERVICE_ERRORS
+                .iter()
+                .find(|(known, _)| *known == code)
+                .map(|(_, text)| text.to_string())
+        })
+        .unwrap_or_else(|| "(unknown error)".to_string())
+}
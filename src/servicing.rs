@@ -0,0 +1,308 @@
+use chrono::{DateTime, Utc};
+
+use crate::helpers;
+use crate::models::DisplayEvent;
+
+/// Log the component servicing stack (CBS) writes package state changes to.
+pub const SETUP_CHANNEL: &str = "Setup";
+/// Package changes that finished or failed (2) or wait for a reboot (4).
+pub const PACKAGE_QUERY: &str =
+    "*[System[Provider[@Name='Microsoft-Windows-Servicing'] and (EventID=2 or EventID=4)]]";
+/// Updates Windows Update installed (19) or failed to install (20).
+pub const UPDATE_QUERY: &str = "*[System[Provider[@Name='Microsoft-Windows-WindowsUpdateClient'] and (EventID=19 or EventID=20)]]";
+
+/// Most events read from each log.
+pub const MAX_SERVICING_EVENTS: usize = 5000;
+
+/// Servicing and Windows Update errors behind most failed updates, with what they usually
+/// mean.
+const SERVICING_ERRORS: &[(u32, &str, &str)] = &[
+    (
+        0x800F081F,
+        "CBS_E_SOURCE_MISSING",
+        "the package source files could not be found",
+    ),
+    (
+        0x800F0831,
+        "CBS_E_STORE_CORRUPTION",
+        "the manifest of a package this update builds on is missing from the component store",
+    ),
+    (
+        0x800F0906,
+        "CBS_E_DOWNLOAD_FAILURE",
+        "the source files could not be downloaded",
+    ),
+    (
+        0x800F0907,
+        "CBS_E_GROUPPOLICY_DISALLOWED",
+        "Group Policy does not allow downloading the source files",
+    ),
+    (
+        0x800F0922,
+        "CBS_E_INSTALLERS_FAILED",
+        "an advanced installer failed, often for lack of space on the System Reserved partition",
+    ),
+    (
+        0x800F0982,
+        "PSFX_E_MATCHING_COMPONENT_NOT_FOUND",
+        "a component the update patches is missing; repair the store with DISM /RestoreHealth",
+    ),
+    (
+        0x80073712,
+        "ERROR_SXS_COMPONENT_STORE_CORRUPT",
+        "the component store is corrupt; repair it with DISM /RestoreHealth",
+    ),
+    (
+        0x80070002,
+        "ERROR_FILE_NOT_FOUND",
+        "a file the update needs is missing",
+    ),
+    (0x80070005, "E_ACCESSDENIED", "access was denied"),
+    (0x80070070, "ERROR_DISK_FULL", "the disk is full"),
+    (
+        0x80070BC9,
+        "ERROR_FAIL_REBOOT_REQUIRED",
+        "a pending operation needs a reboot first",
+    ),
+    (
+        0x8024200D,
+        "WU_E_UH_NEEDANOTHERDOWNLOAD",
+        "the update was not fully downloaded",
+    ),
+    (0x80240034, "WU_E_DOWNLOAD_FAILED", "the download failed"),
+    (
+        0x8024402C,
+        "WU_E_PT_WINHTTP_NAME_NOT_RESOLVED",
+        "the update server name could not be resolved",
+    ),
+];
+
+/// One package state change or update installation.
+#[derive(Debug, Clone)]
+pub struct ServicingOutcome {
+    pub time: DateTime<Utc>,
+    /// "CBS" or "Windows Update".
+    pub origin: &'static str,
+    /// Package identifier or update title, as the event names it.
+    pub package: String,
+    pub result: &'static str,
+    pub error: Option<u32>,
+}
+
+impl ServicingOutcome {
+    /// Reads a Servicing (2, 4) or WindowsUpdateClient (19, 20) event.
+    fn of(event: &DisplayEvent) -> Option<Self> {
+        let field = |name: &str| {
+            event
+                .event_data
+                .named()
+                .find(|(field, value)| field.eq_ignore_ascii_case(name) && !value.is_empty())
+                .map(|(_, value)| value.trim().to_string())
+        };
+        let error = field("ErrorCode")
+            .and_then(|code| error_code(&code))
+            .filter(|&code| code != 0);
+        let (origin, package, result) =
+            match (event.provider_name_original.as_str(), event.event_id) {
+                ("Microsoft-Windows-Servicing", 2) => (
+                    "CBS",
+                    field("PackageIdentifier")?,
+                    if error.is_some() {
+                        "Failed"
+                    } else {
+                        "Installed"
+                    },
+                ),
+                ("Microsoft-Windows-Servicing", 4) => {
+                    ("CBS", field("PackageIdentifier")?, "Reboot required")
+                }
+                ("Microsoft-Windows-WindowsUpdateClient", 19) => {
+                    ("Windows Update", field("updateTitle")?, "Installed")
+                }
+                ("Microsoft-Windows-WindowsUpdateClient", 20) => {
+                    ("Windows Update", field("updateTitle")?, "Failed")
+                }
+                _ => return None,
+            };
+        Some(Self {
+            time: event.timestamp?,
+            origin,
+            package,
+            result,
+            error,
+        })
+    }
+
+    pub fn failed(&self) -> bool {
+        self.result == "Failed"
+    }
+}
+
+/// The servicing history of one update, tying the Windows Update and CBS events together by
+/// KB number.
+#[derive(Debug, Clone)]
+pub struct UpdatePackage {
+    /// KB number, or the package identifier when it has none.
+    pub key: String,
+    /// Windows Update title, or the package identifier.
+    pub title: String,
+    pub failures: usize,
+    /// Distinct errors, most recent first.
+    pub errors: Vec<u32>,
+    /// Newest first.
+    pub outcomes: Vec<ServicingOutcome>,
+}
+
+impl UpdatePackage {
+    pub fn last_time(&self) -> DateTime<Utc> {
+        self.outcomes
+            .first()
+            .map(|outcome| outcome.time)
+            .unwrap_or_default()
+    }
+
+    /// Whether the update installed after its last failure.
+    pub fn resolved(&self) -> bool {
+        self.outcomes
+            .iter()
+            .find(|outcome| outcome.result != "Reboot required")
+            .is_some_and(|outcome| !outcome.failed())
+    }
+
+    /// Describes the update, its errors and its history for the detail pane.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Update:       {}", self.key),
+            format!("Title:        {}", self.title),
+            format!("Failures:     {}", self.failures),
+            format!(
+                "Status:       {}",
+                if self.resolved() {
+                    "Installed after failing"
+                } else {
+                    "Failing"
+                }
+            ),
+            String::new(),
+            "--- Errors ---".to_string(),
+        ];
+        lines.extend(self.errors.iter().map(|&code| error_description(code)));
+        lines.push(String::new());
+        lines.push("--- History ---".to_string());
+        lines.extend(self.outcomes.iter().map(|outcome| {
+            let result = match outcome.error {
+                Some(code) if outcome.failed() => format!("Failed 0x{:08X}", code),
+                _ => outcome.result.to_string(),
+            };
+            format!(
+                "{}  {:<16} {:<20} {}",
+                helpers::format_local_time(outcome.time),
+                outcome.origin,
+                result,
+                outcome.package
+            )
+        }));
+        lines
+    }
+}
+
+/// State of the update failures view.
+#[derive(Debug, Clone, Default)]
+pub struct ServicingView {
+    /// Updates that failed at least once: those still failing first, then the most recent.
+    pub packages: Vec<UpdatePackage>,
+    /// Logs that could not be read, with the reason.
+    pub unavailable: Vec<String>,
+    pub selection: usize,
+    /// Update whose history is shown instead of the list.
+    pub open_package: Option<usize>,
+    pub detail_scroll: usize,
+}
+
+impl ServicingView {
+    /// Groups the Servicing and WindowsUpdateClient outcomes by update and keeps the updates
+    /// that failed.
+    pub fn new(events: &[DisplayEvent], unavailable: Vec<String>) -> Self {
+        let mut outcomes: Vec<ServicingOutcome> =
+            events.iter().filter_map(ServicingOutcome::of).collect();
+        outcomes.sort_by_key(|outcome| std::cmp::Reverse(outcome.time));
+
+        let mut packages: Vec<UpdatePackage> = Vec::new();
+        for outcome in outcomes {
+            let key = kb_number(&outcome.package).unwrap_or_else(|| outcome.package.clone());
+            let index = match packages.iter().position(|package| package.key == key) {
+                Some(index) => index,
+                None => {
+                    packages.push(UpdatePackage {
+                        key,
+                        title: outcome.package.clone(),
+                        failures: 0,
+                        errors: Vec::new(),
+                        outcomes: Vec::new(),
+                    });
+                    packages.len() - 1
+                }
+            };
+            let package = &mut packages[index];
+            if outcome.origin == "Windows Update" {
+                package.title = outcome.package.clone();
+            }
+            if outcome.failed() {
+                package.failures += 1;
+                if let Some(code) = outcome.error.filter(|code| !package.errors.contains(code)) {
+                    package.errors.push(code);
+                }
+            }
+            package.outcomes.push(outcome);
+        }
+        packages.retain(|package| package.failures > 0);
+        packages.sort_by(|a, b| {
+            a.resolved()
+                .cmp(&b.resolved())
+                .then(b.last_time().cmp(&a.last_time()))
+        });
+        Self {
+            packages,
+            unavailable,
+            ..Self::default()
+        }
+    }
+}
+
+/// The KB number in a package identifier or update title, such as "KB5034441".
+fn kb_number(text: &str) -> Option<String> {
+    let upper = text.to_uppercase();
+    upper.match_indices("KB").find_map(|(start, _)| {
+        let digits: String = upper[start + 2..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        (digits.len() >= 6).then(|| format!("KB{}", digits))
+    })
+}
+
+/// Reads an error code written in hex ("0x800f0922") or as a decimal number, which may be
+/// negative.
+fn error_code(value: &str) -> Option<u32> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value
+            .parse::<u32>()
+            .ok()
+            .or_else(|| value.parse::<i32>().ok().map(|code| code as u32)),
+    }
+}
+
+/// Names a servicing error and says what it usually means.
+pub fn error_description(code: u32) -> String {
+    match SERVICING_ERRORS.iter().find(|(known, _, _)| *known == code) {
+        Some((_, name, meaning)) => format!("0x{:08X} {}: {}", code, name, meaning),
+        None => match helpers::system_error_message(code) {
+            Some(message) => format!("0x{:08X} {}", code, message),
+            None => format!("0x{:08X}", code),
+        },
+    }
+}
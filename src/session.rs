@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::bookmarks::Bookmark;
+use crate::config;
+use crate::view::ViewState;
+
+const SESSION_FILE_NAME: &str = "session.json";
+
+/// An investigation saved to a file to be picked up later: the view (log, filter, sort and
+/// selected event) and the bookmarks at the time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub saved_at: DateTime<Utc>,
+    pub view: ViewState,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// Returns the path sessions are saved to unless another is given, next to the
+/// configuration file.
+pub fn default_session_path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join(SESSION_FILE_NAME))
+}
+
+pub fn load(path: &Path) -> Result<Session, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid session in {}: {}", path.display(), e))
+}
+
+/// Writes the session file, creating its directory if needed.
+pub fn save(path: &Path, session: &Session) -> Result<(), String> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(session)
+        .map_err(|e| format!("Failed to serialize the session: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
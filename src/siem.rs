@@ -0,0 +1,344 @@
+use serde_json::{Map, Value, json};
+
+use crate::models::{DisplayEvent, EventLevel, ExportFormat, IncidentMarker};
+
+const VENDOR: &str = "Microsoft";
+const PRODUCT: &str = "Microsoft Windows";
+/// Vendor and product of incident marker records, which come from this tool, not Windows.
+const MARKER_VENDOR: &str = "Event Commander";
+
+/// Mapping of an event data field to its CEF and LEEF attribute names.
+struct FieldMapping {
+    data_name: &'static str,
+    cef_key: &'static str,
+    leef_key: &'static str,
+}
+
+/// Event data fields found in common Security events, mapped to SIEM attributes.
+const FIELD_MAPPINGS: &[FieldMapping] = &[
+    FieldMapping {
+        data_name: "SubjectUserName",
+        cef_key: "suser",
+        leef_key: "accountName",
+    },
+    FieldMapping {
+        data_name: "SubjectDomainName",
+        cef_key: "sntdom",
+        leef_key: "accountDomain",
+    },
+    FieldMapping {
+        data_name: "SubjectUserSid",
+        cef_key: "suid",
+        leef_key: "accountSid",
+    },
+    FieldMapping {
+        data_name: "TargetUserName",
+        cef_key: "duser",
+        leef_key: "usrName",
+    },
+    FieldMapping {
+        data_name: "TargetDomainName",
+        cef_key: "dntdom",
+        leef_key: "domain",
+    },
+    FieldMapping {
+        data_name: "TargetUserSid",
+        cef_key: "duid",
+        leef_key: "targetSid",
+    },
+    FieldMapping {
+        data_name: "IpAddress",
+        cef_key: "src",
+        leef_key: "src",
+    },
+    FieldMapping {
+        data_name: "IpPort",
+        cef_key: "spt",
+        leef_key: "srcPort",
+    },
+    FieldMapping {
+        data_name: "WorkstationName",
+        cef_key: "shost",
+        leef_key: "srcHostName",
+    },
+    FieldMapping {
+        data_name: "LogonType",
+        cef_key: "cn1",
+        leef_key: "logonType",
+    },
+    FieldMapping {
+        data_name: "NewProcessName",
+        cef_key: "dproc",
+        leef_key: "processName",
+    },
+    FieldMapping {
+        data_name: "ProcessName",
+        cef_key: "sproc",
+        leef_key: "parentProcessName",
+    },
+    FieldMapping {
+        data_name: "CommandLine",
+        cef_key: "cs2",
+        leef_key: "commandLine",
+    },
+    FieldMapping {
+        data_name: "TaskName",
+        cef_key: "cs3",
+        leef_key: "taskName",
+    },
+    FieldMapping {
+        data_name: "ServiceName",
+        cef_key: "cs4",
+        leef_key: "serviceName",
+    },
+    FieldMapping {
+        data_name: "Status",
+        cef_key: "cs5",
+        leef_key: "status",
+    },
+];
+
+/// Labels for the CEF custom fields used in `FIELD_MAPPINGS`.
+const CEF_LABELS: &[(&str, &str)] = &[
+    ("cn1", "LogonType"),
+    ("cs2", "CommandLine"),
+    ("cs3", "TaskName"),
+    ("cs4", "ServiceName"),
+    ("cs5", "Status"),
+];
+
+/// Short names for well-known Security and System events.
+pub fn known_event_name(provider: &str, event_id: u32) -> Option<&'static str> {
+    let name = match (provider, event_id) {
+        ("Microsoft-Windows-Security-Auditing", 4624) => "An account was successfully logged on",
+        ("Microsoft-Windows-Security-Auditing", 4625) => "An account failed to log on",
+        ("Microsoft-Windows-Security-Auditing", 4634) => "An account was logged off",
+        ("Microsoft-Windows-Security-Auditing", 4647) => "User initiated logoff",
+        ("Microsoft-Windows-Security-Auditing", 4648) => {
+            "A logon was attempted using explicit credentials"
+        }
+        ("Microsoft-Windows-Security-Auditing", 4672) => "Special privileges assigned to new logon",
+        ("Microsoft-Windows-Security-Auditing", 4688) => "A new process has been created",
+        ("Microsoft-Windows-Security-Auditing", 4698) => "A scheduled task was created",
+        ("Microsoft-Windows-Security-Auditing", 4720) => "A user account was created",
+        ("Microsoft-Windows-Security-Auditing", 4722) => "A user account was enabled",
+        ("Microsoft-Windows-Security-Auditing", 4724) => {
+            "An attempt was made to reset an account's password"
+        }
+        ("Microsoft-Windows-Security-Auditing", 4726) => "A user account was deleted",
+        ("Microsoft-Windows-Security-Auditing", 4728) => {
+            "A member was added to a security-enabled global group"
+        }
+        ("Microsoft-Windows-Security-Auditing", 4732) => {
+            "A member was added to a security-enabled local group"
+        }
+        ("Microsoft-Windows-Security-Auditing", 4740) => "A user account was locked out",
+        ("Microsoft-Windows-Eventlog", 1102) => "The audit log was cleared",
+        ("Service Control Manager", 7045) => "A service was installed in the system",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Maps an event level to the 0-10 severity scale shared by CEF and LEEF.
+fn severity(level: EventLevel) -> u8 {
+    match level {
+        EventLevel::Critical => 10,
+        EventLevel::Error => 7,
+        EventLevel::Warning => 5,
+        EventLevel::Information => 3,
+        EventLevel::Verbose => 1,
+        EventLevel::Unknown(_) => 0,
+    }
+}
+
+fn event_name(event: &DisplayEvent) -> String {
+    if let Some(name) = known_event_name(&event.provider_name_original, event.event_id) {
+        return name.to_string();
+    }
+    let body = event.formatted_message.as_deref().unwrap_or(&event.message);
+    let first_line = body.lines().next().unwrap_or_default().trim();
+    if first_line.is_empty() {
+        format!("Event {}", event.event_id)
+    } else {
+        first_line.chars().take(128).collect()
+    }
+}
+
+/// Escapes a CEF or LEEF header field.
+fn escape_header(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Escapes a CEF extension value.
+fn escape_cef_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Makes a LEEF attribute value safe for the tab delimiter.
+fn escape_leef_value(value: &str) -> String {
+    value.replace("\r\n", " ").replace(['\t', '\r', '\n'], " ")
+}
+
+/// Formats a single event as one line in the given export format.
+pub fn format_event(format: ExportFormat, event: &DisplayEvent, channel: &str) -> String {
+    match format {
+        ExportFormat::Cef => to_cef(event, channel),
+        ExportFormat::Leef => to_leef(event, channel),
+        ExportFormat::Ndjson => to_ndjson(event, channel),
+    }
+}
+
+/// Formats an incident marker as one line in the given export format, so the annotated
+/// timeline travels with the exported events.
+pub fn format_marker(format: ExportFormat, marker: &IncidentMarker, channel: &str) -> String {
+    let time = marker.time.timestamp_millis().to_string();
+    match format {
+        ExportFormat::Cef => format!(
+            "CEF:0|{}|{}|{}|marker|{}|0|rt={} cs1Label=Channel cs1={} msg={}",
+            MARKER_VENDOR,
+            MARKER_VENDOR,
+            env!("CARGO_PKG_VERSION"),
+            escape_header(&marker.label),
+            time,
+            escape_cef_value(channel),
+            escape_cef_value(&marker.label)
+        ),
+        ExportFormat::Leef => format!(
+            "LEEF:1.0|{}|{}|{}|marker|devTime={}\tdevTimeFormat=Milliseconds\tcat={}\tmsg={}",
+            MARKER_VENDOR,
+            MARKER_VENDOR,
+            env!("CARGO_PKG_VERSION"),
+            time,
+            escape_leef_value(channel),
+            escape_leef_value(&marker.label)
+        ),
+        ExportFormat::Ndjson => json!({
+            "timestamp": marker.time.to_rfc3339(),
+            "channel": channel,
+            "marker": marker.label,
+        })
+        .to_string(),
+    }
+}
+
+/// Serializes an event as a single-line JSON object.
+pub fn to_ndjson(event: &DisplayEvent, channel: &str) -> String {
+    ndjson_value(event, channel).to_string()
+}
+
+/// The JSON object of an event. Named event data becomes an object; events with unnamed data
+/// keep the values in order.
+pub fn ndjson_value(event: &DisplayEvent, channel: &str) -> Value {
+    let data = if event.event_data.named().next().is_none() {
+        json!(event.event_data.values())
+    } else {
+        Value::Object(
+            event
+                .event_data
+                .named()
+                .map(|(name, value)| (name.to_string(), Value::String(value.to_string())))
+                .collect::<Map<_, _>>(),
+        )
+    };
+    json!({
+        "timestamp": event.timestamp.map(|time| time.to_rfc3339()),
+        "channel": channel,
+        "computer": event.computer,
+        "provider": event.provider_name_original,
+        "event_id": event.event_id,
+        "level": event.level.display_name(),
+        "level_code": event.level.code(),
+        "message": event.formatted_message.as_deref().unwrap_or(&event.message),
+        "data": data,
+    })
+}
+
+/// Serializes an event as an ArcSight Common Event Format (CEF:0) record.
+pub fn to_cef(event: &DisplayEvent, channel: &str) -> String {
+    let body = event.formatted_message.as_deref().unwrap_or(&event.message);
+    let mut extension: Vec<(String, String)> = Vec::new();
+    if let Some(time) = event.timestamp {
+        extension.push(("rt".to_string(), time.timestamp_millis().to_string()));
+    }
+    if !event.computer.is_empty() {
+        extension.push(("dvchost".to_string(), event.computer.clone()));
+    }
+    extension.push(("cs1Label".to_string(), "Channel".to_string()));
+    extension.push(("cs1".to_string(), channel.to_string()));
+    for (name, value) in event.event_data.named() {
+        if value.is_empty() || value == "-" {
+            continue;
+        }
+        let Some(mapping) = FIELD_MAPPINGS.iter().find(|m| m.data_name == name) else {
+            continue;
+        };
+        if let Some((_, label)) = CEF_LABELS.iter().find(|(key, _)| *key == mapping.cef_key) {
+            extension.push((format!("{}Label", mapping.cef_key), label.to_string()));
+        }
+        extension.push((mapping.cef_key.to_string(), value.to_string()));
+    }
+    extension.push(("msg".to_string(), body.to_string()));
+
+    let extension = extension
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, escape_cef_value(value)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "CEF:0|{}|{}||{}:{}|{}|{}|{}",
+        VENDOR,
+        PRODUCT,
+        escape_header(&event.provider_name_original),
+        event.event_id,
+        escape_header(&event_name(event)),
+        severity(event.level),
+        extension
+    )
+}
+
+/// Serializes an event as an IBM QRadar Log Event Extended Format (LEEF:1.0) record.
+pub fn to_leef(event: &DisplayEvent, channel: &str) -> String {
+    let body = event.formatted_message.as_deref().unwrap_or(&event.message);
+    let mut attributes: Vec<(String, String)> = Vec::new();
+    if let Some(time) = event.timestamp {
+        attributes.push(("devTime".to_string(), time.timestamp_millis().to_string()));
+        attributes.push(("devTimeFormat".to_string(), "Milliseconds".to_string()));
+    }
+    attributes.extend([
+        ("sev".to_string(), severity(event.level).to_string()),
+        ("cat".to_string(), channel.to_string()),
+        ("provider".to_string(), event.provider_name_original.clone()),
+    ]);
+    if !event.computer.is_empty() {
+        attributes.push(("identHostName".to_string(), event.computer.clone()));
+    }
+    for (name, value) in event.event_data.named() {
+        if value.is_empty() || value == "-" {
+            continue;
+        }
+        if let Some(mapping) = FIELD_MAPPINGS.iter().find(|m| m.data_name == name) {
+            attributes.push((mapping.leef_key.to_string(), value.to_string()));
+        }
+    }
+    attributes.push(("eventName".to_string(), event_name(event)));
+    attributes.push(("msg".to_string(), body.to_string()));
+
+    let attributes = attributes
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, escape_leef_value(value)))
+        .collect::<Vec<_>>()
+        .join("\t");
+    format!(
+        "LEEF:1.0|{}|{}||{}|{}",
+        VENDOR, PRODUCT, event.event_id, attributes
+    )
+}
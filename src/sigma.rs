@@ -0,0 +1,818 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde_yaml::Value;
+
+use crate::config;
+use crate::models::DisplayEvent;
+
+/// Folder the rules are read from when `sigma_rules_dir` is not set, next to the config file.
+const RULES_DIR_NAME: &str = "sigma";
+
+const SYSMON_CHANNEL: &str = "Microsoft-Windows-Sysmon/Operational";
+const POWERSHELL_CHANNEL: &str = "Microsoft-Windows-PowerShell/Operational";
+
+/// Channels of the `logsource` services.
+const SERVICE_CHANNELS: &[(&str, &str)] = &[
+    ("security", "Security"),
+    ("system", "System"),
+    ("application", "Application"),
+    ("sysmon", SYSMON_CHANNEL),
+    ("powershell", POWERSHELL_CHANNEL),
+    ("powershell-classic", "Windows PowerShell"),
+    (
+        "taskscheduler",
+        "Microsoft-Windows-TaskScheduler/Operational",
+    ),
+    ("wmi", "Microsoft-Windows-WMI-Activity/Operational"),
+    (
+        "windefend",
+        "Microsoft-Windows-Windows Defender/Operational",
+    ),
+    ("dns-server", "DNS Server"),
+    ("bits-client", "Microsoft-Windows-Bits-Client/Operational"),
+    ("ntlm", "Microsoft-Windows-NTLM/Operational"),
+    (
+        "codeintegrity-operational",
+        "Microsoft-Windows-CodeIntegrity/Operational",
+    ),
+    (
+        "driver-framework",
+        "Microsoft-Windows-DriverFrameworks-UserMode/Operational",
+    ),
+    (
+        "firewall-as",
+        "Microsoft-Windows-Windows Firewall With Advanced Security/Firewall",
+    ),
+];
+
+/// Channel and event IDs of the `logsource` categories, as Sysmon and PowerShell log them.
+const CATEGORY_EVENTS: &[(&str, &str, &[u32])] = &[
+    ("process_creation", SYSMON_CHANNEL, &[1]),
+    ("network_connection", SYSMON_CHANNEL, &[3]),
+    ("process_termination", SYSMON_CHANNEL, &[5]),
+    ("driver_load", SYSMON_CHANNEL, &[6]),
+    ("image_load", SYSMON_CHANNEL, &[7]),
+    ("create_remote_thread", SYSMON_CHANNEL, &[8]),
+    ("raw_access_thread", SYSMON_CHANNEL, &[9]),
+    ("process_access", SYSMON_CHANNEL, &[10]),
+    ("file_event", SYSMON_CHANNEL, &[11]),
+    ("registry_add", SYSMON_CHANNEL, &[12]),
+    ("registry_delete", SYSMON_CHANNEL, &[12]),
+    ("registry_set", SYSMON_CHANNEL, &[13]),
+    ("registry_rename", SYSMON_CHANNEL, &[14]),
+    ("registry_event", SYSMON_CHANNEL, &[12, 13, 14]),
+    ("create_stream_hash", SYSMON_CHANNEL, &[15]),
+    ("pipe_created", SYSMON_CHANNEL, &[17, 18]),
+    ("wmi_event", SYSMON_CHANNEL, &[19, 20, 21]),
+    ("dns_query", SYSMON_CHANNEL, &[22]),
+    ("file_delete", SYSMON_CHANNEL, &[23, 26]),
+    ("process_tampering", SYSMON_CHANNEL, &[25]),
+    ("ps_module", POWERSHELL_CHANNEL, &[4103]),
+    ("ps_script", POWERSHELL_CHANNEL, &[4104]),
+];
+
+/// Rule levels from the most to the least severe.
+const LEVELS: [&str; 5] = ["critical", "high", "medium", "low", "informational"];
+
+/// A Sigma rule for Windows event logs, reduced to what matching an event needs.
+#[derive(Debug, Clone)]
+pub struct SigmaRule {
+    pub title: String,
+    /// `informational` to `critical`, as the rule rates its hits.
+    pub level: String,
+    /// Channel the `logsource` stands for, when it names one.
+    channel: Option<String>,
+    /// Event IDs the `logsource` category stands for; empty for any.
+    event_ids: Vec<u32>,
+    selections: HashMap<String, Selection>,
+    condition: Condition,
+}
+
+/// A named search of the `detection` section.
+#[derive(Debug, Clone)]
+enum Selection {
+    /// Groups of field conditions; the selection matches when all conditions of a group do.
+    Fields(Vec<Vec<FieldMatch>>),
+    /// Values looked for in the message and every event data field.
+    Keywords(Vec<Regex>),
+}
+
+#[derive(Debug, Clone)]
+struct FieldMatch {
+    field: String,
+    values: Vec<ValueMatch>,
+    /// `|all`: every value must match instead of any.
+    all: bool,
+}
+
+#[derive(Debug, Clone)]
+enum ValueMatch {
+    Pattern(Regex),
+    /// `null`: the field is absent or empty.
+    Empty,
+    /// `|exists`.
+    Exists(bool),
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    Selection(String),
+    /// `1 of` or, with `all`, `all of` the selections whose names match.
+    Quantified {
+        all: bool,
+        names: Regex,
+    },
+    Not(Box<Condition>),
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+}
+
+/// Loaded events that matched one rule.
+#[derive(Debug, Clone)]
+pub struct RuleHits {
+    pub title: String,
+    pub level: String,
+    pub count: usize,
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+/// State of the Sigma hits panel.
+#[derive(Debug, Clone, Default)]
+pub struct SigmaView {
+    /// Row of the list; 0 is "any rule", the rules follow.
+    pub selection: usize,
+}
+
+/// Returns the folder the rules are read from.
+pub fn rules_dir(configured: Option<&str>) -> Option<PathBuf> {
+    match configured.filter(|dir| !dir.trim().is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => config::config_dir().map(|dir| dir.join(RULES_DIR_NAME)),
+    }
+}
+
+/// Reads the `.yml` and `.yaml` rules in a folder and its subfolders. Rules that cannot be
+/// used are left out and returned as "file: reason".
+pub fn load_rules(dir: &Path) -> (Vec<SigmaRule>, Vec<String>) {
+    let mut rules = Vec::new();
+    let mut skipped = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(folder) = pending.pop() {
+        let entries = match fs::read_dir(&folder) {
+            Ok(entries) => entries,
+            Err(e) => {
+                skipped.push(format!("{}: {}", folder.display(), e));
+                continue;
+            }
+        };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let is_rule = path.extension().is_some_and(|extension| {
+                extension.eq_ignore_ascii_case("yml") || extension.eq_ignore_ascii_case("yaml")
+            });
+            if !is_rule {
+                continue;
+            }
+            match fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| SigmaRule::parse(&content))
+            {
+                Ok(rule) => rules.push(rule),
+                Err(reason) => skipped.push(format!("{}: {}", path.display(), reason)),
+            }
+        }
+    }
+    (rules, skipped)
+}
+
+/// Titles of the rules an event matches.
+pub fn matching_titles(rules: &[SigmaRule], event: &DisplayEvent) -> Vec<String> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches(event))
+        .map(|rule| rule.title.clone())
+        .collect()
+}
+
+/// Hits of each rule among the loaded events, most severe and most frequent first.
+pub fn rule_hits(rules: &[SigmaRule], events: &[DisplayEvent]) -> Vec<RuleHits> {
+    let mut hits: Vec<RuleHits> = Vec::new();
+    for event in events {
+        for title in &event.sigma_hits {
+            match hits.iter_mut().find(|hit| &hit.title == title) {
+                Some(hit) => {
+                    hit.count += 1;
+                    hit.last_seen = hit.last_seen.max(event.timestamp);
+                }
+                None => hits.push(RuleHits {
+                    title: title.clone(),
+                    level: rules
+                        .iter()
+                        .find(|rule| &rule.title == title)
+                        .map(|rule| rule.level.clone())
+                        .unwrap_or_default(),
+                    count: 1,
+                    last_seen: event.timestamp,
+                }),
+            }
+        }
+    }
+    hits.sort_by(|a, b| {
+        level_rank(&a.level)
+            .cmp(&level_rank(&b.level))
+            .then(b.count.cmp(&a.count))
+    });
+    hits
+}
+
+fn level_rank(level: &str) -> usize {
+    LEVELS
+        .iter()
+        .position(|known| known.eq_ignore_ascii_case(level))
+        .unwrap_or(LEVELS.len())
+}
+
+impl SigmaRule {
+    /// Reads a rule from its YAML. Rules for other products, correlations and aggregations,
+    /// and modifiers other than `contains`, `startswith`, `endswith`, `all`, `re`, `exists`
+    /// and `windash` are refused with the reason.
+    pub fn parse(yaml: &str) -> Result<Self, String> {
+        let rule: Value = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+        let title = rule["title"]
+            .as_str()
+            .ok_or("The rule has no title.")?
+            .to_string();
+        let level = rule["level"].as_str().unwrap_or("medium").to_lowercase();
+
+        let logsource = &rule["logsource"];
+        if let Some(product) = logsource["product"].as_str()
+            && !product.eq_ignore_ascii_case("windows")
+        {
+            return Err(format!("Product '{}' is not windows.", product));
+        }
+        let mut channel = None;
+        let mut event_ids = Vec::new();
+        if let Some(service) = logsource["service"].as_str() {
+            let (_, name) = SERVICE_CHANNELS
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(service))
+                .ok_or_else(|| format!("Service '{}' is not supported.", service))?;
+            channel = Some(name.to_string());
+        }
+        if let Some(category) = logsource["category"].as_str() {
+            let (_, name, ids) = CATEGORY_EVENTS
+                .iter()
+                .find(|(known, _, _)| known.eq_ignore_ascii_case(category))
+                .ok_or_else(|| format!("Category '{}' is not supported.", category))?;
+            channel.get_or_insert_with(|| name.to_string());
+            event_ids = ids.to_vec();
+        }
+
+        let detection = rule["detection"]
+            .as_mapping()
+            .ok_or("The rule has no detection section.")?;
+        let mut selections = HashMap::new();
+        let mut condition = None;
+        for (key, value) in detection {
+            let name = key.as_str().ok_or("A detection key is not a name.")?;
+            match name {
+                "condition" => condition = Some(parse_conditions(value)?),
+                "timeframe" => return Err("Timeframes are not supported.".to_string()),
+                _ => {
+                    selections.insert(name.to_string(), Selection::parse(value)?);
+                }
+            }
+        }
+        let condition = condition.ok_or("The rule has no condition.")?;
+        condition.check(&selections)?;
+
+        Ok(Self {
+            title,
+            level,
+            channel,
+            event_ids,
+            selections,
+            condition,
+        })
+    }
+
+    pub fn matches(&self, event: &DisplayEvent) -> bool {
+        if let Some(channel) = &self.channel
+            && !event.channel.eq_ignore_ascii_case(channel)
+        {
+            return false;
+        }
+        if !self.event_ids.is_empty() && !self.event_ids.contains(&event.event_id) {
+            return false;
+        }
+        self.condition.eval(&self.selections, event)
+    }
+}
+
+impl Selection {
+    fn parse(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Mapping(_) => Ok(Self::Fields(vec![parse_field_map(value)?])),
+            Value::Sequence(items) if items.iter().all(Value::is_mapping) => Ok(Self::Fields(
+                items
+                    .iter()
+                    .map(parse_field_map)
+                    .collect::<Result<_, _>>()?,
+            )),
+            Value::Sequence(items) => Ok(Self::Keywords(
+                items
+                    .iter()
+                    .map(keyword_pattern)
+                    .collect::<Result<_, _>>()?,
+            )),
+            _ => Ok(Self::Keywords(vec![keyword_pattern(value)?])),
+        }
+    }
+
+    fn matches(&self, event: &DisplayEvent) -> bool {
+        match self {
+            Self::Fields(groups) => groups
+                .iter()
+                .any(|group| group.iter().all(|field| field.matches(event))),
+            Self::Keywords(patterns) => {
+                let message = event.formatted_message.as_deref().unwrap_or(&event.message);
+                let mut texts = std::iter::once(message).chain(
+                    event
+                        .event_data
+                        .fields
+                        .iter()
+                        .map(|(_, value)| value.as_str()),
+                );
+                texts.any(|text| patterns.iter().any(|pattern| pattern.is_match(text)))
+            }
+        }
+    }
+}
+
+/// Keywords match anywhere in a text.
+fn keyword_pattern(value: &Value) -> Result<Regex, String> {
+    let text = scalar_text(value).ok_or("A keyword is not a plain value.")?;
+    wildcard_pattern(&[text], true, true)
+}
+
+fn parse_field_map(value: &Value) -> Result<Vec<FieldMatch>, String> {
+    let Some(map) = value.as_mapping() else {
+        return Err("A selection is neither a map nor a list.".to_string());
+    };
+    let mut fields = Vec::new();
+    for (key, value) in map {
+        let key = key.as_str().ok_or("A field name is not text.")?;
+        let mut parts = key.split('|');
+        let field = parts.next().unwrap_or_default().to_string();
+        let (mut starts, mut ends, mut all, mut re, mut exists, mut windash) =
+            (false, false, false, false, false, false);
+        for modifier in parts {
+            match modifier {
+                "contains" => (starts, ends) = (true, true),
+                "startswith" => ends = true,
+                "endswith" => starts = true,
+                "all" => all = true,
+                "re" => re = true,
+                "exists" => exists = true,
+                "windash" => windash = true,
+                other => return Err(format!("Modifier '{}' is not supported.", other)),
+            }
+        }
+        let raw_values: Vec<&Value> = match value {
+            Value::Sequence(items) => items.iter().collect(),
+            other => vec![other],
+        };
+        let mut values = Vec::new();
+        for raw in raw_values {
+            if exists {
+                values.push(ValueMatch::Exists(raw.as_bool().unwrap_or(true)));
+                continue;
+            }
+            let Some(text) = scalar_text(raw) else {
+                values.push(ValueMatch::Empty);
+                continue;
+            };
+            if re {
+                let pattern = Regex::new(&text)
+                    .map_err(|e| format!("Invalid regular expression '{}': {}", text, e))?;
+                values.push(ValueMatch::Pattern(pattern));
+                continue;
+            }
+            let mut spellings = vec![text.clone()];
+            if windash {
+                spellings.push(slash_options(&text));
+            }
+            values.push(ValueMatch::Pattern(wildcard_pattern(
+                &spellings, starts, ends,
+            )?));
+        }
+        fields.push(FieldMatch { field, values, all });
+    }
+    Ok(fields)
+}
+
+/// Text of a string, number or boolean; `None` for `null` and collections.
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+/// Writes command line options given as `-x` as `/x`, which Windows tools take as well.
+fn slash_options(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut at_word_start = true;
+    for c in text.chars() {
+        output.push(if c == '-' && at_word_start { '/' } else { c });
+        at_word_start = c.is_whitespace();
+    }
+    output
+}
+
+/// A case-insensitive pattern matching any of the spellings of a Sigma value: `*` and `?` are
+/// wildcards unless escaped with a backslash, and `\\` is one backslash. `any_start` and
+/// `any_end` let the value begin or end anywhere in the field.
+fn wildcard_pattern(spellings: &[String], any_start: bool, any_end: bool) -> Result<Regex, String> {
+    let mut alternatives = Vec::new();
+    for value in spellings {
+        let mut alternative = String::new();
+        if any_start {
+            alternative.push_str(".*");
+        }
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if matches!(chars.peek(), Some('*' | '?' | '\\')) => {
+                    let escaped = chars.next().unwrap_or('\\');
+                    alternative.push_str(&regex::escape(&escaped.to_string()));
+                }
+                '*' => alternative.push_str(".*"),
+                '?' => alternative.push('.'),
+                _ => alternative.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        if any_end {
+            alternative.push_str(".*");
+        }
+        alternatives.push(alternative);
+    }
+    let pattern = format!("(?is)^(?:{})$", alternatives.join("|"));
+    Regex::new(&pattern).map_err(|e| format!("Invalid value '{}': {}", spellings.join("' or '"), e))
+}
+
+impl FieldMatch {
+    fn matches(&self, event: &DisplayEvent) -> bool {
+        let value = field_value(event, &self.field);
+        let value_matches = |expected: &ValueMatch| match expected {
+            ValueMatch::Pattern(pattern) => value.as_deref().is_some_and(|v| pattern.is_match(v)),
+            ValueMatch::Empty => value.as_deref().is_none_or(str::is_empty),
+            ValueMatch::Exists(exists) => value.is_some() == *exists,
+        };
+        if self.all {
+            self.values.iter().all(value_matches)
+        } else {
+            self.values.iter().any(value_matches)
+        }
+    }
+}
+
+/// Value of a field named as in Sigma rules: the System values by their usual names, else the
+/// event data field of that name.
+fn field_value(event: &DisplayEvent, field: &str) -> Option<String> {
+    match field {
+        "EventID" => Some(event.event_id.to_string()),
+        "Provider_Name" => Some(event.provider_name_original.clone()),
+        "Channel" => Some(event.channel.clone()),
+        "Computer" | "ComputerName" => Some(event.computer.clone()),
+        _ => event
+            .event_data
+            .named()
+            .find(|(name, _)| name.eq_ignore_ascii_case(field))
+            .map(|(_, value)| value.to_string()),
+    }
+}
+
+/// Reads `condition`, which may list several conditions, any of which must hold.
+fn parse_conditions(value: &Value) -> Result<Condition, String> {
+    match value {
+        Value::String(text) => parse_condition(text),
+        Value::Sequence(items) => Ok(Condition::Or(
+            items
+                .iter()
+                .map(|item| {
+                    item.as_str()
+                        .ok_or_else(|| "A condition is not text.".to_string())
+                        .and_then(parse_condition)
+                })
+                .collect::<Result<_, _>>()?,
+        )),
+        _ => Err("The condition is not text.".to_string()),
+    }
+}
+
+fn parse_condition(text: &str) -> Result<Condition, String> {
+    if text.contains('|') {
+        return Err("Aggregations in conditions are not supported.".to_string());
+    }
+    let spaced = text.replace('(', " ( ").replace(')', " ) ");
+    let tokens: Vec<&str> = spaced.split_whitespace().collect();
+    let mut parser = ConditionParser { tokens, next: 0 };
+    let condition = parser.or()?;
+    match parser.tokens.get(parser.next) {
+        Some(token) => Err(format!("Unexpected '{}' in the condition.", token)),
+        None => Ok(condition),
+    }
+}
+
+/// Recursive descent over the condition's words: `or` binds loosest, then `and`, then `not`.
+struct ConditionParser<'a> {
+    tokens: Vec<&'a str>,
+    next: usize,
+}
+
+impl<'a> ConditionParser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.next).copied()
+    }
+
+    fn take(&mut self) -> Option<&'a str> {
+        let token = self.peek();
+        self.next += 1;
+        token
+    }
+
+    fn take_keyword(&mut self, keyword: &str) -> bool {
+        let found = self
+            .peek()
+            .is_some_and(|token| token.eq_ignore_ascii_case(keyword));
+        if found {
+            self.next += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Condition, String> {
+        let mut terms = vec![self.and()?];
+        while self.take_keyword("or") {
+            terms.push(self.and()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Condition::Or(terms)
+        })
+    }
+
+    fn and(&mut self) -> Result<Condition, String> {
+        let mut terms = vec![self.not()?];
+        while self.take_keyword("and") {
+            terms.push(self.not()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            Condition::And(terms)
+        })
+    }
+
+    fn not(&mut self) -> Result<Condition, String> {
+        if self.take_keyword("not") {
+            return Ok(Condition::Not(Box::new(self.not()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Condition, String> {
+        let token = self.take().ok_or("The condition ends too early.")?;
+        if token == "(" {
+            let inner = self.or()?;
+            return match self.take() {
+                Some(")") => Ok(inner),
+                _ => Err("A parenthesis in the condition is not closed.".to_string()),
+            };
+        }
+        let quantifier = if token == "1" || token.eq_ignore_ascii_case("any") {
+            Some(false)
+        } else if token.eq_ignore_ascii_case("all") {
+            Some(true)
+        } else {
+            None
+        };
+        if let Some(all) = quantifier {
+            if !self.take_keyword("of") {
+                return Err(format!("Expected 'of' after '{}' in the condition.", token));
+            }
+            let target = self.take().ok_or("The condition ends after 'of'.")?;
+            // `them` stands for every selection not starting with an underscore.
+            let names = if target.eq_ignore_ascii_case("them") {
+                "^[^_].*$".to_string()
+            } else {
+                format!("^{}$", regex::escape(target).replace(r"\*", ".*"))
+            };
+            let names = Regex::new(&names).map_err(|e| e.to_string())?;
+            return Ok(Condition::Quantified { all, names });
+        }
+        Ok(Condition::Selection(token.to_string()))
+    }
+}
+
+impl Condition {
+    /// Fails when the condition names a selection the rule does not define.
+    fn check(&self, selections: &HashMap<String, Selection>) -> Result<(), String> {
+        match self {
+            Self::Selection(name) if !selections.contains_key(name) => Err(format!(
+                "The condition names an unknown selection '{}'.",
+                name
+            )),
+            Self::Selection(_) | Self::Quantified { .. } => Ok(()),
+            Self::Not(inner) => inner.check(selections),
+            Self::And(terms) | Self::Or(terms) => {
+                terms.iter().try_for_each(|term| term.check(selections))
+            }
+        }
+    }
+
+    fn eval(&self, selections: &HashMap<String, Selection>, event: &DisplayEvent) -> bool {
+        match self {
+            Self::Selection(name) => selections
+                .get(name)
+                .is_some_and(|selection| selection.matches(event)),
+            Self::Quantified { all, names } => {
+                let mut chosen = selections
+                    .iter()
+                    .filter(|(name, _)| names.is_match(name))
+                    .map(|(_, selection)| selection)
+                    .peekable();
+                if chosen.peek().is_none() {
+                    return false;
+                }
+                if *all {
+                    chosen.all(|selection| selection.matches(event))
+                } else {
+                    chosen.any(|selection| selection.matches(event))
+                }
+            }
+            Self::Not(inner) => !inner.eval(selections, event),
+            Self::And(terms) => terms.iter().all(|term| term.eval(selections, event)),
+            Self::Or(terms) => terms.iter().any(|term| term.eval(selections, event)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+
+    /// A Sysmon process creation event with the given event data.
+    fn process_event(fields: &[(&str, &str)]) -> DisplayEvent {
+        let mut event = mock::event(SYSMON_CHANNEL, "Sysmon", "Process Create", 1, Utc::now());
+        event.event_id = 1;
+        event.event_data.fields = fields
+            .iter()
+            .map(|(name, value)| (Some(name.to_string()), value.to_string()))
+            .collect();
+        event
+    }
+
+    /// A process creation rule with `detection` as its detection section.
+    fn rule(detection: &str) -> SigmaRule {
+        let yaml = format!(
+            "title: Test\nlogsource:\n  product: windows\n  category: process_creation\ndetection:\n{}",
+            detection
+        );
+        SigmaRule::parse(&yaml).expect("rule parses")
+    }
+
+    fn matches(detection: &str, fields: &[(&str, &str)]) -> bool {
+        rule(detection).matches(&process_event(fields))
+    }
+
+    #[test]
+    fn contains_startswith_and_endswith_anchor_the_value() {
+        let contains = "  sel:\n    CommandLine|contains: 'mimikatz'\n  condition: sel";
+        assert!(matches(
+            contains,
+            &[("CommandLine", "run MimiKatz.exe now")]
+        ));
+        assert!(!matches(contains, &[("CommandLine", "notepad.exe")]));
+
+        let starts = "  sel:\n    Image|startswith: 'C:\\Windows\\'\n  condition: sel";
+        assert!(matches(
+            starts,
+            &[("Image", "c:\\windows\\system32\\cmd.exe")]
+        ));
+        assert!(!matches(starts, &[("Image", "D:\\C:\\Windows\\cmd.exe")]));
+
+        let ends = "  sel:\n    Image|endswith: '\\cmd.exe'\n  condition: sel";
+        assert!(matches(
+            ends,
+            &[("Image", "C:\\Windows\\System32\\cmd.exe")]
+        ));
+        assert!(!matches(ends, &[("Image", "C:\\cmd.exe.bak")]));
+
+        let exact = "  sel:\n    Image: 'cmd.exe'\n  condition: sel";
+        assert!(matches(exact, &[("Image", "CMD.EXE")]));
+        assert!(!matches(exact, &[("Image", "C:\\cmd.exe")]));
+    }
+
+    #[test]
+    fn wildcards_match_any_text_and_one_character() {
+        // A backslash before a wildcard is written doubled.
+        let detection = "  sel:\n    Image: 'C:\\\\*\\power?hell.exe'\n  condition: sel";
+        assert!(matches(
+            detection,
+            &[("Image", "C:\\Windows\\System32\\powershell.exe")]
+        ));
+        assert!(!matches(
+            detection,
+            &[("Image", "C:\\Windows\\System32\\powerhell.exe")]
+        ));
+
+        let escaped = "  sel:\n    CommandLine: 'echo \\*'\n  condition: sel";
+        assert!(matches(escaped, &[("CommandLine", "echo *")]));
+        assert!(!matches(escaped, &[("CommandLine", "echo everything")]));
+    }
+
+    #[test]
+    fn re_matches_a_regular_expression() {
+        let detection = "  sel:\n    CommandLine|re: '-e(nc|ncodedcommand)\\s'\n  condition: sel";
+        assert!(matches(
+            detection,
+            &[("CommandLine", "powershell -enc SQBFAFgA")]
+        ));
+        assert!(!matches(
+            detection,
+            &[("CommandLine", "powershell -File a.ps1")]
+        ));
+    }
+
+    #[test]
+    fn all_requires_every_value() {
+        let detection = "  sel:\n    CommandLine|contains|all:\n      - 'net'\n      - 'user'\n  condition: sel";
+        assert!(matches(
+            detection,
+            &[("CommandLine", "net user admin /add")]
+        ));
+        assert!(!matches(detection, &[("CommandLine", "net group")]));
+    }
+
+    #[test]
+    fn windash_accepts_either_dash_for_each_value() {
+        let detection = "  sel:\n    CommandLine|windash|contains|all:\n      - ' -s '\n      - ' -t '\n  condition: sel";
+        assert!(matches(detection, &[("CommandLine", "shutdown -s -t 0")]));
+        assert!(matches(detection, &[("CommandLine", "shutdown /s /t 0")]));
+        assert!(matches(detection, &[("CommandLine", "shutdown /s -t 0")]));
+        assert!(!matches(detection, &[("CommandLine", "shutdown /s")]));
+    }
+
+    #[test]
+    fn one_of_and_all_of_pick_selections_by_name() {
+        let detection = "  sel_image:\n    Image|endswith: '\\whoami.exe'\n  sel_user:\n    User|contains: 'SYSTEM'\n  condition: {}";
+        let one_of = detection.replace("{}", "1 of sel_*");
+        let all_of = detection.replace("{}", "all of sel_*");
+        let whoami = [("Image", "C:\\Windows\\whoami.exe"), ("User", "alice")];
+        let system_whoami = [
+            ("Image", "C:\\Windows\\whoami.exe"),
+            ("User", "NT AUTHORITY\\SYSTEM"),
+        ];
+        assert!(matches(&one_of, &whoami));
+        assert!(!matches(&all_of, &whoami));
+        assert!(matches(&all_of, &system_whoami));
+        assert!(!matches(
+            &one_of,
+            &[("Image", "cmd.exe"), ("User", "alice")]
+        ));
+    }
+
+    #[test]
+    fn not_excludes_a_selection() {
+        let detection = "  selection:\n    Image|endswith: '\\rundll32.exe'\n  filter:\n    ParentImage|endswith: '\\explorer.exe'\n  condition: selection and not filter";
+        assert!(matches(
+            detection,
+            &[
+                ("Image", "C:\\rundll32.exe"),
+                ("ParentImage", "C:\\cmd.exe")
+            ]
+        ));
+        assert!(!matches(
+            detection,
+            &[
+                ("Image", "C:\\rundll32.exe"),
+                ("ParentImage", "C:\\explorer.exe")
+            ]
+        ));
+    }
+}
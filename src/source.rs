@@ -0,0 +1,232 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use crate::models::{AppState, ChannelAccess, DisplayEvent, FilterCriteria};
+
+#[cfg(target_os = "windows")]
+use crate::hosts::Login;
+#[cfg(target_os = "windows")]
+use std::collections::{BTreeSet, HashMap};
+
+/// A log backend: the channels it offers as tabs, and queries over them read in batches.
+/// `AppState` holds it as a trait object, so a mock or another backend (files, a remote
+/// machine) can be passed to `AppState::with_source` in place of the platform's.
+pub trait EventSource {
+    /// Channels shown as log tabs, in order.
+    fn channels(&self) -> Vec<String>;
+
+    /// Whether the current user can read `channel`.
+    fn check_access(&self, channel: &str) -> ChannelAccess;
+
+    /// Opens a query on `channel`, replacing the open one. `query` is in the backend's own
+    /// language: XPath or a structured XML query for the Windows Event Log, journal matches
+    /// for journald. With `reverse` the newest events are read first.
+    fn query(&mut self, channel: &str, query: &str, reverse: bool) -> Result<(), String>;
+
+    /// Reads up to `max` events of the open query. An empty batch means it is exhausted.
+    fn next_batch(&mut self, max: usize) -> Result<Vec<DisplayEvent>, String>;
+
+    /// Closes the open query, if any.
+    fn close_query(&mut self);
+
+    /// The complete record of an event as the backend stores it, for the raw preview.
+    fn render_detail(&self, event: &DisplayEvent) -> String;
+
+    /// Another source on the same backend and computer with no query open, for the reads made
+    /// beside the event list's own query: auto-refresh, analysis views, split panes.
+    fn fork(&self) -> Box<dyn EventSource>;
+
+    /// A source on the computer `host`, connected as `login` says. With `system_only` its
+    /// events carry only their System values, leaving out the event data and message.
+    #[cfg(target_os = "windows")]
+    fn connect(
+        &self,
+        host: &str,
+        _login: &Login,
+        _system_only: bool,
+    ) -> Result<Box<dyn EventSource>, String> {
+        Err(format!("This backend cannot read events from '{}'.", host))
+    }
+
+    /// Message templates the publisher `provider` defines for `event_ids`, for events whose
+    /// message it could not format. Backends without publisher metadata have none.
+    #[cfg(target_os = "windows")]
+    fn message_templates(
+        &mut self,
+        _provider: &str,
+        _event_ids: &BTreeSet<u32>,
+    ) -> HashMap<u32, String> {
+        HashMap::new()
+    }
+}
+
+/// Runs a one-off query on `source` and reads up to `max` of its events.
+#[cfg(target_os = "windows")]
+pub fn query_events(
+    source: &mut dyn EventSource,
+    channel: &str,
+    query: &str,
+    reverse: bool,
+    max: usize,
+) -> Result<Vec<DisplayEvent>, String> {
+    source
+        .query(channel, query, reverse)
+        .map_err(|e| format!("Failed to query '{}': {}", channel, e))?;
+    let mut tuner = BatchTuner::interactive();
+    let mut events = Vec::new();
+    while events.len() < max {
+        let batch = tuner.next_batch(source, max - events.len())?;
+        if batch.is_empty() {
+            break;
+        }
+        events.extend(batch);
+    }
+    source.close_query();
+    Ok(events)
+}
+
+/// Sizes the batches read from a source by the throughput measured on this machine and log, so
+/// that each batch takes about the target time to read.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchTuner {
+    size: usize,
+    min: usize,
+    max: usize,
+    target: Duration,
+    /// Events per second, smoothed over the batches read so far.
+    rate: Option<f64>,
+}
+
+impl BatchTuner {
+    /// For reads between two redraws, which must not hold up the interface.
+    pub fn interactive() -> Self {
+        Self::new(100, 16, 1000, Duration::from_millis(50))
+    }
+
+    /// For exports and reports, which read fastest in few large batches.
+    pub fn bulk() -> Self {
+        Self::new(500, 100, 5000, Duration::from_millis(500))
+    }
+
+    fn new(size: usize, min: usize, max: usize, target: Duration) -> Self {
+        Self {
+            size,
+            min,
+            max,
+            target,
+            rate: None,
+        }
+    }
+
+    /// Events to read in the next batch.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Events read per second, once a batch has been measured.
+    pub fn rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// Records that `events` were read in `elapsed` and sizes the next batch from the rate.
+    /// The rate is smoothed so that one slow call does not shrink the batches at once.
+    pub fn record(&mut self, events: usize, elapsed: Duration) {
+        if events == 0 {
+            return;
+        }
+        let rate = events as f64 / elapsed.as_secs_f64().max(0.000_1);
+        let rate = self.rate.map_or(rate, |old| old * 0.7 + rate * 0.3);
+        self.rate = Some(rate);
+        self.size = ((rate * self.target.as_secs_f64()) as usize).clamp(self.min, self.max);
+    }
+
+    /// Reads the next batch of the open query, at most `max` events, and measures it.
+    pub fn next_batch(
+        &mut self,
+        source: &mut dyn EventSource,
+        max: usize,
+    ) -> Result<Vec<DisplayEvent>, String> {
+        let started = Instant::now();
+        let batch = source.next_batch(self.size.min(max))?;
+        self.record(batch.len(), started.elapsed());
+        Ok(batch)
+    }
+}
+
+/// Backend of this platform.
+#[cfg(target_os = "windows")]
+pub type PlatformSource = crate::event_api::WindowsEventLog;
+#[cfg(all(target_os = "linux", feature = "journald"))]
+pub type PlatformSource = crate::journal::JournalSource;
+#[cfg(not(any(target_os = "windows", all(target_os = "linux", feature = "journald"))))]
+pub type PlatformSource = UnsupportedSource;
+
+/// Stands in on platforms with neither the Windows Event Log nor journald, and on Linux
+/// builds without the `journald` feature; it offers no channels.
+#[cfg(not(any(target_os = "windows", all(target_os = "linux", feature = "journald"))))]
+#[derive(Debug, Default)]
+pub struct UnsupportedSource;
+
+#[cfg(not(any(target_os = "windows", all(target_os = "linux", feature = "journald"))))]
+impl EventSource for UnsupportedSource {
+    fn channels(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn check_access(&self, _channel: &str) -> ChannelAccess {
+        ChannelAccess::Unknown
+    }
+
+    fn query(&mut self, _channel: &str, _query: &str, _reverse: bool) -> Result<(), String> {
+        if cfg!(target_os = "linux") {
+            Err("This build cannot read the journal; build with --features journald.".to_string())
+        } else {
+            Err("There is no event log backend for this platform.".to_string())
+        }
+    }
+
+    fn next_batch(&mut self, _max: usize) -> Result<Vec<DisplayEvent>, String> {
+        Ok(Vec::new())
+    }
+
+    fn close_query(&mut self) {}
+
+    fn render_detail(&self, event: &DisplayEvent) -> String {
+        event.raw_data.clone()
+    }
+
+    fn fork(&self) -> Box<dyn EventSource> {
+        Box::new(Self)
+    }
+}
+
+impl AppState {
+    /// The query for the active filter, in the language of the platform's backend.
+    pub fn filter_query(&self) -> String {
+        #[cfg(target_os = "windows")]
+        return self.build_xpath_from_filter();
+        #[cfg(all(target_os = "linux", feature = "journald"))]
+        return crate::journal::matches_from_filter(self.active_filter.as_ref());
+        #[cfg(not(any(target_os = "windows", all(target_os = "linux", feature = "journald"))))]
+        String::new()
+    }
+}
+
+/// The query of `filter` for the events logged from `start` to before `end`, in place of its
+/// own time range, in the language of the platform's backend.
+pub fn window_query(
+    filter: Option<&FilterCriteria>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> String {
+    #[cfg(target_os = "windows")]
+    return crate::event_api::xpath_for_window(filter, start, end);
+    #[cfg(all(target_os = "linux", feature = "journald"))]
+    return crate::journal::matches_for_window(filter, start, end);
+    #[cfg(not(any(target_os = "windows", all(target_os = "linux", feature = "journald"))))]
+    {
+        let _ = (filter, start, end);
+        String::new()
+    }
+}
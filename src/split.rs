@@ -0,0 +1,92 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::models::{DisplayEvent, FilterCriteria};
+use crate::source::{BatchTuner, EventSource};
+
+/// How far before and after the synchronized time the other pane is read when its events do
+/// not reach that time.
+pub const SYNC_WINDOW_MINUTES: i64 = 60;
+/// Most events read when synchronizing the other pane.
+const MAX_SYNC_EVENTS: usize = 50_000;
+
+/// The event pane shown beside the focused one in split view: the log and filter it was
+/// loaded with and the events it had when focus left it. Keys act on the focused pane;
+/// switching panes swaps the two and reloads the newly focused one.
+#[derive(Debug, Clone)]
+pub struct SplitPane {
+    pub log_index: usize,
+    pub log_name: String,
+    pub filter: Option<FilterCriteria>,
+    pub raw_query: Option<String>,
+    pub events: Vec<DisplayEvent>,
+    /// Index of the selected event in `events`.
+    pub selection: Option<usize>,
+    /// Whether the pane is drawn left of the focused one, so the panes keep their places when
+    /// focus moves between them.
+    pub on_left: bool,
+}
+
+impl SplitPane {
+    /// Index of the event logged closest to `time`.
+    pub fn nearest(&self, time: DateTime<Utc>) -> Option<usize> {
+        self.events
+            .iter()
+            .enumerate()
+            .filter_map(|(index, event)| Some((index, event.timestamp?)))
+            .min_by_key(|(_, logged)| (*logged - time).num_milliseconds().unsigned_abs())
+            .map(|(index, _)| index)
+    }
+
+    /// Whether `time` lies within the events the pane has loaded.
+    pub fn covers(&self, time: DateTime<Utc>) -> bool {
+        let oldest = self.events.iter().filter_map(|event| event.timestamp).min();
+        let newest = self.events.iter().filter_map(|event| event.timestamp).max();
+        matches!((oldest, newest), (Some(oldest), Some(newest)) if oldest <= time && time <= newest)
+    }
+
+    /// Title of the pane: its log and the filter or query it shows.
+    pub fn title(&self) -> String {
+        match (&self.raw_query, &self.filter) {
+            (Some(query), _) => format!(" Events: {} [Query: {}] ", self.log_name, query),
+            (None, Some(filter)) => format!(" Events: {} [{}] ", self.log_name, filter.summary()),
+            (None, None) => format!(" Events: {} ", self.log_name),
+        }
+    }
+}
+
+/// Reads the events of `log` logged within `SYNC_WINDOW_MINUTES` of `time` from `source` with
+/// a query built by `query` for that window, newest first. Events outside the window are
+/// skipped, for backends whose queries cannot bound the time.
+pub fn load_around(
+    source: &mut dyn EventSource,
+    log: &str,
+    time: DateTime<Utc>,
+    query: impl Fn(DateTime<Utc>, DateTime<Utc>) -> String,
+) -> Result<Vec<DisplayEvent>, String> {
+    let start = time - Duration::minutes(SYNC_WINDOW_MINUTES);
+    let end = time + Duration::minutes(SYNC_WINDOW_MINUTES);
+    source.query(log, &query(start, end), true)?;
+    let mut tuner = BatchTuner::bulk();
+    let mut events = Vec::new();
+    let mut read = 0;
+    while read < MAX_SYNC_EVENTS {
+        let batch = tuner.next_batch(source, MAX_SYNC_EVENTS - read)?;
+        if batch.is_empty() {
+            break;
+        }
+        read += batch.len();
+        let before_window = batch
+            .last()
+            .and_then(|event| event.timestamp)
+            .is_some_and(|logged| logged < start);
+        events.extend(batch.into_iter().filter(|event| {
+            event
+                .timestamp
+                .is_some_and(|logged| start <= logged && logged < end)
+        }));
+        if before_window {
+            break;
+        }
+    }
+    Ok(events)
+}
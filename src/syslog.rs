@@ -0,0 +1,246 @@
+use std::io::Write;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+use chrono::SecondsFormat;
+
+use crate::models::{DisplayEvent, EventLevel, SyslogFormat};
+use crate::siem;
+
+/// Ports collectors listen on when the address names none (RFC 5426 for UDP, RFC 6587 for TCP).
+const UDP_PORT: u16 = 514;
+const TCP_PORT: u16 = 601;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Longest record sent over UDP; longer ones are cut rather than dropped by the network.
+const MAX_UDP_RECORD: usize = 8192;
+/// Private enterprise number of the structured data IDs, the one reserved for examples.
+const ENTERPRISE_ID: u32 = 32473;
+/// Facilities of the records: security/authorization (authpriv) for the Security log, user
+/// level for the others.
+const SECURITY_FACILITY: u8 = 10;
+const USER_FACILITY: u8 = 1;
+
+enum Transport {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+}
+
+/// Connection to a syslog collector that events are sent to one record each.
+pub struct SyslogForwarder {
+    transport: Transport,
+    format: SyslogFormat,
+    /// The collector as configured, for messages.
+    pub collector: String,
+}
+
+impl SyslogForwarder {
+    /// Connects to `collector`, given as `host[:port]` with an optional `udp://` or `tcp://`
+    /// prefix; UDP is used when there is none.
+    pub fn connect(collector: &str, format: SyslogFormat) -> Result<Self, String> {
+        let collector = collector.trim();
+        if collector.is_empty() {
+            return Err(
+                "No syslog collector configured.\n\nSet syslog_collector in the configuration, e.g. \"udp://siem.example.com:514\" or \"tcp://siem.example.com:601\"."
+                    .to_string(),
+            );
+        }
+        let lower = collector.to_lowercase();
+        let (is_tcp, address) = if lower.starts_with("tcp://") {
+            (true, &collector[6..])
+        } else if lower.starts_with("udp://") {
+            (false, &collector[6..])
+        } else {
+            (false, collector)
+        };
+        let address = resolve(address, if is_tcp { TCP_PORT } else { UDP_PORT })
+            .map_err(|e| format!("Syslog collector '{}' not found: {}", collector, e))?;
+
+        let transport = if is_tcp {
+            let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+                .map_err(|e| format!("Failed to connect to {}: {}", collector, e))?;
+            stream
+                .set_write_timeout(Some(WRITE_TIMEOUT))
+                .map_err(|e| e.to_string())?;
+            Transport::Tcp(stream)
+        } else {
+            let local = if address.is_ipv4() {
+                "0.0.0.0:0"
+            } else {
+                "[::]:0"
+            };
+            let socket = UdpSocket::bind(local)
+                .and_then(|socket| socket.connect(address).map(|()| socket))
+                .map_err(|e| format!("Failed to open a UDP socket to {}: {}", collector, e))?;
+            Transport::Udp(socket)
+        };
+        Ok(Self {
+            transport,
+            format,
+            collector: collector.to_string(),
+        })
+    }
+
+    /// Sends one event as a record.
+    pub fn send(&mut self, event: &DisplayEvent, channel: &str) -> Result<(), String> {
+        let record = format_record(self.format, event, channel);
+        let result = match &mut self.transport {
+            Transport::Udp(socket) => {
+                let mut end = record.len().min(MAX_UDP_RECORD);
+                while !record.is_char_boundary(end) {
+                    end -= 1;
+                }
+                socket.send(&record.as_bytes()[..end]).map(|_| ())
+            }
+            // Octet counting (RFC 6587), so records may span lines.
+            Transport::Tcp(stream) => stream
+                .write_all(format!("{} {}", record.len(), record).as_bytes())
+                .and_then(|()| stream.flush()),
+        };
+        result.map_err(|e| format!("Failed to send to {}: {}", self.collector, e))
+    }
+
+    /// Sends the events in order, each under its own channel, or `channel` when it has none.
+    /// Returns the number sent, or an error telling how many were sent before it.
+    pub fn send_all(&mut self, events: &[DisplayEvent], channel: &str) -> Result<usize, String> {
+        for (sent, event) in events.iter().enumerate() {
+            let channel = if event.channel.is_empty() {
+                channel
+            } else {
+                &event.channel
+            };
+            self.send(event, channel)
+                .map_err(|e| format!("{}\n\n{} of {} events were sent.", e, sent, events.len()))?;
+        }
+        Ok(events.len())
+    }
+}
+
+/// Resolves `host[:port]`, adding `default_port` when the address has no port.
+fn resolve(address: &str, default_port: u16) -> Result<SocketAddr, String> {
+    let has_port = address.rsplit_once(':').is_some_and(|(host, port)| {
+        port.parse::<u16>().is_ok() && (!host.contains(':') || host.ends_with(']'))
+    });
+    let address = if has_port {
+        address.to_string()
+    } else if address.contains(':') && !address.starts_with('[') {
+        format!("[{}]:{}", address, default_port)
+    } else {
+        format!("{}:{}", address, default_port)
+    };
+    address
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| "the name has no address".to_string())
+}
+
+/// Formats an event as an RFC 5424 record. The message is the event's text with its System
+/// values and named event data as structured data, or a CEF record.
+pub fn format_record(format: SyslogFormat, event: &DisplayEvent, channel: &str) -> String {
+    let facility = if channel.eq_ignore_ascii_case("Security") {
+        SECURITY_FACILITY
+    } else {
+        USER_FACILITY
+    };
+    let process_id = event
+        .process_id
+        .map(|id| id.to_string())
+        .unwrap_or_default();
+    let header = format!(
+        "<{}>1 {} {} {} {} {}",
+        facility * 8 + severity(event.level),
+        event.timestamp.map_or_else(
+            || "-".to_string(),
+            |time| time.to_rfc3339_opts(SecondsFormat::Micros, true)
+        ),
+        header_field(&event.computer, 255),
+        header_field(&event.provider_name_original, 48),
+        header_field(&process_id, 128),
+        event.event_id
+    );
+    match format {
+        SyslogFormat::Rfc5424 => {
+            let body = event.formatted_message.as_deref().unwrap_or(&event.message);
+            format!(
+                "{} {} {}",
+                header,
+                structured_data(event, channel),
+                body.replace("\r\n", " ").replace(['\r', '\n'], " ")
+            )
+        }
+        SyslogFormat::Cef => format!("{} - {}", header, siem::to_cef(event, channel)),
+    }
+}
+
+/// Maps an event level to a syslog severity.
+fn severity(level: EventLevel) -> u8 {
+    match level {
+        EventLevel::Critical => 2,
+        EventLevel::Error => 3,
+        EventLevel::Warning => 4,
+        EventLevel::Unknown(_) => 5,
+        EventLevel::Information => 6,
+        EventLevel::Verbose => 7,
+    }
+}
+
+/// A header field: printable ASCII without spaces, at most `max` characters, `-` when empty.
+fn header_field(value: &str, max: usize) -> String {
+    let field: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max)
+        .collect();
+    if field.is_empty() {
+        "-".to_string()
+    } else {
+        field
+    }
+}
+
+/// A structured data parameter name: at most 32 printable ASCII characters other than `=`,
+/// `]` and `"`.
+fn is_param_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 32
+        && name
+            .chars()
+            .all(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+}
+
+fn param_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(']', "\\]")
+}
+
+/// `[event@…]` with the System values, then `[data@…]` with the named event data fields.
+fn structured_data(event: &DisplayEvent, channel: &str) -> String {
+    let mut system = vec![
+        ("channel", channel.to_string()),
+        ("eventID", event.event_id.to_string()),
+        ("level", event.level.display_name()),
+        ("recordID", event.record_id.to_string()),
+    ];
+    if !event.user.is_empty() {
+        system.push(("userSID", event.user.clone()));
+    }
+    let mut element = format!("[event@{}", ENTERPRISE_ID);
+    for (name, value) in system {
+        element.push_str(&format!(" {}=\"{}\"", name, param_value(&value)));
+    }
+    element.push(']');
+
+    let data: Vec<String> = event
+        .event_data
+        .named()
+        .filter(|(name, _)| is_param_name(name))
+        .map(|(name, value)| format!(" {}=\"{}\"", name, param_value(value)))
+        .collect();
+    if !data.is_empty() {
+        element.push_str(&format!("[data@{}{}]", ENTERPRISE_ID, data.concat()));
+    }
+    element
+}
@@ -0,0 +1,141 @@
+use std::error::Error;
+
+use crate::cli::TailArgs;
+
+#[cfg(target_os = "windows")]
+use crate::config::Config;
+#[cfg(target_os = "windows")]
+use crate::publishers::PublisherCache;
+#[cfg(target_os = "windows")]
+use crate::syslog::SyslogForwarder;
+#[cfg(target_os = "windows")]
+use crate::{event_api, event_parser, siem};
+#[cfg(target_os = "windows")]
+use std::io::{self, Write};
+#[cfg(target_os = "windows")]
+use windows::{
+    Win32::Foundation::{CloseHandle, ERROR_NO_MORE_ITEMS, GetLastError},
+    Win32::System::EventLog::{
+        EVT_HANDLE, EvtClose, EvtNext, EvtSubscribe, EvtSubscribeToFutureEvents,
+    },
+    Win32::System::Threading::{CreateEventW, INFINITE, ResetEvent, WaitForSingleObject},
+    core::PCWSTR,
+};
+
+/// Number of events pulled from the subscription per EvtNext call.
+#[cfg(target_os = "windows")]
+const TAIL_BATCH_SIZE: usize = 64;
+
+/// Subscribes to new events on the channel and writes each one to stdout as a single line,
+/// or forwards it to a syslog collector with `--syslog`, until the process is interrupted or
+/// stdout is closed.
+#[cfg(target_os = "windows")]
+pub fn run(args: &TailArgs) -> Result<(), Box<dyn Error>> {
+    let mut forwarder = match &args.syslog {
+        Some(collector) => Some(connect_forwarder(args, collector)?),
+        None => None,
+    };
+    let channel_wide = event_api::to_wide_string(&args.log);
+    let query_wide = event_api::to_wide_string("*");
+    let mut publisher_metadata_cache = PublisherCache::default();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    unsafe {
+        let signal = CreateEventW(None, true, true, PCWSTR::null())?;
+        let subscription = match EvtSubscribe(
+            None,
+            signal,
+            PCWSTR::from_raw(channel_wide.as_ptr()),
+            PCWSTR::from_raw(query_wide.as_ptr()),
+            None,
+            None,
+            None,
+            EvtSubscribeToFutureEvents.0,
+        ) {
+            Ok(handle) => handle,
+            Err(e) => {
+                let _ = CloseHandle(signal);
+                return Err(format!("Failed to subscribe to '{}': {}", args.log, e).into());
+            }
+        };
+
+        let result = 'stream: loop {
+            WaitForSingleObject(signal, INFINITE);
+            loop {
+                let mut events_buffer = vec![EVT_HANDLE::default(); TAIL_BATCH_SIZE];
+                let events_slice: &mut [isize] = std::mem::transmute(events_buffer.as_mut_slice());
+                let mut fetched = 0;
+                if EvtNext(subscription, events_slice, 0, 0, &mut fetched).is_err() {
+                    let error = GetLastError();
+                    if error != ERROR_NO_MORE_ITEMS {
+                        break 'stream Err(format!(
+                            "Error reading subscription for '{}': WIN32_ERROR({})",
+                            args.log, error.0
+                        ));
+                    }
+                    break;
+                }
+                let mut write_result = Ok(());
+                let mut forward_result = Ok(());
+                for &event_handle in &events_buffer[..fetched as usize] {
+                    let xml = event_api::render_event_xml(event_handle)
+                        .filter(|_| write_result.is_ok() && forward_result.is_ok());
+                    if let Some(xml) = xml {
+                        let mut display_event = event_parser::parse_event_xml(&xml);
+                        display_event.formatted_message = event_api::format_event_message(
+                            &mut publisher_metadata_cache,
+                            &display_event.provider_name_original,
+                            event_handle,
+                        );
+                        match &mut forwarder {
+                            Some(forwarder) => {
+                                forward_result = forwarder.send(&display_event, &args.log);
+                            }
+                            None => {
+                                write_result = writeln!(
+                                    out,
+                                    "{}",
+                                    siem::format_event(args.format, &display_event, &args.log)
+                                );
+                            }
+                        }
+                    }
+                    let _ = EvtClose(event_handle);
+                }
+                if let Err(e) = forward_result {
+                    break 'stream Err(e);
+                }
+                if write_result.and_then(|_| out.flush()).is_err() {
+                    // The reader went away (e.g. the pipe was closed); stop quietly.
+                    break 'stream Ok(());
+                }
+            }
+            let _ = ResetEvent(signal);
+        };
+
+        let _ = EvtClose(subscription);
+        let _ = CloseHandle(signal);
+        result.map_err(Into::into)
+    }
+}
+
+/// Connects to the collector given with `--syslog`, or to `syslog_collector` when it was
+/// given without one.
+#[cfg(target_os = "windows")]
+fn connect_forwarder(args: &TailArgs, collector: &str) -> Result<SyslogForwarder, String> {
+    let config = Config::load().unwrap_or_default();
+    let collector = match collector {
+        "" => config.syslog_collector.unwrap_or_default(),
+        collector => collector.to_string(),
+    };
+    SyslogForwarder::connect(
+        &collector,
+        args.syslog_format.unwrap_or(config.syslog_format),
+    )
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn run(_args: &TailArgs) -> Result<(), Box<dyn Error>> {
+    Err("tail requires the Windows Event Log service".into())
+}
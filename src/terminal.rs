@@ -1,21 +1,86 @@
 use crossterm::{
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode, enable_raw_mode,
+    },
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
+#[cfg(not(target_os = "windows"))]
+use std::io::Write;
 use std::io::{self, Stdout, stdout};
+use std::panic;
+#[cfg(target_os = "windows")]
+use std::sync::OnceLock;
+#[cfg(target_os = "windows")]
+use windows::{
+    Win32::System::Console::{GetConsoleTitleW, SetConsoleTitleW},
+    core::PCWSTR,
+};
+
+/// Console title from before the first `set_title`, NUL terminated.
+#[cfg(target_os = "windows")]
+static SAVED_TITLE: OnceLock<Vec<u16>> = OnceLock::new();
 
-/// Initializes the terminal in raw mode and enters the alternate screen.
+/// Initializes the terminal in raw mode and enters the alternate screen. The window title is
+/// saved first, and a panic restores the terminal before its message is printed.
 pub fn init_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
+    save_title()?;
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
     enable_raw_mode()?;
     let mut stdout = stdout();
     execute!(stdout, EnterAlternateScreen)?;
     Terminal::new(CrosstermBackend::new(stdout))
 }
 
-/// Restores the terminal to its previous state and leaves the alternate screen.
+/// Restores the terminal to its previous state, leaves the alternate screen and puts back the
+/// window title.
 pub fn restore_terminal() -> io::Result<()> {
     disable_raw_mode()?;
     execute!(stdout(), LeaveAlternateScreen)?;
+    restore_title()
+}
+
+/// Sets the terminal window title.
+pub fn set_title(title: &str) -> io::Result<()> {
+    execute!(stdout(), SetTitle(title))
+}
+
+/// Pushes the window title onto the terminal's title stack (XTWINOPS 22).
+#[cfg(not(target_os = "windows"))]
+fn save_title() -> io::Result<()> {
+    let mut stdout = stdout();
+    write!(stdout, "\x1b[22;0t")?;
+    stdout.flush()
+}
+
+/// Pops the title pushed by `save_title` (XTWINOPS 23).
+#[cfg(not(target_os = "windows"))]
+fn restore_title() -> io::Result<()> {
+    let mut stdout = stdout();
+    write!(stdout, "\x1b[23;0t")?;
+    stdout.flush()
+}
+
+/// Reads the console title, which the console keeps no stack for.
+#[cfg(target_os = "windows")]
+fn save_title() -> io::Result<()> {
+    let mut title = vec![0u16; 1024];
+    let length = unsafe { GetConsoleTitleW(&mut title) } as usize;
+    title.truncate(length);
+    title.push(0);
+    let _ = SAVED_TITLE.set(title);
+    Ok(())
+}
+
+/// Sets the console title read by `save_title` again.
+#[cfg(target_os = "windows")]
+fn restore_title() -> io::Result<()> {
+    if let Some(title) = SAVED_TITLE.get() {
+        unsafe { SetConsoleTitleW(PCWSTR::from_raw(title.as_ptr())) }?;
+    }
     Ok(())
 }
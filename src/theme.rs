@@ -0,0 +1,280 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Names of the color schemes that ship with the application.
+pub const BUILT_IN_THEMES: [&str; 4] = ["classic-blue", "dark", "light", "solarized"];
+
+/// Color scheme used by every render function. User-defined themes in the config file may
+/// set any subset of the colors; the rest fall back to the classic blue scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub bg: Color,
+    pub fg: Color,
+    pub border: Color,
+    pub focused_border: Color,
+    pub highlight_bg: Color,
+    pub highlight_fg: Color,
+    pub alt_fg: Color,
+    pub error_fg: Color,
+    pub warn_fg: Color,
+    /// Placeholder text such as "No events found".
+    pub muted_fg: Color,
+    /// De-emphasized parts of highlighted rows, like the "Microsoft-Windows-" prefix.
+    pub dim_fg: Color,
+    pub dialog_bg: Color,
+    pub dialog_fg: Color,
+    pub dialog_error_bg: Color,
+    pub dialog_error_fg: Color,
+    pub input_bg: Color,
+    pub input_fg: Color,
+    pub footer_bg: Color,
+    pub footer_fg: Color,
+    /// Element names and brackets in the raw XML preview.
+    pub xml_tag: Color,
+    pub xml_attribute: Color,
+    pub xml_value: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic_blue()
+    }
+}
+
+impl Theme {
+    /// The Norton Commander inspired blue scheme.
+    pub fn classic_blue() -> Self {
+        Self {
+            bg: Color::Blue,
+            fg: Color::White,
+            border: Color::LightCyan,
+            focused_border: Color::LightYellow,
+            highlight_bg: Color::Cyan,
+            highlight_fg: Color::Blue,
+            alt_fg: Color::LightYellow,
+            error_fg: Color::LightRed,
+            warn_fg: Color::LightYellow,
+            muted_fg: Color::Gray,
+            dim_fg: Color::DarkGray,
+            dialog_bg: Color::Cyan,
+            dialog_fg: Color::Black,
+            dialog_error_bg: Color::Red,
+            dialog_error_fg: Color::LightYellow,
+            input_bg: Color::Black,
+            input_fg: Color::LightYellow,
+            footer_bg: Color::Black,
+            footer_fg: Color::Gray,
+            xml_tag: Color::LightCyan,
+            xml_attribute: Color::LightGreen,
+            xml_value: Color::LightYellow,
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            bg: Color::Black,
+            fg: Color::Gray,
+            border: Color::DarkGray,
+            focused_border: Color::LightCyan,
+            highlight_bg: Color::DarkGray,
+            highlight_fg: Color::White,
+            alt_fg: Color::LightCyan,
+            error_fg: Color::LightRed,
+            warn_fg: Color::Yellow,
+            muted_fg: Color::DarkGray,
+            dim_fg: Color::Gray,
+            dialog_bg: Color::DarkGray,
+            dialog_fg: Color::White,
+            dialog_error_bg: Color::Red,
+            dialog_error_fg: Color::White,
+            input_bg: Color::Black,
+            input_fg: Color::LightCyan,
+            footer_bg: Color::Black,
+            footer_fg: Color::DarkGray,
+            xml_tag: Color::LightBlue,
+            xml_attribute: Color::LightGreen,
+            xml_value: Color::Yellow,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            bg: Color::White,
+            fg: Color::Black,
+            border: Color::Gray,
+            focused_border: Color::Blue,
+            highlight_bg: Color::Blue,
+            highlight_fg: Color::White,
+            alt_fg: Color::Blue,
+            error_fg: Color::Red,
+            warn_fg: Color::Rgb(0xb5, 0x6a, 0x00),
+            muted_fg: Color::DarkGray,
+            dim_fg: Color::Gray,
+            dialog_bg: Color::Gray,
+            dialog_fg: Color::Black,
+            dialog_error_bg: Color::Red,
+            dialog_error_fg: Color::White,
+            input_bg: Color::White,
+            input_fg: Color::Blue,
+            footer_bg: Color::Gray,
+            footer_fg: Color::Black,
+            xml_tag: Color::Blue,
+            xml_attribute: Color::Magenta,
+            xml_value: Color::Rgb(0xa3, 0x15, 0x15),
+        }
+    }
+
+    /// Solarized dark.
+    pub fn solarized() -> Self {
+        Self {
+            bg: Color::Rgb(0x00, 0x2b, 0x36),
+            fg: Color::Rgb(0x83, 0x94, 0x96),
+            border: Color::Rgb(0x58, 0x6e, 0x75),
+            focused_border: Color::Rgb(0xb5, 0x89, 0x00),
+            highlight_bg: Color::Rgb(0x26, 0x8b, 0xd2),
+            highlight_fg: Color::Rgb(0xfd, 0xf6, 0xe3),
+            alt_fg: Color::Rgb(0x2a, 0xa1, 0x98),
+            error_fg: Color::Rgb(0xdc, 0x32, 0x2f),
+            warn_fg: Color::Rgb(0xb5, 0x89, 0x00),
+            muted_fg: Color::Rgb(0x58, 0x6e, 0x75),
+            dim_fg: Color::Rgb(0x07, 0x36, 0x42),
+            dialog_bg: Color::Rgb(0x07, 0x36, 0x42),
+            dialog_fg: Color::Rgb(0x93, 0xa1, 0xa1),
+            dialog_error_bg: Color::Rgb(0xdc, 0x32, 0x2f),
+            dialog_error_fg: Color::Rgb(0xfd, 0xf6, 0xe3),
+            input_bg: Color::Rgb(0x00, 0x2b, 0x36),
+            input_fg: Color::Rgb(0xb5, 0x89, 0x00),
+            footer_bg: Color::Rgb(0x07, 0x36, 0x42),
+            footer_fg: Color::Rgb(0x83, 0x94, 0x96),
+            xml_tag: Color::Rgb(0x26, 0x8b, 0xd2),
+            xml_attribute: Color::Rgb(0xb5, 0x89, 0x00),
+            xml_value: Color::Rgb(0x2a, 0xa1, 0x98),
+        }
+    }
+
+    /// Looks up a theme by name, preferring user-defined themes over the built-in ones.
+    pub fn resolve(name: &str, user_themes: &BTreeMap<String, Theme>) -> Option<Self> {
+        if let Some(theme) = user_themes.get(name) {
+            return Some(*theme);
+        }
+        match name {
+            "classic-blue" => Some(Self::classic_blue()),
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the theme after `current`, cycling through the built-in themes
+    /// followed by the user-defined ones.
+    pub fn next_name(current: &str, user_themes: &BTreeMap<String, Theme>) -> String {
+        let names: Vec<&str> = BUILT_IN_THEMES
+            .iter()
+            .copied()
+            .chain(
+                user_themes
+                    .keys()
+                    .map(String::as_str)
+                    .filter(|name| !BUILT_IN_THEMES.contains(name)),
+            )
+            .collect();
+        let next_index = names
+            .iter()
+            .position(|name| *name == current)
+            .map_or(0, |index| (index + 1) % names.len());
+        names[next_index].to_string()
+    }
+
+    pub fn default_style(&self) -> Style {
+        Style::new().bg(self.bg).fg(self.fg)
+    }
+
+    pub fn border_style(&self, is_focused: bool) -> Style {
+        Style::new().fg(if is_focused {
+            self.focused_border
+        } else {
+            self.border
+        })
+    }
+
+    pub fn selection_style(&self) -> Style {
+        Style::new().bg(self.highlight_bg).fg(self.highlight_fg)
+    }
+
+    pub fn title_style(&self) -> Style {
+        self.selection_style()
+    }
+
+    pub fn key_style(&self) -> Style {
+        self.selection_style()
+    }
+
+    pub fn alt_fg_style(&self) -> Style {
+        self.default_style().fg(self.alt_fg)
+    }
+
+    pub fn error_fg_style(&self) -> Style {
+        self.default_style().fg(self.error_fg)
+    }
+
+    pub fn warn_fg_style(&self) -> Style {
+        self.default_style().fg(self.warn_fg)
+    }
+
+    pub fn header_style(&self) -> Style {
+        self.alt_fg_style().add_modifier(Modifier::BOLD)
+    }
+
+    /// Event list rows that match the active search.
+    pub fn search_match_style(&self) -> Style {
+        self.alt_fg_style().add_modifier(Modifier::BOLD)
+    }
+
+    /// Matched text inside the preview panel.
+    pub fn search_highlight_style(&self) -> Style {
+        Style::new()
+            .bg(self.warn_fg)
+            .fg(self.bg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Marker shown next to bookmarked events.
+    pub fn bookmark_style(&self) -> Style {
+        self.warn_fg_style().add_modifier(Modifier::BOLD)
+    }
+
+    /// Parts of the raw XML preview: element names, attribute names and attribute values.
+    pub fn xml_tag_style(&self) -> Style {
+        self.default_style().fg(self.xml_tag)
+    }
+
+    pub fn xml_attribute_style(&self) -> Style {
+        self.default_style().fg(self.xml_attribute)
+    }
+
+    pub fn xml_value_style(&self) -> Style {
+        self.default_style().fg(self.xml_value)
+    }
+
+    pub fn footer_style(&self) -> Style {
+        Style::new().bg(self.footer_bg).fg(self.footer_fg)
+    }
+
+    pub fn dialog_style(&self) -> Style {
+        Style::new().bg(self.dialog_bg).fg(self.dialog_fg)
+    }
+
+    pub fn dialog_error_style(&self) -> Style {
+        Style::new()
+            .bg(self.dialog_error_bg)
+            .fg(self.dialog_error_fg)
+    }
+
+    /// Style of focused text inputs and selections inside dialogs.
+    pub fn dialog_selection_style(&self) -> Style {
+        Style::new().bg(self.input_bg).fg(self.input_fg)
+    }
+}
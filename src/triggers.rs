@@ -0,0 +1,328 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::helpers::{self, EventIdSpec};
+use crate::models::{DisplayEvent, EventLevel, EventLevelFilter};
+use crate::siem;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+/// Firings waiting for the worker at most; further ones are dropped and reported.
+const MAX_QUEUED_ACTIONS: usize = 64;
+
+/// A rule run on the events auto-refresh brings in: when one matches, its JSON is POSTed to
+/// `webhook` and/or given to `command`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Trigger {
+    pub name: String,
+    /// Channel the events must come from; any when unset.
+    pub log: Option<String>,
+    /// Provider name, case-insensitive.
+    pub source: Option<String>,
+    /// Event IDs in the filter dialog's syntax, e.g. "4625,4740" or "4700-4799,-4702".
+    pub event_id: Option<String>,
+    pub level: EventLevelFilter,
+    /// Text the message or event data must contain, case-insensitive.
+    pub text: Option<String>,
+    pub webhook: Option<String>,
+    /// Shell command run with the event JSON on stdin and its main values in `EC_*` variables.
+    pub command: Option<String>,
+    /// Least seconds between two firings. Matches in between are counted and reported with the
+    /// next firing instead of each running the action.
+    pub throttle_secs: u64,
+}
+
+/// A trigger whose settings checked out, with its Event ID list parsed and its throttling
+/// state.
+struct ActiveTrigger {
+    trigger: Trigger,
+    event_ids: Option<EventIdSpec>,
+    last_fired: Option<Instant>,
+    /// Matches held back by throttling since the trigger last fired.
+    suppressed: usize,
+}
+
+impl ActiveTrigger {
+    /// Fails when the trigger has no action or an Event ID list that does not parse.
+    fn new(trigger: &Trigger) -> Result<Self, String> {
+        if trigger.webhook.is_none() && trigger.command.is_none() {
+            return Err("it has neither a webhook nor a command".to_string());
+        }
+        let event_ids = trigger
+            .event_id
+            .as_deref()
+            .map(helpers::parse_event_id_spec)
+            .transpose()?;
+        Ok(Self {
+            trigger: trigger.clone(),
+            event_ids,
+            last_fired: None,
+            suppressed: 0,
+        })
+    }
+
+    fn matches(&self, event: &DisplayEvent) -> bool {
+        let trigger = &self.trigger;
+        if trigger
+            .log
+            .as_ref()
+            .is_some_and(|log| !log.eq_ignore_ascii_case(&event.channel))
+        {
+            return false;
+        }
+        if trigger
+            .source
+            .as_ref()
+            .is_some_and(|source| !source.eq_ignore_ascii_case(&event.provider_name_original))
+        {
+            return false;
+        }
+        if self
+            .event_ids
+            .as_ref()
+            .is_some_and(|spec| !spec.matches(event.event_id))
+        {
+            return false;
+        }
+        let level_matches = match trigger.level {
+            EventLevelFilter::All => true,
+            EventLevelFilter::Information => event.level == EventLevel::Information,
+            EventLevelFilter::Warning => event.level == EventLevel::Warning,
+            EventLevelFilter::Error => {
+                matches!(event.level, EventLevel::Error | EventLevel::Critical)
+            }
+        };
+        if !level_matches {
+            return false;
+        }
+        match &trigger.text {
+            Some(text) => {
+                let text = text.to_lowercase();
+                let message = event.formatted_message.as_deref().unwrap_or(&event.message);
+                message.to_lowercase().contains(&text)
+                    || event
+                        .event_data
+                        .fields
+                        .iter()
+                        .any(|(_, value)| value.to_lowercase().contains(&text))
+            }
+            None => true,
+        }
+    }
+
+    /// The actions of a firing on `event`, for the worker thread.
+    fn action(&self, event: &DisplayEvent, suppressed: usize) -> Action {
+        let trigger = &self.trigger;
+        let payload = json!({
+            "trigger": trigger.name,
+            "throttled": suppressed,
+            "event": siem::ndjson_value(event, &event.channel),
+        })
+        .to_string();
+        let variables = vec![
+            ("EC_TRIGGER", trigger.name.clone()),
+            ("EC_CHANNEL", event.channel.clone()),
+            ("EC_PROVIDER", event.provider_name_original.clone()),
+            ("EC_EVENT_ID", event.event_id.to_string()),
+            ("EC_LEVEL", event.level.display_name()),
+            ("EC_COMPUTER", event.computer.clone()),
+            ("EC_RECORD_ID", event.record_id.to_string()),
+            (
+                "EC_TIME",
+                event
+                    .timestamp
+                    .map(|time| time.to_rfc3339())
+                    .unwrap_or_default(),
+            ),
+        ];
+        Action {
+            name: trigger.name.clone(),
+            webhook: trigger.webhook.clone(),
+            command: trigger.command.clone(),
+            payload,
+            variables,
+        }
+    }
+}
+
+/// The webhook and command of one firing, with what they are given.
+struct Action {
+    name: String,
+    webhook: Option<String>,
+    command: Option<String>,
+    payload: String,
+    variables: Vec<(&'static str, String)>,
+}
+
+/// The configured triggers that checked out, and the worker thread running their actions one
+/// at a time, so a slow webhook or command does not hold up the interface. The worker reports
+/// failures back through a channel.
+pub struct TriggerRunner {
+    triggers: Vec<ActiveTrigger>,
+    /// Queue of the worker; `None` when no trigger is active.
+    actions: Option<SyncSender<Action>>,
+    failures: Receiver<String>,
+}
+
+impl Default for TriggerRunner {
+    fn default() -> Self {
+        let (_, failures) = mpsc::channel();
+        Self {
+            triggers: Vec::new(),
+            actions: None,
+            failures,
+        }
+    }
+}
+
+impl TriggerRunner {
+    /// Prepares the configured triggers, starting the worker when any is usable. Returns a
+    /// line for each trigger left out and why.
+    pub fn load(triggers: &[Trigger]) -> (Self, Vec<String>) {
+        let mut ignored = Vec::new();
+        let triggers: Vec<ActiveTrigger> = triggers
+            .iter()
+            .filter_map(|trigger| match ActiveTrigger::new(trigger) {
+                Ok(active) => Some(active),
+                Err(e) => {
+                    ignored.push(format!("Trigger '{}' ignored: {}", trigger.name, e));
+                    None
+                }
+            })
+            .collect();
+        if triggers.is_empty() {
+            return (Self::default(), ignored);
+        }
+        let (actions, queue) = mpsc::sync_channel::<Action>(MAX_QUEUED_ACTIONS);
+        let (failures_sender, failures) = mpsc::channel();
+        thread::spawn(move || {
+            for action in queue {
+                let report = |result: Result<(), String>| {
+                    if let Err(e) = result {
+                        let _ = failures_sender
+                            .send(format!("Trigger '{}' failed: {}", action.name, e));
+                    }
+                };
+                if let Some(url) = &action.webhook {
+                    report(post_webhook(url, &action.payload));
+                }
+                if let Some(command) = &action.command {
+                    report(run_command(command, &action.payload, &action.variables));
+                }
+            }
+        });
+        let runner = Self {
+            triggers,
+            actions: Some(actions),
+            failures,
+        };
+        (runner, ignored)
+    }
+
+    /// Fires the triggers matched by new events, oldest event first, unless throttled.
+    /// Returns a line for each firing, and for each one dropped because the worker's queue is
+    /// full.
+    pub fn run(&mut self, events: &[DisplayEvent]) -> Vec<String> {
+        let mut notices = Vec::new();
+        let Some(actions) = &self.actions else {
+            return notices;
+        };
+        for event in events.iter().rev() {
+            for active in &mut self.triggers {
+                if !active.matches(event) {
+                    continue;
+                }
+                let throttle = Duration::from_secs(active.trigger.throttle_secs);
+                if active
+                    .last_fired
+                    .is_some_and(|fired| fired.elapsed() < throttle)
+                {
+                    active.suppressed += 1;
+                    continue;
+                }
+                let suppressed = std::mem::take(&mut active.suppressed);
+                active.last_fired = Some(Instant::now());
+                let name = &active.trigger.name;
+                if actions.try_send(active.action(event, suppressed)).is_err() {
+                    notices.push(format!(
+                        "Trigger '{}' skipped: {} actions are already waiting to run",
+                        name, MAX_QUEUED_ACTIONS
+                    ));
+                    continue;
+                }
+                let mut notice = format!(
+                    "Trigger '{}' fired: {} {} on {}",
+                    name, event.source, event.event_id, event.computer
+                );
+                if suppressed > 0 {
+                    notice.push_str(&format!(" (+{} throttled)", suppressed));
+                }
+                notices.push(notice);
+            }
+        }
+        notices
+    }
+
+    /// Failures of webhooks and commands since the last call.
+    pub fn failures(&self) -> Vec<String> {
+        self.failures.try_iter().collect()
+    }
+}
+
+fn post_webhook(url: &str, payload: &str) -> Result<(), String> {
+    let connector =
+        native_tls::TlsConnector::new().map_err(|e| format!("Failed to initialize TLS: {}", e))?;
+    let agent = ureq::AgentBuilder::new()
+        .tls_connector(Arc::new(connector))
+        .timeout(WEBHOOK_TIMEOUT)
+        .build();
+    match agent
+        .post(url)
+        .set("Content-Type", "application/json")
+        .send_string(payload)
+    {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, _)) => Err(format!("{} answered HTTP {}", url, code)),
+        Err(e) => Err(format!("{} not reached: {}", url, e)),
+    }
+}
+
+/// Runs the command through the shell with the payload on stdin and waits for it.
+fn run_command(command: &str, payload: &str, variables: &[(&str, String)]) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let mut shell = {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut shell = {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    };
+    let mut child = shell
+        .envs(variables.iter().map(|(name, value)| (name, value)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("'{}' did not start: {}", command, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Commands that do not read the event close stdin early; that is not a failure.
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("'{}' exited with {}", command, status))
+    }
+}
@@ -1,195 +1,192 @@
-use lazy_static::lazy_static;
 use ratatui::{
     prelude::*,
     text::{Line, Span},
     widgets::block::{Position, Title},
     widgets::{
-        Block, BorderType, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table,
-        Wrap,
+        Block, BorderType, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row,
+        Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState, Wrap,
     },
 };
 
+#[cfg(target_os = "windows")]
+use crate::authflow;
+use crate::bitlocker;
+use crate::compare::{Trend, Window};
+#[cfg(target_os = "windows")]
+use crate::devices::DeviceAction;
 use crate::helpers;
-use crate::models::{AppState, FilterFieldFocus, LOG_NAMES, PanelFocus, PreviewViewMode};
-
-// --- Theme Constants ---
-const THEME_BG: Color = Color::Blue;
-const THEME_FG: Color = Color::White;
-const THEME_BORDER: Color = Color::LightCyan;
-const THEME_FOCUSED_BORDER: Color = Color::LightYellow;
-const THEME_HIGHLIGHT_BG: Color = Color::Cyan;
-const THEME_HIGHLIGHT_FG: Color = THEME_BG;
-const THEME_ALT_FG: Color = Color::LightYellow;
-const THEME_ERROR_FG: Color = Color::LightRed;
-const THEME_WARN_FG: Color = Color::LightYellow;
-const THEME_DIALOG_DEFAULT_BG: Color = Color::Cyan;
-const THEME_DIALOG_DEFAULT_FG: Color = Color::Black;
-const THEME_DIALOG_ERROR_BG: Color = Color::Red;
-const THEME_DIALOG_ERROR_FG: Color = Color::LightYellow;
-const THEME_DIALOG_WARN_BG: Color = Color::Yellow;
-const THEME_DIALOG_WARN_FG: Color = Color::LightYellow;
-const THEME_FOOTER_BG: Color = Color::Black;
-const THEME_FOOTER_FG: Color = Color::Gray;
+use crate::hosts::{self, HostStatus, PickerRow, RemoteAuth};
+use crate::models::{
+    AdminAction, AppState, ColumnSettings, DisplayEvent, EventAction, EventColumn, EventLevel,
+    FilterFieldFocus, MERGED_TAB_NAME, MarkKey, PanelFocus, PreviewViewMode, PromptKind,
+};
+use crate::sigma;
+use crate::theme::Theme;
+
 const BORDER_TYPE_THEME: BorderType = BorderType::Double;
 const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Bars drawn for event density, from fewest to most events.
+const DENSITY_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Smallest event panel inner height at which the histogram strip is drawn.
+const HISTOGRAM_MIN_HEIGHT: u16 = 8;
+/// Seconds a notice such as a trigger firing stays in the footer.
+const FOOTER_NOTICE_SECS: u64 = 30;
+
+/// Builds a key hint span such as "[q]" in the theme's key style.
+fn key_span(label: &'static str, theme: &Theme) -> Span<'static> {
+    Span::styled(label, theme.key_style())
+}
 
-#[allow(dead_code)]
-const WHITE: Color = Color::White;
-const GRAY: Color = Color::Gray;
-#[allow(dead_code)]
-const DARK_GRAY: Color = Color::DarkGray;
-#[allow(dead_code)]
-const RED: Color = Color::Red;
-#[allow(dead_code)]
-const GREEN: Color = Color::Green;
-#[allow(dead_code)]
-const MAGENTA: Color = Color::Magenta;
-
-lazy_static! {
-    // Core Theme Styles
-    static ref DEFAULT_STYLE: Style = Style::new().bg(THEME_BG).fg(THEME_FG);
-    static ref BORDER_STYLE: Style = Style::new().fg(THEME_BORDER);
-    static ref SELECTION_STYLE: Style = Style::new().bg(THEME_HIGHLIGHT_BG).fg(THEME_HIGHLIGHT_FG);
-    static ref ALT_FG_STYLE: Style = DEFAULT_STYLE.patch(Style::new().fg(THEME_ALT_FG));
-    static ref ERROR_FG_STYLE: Style = DEFAULT_STYLE.patch(Style::new().fg(THEME_ERROR_FG));
-    static ref WARN_FG_STYLE: Style = DEFAULT_STYLE.patch(Style::new().fg(THEME_WARN_FG));
-    static ref TITLE_STYLE: Style = *SELECTION_STYLE;
-    static ref FOOTER_STYLE: Style = Style::new().bg(THEME_FOOTER_BG).fg(THEME_FOOTER_FG);
-    static ref DIALOG_SELECTION_STYLE: Style = Style::new().bg(THEME_DIALOG_DEFAULT_FG).fg(THEME_ALT_FG);
-    static ref DIALOG_DEFAULT_STYLE: Style = Style::new().bg(THEME_DIALOG_DEFAULT_BG).fg(THEME_DIALOG_DEFAULT_FG);
-    static ref DIALOG_ERROR_STYLE: Style = Style::new().bg(THEME_DIALOG_ERROR_BG).fg(THEME_DIALOG_ERROR_FG);
-    static ref DIALOG_WARN_STYLE: Style = Style::new().bg(THEME_DIALOG_WARN_BG).fg(THEME_DIALOG_WARN_FG);
-
-    // Component Styles
-    static ref BOLD_STYLE: Style = DEFAULT_STYLE.patch(Style::new().add_modifier(Modifier::BOLD));
-    static ref HEADER_STYLE: Style = DEFAULT_STYLE.patch(Style::new().fg(THEME_ALT_FG).add_modifier(Modifier::BOLD));
-    static ref HEADER_ROW_STYLE: Style = *DEFAULT_STYLE;
-    static ref INPUT_FOCUSED_STYLE: Style = *SELECTION_STYLE;
-    static ref INPUT_UNFOCUSED_STYLE: Style = *DEFAULT_STYLE;
-
-    // Keybinding Styles
-    static ref KEY_STYLE: Style = *SELECTION_STYLE;
-    static ref KEY_Q: Span<'static> = Span::styled("[q]", *KEY_STYLE);
-    static ref KEY_F1: Span<'static> = Span::styled("[F1]", *KEY_STYLE);
-    static ref KEY_S_SORT: Span<'static> = Span::styled("[s]", *KEY_STYLE);
-    static ref KEY_L_LEVEL: Span<'static> = Span::styled("[l]", *KEY_STYLE);
-    static ref KEY_F_FILTER: Span<'static> = Span::styled("[f]", *KEY_STYLE);
-    static ref KEY_SLASH_SEARCH: Span<'static> = Span::styled("[/]", *KEY_STYLE);
-    static ref KEY_N_NEXT: Span<'static> = Span::styled("[n]", *KEY_STYLE);
-    static ref KEY_P_PREV: Span<'static> = Span::styled("[p]", *KEY_STYLE);
-    static ref KEY_ESC: Span<'static> = Span::styled("[Esc]", *KEY_STYLE);
-    static ref KEY_ESC_LEFT: Span<'static> = Span::styled("[Esc/←]", *KEY_STYLE);
-    static ref KEY_V_TOGGLE: Span<'static> = Span::styled("[v]", *KEY_STYLE);
-    static ref KEY_S_SAVE: Span<'static> = Span::styled("[s]", *KEY_STYLE);
-    static ref KEY_ENTER_ESC: Span<'static> = Span::styled("[Enter/Esc]", *KEY_STYLE);
-    static ref KEY_SCROLL: Span<'static> = Span::styled("[↑↓ PgUpDn HmEnd]", *KEY_STYLE);
-
-    // Static Titles/Lines
-
-    static ref STATUS_DISMISS_LINE: Line<'static> = Line::from(vec![
-        KEY_ENTER_ESC.clone(), Span::raw(" Dismiss "),
-    ]).alignment(Alignment::Center);
-    static ref STATUS_DISMISS_TITLE: Title<'static> = Title::from(STATUS_DISMISS_LINE.clone())
-        .position(Position::Bottom).alignment(Alignment::Center);
-
-    static ref FILTER_CANCEL_LINE: Line<'static> = Line::from(vec![
-        KEY_ESC.clone(),
-    ]).alignment(Alignment::Center);
-    static ref FILTER_CANCEL_TITLE: Title<'static> = Title::from(FILTER_CANCEL_LINE.clone())
-        .position(Position::Bottom).alignment(Alignment::Center);
-
-    static ref SEARCH_BAR_TITLE: Title<'static> = Title::from(
-        Span::styled(" Find (Enter to search, Esc to cancel) ", *TITLE_STYLE)
-    ).alignment(Alignment::Left).position(Position::Top);
-
-    static ref HELP_DISMISS_TEXT_LINE: Line<'static> = Line::from(vec![
-        KEY_ESC.clone(),
-        Span::raw(" Dismiss "),
-        KEY_SCROLL.clone(),
-        Span::raw(" Scroll "),
-    ]).alignment(Alignment::Center);
-    static ref HELP_DISMISS_TITLE: Title<'static> = Title::from(HELP_DISMISS_TEXT_LINE.clone())
-        .position(Position::Bottom).alignment(Alignment::Center);
-
-    // Styling for the keybindings in the help dialog
-    static ref HELP_KEY_STYLE: Style = DIALOG_DEFAULT_STYLE.patch(Style::new().add_modifier(Modifier::BOLD));
-    static ref HELP_SECTION_STYLE: Style = DIALOG_DEFAULT_STYLE.patch(Style::new().add_modifier(Modifier::BOLD).add_modifier(Modifier::UNDERLINED));
-    static ref HELP_BODY_STYLE: Style = *DIALOG_DEFAULT_STYLE;
-    static ref HELP_URL_STYLE: Style = DIALOG_DEFAULT_STYLE.patch(Style::new().add_modifier(Modifier::ITALIC));
-
-    static ref HELP_TEXT_LINES: Vec<Line<'static>> = vec![
-        Line::from(Span::styled("Event Commander", *HELP_KEY_STYLE)),
-        Line::from(Span::styled("A TUI for browsing Windows Event Logs.", *HELP_BODY_STYLE)),
+#[rustfmt::skip]
+fn help_text_lines(theme: &Theme) -> Vec<Line<'static>> {
+    let key_style = theme.dialog_style().add_modifier(Modifier::BOLD);
+    let section_style = key_style.add_modifier(Modifier::UNDERLINED);
+    let body_style = theme.dialog_style();
+    let url_style = body_style.add_modifier(Modifier::ITALIC);
+    vec![
+        Line::from(Span::styled("Event Commander", key_style)),
+        Line::from(Span::styled("A TUI for browsing Windows Event Logs.", body_style)),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Developed by: ", *HELP_BODY_STYLE),
-            Span::styled("Toby Martin", *HELP_BODY_STYLE),
+            Span::styled("Developed by: ", body_style),
+            Span::styled("Toby Martin", body_style),
         ]),
         Line::from(vec![
-            Span::styled("Source Code: ", *HELP_BODY_STYLE),
-            Span::styled("https://github.com/Dastari/event_commander", *HELP_URL_STYLE),
+            Span::styled("Source Code: ", body_style),
+            Span::styled("https://github.com/Dastari/event_commander", url_style),
         ]),
         Line::from(""),
-        Line::from(Span::styled("License: GPL-3.0-or-later", *HELP_BODY_STYLE)),
-        Line::from(Span::styled("THE GNU GPLV3 GRANTS USERS FREEDOM TO RUN, STUDY, SHARE, AND MODIFY THE SOFTWARE. DERIVATIVE WORKS MUST ALSO BE DISTRIBUTED AS OPEN SOURCE.", *HELP_BODY_STYLE)),
+        Line::from(Span::styled("License: GPL-3.0-or-later", body_style)),
+        Line::from(Span::styled("THE GNU GPLV3 GRANTS USERS FREEDOM TO RUN, STUDY, SHARE, AND MODIFY THE SOFTWARE. DERIVATIVE WORKS MUST ALSO BE DISTRIBUTED AS OPEN SOURCE.", body_style)),
         Line::from(""),
-        Line::from(Span::styled("--- Global Keys ---", *HELP_SECTION_STYLE)),
+        Line::from(Span::styled("--- Global Keys ---", section_style)),
         Line::from(""),
-        Line::from(vec![Span::styled("  [q]          ", *HELP_KEY_STYLE), Span::styled("Quit application", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [F1]         ", *HELP_KEY_STYLE), Span::styled("Show/Hide this Help dialog", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [1]..[5]    ", *HELP_KEY_STYLE), Span::styled("Switch Event Log (Application, System, etc.)", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Tab]        ", *HELP_KEY_STYLE), Span::styled("Cycle focus forward (Events -> Preview)", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Shift+Tab]  ", *HELP_KEY_STYLE), Span::styled("Cycle focus backward (Preview -> Events)", *HELP_BODY_STYLE)]),
+        Line::from(vec![Span::styled("  [q]          ", key_style), Span::styled("Quit application", body_style)]),
+        Line::from(vec![Span::styled("  [F1]         ", key_style), Span::styled("Show/Hide this Help dialog", body_style)]),
+        Line::from(vec![Span::styled("  [1]..[5]    ", key_style), Span::styled("Switch Event Log (Application, System, etc.)", body_style)]),
+        Line::from(vec![Span::styled("  [6]          ", key_style), Span::styled("Merged tab: the logs in merged_logs interleaved by time", body_style)]),
+        Line::from(vec![Span::styled("  [F5]         ", key_style), Span::styled("Toggle auto-refresh: merge new events into the list every auto_refresh_secs", body_style)]),
+        Line::from(vec![Span::styled("  [Esc]        ", key_style), Span::styled("While loading: stop, keeping the events read so far", body_style)]),
+        Line::from(vec![Span::styled("  [T]          ", key_style), Span::styled("Cycle color theme (classic-blue, dark, light, solarized, custom)", body_style)]),
+        Line::from(vec![Span::styled("  [Ctrl+Left]  ", key_style), Span::styled("Shrink the event list, widening the preview", body_style)]),
+        Line::from(vec![Span::styled("  [Ctrl+Right] ", key_style), Span::styled("Grow the event list, narrowing the preview", body_style)]),
+        Line::from(vec![Span::styled("  [Shift+Left] ", key_style), Span::styled("Jump to the previous busy period on the event histogram", body_style)]),
+        Line::from(vec![Span::styled("  [Shift+Right]", key_style), Span::styled("Jump to the next busy period on the event histogram", body_style)]),
+        Line::from(vec![Span::styled("  [Tab]        ", key_style), Span::styled("Cycle focus forward (Events -> Preview)", body_style)]),
+        Line::from(vec![Span::styled("  [Shift+Tab]  ", key_style), Span::styled("Cycle focus backward (Preview -> Events)", body_style)]),
         Line::from(""),
-        Line::from(Span::styled("--- Event List Panel --- (When Focused)", *HELP_SECTION_STYLE)),
+        Line::from(Span::styled("--- Event List Panel --- (When Focused)", section_style)),
         Line::from(""),
-        Line::from(vec![Span::styled("  [↑]/[↓]      ", *HELP_KEY_STYLE), Span::styled("Scroll up/down one event", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [PgUp]/[PgDn]", *HELP_KEY_STYLE), Span::styled("Scroll up/down one page", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Home]/[g]   ", *HELP_KEY_STYLE), Span::styled("Go to top event", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [End]/[G]    ", *HELP_KEY_STYLE), Span::styled("Go to bottom event", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [s]          ", *HELP_KEY_STYLE), Span::styled("Toggle sort order (Date/Time)", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [l]          ", *HELP_KEY_STYLE), Span::styled("Cycle minimum level filter (All->Info->Warn->Err)", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [f]          ", *HELP_KEY_STYLE), Span::styled("Open Advanced Filter dialog", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [/]          ", *HELP_KEY_STYLE), Span::styled("Open Search input", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [n]          ", *HELP_KEY_STYLE), Span::styled("Find next search match", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [p]          ", *HELP_KEY_STYLE), Span::styled("Find previous search match", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Enter]      ", *HELP_KEY_STYLE), Span::styled("Focus Preview panel for selected event", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [←]/[→]    ", *HELP_KEY_STYLE), Span::styled("Cycle focus (same as Tab/Shift+Tab)", *HELP_BODY_STYLE)]),
+        Line::from(vec![Span::styled("  [↑]/[↓]      ", key_style), Span::styled("Scroll up/down one event (also [k]/[j] with vim_keys)", body_style)]),
+        Line::from(vec![Span::styled("  [Ctrl+U/D]   ", key_style), Span::styled("With vim_keys: scroll up/down half a page", body_style)]),
+        Line::from(vec![Span::styled("  [20j]/[5G]   ", key_style), Span::styled("With vim_keys: move 20 events down / go to the 5th event", body_style)]),
+        Line::from(vec![Span::styled("  ['a]/[`a]    ", key_style), Span::styled("With vim_keys: mark the selected event as 'a' / jump back to it", body_style)]),
+        Line::from(vec![Span::styled("  [PgUp]/[PgDn]", key_style), Span::styled("Scroll up/down one page", body_style)]),
+        Line::from(vec![Span::styled("  [Home]/[g]   ", key_style), Span::styled("Go to top event", body_style)]),
+        Line::from(vec![Span::styled("  [End]/[G]    ", key_style), Span::styled("Go to bottom event", body_style)]),
+        Line::from(vec![Span::styled("  [s]          ", key_style), Span::styled("Toggle sort order (ascending/descending)", body_style)]),
+        Line::from(vec![Span::styled("  [o]          ", key_style), Span::styled("Cycle sort column (Date/Time, Event ID, Level, Source)", body_style)]),
+        Line::from(vec![Span::styled("  [i]          ", key_style), Span::styled("Incident mode: lock all tabs to a time window (empty to leave)", body_style)]),
+        Line::from(vec![Span::styled("  [A]          ", key_style), Span::styled("Label the selected event's time on the incident timeline", body_style)]),
+        Line::from(vec![Span::styled("  [V]          ", key_style), Span::styled("Share the current view as a token, or open a shared one", body_style)]),
+        Line::from(vec![Span::styled("  [Ctrl+S/O]   ", key_style), Span::styled("Save the session (view and bookmarks) to a file, or restore one", body_style)]),
+        Line::from(vec![Span::styled("  [b]          ", key_style), Span::styled("Bookmark the selected event, or remove its bookmark", body_style)]),
+        Line::from(vec![Span::styled("  [B]          ", key_style), Span::styled("List bookmarks across logs; Enter jumps to the event, x removes it", body_style)]),
+        Line::from(vec![Span::styled("  [J]          ", key_style), Span::styled("Go to an event by record ID, loading it if needed", body_style)]),
+        Line::from(vec![Span::styled("  [F]          ", key_style), Span::styled("Filter on the binary an AppLocker/WDAC event audited or blocked", body_style)]),
+        Line::from(vec![Span::styled("  [w]          ", key_style), Span::styled("Explain why a process creation event (4688) has no command line", body_style)]),
+        Line::from(vec![Span::styled("  [r]          ", key_style), Span::styled("Show every event in the log sharing the selected event's activity ID", body_style)]),
+        Line::from(vec![Span::styled("  [S]          ", key_style), Span::styled("Filter on the selected event's source", body_style)]),
+        Line::from(vec![Span::styled("  [I]          ", key_style), Span::styled("Filter on the selected event's Event ID", body_style)]),
+        Line::from(vec![Span::styled("  [X]          ", key_style), Span::styled("Show every event in the log written by the selected event's process", body_style)]),
+        Line::from(vec![Span::styled("  [Space/m]    ", key_style), Span::styled("Open the actions menu for the selected event", body_style)]),
+        Line::from(vec![Span::styled("  [L]          ", key_style), Span::styled("Log administration: back up, export filtered events or clear the log", body_style)]),
+        Line::from(vec![Span::styled("  [y]/[c]      ", key_style), Span::styled("Copy the selected event to the clipboard as text / as XML", body_style)]),
+        Line::from(vec![Span::styled("  [Ctrl+X]     ", key_style), Span::styled("Copy an XPath query matching only the selected event", body_style)]),
+        Line::from(vec![Span::styled("  [H]          ", key_style), Span::styled("Group Hyper-V events by guest, with per-guest state timelines", body_style)]),
+        Line::from(vec![Span::styled("  [U]          ", key_style), Span::styled("Group Windows Installer events into install transactions with decoded results", body_style)]),
+        Line::from(vec![Span::styled("  [P]          ", key_style), Span::styled("Sum up printed and failed print jobs per printer and user", body_style)]),
+        Line::from(vec![Span::styled("  [u]          ", key_style), Span::styled("Show a connect/disconnect timeline per USB and PnP device", body_style)]),
+        Line::from(vec![Span::styled("  [N]          ", key_style), Span::styled("Merge Failover Clustering events from several nodes into one view", body_style)]),
+        Line::from(vec![Span::styled("               ", key_style), Span::styled("With hosts.toml: pick hosts, [Space] tick, [c] check connectivity", body_style)]),
+        Line::from(vec![Span::styled("  [W]          ", key_style), Span::styled("Interleave IIS and HTTPERR log entries with the loaded events (again to remove)", body_style)]),
+        Line::from(vec![Span::styled("  [Q]          ", key_style), Span::styled("Group WMI client failures (5858) by process, operation and result", body_style)]),
+        Line::from(vec![Span::styled("  [z]          ", key_style), Span::styled("Forward the shown events to the syslog collector (RFC 5424 or CEF)", body_style)]),
+        Line::from(vec![Span::styled("  [a]          ", key_style), Span::styled("List the Sigma rules the loaded events matched (Enter shows their hits)", body_style)]),
+        Line::from(vec![Span::styled("  [k]          ", key_style), Span::styled("Compare Event ID counts of two time windows ([←/→] switches windows)", body_style)]),
+        Line::from(vec![Span::styled("  [|] / [\\]    ", key_style), Span::styled("Split the event list into two panes / focus the other pane", body_style)]),
+        Line::from(vec![Span::styled("  [j]          ", key_style), Span::styled("Select the other pane's event closest in time to the selected one", body_style)]),
+        Line::from(vec![Span::styled("               ", key_style), Span::styled("(with vim_keys, [Alt+k] compares and [Alt+j] synchronizes)", body_style)]),
+        Line::from(vec![Span::styled("  [Y]          ", key_style), Span::styled("Show the audit policy, flagging subcategories the Event ID filter needs", body_style)]),
+        Line::from(vec![Span::styled("  [t]          ", key_style), Span::styled("Trace the Kerberos/NTLM authentication flow of the selected event's account", body_style)]),
+        Line::from(vec![Span::styled("  [h]          ", key_style), Span::styled("Object access: file and registry audits by path, with decoded access masks", body_style)]),
+        Line::from(vec![Span::styled("  [Z]          ", key_style), Span::styled("List failed updates with their CBS packages and decoded errors", body_style)]),
+        Line::from(vec![Span::styled("  [D]          ", key_style), Span::styled("Show failed DNS resolutions by name and response code", body_style)]),
+        Line::from(vec![Span::styled("  [d]          ", key_style), Span::styled("Open the detail view: every System and EventData field, one per row", body_style)]),
+        Line::from(vec![Span::styled("  [K]          ", key_style), Span::styled("Choose, order and size the event table columns", body_style)]),
+        Line::from(vec![Span::styled("  [l]          ", key_style), Span::styled("Cycle minimum level filter (All->Info->Warn->Err)", body_style)]),
+        Line::from(vec![Span::styled("  [f]          ", key_style), Span::styled("Open Advanced Filter dialog", body_style)]),
+        Line::from(vec![Span::styled("  [Alt+←/→]    ", key_style), Span::styled("Go back/forward through the filters applied (Recent in the dialog)", body_style)]),
+        Line::from(vec![Span::styled("  [x]          ", key_style), Span::styled("Enter a raw XPath/structured XML query (bypasses filter)", body_style)]),
+        Line::from(vec![Span::styled("  [C]          ", key_style), Span::styled("Export message catalog bundle for loaded events", body_style)]),
+        Line::from(vec![Span::styled("  [e]          ", key_style), Span::styled("Export loaded events for SIEM ingestion (CEF, LEEF or NDJSON)", body_style)]),
+        Line::from(vec![Span::styled("  [M]          ", key_style), Span::styled("Export the loaded events matching the last search", body_style)]),
+        Line::from(vec![Span::styled("  [Ctrl+E]     ", key_style), Span::styled("Export search matches from the whole log, loading the rest first", body_style)]),
+        Line::from(vec![Span::styled("  [R]          ", key_style), Span::styled("Toggle redaction of user, host and address data in exports and saved events", body_style)]),
+        Line::from(vec![Span::styled("  [E]          ", key_style), Span::styled("Switch the export format (CEF, LEEF, NDJSON)", body_style)]),
+        Line::from(vec![Span::styled("  [O]          ", key_style), Span::styled("Send loaded events to the OTLP endpoint (otlp_endpoint) in the background", body_style)]),
+        Line::from(vec![Span::styled("  [/]          ", key_style), Span::styled("Open Search input", body_style)]),
+        Line::from(vec![Span::styled("  [Ctrl+F]     ", key_style), Span::styled("Filter the loaded events as you type, without querying the log again", body_style)]),
+        Line::from(vec![Span::styled("  [n]          ", key_style), Span::styled("Find next search match, reading the rest of the log if needed (Esc cancels)", body_style)]),
+        Line::from(vec![Span::styled("  [p]          ", key_style), Span::styled("Find previous search match", body_style)]),
+        Line::from(vec![Span::styled("  [Enter]      ", key_style), Span::styled("Focus Preview panel for selected event", body_style)]),
+        Line::from(vec![Span::styled("  [←]/[→]    ", key_style), Span::styled("Cycle focus (same as Tab/Shift+Tab)", body_style)]),
         Line::from(""),
-        Line::from(Span::styled("--- Preview Panel --- (When Focused)", *HELP_SECTION_STYLE)),
+        Line::from(Span::styled("--- Preview Panel --- (When Focused)", section_style)),
         Line::from(""),
-        Line::from(vec![Span::styled("  [↑]/[↓]      ", *HELP_KEY_STYLE), Span::styled("Scroll content up/down one line", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [PgUp]/[PgDn]", *HELP_KEY_STYLE), Span::styled("Scroll content up/down one page", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Home]/[g]   ", *HELP_KEY_STYLE), Span::styled("Scroll to top", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [End]/[G]    ", *HELP_KEY_STYLE), Span::styled("Scroll to bottom", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [v]          ", *HELP_KEY_STYLE), Span::styled("Toggle view (Formatted/XML)", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [s]          ", *HELP_KEY_STYLE), Span::styled("Save current event details to XML file", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Esc]/[←]    ", *HELP_KEY_STYLE), Span::styled("Return focus to Event List panel", *HELP_BODY_STYLE)]),
+        Line::from(vec![Span::styled("  [↑]/[↓]      ", key_style), Span::styled("Scroll content up/down one line", body_style)]),
+        Line::from(vec![Span::styled("  [PgUp]/[PgDn]", key_style), Span::styled("Scroll content up/down one page", body_style)]),
+        Line::from(vec![Span::styled("  [Home]/[g]   ", key_style), Span::styled("Scroll to top", body_style)]),
+        Line::from(vec![Span::styled("  [End]/[G]    ", key_style), Span::styled("Scroll to bottom", body_style)]),
+        Line::from(vec![Span::styled("  [Enter]/[d]  ", key_style), Span::styled("Open the detail view; [Enter]/[y] there copies the selected value", body_style)]),
+        Line::from(vec![Span::styled("  [y]/[c]      ", key_style), Span::styled("Copy the event to the clipboard as text / as XML", body_style)]),
+        Line::from(vec![Span::styled("  [Ctrl+X]     ", key_style), Span::styled("Copy an XPath query matching only the event", body_style)]),
+        Line::from(vec![Span::styled("  [v]          ", key_style), Span::styled("Toggle view (Formatted/XML)", body_style)]),
+        Line::from(vec![Span::styled("  [s]          ", key_style), Span::styled("Save current event details to XML file", body_style)]),
+        Line::from(vec![Span::styled("  [F]          ", key_style), Span::styled("Filter on the binary an AppLocker/WDAC event audited or blocked", body_style)]),
+        Line::from(vec![Span::styled("  [w]          ", key_style), Span::styled("Toggle line wrapping; unwrapped, [←]/[→] scroll sideways", body_style)]),
+        Line::from(vec![Span::styled("  [r]          ", key_style), Span::styled("Show every event in the log sharing the selected event's activity ID", body_style)]),
+        Line::from(vec![Span::styled("  [X]          ", key_style), Span::styled("Show every event in the log written by the selected event's process", body_style)]),
+        Line::from(vec![Span::styled("  [Space/m]    ", key_style), Span::styled("Open the actions menu for the selected event", body_style)]),
+        Line::from(vec![Span::styled("  [Esc]/[←]    ", key_style), Span::styled("Return focus to Event List panel (once scrolled back to the left)", body_style)]),
         Line::from(""),
-        Line::from(Span::styled("--- Search Input --- (When Active)", *HELP_SECTION_STYLE)),
+        Line::from(Span::styled("--- Search Input --- (When Active)", section_style)),
         Line::from(""),
-        Line::from(vec![Span::styled("  [Enter]      ", *HELP_KEY_STYLE), Span::styled("Perform search and close", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Esc]        ", *HELP_KEY_STYLE), Span::styled("Cancel search and close", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  Text Input   ", *HELP_KEY_STYLE), Span::styled("Standard text input keys (Backspace, Delete, Arrows, Home, End)", *HELP_BODY_STYLE)]),
+        Line::from(vec![Span::styled("  [Enter]      ", key_style), Span::styled("Keep the match jumped to and close; searches the rest of the log if none is loaded", body_style)]),
+        Line::from(vec![Span::styled("  [Esc]        ", key_style), Span::styled("Cancel search, returning to the previous selection", body_style)]),
+        Line::from(vec![Span::styled("  Text Input   ", key_style), Span::styled("Standard text input keys; jumps to the first loaded match as you type", body_style)]),
         Line::from(""),
-        Line::from(Span::styled("--- Filter Dialog --- (When Active)", *HELP_SECTION_STYLE)),
+        Line::from(Span::styled("--- Filter Dialog --- (When Active)", section_style)),
         Line::from(""),
-        Line::from(vec![Span::styled("  [Tab]        ", *HELP_KEY_STYLE), Span::styled("Move focus to next field/button", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Shift+Tab]  ", *HELP_KEY_STYLE), Span::styled("Move focus to previous field/button", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Esc]        ", *HELP_KEY_STYLE), Span::styled("Cancel filtering and close dialog", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Enter]      ", *HELP_KEY_STYLE), Span::styled("Confirm input / Select Level / Activate Button", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  Text Input   ", *HELP_KEY_STYLE), Span::styled("Standard keys for EventID/Source fields", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [←]/[→]    ", *HELP_KEY_STYLE), Span::styled("Change Level / Move between Apply/Clear buttons", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [↑]/[↓]      ", *HELP_KEY_STYLE), Span::styled("Select previous/next Source from list (updates input)", *HELP_BODY_STYLE)]),
+        Line::from(vec![Span::styled("  [Tab]        ", key_style), Span::styled("Move focus to next field/button", body_style)]),
+        Line::from(vec![Span::styled("  [Shift+Tab]  ", key_style), Span::styled("Move focus to previous field/button", body_style)]),
+        Line::from(vec![Span::styled("  [Esc]        ", key_style), Span::styled("Cancel filtering and close dialog", body_style)]),
+        Line::from(vec![Span::styled("  [Enter]      ", key_style), Span::styled("Confirm input / Select Level / Activate Button", body_style)]),
+        Line::from(vec![Span::styled("  Text Input   ", key_style), Span::styled("Standard keys for EventID/Source fields", body_style)]),
+        Line::from(vec![Span::styled("  Event IDs    ", key_style), Span::styled("Lists, ranges and exclusions, e.g. 4624,4700-4799,-4688", body_style)]),
+        Line::from(vec![Span::styled("  [←]/[→]    ", key_style), Span::styled("Change Level/Time/Keywords / Move between Apply/Clear buttons", body_style)]),
+        Line::from(vec![Span::styled("  Task/Opcode  ", key_style), Span::styled("Numeric Task Category and Opcode values (e.g. 12544 Logon)", body_style)]),
+        Line::from(vec![Span::styled("  User/Computer", key_style), Span::styled("Match the Security UserID (SID) or Computer name exactly", body_style)]),
+        Line::from(vec![Span::styled("  [↑]/[↓]      ", key_style), Span::styled("Select previous/next Source from list (updates input)", body_style)]),
         Line::from(""),
-        Line::from(Span::styled("--- Help Dialog --- (This Screen)", *HELP_SECTION_STYLE)),
+        Line::from(Span::styled("--- Help Dialog --- (This Screen)", section_style)),
         Line::from(""),
-        Line::from(vec![Span::styled("  [Esc]        ", *HELP_KEY_STYLE), Span::styled("Dismiss this help dialog", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [↑]/[↓]      ", *HELP_KEY_STYLE), Span::styled("Scroll up/down one line", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [PgUp]/[PgDn]", *HELP_KEY_STYLE), Span::styled("Scroll up/down one page", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [Home]/[g]   ", *HELP_KEY_STYLE), Span::styled("Scroll to top", *HELP_BODY_STYLE)]),
-        Line::from(vec![Span::styled("  [End]/[G]    ", *HELP_KEY_STYLE), Span::styled("Scroll to bottom", *HELP_BODY_STYLE)]),
-    ];
+        Line::from(vec![Span::styled("  [Esc]        ", key_style), Span::styled("Dismiss this help dialog", body_style)]),
+        Line::from(vec![Span::styled("  [↑]/[↓]      ", key_style), Span::styled("Scroll up/down one line", body_style)]),
+        Line::from(vec![Span::styled("  [PgUp]/[PgDn]", key_style), Span::styled("Scroll up/down one page", body_style)]),
+        Line::from(vec![Span::styled("  [Home]/[g]   ", key_style), Span::styled("Scroll to top", body_style)]),
+        Line::from(vec![Span::styled("  [End]/[G]    ", key_style), Span::styled("Scroll to bottom", body_style)]),
+    ]
 }
 
 // --- Helper Functions ---
@@ -212,72 +209,140 @@ fn create_dialog_block(
         .style(dialog_style)
 }
 
-fn render_scroll_indicator(
+/// Draws a scrollbar over the right border of `area` when there is more content than fits,
+/// with a thumb as long as the share of the content in view. `position` is the first line in
+/// view and `max_scroll` the furthest it can go.
+fn render_scrollbar(
     frame: &mut Frame,
     area: Rect,
-    current_line: usize,
-    total_lines: usize,
+    position: usize,
+    max_scroll: usize,
+    viewport: usize,
     style: Style,
 ) {
-    if area.height == 0 || area.width < 5 || total_lines == 0 {
-        return;
-    }
-
-    let display_current = current_line.min(total_lines);
-    let scroll_info = format!("[{}/{}]", display_current, total_lines);
-
-    let indicator_width = scroll_info.len() as u16;
-    if indicator_width > area.width {
+    if max_scroll == 0 || area.height == 0 {
         return;
     }
-    let x_pos = area.right().saturating_sub(indicator_width);
-    let y_pos = area.y;
-    let scroll_rect = Rect::new(x_pos, y_pos, indicator_width, 1);
-    frame.render_widget(Paragraph::new(scroll_info).style(style), scroll_rect);
+    let mut scrollbar_state = ScrollbarState::new(max_scroll + 1)
+        .position(position.min(max_scroll))
+        .viewport_content_length(viewport);
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .track_symbol(None)
+            .style(style),
+        area,
+        &mut scrollbar_state,
+    );
 }
 
 // --- Main UI Rendering ---
 
 pub fn ui(frame: &mut Frame, app_state: &mut AppState) {
+    let theme = app_state.theme;
+    let ruler_height = if app_state.incident.is_some() { 4 } else { 0 };
     let main_chunks = Layout::vertical([
         Constraint::Length(3),
+        Constraint::Length(ruler_height),
         Constraint::Min(0),
         Constraint::Length(1),
     ])
     .split(frame.size());
 
-    render_log_tabs(frame, app_state, main_chunks[0]);
-    let middle_chunks =
-        Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
-            .split(main_chunks[1]);
-    render_event_table(frame, app_state, middle_chunks[0]);
-    render_preview_panel(frame, app_state, middle_chunks[1]);
-    render_bottom_bar(frame, app_state, main_chunks[2]);
-
-    render_status_dialog(frame, app_state);
-    render_filter_dialog(frame, app_state);
-    render_help_dialog(frame, app_state);
-    render_search_bar(frame, app_state);
+    render_log_tabs(frame, app_state, main_chunks[0], &theme);
+    render_incident_ruler(frame, app_state, main_chunks[1], &theme);
+    let split = [
+        Constraint::Percentage(app_state.config.split_percent),
+        Constraint::Percentage(100 - app_state.config.split_percent),
+    ];
+    let middle_chunks = if main_chunks[2].width < app_state.config.stack_below_width {
+        Layout::vertical(split).split(main_chunks[2])
+    } else {
+        Layout::horizontal(split).split(main_chunks[2])
+    };
+    let search_matches = app_state.search_matches();
+    let events_area = match app_state.split_pane.as_ref().map(|pane| pane.on_left) {
+        Some(pane_on_left) => {
+            let [left, right] =
+                Layout::horizontal([Constraint::Percentage(50); 2]).areas(middle_chunks[0]);
+            let (pane_area, events_area) = if pane_on_left {
+                (left, right)
+            } else {
+                (right, left)
+            };
+            render_split_pane(frame, app_state, pane_area, &theme);
+            events_area
+        }
+        None => middle_chunks[0],
+    };
+    render_event_table(frame, app_state, events_area, &search_matches, &theme);
+    render_preview_panel(frame, app_state, middle_chunks[1], &theme);
+    render_bottom_bar(frame, app_state, main_chunks[3], &search_matches, &theme);
+
+    render_filter_dialog(frame, app_state, &theme);
+    render_column_dialog(frame, app_state, &theme);
+    render_bookmarks_dialog(frame, app_state, &theme);
+    render_detail_view(frame, app_state, &theme);
+    #[cfg(target_os = "windows")]
+    render_dns_analysis(frame, app_state, &theme);
+    #[cfg(target_os = "windows")]
+    render_hyperv_view(frame, app_state, &theme);
+    #[cfg(target_os = "windows")]
+    render_installer_view(frame, app_state, &theme);
+    #[cfg(target_os = "windows")]
+    render_print_view(frame, app_state, &theme);
+    #[cfg(target_os = "windows")]
+    render_device_view(frame, app_state, &theme);
+    #[cfg(target_os = "windows")]
+    render_wmi_analysis(frame, app_state, &theme);
+    render_sigma_view(frame, app_state, &theme);
+    render_comparison(frame, app_state, &theme);
+    #[cfg(target_os = "windows")]
+    render_servicing_view(frame, app_state, &theme);
+    render_audit_policy(frame, app_state, &theme);
+    #[cfg(target_os = "windows")]
+    render_auth_flow(frame, app_state, &theme);
+    #[cfg(target_os = "windows")]
+    render_object_access(frame, app_state, &theme);
+    render_host_picker(frame, app_state, &theme);
+    render_context_menu(frame, app_state, &theme);
+    render_admin_menu(frame, app_state, &theme);
+    render_help_dialog(frame, app_state, &theme);
+    render_search_bar(frame, app_state, &theme);
+    render_query_input(frame, app_state, &theme);
+    render_local_filter_bar(frame, app_state, &theme);
+    render_deep_search_progress(frame, app_state, &theme);
+    render_prompt(frame, app_state, &theme);
+    render_elevation_prompt(frame, app_state, &theme);
+    render_status_dialog(frame, app_state, &theme);
 }
 
 // --- Panel Rendering ---
 
-fn render_log_tabs(frame: &mut Frame, app_state: &mut AppState, area: Rect) {
+fn render_log_tabs(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
     let block = Block::new()
         .title(
-            Title::from(Span::styled(" Event Commander ", *TITLE_STYLE))
-                .alignment(Alignment::Left)
-                .position(Position::Top),
+            Title::from(Span::styled(
+                if app_state.is_elevated {
+                    " Event Commander (Administrator) "
+                } else {
+                    " Event Commander "
+                },
+                theme.title_style(),
+            ))
+            .alignment(Alignment::Left)
+            .position(Position::Top),
         )
         .title(
-            Title::from(Span::styled(format!("v{}", VERSION), *DEFAULT_STYLE))
+            Title::from(Span::styled(format!("v{}", VERSION), theme.default_style()))
                 .alignment(Alignment::Right)
                 .position(Position::Top),
         )
         .borders(Borders::ALL)
-        .border_style(*BORDER_STYLE)
+        .border_style(theme.border_style(false))
         .border_type(BORDER_TYPE_THEME)
-        .style(*DEFAULT_STYLE);
+        .style(theme.default_style());
     frame.render_widget(block.clone(), area);
 
     let inner_area = block.inner(area);
@@ -285,24 +350,44 @@ fn render_log_tabs(frame: &mut Frame, app_state: &mut AppState, area: Rect) {
         return;
     }
 
-    let mut tab_spans = vec![Span::styled(" Event Logs: ", *ALT_FG_STYLE)];
-    for (i, log_name) in LOG_NAMES.iter().enumerate() {
+    let mut tab_spans = vec![Span::styled(" Event Logs: ", theme.alt_fg_style())];
+    for (i, log_name) in app_state.channels.iter().enumerate() {
         let is_selected = app_state.selected_log_index == i;
+        let is_readable = app_state.channel_access[i].is_readable();
         let style = if is_selected {
-            *SELECTION_STYLE
+            theme.selection_style()
+        } else if !is_readable {
+            theme.default_style().fg(theme.muted_fg)
+        } else {
+            theme.default_style()
+        };
+        let label = if is_readable {
+            log_name.to_string()
         } else {
-            *DEFAULT_STYLE
+            format!("{} (no access)", log_name)
         };
         tab_spans.extend([
-            Span::styled(format!("[{}]", i + 1), *KEY_STYLE),
+            Span::styled(format!("[{}]", i + 1), theme.key_style()),
             Span::raw(":").style(style),
-            Span::styled(log_name.to_string(), style),
-            Span::raw("  ").style(*DEFAULT_STYLE),
+            Span::styled(label, style),
+            Span::raw("  ").style(theme.default_style()),
+        ]);
+    }
+    if let Some(merged_index) = app_state.merged_tab_index() {
+        let merged_style = if app_state.is_merged_tab() {
+            theme.selection_style()
+        } else {
+            theme.default_style()
+        };
+        tab_spans.extend([
+            Span::styled(format!("[{}]", merged_index + 1), theme.key_style()),
+            Span::raw(":").style(merged_style),
+            Span::styled(MERGED_TAB_NAME, merged_style),
         ]);
     }
 
-    let tabs_paragraph =
-        Paragraph::new(Line::from(tab_spans).alignment(Alignment::Left)).style(*DEFAULT_STYLE);
+    let tabs_paragraph = Paragraph::new(Line::from(tab_spans).alignment(Alignment::Left))
+        .style(theme.default_style());
     let tabs_render_area = Rect {
         y: inner_area.y + inner_area.height.saturating_sub(1) / 2,
         height: 1,
@@ -311,13 +396,176 @@ fn render_log_tabs(frame: &mut Frame, app_state: &mut AppState, area: Rect) {
     frame.render_widget(tabs_paragraph, tabs_render_area);
 }
 
-fn render_event_table(frame: &mut Frame, app_state: &mut AppState, area: Rect) {
+/// Draws the incident timeline: event density across the window, with markers and the
+/// selected event, and the marker labels underneath.
+fn render_incident_ruler(frame: &mut Frame, app_state: &AppState, area: Rect, theme: &Theme) {
+    let Some(incident) = &app_state.incident else {
+        return;
+    };
+    let block = Block::new()
+        .title(
+            Title::from(Span::styled(" Incident ", theme.title_style()))
+                .alignment(Alignment::Left)
+                .position(Position::Top),
+        )
+        .title(
+            Title::from(Span::styled(
+                format!(
+                    " {} window | {} markers ",
+                    helpers::format_duration(incident.end - incident.start),
+                    incident.markers.len()
+                ),
+                theme.default_style(),
+            ))
+            .alignment(Alignment::Right)
+            .position(Position::Top),
+        )
+        .title(
+            Title::from(Span::styled(
+                format!(" {} ", helpers::format_local_time(incident.start)),
+                theme.default_style(),
+            ))
+            .alignment(Alignment::Left)
+            .position(Position::Bottom),
+        )
+        .title(
+            Title::from(Span::styled(
+                format!(" {} ", helpers::format_local_time(incident.end)),
+                theme.default_style(),
+            ))
+            .alignment(Alignment::Right)
+            .position(Position::Bottom),
+        )
+        .borders(Borders::ALL)
+        .border_style(theme.border_style(false))
+        .border_type(BORDER_TYPE_THEME)
+        .style(theme.default_style());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let width = inner.width as usize;
+    if width == 0 {
+        return;
+    }
+
+    let column_of = |time: chrono::DateTime<chrono::Utc>| {
+        helpers::time_bucket(time, incident.start, incident.end, width)
+    };
+
+    let mut counts = vec![0usize; width];
+    for column in app_state
+        .events
+        .iter()
+        .filter_map(|e| column_of(e.timestamp?))
+    {
+        counts[column] += 1;
+    }
+    let max_count = counts.iter().copied().max().unwrap_or(0).max(1);
+    let mut ruler: Vec<Span> = counts
+        .iter()
+        .map(|&count| {
+            Span::styled(
+                density_bar(count, max_count).to_string(),
+                theme.alt_fg_style(),
+            )
+        })
+        .collect();
+
+    let mut labels = String::new();
+    for marker in &incident.markers {
+        let Some(column) = column_of(marker.time) else {
+            continue;
+        };
+        ruler[column] = Span::styled("▼", theme.warn_fg_style());
+        // Labels that would overlap the previous one are left to the marker symbol.
+        let used = labels.chars().count();
+        if column >= used + usize::from(used > 0) {
+            labels.push_str(&" ".repeat(column - used));
+            labels.extend(format!("▲{}", marker.label).chars().take(width - column));
+        }
+    }
+    if let Some(column) = app_state
+        .selected_event()
+        .and_then(|e| column_of(e.timestamp?))
+    {
+        ruler[column] = Span::styled("┃", theme.selection_style());
+    }
+
+    let lines = vec![
+        Line::from(ruler),
+        Line::from(Span::styled(labels, theme.warn_fg_style())),
+    ];
+    frame.render_widget(Paragraph::new(lines).style(theme.default_style()), inner);
+}
+
+/// Returns the bar for `count` events scaled against `max_count`, or a line for none.
+fn density_bar(count: usize, max_count: usize) -> char {
+    if count == 0 {
+        return '─';
+    }
+    DENSITY_BARS[(count * DENSITY_BARS.len())
+        .div_ceil(max_count.max(1))
+        .clamp(1, DENSITY_BARS.len())
+        - 1]
+}
+
+/// Draws the density of the listed events over their time span, one bucket per column, with
+/// the selected event's bucket highlighted.
+fn render_event_histogram(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
+    let buckets = area.width as usize;
+    app_state.histogram_buckets = buckets;
+    let counts = app_state.histogram_counts(buckets);
+    let max_count = counts.iter().copied().max().unwrap_or(0);
+    let selected = app_state.histogram_range().and_then(|(start, end)| {
+        app_state
+            .selected_event()
+            .and_then(|event| helpers::time_bucket(event.timestamp?, start, end, buckets))
+    });
+    let bars: Vec<Span> = counts
+        .iter()
+        .enumerate()
+        .map(|(bucket, &count)| {
+            let style = if Some(bucket) == selected {
+                theme.selection_style()
+            } else {
+                theme.alt_fg_style()
+            };
+            Span::styled(density_bar(count, max_count).to_string(), style)
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(Line::from(bars)).style(theme.default_style()),
+        area,
+    );
+}
+
+/// Describes the histogram bucket holding the selected event: its time slice and event count.
+fn histogram_bucket_text(app_state: &AppState) -> Option<String> {
+    let buckets = app_state.histogram_buckets;
+    let (start, end) = app_state.histogram_range()?;
+    let bucket = app_state
+        .selected_event()
+        .and_then(|event| helpers::time_bucket(event.timestamp?, start, end, buckets))?;
+    let count = app_state.histogram_counts(buckets)[bucket];
+    let slices = (buckets as i64 - 1).max(1);
+    let span_ms = (end - start).num_milliseconds();
+    let bucket_time = |index: i64| start + chrono::Duration::milliseconds(span_ms * index / slices);
+    Some(format!(
+        " {} .. {}: {} events ",
+        helpers::format_local_time(bucket_time(bucket as i64)),
+        helpers::format_local_time(bucket_time(bucket as i64 + 1).min(end)),
+        count
+    ))
+}
+
+fn render_event_table(
+    frame: &mut Frame,
+    app_state: &mut AppState,
+    area: Rect,
+    search_matches: &[usize],
+    theme: &Theme,
+) {
     let is_focused = app_state.focus == PanelFocus::Events;
-    let border_style = BORDER_STYLE.patch(Style::new().fg(if is_focused {
-        THEME_FOCUSED_BORDER
-    } else {
-        THEME_BORDER
-    }));
+    let border_style = theme.border_style(is_focused);
 
     // Add loading indicator text conditionally
     let loading_indicator = if app_state.is_loading {
@@ -325,39 +573,113 @@ fn render_event_table(frame: &mut Frame, app_state: &mut AppState, area: Rect) {
     } else {
         ""
     };
-    let events_title_text = format!(" Events: {} ", app_state.selected_log_name);
-    let events_count_text = format!(
-        " {} Events Loaded{} ",
-        app_state.events.len(),
-        loading_indicator
-    );
+    let log_title = if app_state.is_merged_tab() {
+        format!(
+            "{} ({})",
+            app_state.selected_log_name,
+            app_state.config.merged_logs.join(" + ")
+        )
+    } else {
+        app_state.selected_log_name.clone()
+    };
+    let events_title_text = match (&app_state.cluster_nodes, &app_state.raw_query) {
+        (Some(nodes), _) => format!(" Events: Failover Clustering on {} ", nodes.join(", ")),
+        (None, Some(query)) => format!(" Events: {} [Query: {}] ", log_title, query),
+        (None, None) => format!(" Events: {} ", log_title),
+    };
+    let retention_text = app_state
+        .retention
+        .as_ref()
+        .filter(|r| r.is_losing_events())
+        .and_then(|r| r.horizon())
+        .map(|horizon| format!(" | Retains ~{}", helpers::format_duration(horizon)))
+        .unwrap_or_default();
+    let unloaded_text = if app_state.unloaded_before.is_some() {
+        " | More Above"
+    } else {
+        ""
+    };
+    let mut filter_text = String::new();
+    if !app_state.local_filter.is_empty() {
+        filter_text.push_str(&format!(" | Local Filter: '{}'", app_state.local_filter));
+    }
+    match app_state.sigma_filter.as_deref() {
+        Some("") => filter_text.push_str(" | Sigma: Any Rule"),
+        Some(title) => filter_text.push_str(&format!(" | Sigma: '{}'", title)),
+        None => {}
+    }
+    let events_count_text = if app_state.local_filter_indices.is_some() {
+        format!(
+            " {} of {} Events Loaded{}{}{}{} ",
+            app_state.row_count(),
+            app_state.events.len(),
+            filter_text,
+            unloaded_text,
+            retention_text,
+            loading_indicator
+        )
+    } else {
+        format!(
+            " {} Events Loaded{}{}{} ",
+            app_state.events.len(),
+            unloaded_text,
+            retention_text,
+            loading_indicator
+        )
+    };
+
+    let show_histogram = app_state.config.show_histogram
+        && app_state.row_count() > 0
+        && area.height.saturating_sub(2) >= HISTOGRAM_MIN_HEIGHT;
+    let bucket_text = if show_histogram {
+        histogram_bucket_text(app_state).unwrap_or_default()
+    } else {
+        app_state.histogram_buckets = 0;
+        String::new()
+    };
 
     let block = Block::new()
         .title(
-            Title::from(Span::styled(events_title_text, *TITLE_STYLE))
+            Title::from(Span::styled(events_title_text, theme.title_style()))
                 .alignment(Alignment::Left)
                 .position(Position::Top),
         )
         .title(
-            Title::from(Span::styled(events_count_text, *TITLE_STYLE))
+            Title::from(Span::styled(bucket_text, theme.default_style()))
+                .alignment(Alignment::Right)
+                .position(Position::Top),
+        )
+        .title(
+            Title::from(Span::styled(events_count_text, theme.title_style()))
                 .alignment(Alignment::Center)
                 .position(Position::Bottom),
         )
         .borders(Borders::ALL)
         .border_style(border_style)
         .border_type(BORDER_TYPE_THEME)
-        .style(*DEFAULT_STYLE);
+        .style(theme.default_style());
 
-    if app_state.events.is_empty() {
+    if app_state.row_count() == 0 {
         frame.render_widget(block.clone(), area);
         let inner_area = block.inner(area);
-        let message = if app_state.active_filter.is_some() {
+        let message = if app_state.local_filter_indices.is_some() && !app_state.events.is_empty() {
+            if app_state.local_filter.is_empty() {
+                "No loaded events match the Sigma rule"
+            } else {
+                "No loaded events contain the local filter text"
+            }
+        } else if app_state.active_filter.is_some() {
             "No events found matching filter criteria"
         } else {
             "No events found"
         };
         let centered_text = Paragraph::new(message)
-            .style(DEFAULT_STYLE.patch(Style::new().fg(GRAY).add_modifier(Modifier::BOLD)))
+            .style(
+                theme
+                    .default_style()
+                    .fg(theme.muted_fg)
+                    .add_modifier(Modifier::BOLD),
+            )
             .alignment(Alignment::Center);
         let layout = Layout::vertical([
             Constraint::Percentage(40),
@@ -367,41 +689,110 @@ fn render_event_table(frame: &mut Frame, app_state: &mut AppState, area: Rect) {
         .split(inner_area);
         frame.render_widget(centered_text, layout[1]);
     } else {
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let table_area = if show_histogram {
+            let [histogram_area, table_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+            render_event_histogram(frame, app_state, histogram_area, theme);
+            table_area
+        } else {
+            inner
+        };
+
         let selected_index = app_state.table_state.selected();
         const MS_PREFIX: &str = "Microsoft-Windows-";
-        let gray_style = Style::default().fg(Color::DarkGray);
+        let gray_style = Style::default().fg(theme.dim_fg);
 
-        let event_rows: Vec<Row> = app_state
-            .events
+        let mut columns: Vec<ColumnSettings> = app_state
+            .config
+            .columns
             .iter()
+            .filter(|c| c.visible)
+            .copied()
+            .collect();
+        // Merged views tell their sources apart by the computer or channel of each event.
+        let origin_column = if app_state.cluster_nodes.is_some() {
+            Some(EventColumn::Computer)
+        } else if app_state.is_merged_tab() {
+            Some(EventColumn::Channel)
+        } else {
+            None
+        };
+        if let Some(column) =
+            origin_column.filter(|&column| !columns.iter().any(|c| c.column == column))
+        {
+            columns.insert(
+                1.min(columns.len()),
+                ColumnSettings {
+                    column,
+                    visible: true,
+                    width: column.default_width(),
+                },
+            );
+        }
+
+        let message_percent = app_state.config.message_column_percent;
+        // Borders, the highlight symbol and the bookmark marker take five columns of the
+        // table area.
+        let message_chars = (area.width.saturating_sub(5) * message_percent / 100) as usize;
+
+        // Borrow the fields directly so the rows don't hold the whole state while the table
+        // state is updated below.
+        let shown_events: Vec<&DisplayEvent> = match &app_state.local_filter_indices {
+            Some(indices) => indices
+                .iter()
+                .filter_map(|&index| app_state.events.get(index))
+                .collect(),
+            None => app_state.events.iter().collect(),
+        };
+        let event_rows: Vec<Row> = shown_events
+            .into_iter()
             .enumerate()
             .map(|(i, event)| {
-                let level_style = match event.level.as_str() {
-                    "Warning" => *WARN_FG_STYLE,
-                    "Error" | "Critical" => *ERROR_FG_STYLE,
-                    _ => *DEFAULT_STYLE,
+                let cells = columns.iter().map(|settings| match settings.column {
+                    EventColumn::Level => {
+                        let level_style = match event.level {
+                            EventLevel::Warning => theme.warn_fg_style(),
+                            EventLevel::Error | EventLevel::Critical => theme.error_fg_style(),
+                            _ => theme.default_style(),
+                        };
+                        Cell::from(event.level.display_name()).style(level_style)
+                    }
+                    EventColumn::Source
+                        if selected_index == Some(i)
+                            && event.provider_name_original.starts_with(MS_PREFIX) =>
+                    {
+                        let prefix =
+                            Span::styled(MS_PREFIX, gray_style.patch(theme.selection_style()));
+                        let suffix = Span::styled(
+                            &event.provider_name_original[MS_PREFIX.len()..],
+                            theme.selection_style(),
+                        );
+                        Cell::from(Line::from(vec![prefix, suffix]))
+                    }
+                    EventColumn::Message => Cell::from(helpers::truncate_at_word(
+                        &EventColumn::Message.text(event),
+                        message_chars,
+                    )),
+                    column => Cell::from(column.text(event)),
+                });
+                let marker = if app_state.is_bookmarked(event) {
+                    Cell::from("◆").style(theme.bookmark_style())
+                } else if !event.sigma_hits.is_empty() {
+                    Cell::from("!").style(theme.error_fg_style().add_modifier(Modifier::BOLD))
+                } else {
+                    Cell::from(" ")
                 };
-
-                let source_cell = if selected_index == Some(i)
-                    && event.provider_name_original.starts_with(MS_PREFIX)
-                {
-                    let prefix = Span::styled(MS_PREFIX, gray_style.patch(*SELECTION_STYLE));
-                    let suffix = Span::styled(
-                        &event.provider_name_original[MS_PREFIX.len()..],
-                        *SELECTION_STYLE,
-                    );
-                    Cell::from(Line::from(vec![prefix, suffix]))
+                let cells = std::iter::once(marker).chain(cells);
+                let row_style = if search_matches.binary_search(&i).is_ok() {
+                    theme.search_match_style()
+                } else if bitlocker::is_recovery(event) {
+                    theme.error_fg_style().add_modifier(Modifier::BOLD)
                 } else {
-                    Cell::from(event.source.clone())
+                    theme.default_style()
                 };
-
-                Row::new([
-                    Cell::from(event.level.clone()).style(level_style),
-                    Cell::from(event.datetime.clone()),
-                    source_cell,
-                    Cell::from(event.id.clone()),
-                ])
-                .style(*DEFAULT_STYLE)
+                Row::new(cells).style(row_style)
             })
             .collect();
 
@@ -410,71 +801,286 @@ fn render_event_table(frame: &mut Frame, app_state: &mut AppState, area: Rect) {
         } else {
             " ↑"
         };
-        let header = Row::new([
-            Cell::from("Level").style(*HEADER_STYLE),
-            Cell::from(format!("Date and Time{}", sort_indicator)).style(*HEADER_STYLE),
-            Cell::from("Source").style(*HEADER_STYLE),
-            Cell::from("Event ID").style(*HEADER_STYLE),
-        ])
-        .style(*HEADER_ROW_STYLE)
+        let header = Row::new(std::iter::once(Cell::from(" ")).chain(columns.iter().map(
+            |settings| {
+                let label = settings.column.display_name();
+                let text = if settings.column.sort_key() == Some(app_state.sort_key) {
+                    format!("{}{}", label, sort_indicator)
+                } else {
+                    label.to_string()
+                };
+                Cell::from(text).style(theme.header_style())
+            },
+        )))
+        .style(theme.default_style())
         .height(1);
 
-        let table = Table::new(
-            event_rows,
-            [
-                Constraint::Length(11),
-                Constraint::Length(22),
-                Constraint::Percentage(60),
-                Constraint::Length(10),
-            ],
+        let widths = std::iter::once(Constraint::Length(1)).chain(columns.iter().map(|settings| {
+            match settings.width {
+                _ if settings.column == EventColumn::Message => {
+                    Constraint::Percentage(message_percent)
+                }
+                0 => Constraint::Fill(1),
+                width => Constraint::Length(width),
+            }
+        }));
+        let table = Table::new(event_rows, widths)
+            .header(header)
+            .highlight_style(theme.selection_style())
+            .highlight_symbol(" ")
+            .column_spacing(1)
+            .style(theme.default_style());
+
+        frame.render_stateful_widget(table, table_area, &mut app_state.table_state);
+
+        // Beside the rows, below the header.
+        let visible_rows = table_area.height.saturating_sub(1) as usize;
+        app_state.events_visible_rows = visible_rows;
+        let scrollbar_area = Rect::new(
+            area.x,
+            table_area.y + 1,
+            area.width,
+            table_area.height.saturating_sub(1),
+        );
+        render_scrollbar(
+            frame,
+            scrollbar_area,
+            app_state.table_state.offset(),
+            app_state.row_count().saturating_sub(visible_rows),
+            visible_rows,
+            border_style,
+        );
+    }
+}
+
+/// Renders the unfocused pane of split view with the events and selection it had when focus
+/// left it, or from the last time synchronization.
+fn render_split_pane(frame: &mut Frame, app_state: &AppState, area: Rect, theme: &Theme) {
+    let Some(pane) = &app_state.split_pane else {
+        return;
+    };
+    let block = Block::new()
+        .title(
+            Title::from(Span::styled(pane.title(), theme.title_style()))
+                .alignment(Alignment::Left)
+                .position(Position::Top),
+        )
+        .title(
+            Title::from(Span::styled(
+                format!(
+                    " {} Events Loaded | [\\] Switch Pane [{}] Sync Time ",
+                    pane.events.len(),
+                    if app_state.config.vim_keys {
+                        "Alt+j"
+                    } else {
+                        "j"
+                    }
+                ),
+                theme.title_style(),
+            ))
+            .alignment(Alignment::Center)
+            .position(Position::Bottom),
         )
+        .borders(Borders::ALL)
+        .border_style(theme.border_style(false))
+        .border_type(BORDER_TYPE_THEME)
+        .style(theme.default_style());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let columns: Vec<ColumnSettings> = app_state
+        .config
+        .columns
+        .iter()
+        .filter(|c| c.visible)
+        .copied()
+        .collect();
+    let rows: Vec<Row> = pane
+        .events
+        .iter()
+        .map(|event| {
+            Row::new(columns.iter().map(|settings| match settings.column {
+                EventColumn::Level => {
+                    let level_style = match event.level {
+                        EventLevel::Warning => theme.warn_fg_style(),
+                        EventLevel::Error | EventLevel::Critical => theme.error_fg_style(),
+                        _ => theme.default_style(),
+                    };
+                    Cell::from(event.level.display_name()).style(level_style)
+                }
+                column => Cell::from(column.text(event)),
+            }))
+        })
+        .collect();
+    let header =
+        Row::new(columns.iter().map(|settings| {
+            Cell::from(settings.column.display_name()).style(theme.header_style())
+        }))
+        .style(theme.default_style());
+    let widths = columns.iter().map(|settings| match settings.width {
+        _ if settings.column == EventColumn::Message => Constraint::Fill(1),
+        0 => Constraint::Fill(1),
+        width => Constraint::Length(width),
+    });
+    let table = Table::new(rows, widths)
         .header(header)
-        .block(block)
-        .highlight_style(*SELECTION_STYLE)
-        .highlight_symbol(" ")
+        .highlight_style(theme.selection_style())
         .column_spacing(1)
-        .style(*DEFAULT_STYLE);
+        .style(theme.default_style());
+    let mut table_state = TableState::default().with_selected(pane.selection);
+    frame.render_stateful_widget(table, inner, &mut table_state);
+}
+
+/// Splits spans around case-insensitive occurrences of `term` and applies `style` to them.
+fn highlight_matches<'a>(text: Text<'a>, term: &str, style: Style) -> Text<'a> {
+    let term_lower = term.to_lowercase();
+    if term_lower.is_empty() {
+        return text;
+    }
+    let lines = text
+        .lines
+        .into_iter()
+        .map(|line| {
+            let mut spans = Vec::with_capacity(line.spans.len());
+            for span in line.spans {
+                let content_lower = span.content.to_lowercase();
+                // Lowercasing can change byte lengths for a few scripts; leave those spans as is.
+                if content_lower.len() != span.content.len() || !content_lower.contains(&term_lower)
+                {
+                    spans.push(span);
+                    continue;
+                }
+                let mut last = 0;
+                for (start, _) in content_lower.match_indices(&term_lower) {
+                    let end = start + term_lower.len();
+                    if !span.content.is_char_boundary(start) || !span.content.is_char_boundary(end)
+                    {
+                        continue;
+                    }
+                    if start > last {
+                        spans.push(Span::styled(
+                            span.content[last..start].to_string(),
+                            span.style,
+                        ));
+                    }
+                    spans.push(Span::styled(
+                        span.content[start..end].to_string(),
+                        span.style.patch(style),
+                    ));
+                    last = end;
+                }
+                if last < span.content.len() {
+                    spans.push(Span::styled(span.content[last..].to_string(), span.style));
+                }
+            }
+            Line::from(spans)
+                .style(line.style)
+                .alignment(line.alignment.unwrap_or(Alignment::Left))
+        })
+        .collect::<Vec<_>>();
+    Text::from(lines)
+}
+
+/// Where `highlight_xml` is in the markup; carried from one line to the next.
+#[derive(Clone, Copy, PartialEq)]
+enum XmlToken {
+    Text,
+    TagName,
+    InTag,
+    Value(char),
+    Comment,
+}
 
-        frame.render_stateful_widget(table, area, &mut app_state.table_state);
+/// Colors the element names, attribute names and attribute values of XML, and dims comments.
+/// It only tokenizes, so malformed XML is colored as far as it goes.
+fn highlight_xml(xml: &str, theme: &Theme) -> Text<'static> {
+    let mut token = XmlToken::Text;
+    let mut lines = Vec::new();
+    for line in xml.lines() {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut run = String::new();
+        let mut run_style = theme.default_style();
+        for (index, c) in line.char_indices() {
+            let rest = &line[index..];
+            let style = match token {
+                XmlToken::Text if rest.starts_with("<!--") => {
+                    token = XmlToken::Comment;
+                    theme.default_style().fg(theme.muted_fg)
+                }
+                XmlToken::Text if c == '<' => {
+                    token = XmlToken::TagName;
+                    theme.xml_tag_style()
+                }
+                XmlToken::Text => theme.default_style(),
+                XmlToken::Comment => {
+                    if c == '>' && run.ends_with("--") {
+                        token = XmlToken::Text;
+                    }
+                    theme.default_style().fg(theme.muted_fg)
+                }
+                XmlToken::TagName | XmlToken::InTag if c == '>' => {
+                    token = XmlToken::Text;
+                    theme.xml_tag_style()
+                }
+                XmlToken::TagName | XmlToken::InTag if c == '/' || c == '?' => {
+                    theme.xml_tag_style()
+                }
+                XmlToken::TagName if c.is_whitespace() => {
+                    token = XmlToken::InTag;
+                    theme.default_style()
+                }
+                XmlToken::TagName => theme.xml_tag_style(),
+                XmlToken::InTag if c == '"' || c == '\'' => {
+                    token = XmlToken::Value(c);
+                    theme.xml_value_style()
+                }
+                XmlToken::InTag if c.is_whitespace() || c == '=' => theme.default_style(),
+                XmlToken::InTag => theme.xml_attribute_style(),
+                XmlToken::Value(quote) => {
+                    if c == quote {
+                        token = XmlToken::InTag;
+                    }
+                    theme.xml_value_style()
+                }
+            };
+            if style != run_style && !run.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut run), run_style));
+            }
+            run_style = style;
+            run.push(c);
+        }
+        if !run.is_empty() {
+            spans.push(Span::styled(run, run_style));
+        }
+        lines.push(Line::from(spans));
     }
+    Text::from(lines)
 }
 
-fn render_preview_panel(frame: &mut Frame, app_state: &mut AppState, area: Rect) {
+fn render_preview_panel(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
     let is_focused = app_state.focus == PanelFocus::Preview;
-    let border_style = BORDER_STYLE.patch(Style::new().fg(if is_focused {
-        THEME_FOCUSED_BORDER
-    } else {
-        THEME_BORDER
-    }));
+    let border_style = theme.border_style(is_focused);
 
     let title_text: String;
     let content_to_render: Text;
-    let wrap_behavior = Wrap { trim: false };
+    let wrap = app_state.config.preview_wrap;
+    let no_wrap_note = if wrap { "" } else { ", No Wrap" };
 
     match app_state.preview_view_mode {
         PreviewViewMode::RawXml => {
-            let raw_xml_string = if let Some(ref raw_xml) = app_state.preview_raw_xml {
-                match helpers::pretty_print_xml(raw_xml) {
-                    Ok(pretty_xml) => {
-                        title_text = " Event Details (Pretty XML) ".to_string();
-                        pretty_xml
-                    }
-                    Err(e) => {
-                        title_text = " Event Details (Raw XML - Error) ".to_string();
-                        format!(
-                            "<Failed to pretty-print XML: {}. Displaying raw XML.>\n\n{}",
-                            e, raw_xml
-                        )
-                    }
-                }
+            title_text = format!(" Event Details (Raw{}) ", no_wrap_note);
+            let raw_string = match app_state.selected_event() {
+                Some(event) => app_state.source.render_detail(event),
+                None => "<No event selected>".to_string(),
+            };
+            content_to_render = if raw_string.trim_start().starts_with('<') {
+                highlight_xml(&raw_string, theme)
             } else {
-                title_text = " Event Details (Raw XML) ".to_string();
-                "<No event selected or raw XML unavailable>".to_string()
+                Text::from(raw_string)
             };
-            content_to_render = Text::from(raw_xml_string);
         }
         PreviewViewMode::Formatted => {
-            title_text = " Event Details (Formatted) ".to_string();
+            title_text = format!(" Event Details (Formatted{}) ", no_wrap_note);
             content_to_render = app_state
                 .preview_content
                 .clone()
@@ -484,161 +1090,158 @@ fn render_preview_panel(frame: &mut Frame, app_state: &mut AppState, area: Rect)
 
     let block = Block::new()
         .title(
-            Title::from(Span::styled(title_text, *TITLE_STYLE))
+            Title::from(Span::styled(title_text, theme.title_style()))
                 .alignment(Alignment::Left)
                 .position(Position::Top),
         )
         .borders(Borders::ALL)
         .border_style(border_style)
         .border_type(BORDER_TYPE_THEME)
-        .style(*DEFAULT_STYLE);
+        .style(theme.default_style());
 
     let inner_area = block.inner(area);
     frame.render_widget(block, area);
 
-    let effective_total_lines = if inner_area.width > 0 {
-        let available_width = inner_area.width as usize;
-        content_to_render.lines.iter().fold(0, |acc, line| {
-            let mut line_wrapped_height = 0;
-            let mut current_line_len = 0;
-            for span in &line.spans {
-                let content_len = span.content.chars().count();
-                if current_line_len + content_len > available_width {
-                    let span_lines =
-                        ((content_len as f32) / (available_width as f32)).ceil() as usize;
-                    line_wrapped_height += span_lines;
-                    current_line_len = content_len % available_width;
-                    if current_line_len == 0 && span_lines > 0 {
-                        if content_len > 0 {
-                            current_line_len = 0;
-                        } else {
-                            line_wrapped_height = line_wrapped_height.saturating_sub(1);
-                        }
-                    } else if span_lines == 0 && content_len > 0 {
-                        line_wrapped_height += 1;
-                        current_line_len = content_len;
-                    }
-                } else {
-                    current_line_len += content_len;
-                }
-            }
-            acc + line_wrapped_height.max(1)
-        })
+    if wrap {
+        app_state.preview_hscroll = 0;
     } else {
-        content_to_render.lines.len().max(1)
+        let longest_line = content_to_render
+            .lines
+            .iter()
+            .map(|line| line.width())
+            .max()
+            .unwrap_or(0);
+        app_state.preview_hscroll = app_state
+            .preview_hscroll
+            .min(longest_line.saturating_sub(inner_area.width as usize));
+    }
+
+    let content_to_render = match &app_state.last_search_term {
+        Some(term) => highlight_matches(content_to_render, term, theme.search_highlight_style()),
+        None => content_to_render,
     };
+    let mut paragraph_to_render = Paragraph::new(content_to_render).style(theme.default_style());
+    if wrap {
+        paragraph_to_render = paragraph_to_render.wrap(Wrap { trim: false });
+    }
 
+    // Counted by the same line wrapping the paragraph renders with, so the scroll limit and
+    // indicator match what is shown.
+    let effective_total_lines = paragraph_to_render.line_count(inner_area.width);
     let available_height = inner_area.height as usize;
-
-    if effective_total_lines > 0 && available_height > 0 {
-        let max_scroll = effective_total_lines.saturating_sub(available_height);
-        app_state.preview_scroll = app_state.preview_scroll.min(max_scroll);
+    app_state.preview_max_scroll = if available_height > 0 {
+        effective_total_lines.saturating_sub(available_height)
     } else {
-        app_state.preview_scroll = 0;
-    }
-
-    let scroll_offset = (app_state.preview_scroll as u16, 0);
-
-    let paragraph_to_render = Paragraph::new(content_to_render)
-        .wrap(wrap_behavior)
-        .scroll(scroll_offset)
-        .style(*DEFAULT_STYLE);
+        0
+    };
+    app_state.preview_scroll = app_state.preview_scroll.min(app_state.preview_max_scroll);
+    let paragraph_to_render = paragraph_to_render.scroll((
+        app_state.preview_scroll as u16,
+        app_state.preview_hscroll as u16,
+    ));
 
     frame.render_widget(paragraph_to_render, inner_area);
 
-    if effective_total_lines > available_height {
-        render_scroll_indicator(
-            frame,
-            inner_area,
-            app_state.preview_scroll + 1,
-            effective_total_lines,
-            border_style,
-        );
-    }
+    render_scrollbar(
+        frame,
+        area.inner(&Margin::new(0, 1)),
+        app_state.preview_scroll,
+        app_state.preview_max_scroll,
+        available_height,
+        border_style,
+    );
 }
 
 // --- Dialog Rendering ---
 
-fn render_status_dialog(frame: &mut Frame, app_state: &mut AppState) {
-    if let Some(status_dialog) = &app_state.status_dialog {
-        if status_dialog.visible {
-            let frame_width = frame.size().width;
-            let frame_height = frame.size().height;
-
-            let title_width = status_dialog.title.len() as u16;
-            let message_lines: Vec<&str> = status_dialog.message.lines().collect();
-            let max_message_line_width =
-                message_lines.iter().map(|l| l.len()).max().unwrap_or(0) as u16;
-
-            let min_width = 20;
-            let max_width_pct = 0.8;
-            let h_padding = 2;
-
-            let desired_width = (title_width.max(max_message_line_width) + h_padding)
-                .max(min_width)
-                .min((frame_width as f32 * max_width_pct) as u16);
-
-            let effective_content_width = desired_width.saturating_sub(2);
-            let mut estimated_lines = 0;
-            if effective_content_width > 0 {
-                estimated_lines = message_lines
-                    .iter()
-                    .map(|line| (line.len() as f32 / effective_content_width as f32).ceil() as u16)
-                    .sum();
-            }
-            estimated_lines = estimated_lines.max(1);
+fn render_status_dialog(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    if let Some(status_dialog) = &app_state.status_dialog
+        && status_dialog.visible
+    {
+        let frame_width = frame.size().width;
+        let frame_height = frame.size().height;
 
-            let min_height = 5;
-            let max_height_pct = 0.8;
-            let v_padding = 4;
+        let title_width = status_dialog.title.len() as u16;
+        let message_lines: Vec<&str> = status_dialog.message.lines().collect();
+        let max_message_line_width =
+            message_lines.iter().map(|l| l.len()).max().unwrap_or(0) as u16;
 
-            let desired_height = (estimated_lines + v_padding)
-                .max(min_height)
-                .min((frame_height as f32 * max_height_pct) as u16);
+        let min_width = 20;
+        let max_width_pct = 0.8;
+        let h_padding = 2;
 
-            let dialog_area =
-                helpers::centered_fixed_rect(desired_width, desired_height, frame.size());
+        let desired_width = (title_width.max(max_message_line_width) + h_padding)
+            .max(min_width)
+            .min((frame_width as f32 * max_width_pct) as u16);
 
-            frame.render_widget(Clear, dialog_area);
+        let effective_content_width = desired_width.saturating_sub(2);
+        let mut estimated_lines = 0;
+        if effective_content_width > 0 {
+            estimated_lines = message_lines
+                .iter()
+                .map(|line| (line.len() as f32 / effective_content_width as f32).ceil() as u16)
+                .sum();
+        }
+        estimated_lines = estimated_lines.max(1);
 
-            let dialog_style = if status_dialog.is_error {
-                *DIALOG_ERROR_STYLE
-            } else {
-                *DIALOG_DEFAULT_STYLE
-            };
+        let min_height = 5;
+        let max_height_pct = 0.8;
+        let v_padding = 4;
 
-            let inverted_dialog_style = Style {
-                fg: dialog_style.bg,
-                bg: dialog_style.fg,
-                ..dialog_style
-            };
+        let desired_height = (estimated_lines + v_padding)
+            .max(min_height)
+            .min((frame_height as f32 * max_height_pct) as u16);
+
+        let dialog_area = helpers::centered_fixed_rect(desired_width, desired_height, frame.size());
+
+        frame.render_widget(Clear, dialog_area);
+
+        let dialog_style = if status_dialog.is_error {
+            theme.dialog_error_style()
+        } else {
+            theme.dialog_style()
+        };
+
+        let inverted_dialog_style = Style {
+            fg: dialog_style.bg,
+            bg: dialog_style.fg,
+            ..dialog_style
+        };
 
-            let status_dismiss_line: Line<'static> = Line::from(vec![
-                KEY_ENTER_ESC.clone().style(inverted_dialog_style),
+        let status_dismiss_line: Line<'static> = if app_state.pending_action.is_some() {
+            Line::from(vec![
+                key_span("[Enter]", theme).style(inverted_dialog_style),
+                Span::raw(" Proceed ").style(dialog_style),
+                key_span("[Esc]", theme).style(inverted_dialog_style),
+                Span::raw(" Cancel ").style(dialog_style),
+            ])
+        } else {
+            Line::from(vec![
+                key_span("[Enter/Esc]", theme).style(inverted_dialog_style),
                 Span::raw(" Dismiss ").style(dialog_style),
             ])
+        }
+        .alignment(Alignment::Center);
+        let status_dismiss_title: Title<'static> = Title::from(status_dismiss_line.clone())
+            .position(Position::Bottom)
             .alignment(Alignment::Center);
-            let status_dismiss_title: Title<'static> = Title::from(status_dismiss_line.clone())
-                .position(Position::Bottom)
-                .alignment(Alignment::Center);
 
-            let dialog_block =
-                create_dialog_block(&status_dialog.title, status_dismiss_title, dialog_style);
+        let dialog_block =
+            create_dialog_block(&status_dialog.title, status_dismiss_title, dialog_style);
 
-            frame.render_widget(dialog_block.clone(), dialog_area);
-            let content_area = dialog_block.inner(dialog_area);
+        frame.render_widget(dialog_block.clone(), dialog_area);
+        let content_area = dialog_block.inner(dialog_area);
 
-            let message_paragraph = Paragraph::new(status_dialog.message.clone())
-                .wrap(Wrap { trim: true })
-                .alignment(Alignment::Center)
-                .style(dialog_style);
+        let message_paragraph = Paragraph::new(status_dialog.message.clone())
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center)
+            .style(dialog_style);
 
-            frame.render_widget(message_paragraph, content_area);
-        }
+        frame.render_widget(message_paragraph, content_area);
     }
 }
 
-fn render_search_bar(frame: &mut Frame, app_state: &mut AppState) {
+fn render_search_bar(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
     if app_state.is_searching {
         let search_width = 40.min(frame.size().width.saturating_sub(4));
         let search_height = 3;
@@ -646,7 +1249,7 @@ fn render_search_bar(frame: &mut Frame, app_state: &mut AppState) {
         let x_pos = (frame.size().width.saturating_sub(search_width)) / 2;
         let search_area = Rect::new(x_pos, y_pos, search_width, search_height);
 
-        let dialog_style = *DIALOG_DEFAULT_STYLE;
+        let dialog_style = theme.dialog_style();
         let inverted_style = Style {
             fg: dialog_style.bg,
             bg: dialog_style.fg,
@@ -655,7 +1258,7 @@ fn render_search_bar(frame: &mut Frame, app_state: &mut AppState) {
 
         let search_bottom_line = Line::from(vec![
             Span::styled(" [Enter] ", inverted_style),
-            Span::styled("Search ", dialog_style),
+            Span::styled("Done ", dialog_style),
             Span::styled(" [Esc] ", inverted_style),
             Span::styled("Cancel", dialog_style),
         ])
@@ -664,8 +1267,17 @@ fn render_search_bar(frame: &mut Frame, app_state: &mut AppState) {
             .position(Position::Bottom)
             .alignment(Alignment::Center);
 
+        let title = match app_state.search_matches().len() {
+            _ if app_state.search_term.is_empty() => " Find ".to_string(),
+            1 => " Find: 1 match in loaded events ".to_string(),
+            count => format!(" Find: {} matches in loaded events ", count),
+        };
         let search_block = Block::new()
-            .title(SEARCH_BAR_TITLE.clone())
+            .title(
+                Title::from(Span::styled(title, theme.title_style()))
+                    .alignment(Alignment::Left)
+                    .position(Position::Top),
+            )
             .title(search_bottom_title)
             .borders(Borders::ALL)
             .border_style(dialog_style)
@@ -683,49 +1295,365 @@ fn render_search_bar(frame: &mut Frame, app_state: &mut AppState) {
 
         let search_paragraph = Paragraph::new(display_text)
             .block(search_block)
-            .style(*DIALOG_SELECTION_STYLE);
+            .style(theme.dialog_selection_style());
 
         frame.render_widget(Clear, search_area);
         frame.render_widget(search_paragraph, search_area);
     }
 }
 
-fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
-    if app_state.is_filter_dialog_visible {
-        const DIALOG_FIXED_HEIGHT: u16 = 17;
-        const DIALOG_WIDTH: u16 = 60;
-        const FILTER_LIST_MAX_HEIGHT: u16 = 5;
-
-        let is_source_focused = app_state.filter_dialog_focus == FilterFieldFocus::Source;
-        let source_input_present = !app_state.filter_dialog_source_input.is_empty();
-        let list_area_should_show = is_source_focused && source_input_present;
-        let sources_found = !app_state.filter_dialog_filtered_sources.is_empty();
-
-        let list_render_height = if list_area_should_show {
-            if sources_found {
-                FILTER_LIST_MAX_HEIGHT
-                    .min(app_state.filter_dialog_filtered_sources.len() as u16)
-                    .max(1)
-            } else {
-                1
-            }
-        } else {
-            0
-        };
-
-        let dialog_area =
-            helpers::centered_fixed_rect(DIALOG_WIDTH, DIALOG_FIXED_HEIGHT, frame.size());
-
-        frame.render_widget(Clear, dialog_area);
+fn render_local_filter_bar(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    if app_state.is_local_filter_input {
+        let filter_width = 50.min(frame.size().width.saturating_sub(4));
+        let filter_height = 3;
+        let y_pos = frame.size().height.saturating_sub(filter_height + 2);
+        let x_pos = (frame.size().width.saturating_sub(filter_width)) / 2;
+        let filter_area = Rect::new(x_pos, y_pos, filter_width, filter_height);
 
-        let dialog_style = *DIALOG_DEFAULT_STYLE;
+        let dialog_style = theme.dialog_style();
         let inverted_style = Style {
             fg: dialog_style.bg,
             bg: dialog_style.fg,
             ..dialog_style
         };
 
-        let filter_cancel_line = Line::from(vec![
+        let filter_bottom_line = Line::from(vec![
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("Keep ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Clear", dialog_style),
+        ])
+        .alignment(Alignment::Center);
+        let filter_bottom_title = Title::from(filter_bottom_line)
+            .position(Position::Bottom)
+            .alignment(Alignment::Center);
+
+        let filter_block = Block::new()
+            .title(
+                Title::from(Span::styled(
+                    format!(
+                        " Filter Loaded Events ({} of {}) ",
+                        app_state.row_count(),
+                        app_state.events.len()
+                    ),
+                    theme.title_style(),
+                ))
+                .alignment(Alignment::Left)
+                .position(Position::Top),
+            )
+            .title(filter_bottom_title)
+            .borders(Borders::ALL)
+            .border_style(dialog_style)
+            .border_type(BORDER_TYPE_THEME)
+            .style(dialog_style);
+
+        let mut display_text = app_state.local_filter.clone();
+        let byte_idx = display_text
+            .char_indices()
+            .nth(app_state.local_filter_cursor)
+            .map(|(idx, _)| idx)
+            .unwrap_or(display_text.len());
+        display_text.insert(byte_idx, '_');
+
+        let filter_paragraph = Paragraph::new(display_text)
+            .block(filter_block)
+            .style(theme.dialog_selection_style());
+
+        frame.render_widget(Clear, filter_area);
+        frame.render_widget(filter_paragraph, filter_area);
+    }
+}
+
+fn render_deep_search_progress(frame: &mut Frame, app_state: &AppState, theme: &Theme) {
+    let Some(search) = &app_state.deep_search else {
+        return;
+    };
+    let width = 60.min(frame.size().width.saturating_sub(4));
+    let height = 3;
+    let y_pos = frame.size().height.saturating_sub(height + 2);
+    let x_pos = (frame.size().width.saturating_sub(width)) / 2;
+    let area = Rect::new(x_pos, y_pos, width, height);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_title = Title::from(Line::from(vec![
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Cancel ", dialog_style),
+    ]))
+    .position(Position::Bottom)
+    .alignment(Alignment::Center);
+    let block = Block::new()
+        .title(
+            Title::from(Span::styled(
+                format!(" Searching the whole log for '{}' ", search.term),
+                theme.title_style(),
+            ))
+            .alignment(Alignment::Left)
+            .position(Position::Top),
+        )
+        .title(bottom_title)
+        .borders(Borders::ALL)
+        .border_style(dialog_style)
+        .border_type(BORDER_TYPE_THEME)
+        .style(dialog_style);
+
+    let text = format!(
+        "{} more events read, {} loaded in total",
+        search.scanned,
+        app_state.events.len()
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(text).block(block), area);
+}
+
+fn render_query_input(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    if app_state.is_query_input_visible {
+        let query_width = 100.min(frame.size().width.saturating_sub(4));
+        let query_height = 3;
+        let y_pos = frame.size().height.saturating_sub(query_height + 2);
+        let x_pos = (frame.size().width.saturating_sub(query_width)) / 2;
+        let query_area = Rect::new(x_pos, y_pos, query_width, query_height);
+
+        let dialog_style = theme.dialog_style();
+        let inverted_style = Style {
+            fg: dialog_style.bg,
+            bg: dialog_style.fg,
+            ..dialog_style
+        };
+
+        let query_bottom_line = Line::from(vec![
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("Run Query ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Cancel", dialog_style),
+        ])
+        .alignment(Alignment::Center);
+        let query_bottom_title = Title::from(query_bottom_line)
+            .position(Position::Bottom)
+            .alignment(Alignment::Center);
+
+        let query_block = Block::new()
+            .title(
+                Title::from(Span::styled(
+                    " XPath / Structured XML Query (empty to clear) ",
+                    theme.title_style(),
+                ))
+                .alignment(Alignment::Left)
+                .position(Position::Top),
+            )
+            .title(query_bottom_title)
+            .borders(Borders::ALL)
+            .border_style(dialog_style)
+            .border_type(BORDER_TYPE_THEME)
+            .style(dialog_style);
+
+        let mut display_text = app_state.query_input.clone();
+        let byte_idx = display_text
+            .char_indices()
+            .nth(app_state.query_cursor)
+            .map(|(idx, _)| idx)
+            .unwrap_or(display_text.len());
+        display_text.insert(byte_idx, '_');
+
+        // Keep the cursor visible when the query is wider than the input.
+        let inner_width = query_width.saturating_sub(2) as usize;
+        let horizontal_scroll = app_state
+            .query_cursor
+            .saturating_sub(inner_width.saturating_sub(1));
+
+        let query_paragraph = Paragraph::new(display_text)
+            .block(query_block)
+            .scroll((0, horizontal_scroll as u16))
+            .style(theme.dialog_selection_style());
+
+        frame.render_widget(Clear, query_area);
+        frame.render_widget(query_paragraph, query_area);
+    }
+}
+
+fn render_prompt(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(prompt) = app_state.prompt else {
+        return;
+    };
+    let (title, action): (String, &str) = match prompt {
+        PromptKind::IncidentWindow => (
+            " Incident Window: YYYY-MM-DD HH:MM[:SS] .. YYYY-MM-DD HH:MM[:SS] (empty to leave) "
+                .to_string(),
+            "Lock Window ",
+        ),
+        PromptKind::IncidentMarker => (
+            " Marker Label at Selected Event (empty to remove) ".to_string(),
+            "Place Marker ",
+        ),
+        PromptKind::ViewToken => (
+            " View Token: copy to share this view, or paste a shared token ".to_string(),
+            "Open View ",
+        ),
+        PromptKind::GoToRecord => (" Go to Record: EventRecordID ".to_string(), "Go to Record "),
+        PromptKind::ClusterNodes => (
+            " Cluster Nodes: names or @host-groups separated by commas ".to_string(),
+            "Merge Node Logs ",
+        ),
+        PromptKind::BackUpLog => (
+            format!(" Back Up '{}' to .evtx File ", app_state.selected_log_name),
+            "Back Up ",
+        ),
+        PromptKind::ExportFilteredLog => (
+            format!(
+                " Export '{}' Events Matching the Filter to .evtx File ",
+                app_state.selected_log_name
+            ),
+            "Export ",
+        ),
+        PromptKind::ClearLogBackup => (
+            format!(
+                " Clear '{}': back it up to .evtx first (empty to skip the backup) ",
+                app_state.selected_log_name
+            ),
+            "Continue ",
+        ),
+        PromptKind::CredentialUser => (
+            format!(
+                " Credential for {}: user name, as DOMAIN\\user or user@domain ",
+                credential_entry_name(app_state)
+            ),
+            "Continue ",
+        ),
+        PromptKind::CredentialPassword => (
+            format!(
+                " Credential for {}: password, saved in Windows Credential Manager ",
+                credential_entry_name(app_state)
+            ),
+            "Save ",
+        ),
+        PromptKind::SaveSession => (
+            " Save Session: view, filter, sort, selected event and bookmarks to file ".to_string(),
+            "Save ",
+        ),
+        PromptKind::RestoreSession => (" Restore Session from File ".to_string(), "Restore "),
+        PromptKind::ClearLogConfirm => (
+            format!(
+                " Type '{}' to clear it{} ",
+                app_state.selected_log_name,
+                match &app_state.clear_log_backup {
+                    Some(path) => format!(" after backing it up to {}", path.display()),
+                    None => " WITHOUT a backup".to_string(),
+                }
+            ),
+            "Clear Log ",
+        ),
+    };
+    let input_width = 100.min(frame.size().width.saturating_sub(4));
+    let input_height = 3;
+    let y_pos = frame.size().height.saturating_sub(input_height + 2);
+    let x_pos = (frame.size().width.saturating_sub(input_width)) / 2;
+    let input_area = Rect::new(x_pos, y_pos, input_width, input_height);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+
+    let bottom_line = Line::from(vec![
+        Span::styled(" [Enter] ", inverted_style),
+        Span::styled(action, dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Cancel", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+
+    let input_block = Block::new()
+        .title(
+            Title::from(Span::styled(title, theme.title_style()))
+                .alignment(Alignment::Left)
+                .position(Position::Top),
+        )
+        .title(bottom_title)
+        .borders(Borders::ALL)
+        .border_style(dialog_style)
+        .border_type(BORDER_TYPE_THEME)
+        .style(dialog_style);
+
+    let mut display_text = if prompt == PromptKind::CredentialPassword {
+        "*".repeat(app_state.prompt_input.chars().count())
+    } else {
+        app_state.prompt_input.clone()
+    };
+    let byte_idx = display_text
+        .char_indices()
+        .nth(app_state.prompt_cursor)
+        .map(|(idx, _)| idx)
+        .unwrap_or(display_text.len());
+    display_text.insert(byte_idx, '_');
+
+    let inner_width = input_width.saturating_sub(2) as usize;
+    let horizontal_scroll = app_state
+        .prompt_cursor
+        .saturating_sub(inner_width.saturating_sub(1));
+
+    let input_paragraph = Paragraph::new(display_text)
+        .block(input_block)
+        .scroll((0, horizontal_scroll as u16))
+        .style(theme.dialog_selection_style());
+
+    frame.render_widget(Clear, input_area);
+    frame.render_widget(input_paragraph, input_area);
+}
+
+/// The host or `@group` whose credential the prompt asks for.
+fn credential_entry_name(app_state: &AppState) -> &str {
+    app_state
+        .host_picker
+        .as_ref()
+        .and_then(|picker| picker.credential_entry.as_ref())
+        .map_or("", |(name, _)| name.as_str())
+}
+
+fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    if app_state.is_filter_dialog_visible {
+        const DIALOG_FIXED_HEIGHT: u16 = 23;
+        const DIALOG_WIDTH: u16 = 60;
+        const FILTER_LIST_MAX_HEIGHT: u16 = 5;
+
+        let is_source_focused = app_state.filter_dialog_focus == FilterFieldFocus::Source;
+        let source_input_present = !app_state.filter_dialog_source_input.is_empty();
+        let list_area_should_show = is_source_focused && source_input_present;
+        let sources_found = !app_state.filter_dialog_filtered_sources.is_empty();
+
+        let list_render_height = if list_area_should_show {
+            if sources_found {
+                FILTER_LIST_MAX_HEIGHT
+                    .min(app_state.filter_dialog_filtered_sources.len() as u16)
+                    .max(1)
+            } else {
+                1
+            }
+        } else {
+            0
+        };
+
+        let dialog_area =
+            helpers::centered_fixed_rect(DIALOG_WIDTH, DIALOG_FIXED_HEIGHT, frame.size());
+
+        frame.render_widget(Clear, dialog_area);
+
+        let dialog_style = theme.dialog_style();
+        let inverted_style = Style {
+            fg: dialog_style.bg,
+            bg: dialog_style.fg,
+            ..dialog_style
+        };
+
+        let filter_cancel_line = Line::from(vec![
             Span::styled(" [Esc] ", inverted_style),
             Span::styled("Cancel", dialog_style),
         ])
@@ -742,8 +1670,15 @@ fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
         const EVENT_ID_INPUT_HEIGHT: u16 = 1;
         const LEVEL_SELECT_HEIGHT: u16 = 1;
         const TIME_SELECT_HEIGHT: u16 = 1;
+        const KEYWORDS_SELECT_HEIGHT: u16 = 1;
+        const TASK_INPUT_HEIGHT: u16 = 1;
+        const OPCODE_INPUT_HEIGHT: u16 = 1;
+        const USER_INPUT_HEIGHT: u16 = 1;
+        const COMPUTER_INPUT_HEIGHT: u16 = 1;
+        const EXCLUDE_INPUT_HEIGHT: u16 = 1;
         const SOURCE_LABEL_HEIGHT: u16 = 1;
         const SOURCE_INPUT_HEIGHT: u16 = 1;
+        const RECENT_SELECT_HEIGHT: u16 = 1;
         const BUTTON_ROW_HEIGHT: u16 = 1;
 
         let constraints = vec![
@@ -751,9 +1686,16 @@ fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
             Constraint::Length(EVENT_ID_INPUT_HEIGHT),
             Constraint::Length(LEVEL_SELECT_HEIGHT),
             Constraint::Length(TIME_SELECT_HEIGHT),
+            Constraint::Length(KEYWORDS_SELECT_HEIGHT),
+            Constraint::Length(TASK_INPUT_HEIGHT),
+            Constraint::Length(OPCODE_INPUT_HEIGHT),
+            Constraint::Length(USER_INPUT_HEIGHT),
+            Constraint::Length(COMPUTER_INPUT_HEIGHT),
+            Constraint::Length(EXCLUDE_INPUT_HEIGHT),
             Constraint::Length(SOURCE_LABEL_HEIGHT),
             Constraint::Length(SOURCE_INPUT_HEIGHT),
             Constraint::Length(list_render_height),
+            Constraint::Length(RECENT_SELECT_HEIGHT),
             Constraint::Min(0),
             Constraint::Length(BUTTON_ROW_HEIGHT),
         ];
@@ -764,19 +1706,19 @@ fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
             .constraints(constraints)
             .split(inner_area);
 
-        if chunks.len() < 9 {
+        if chunks.len() < 16 {
             return;
         }
 
-        let base_text_style = *DIALOG_DEFAULT_STYLE;
+        let base_text_style = theme.dialog_style();
 
         frame.render_widget(
-            Paragraph::new("Event ID:").style(base_text_style),
+            Paragraph::new("Event ID(s) (e.g. 4624,4700-4799,-4688):").style(base_text_style),
             chunks[0],
         );
         let is_eventid_focused = app_state.filter_dialog_focus == FilterFieldFocus::EventId;
         let event_id_input_style = if is_eventid_focused {
-            *DIALOG_SELECTION_STYLE
+            theme.dialog_selection_style()
         } else {
             base_text_style
         };
@@ -800,12 +1742,12 @@ fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
 
         let is_level_focused = app_state.filter_dialog_focus == FilterFieldFocus::Level;
         let level_name_style = if is_level_focused {
-            *DIALOG_SELECTION_STYLE
+            theme.dialog_selection_style()
         } else {
             base_text_style
         };
         let level_arrow_style = if is_level_focused {
-            *SELECTION_STYLE
+            theme.selection_style()
         } else {
             base_text_style
         };
@@ -822,12 +1764,12 @@ fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
 
         let is_time_focused = app_state.filter_dialog_focus == FilterFieldFocus::Time;
         let time_name_style = if is_time_focused {
-            *DIALOG_SELECTION_STYLE
+            theme.dialog_selection_style()
         } else {
             base_text_style
         };
         let time_arrow_style = if is_time_focused {
-            *SELECTION_STYLE
+            theme.selection_style()
         } else {
             base_text_style
         };
@@ -839,9 +1781,96 @@ fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
         ]);
         frame.render_widget(Paragraph::new(time_text), chunks[3]);
 
-        frame.render_widget(Paragraph::new("Source:").style(base_text_style), chunks[4]);
+        let is_keywords_focused = app_state.filter_dialog_focus == FilterFieldFocus::Keywords;
+        let keywords_name_style = if is_keywords_focused {
+            theme.dialog_selection_style()
+        } else {
+            base_text_style
+        };
+        let keywords_arrow_style = if is_keywords_focused {
+            theme.selection_style()
+        } else {
+            base_text_style
+        };
+        let keywords_text = Line::from(vec![
+            Span::raw("Keywords: ").style(base_text_style),
+            Span::styled("< ", keywords_arrow_style),
+            Span::styled(
+                app_state.filter_dialog_keywords.display_name(),
+                keywords_name_style,
+            ),
+            Span::styled(" >", keywords_arrow_style),
+        ]);
+        frame.render_widget(Paragraph::new(keywords_text), chunks[4]);
+
+        let inline_inputs = [
+            (
+                "Task Category: ",
+                FilterFieldFocus::Task,
+                &app_state.filter_dialog_task,
+                app_state.filter_task_cursor,
+                chunks[5],
+            ),
+            (
+                "Opcode: ",
+                FilterFieldFocus::Opcode,
+                &app_state.filter_dialog_opcode,
+                app_state.filter_opcode_cursor,
+                chunks[6],
+            ),
+            (
+                "User SID: ",
+                FilterFieldFocus::User,
+                &app_state.filter_dialog_user,
+                app_state.filter_user_cursor,
+                chunks[7],
+            ),
+            (
+                "Computer: ",
+                FilterFieldFocus::Computer,
+                &app_state.filter_dialog_computer,
+                app_state.filter_computer_cursor,
+                chunks[8],
+            ),
+            (
+                "Exclude Sources: ",
+                FilterFieldFocus::ExcludeSources,
+                &app_state.filter_dialog_excluded_sources,
+                app_state.filter_excluded_sources_cursor,
+                chunks[9],
+            ),
+        ];
+        for (label, field, value, cursor_pos, chunk) in inline_inputs {
+            let is_focused = app_state.filter_dialog_focus == field;
+            let value_text = if is_focused {
+                let mut display_text = value.clone();
+                let byte_idx = display_text
+                    .char_indices()
+                    .nth(cursor_pos)
+                    .map(|(idx, _)| idx)
+                    .unwrap_or(display_text.len());
+                display_text.insert(byte_idx, '_');
+                display_text
+            } else if value.is_empty() {
+                "[Any]".to_string()
+            } else {
+                value.clone()
+            };
+            let value_style = if is_focused {
+                theme.dialog_selection_style()
+            } else {
+                base_text_style
+            };
+            let input_line = Line::from(vec![
+                Span::raw(label).style(base_text_style),
+                Span::styled(value_text, value_style),
+            ]);
+            frame.render_widget(Paragraph::new(input_line), chunk);
+        }
+
+        frame.render_widget(Paragraph::new("Source:").style(base_text_style), chunks[10]);
         let source_style = if is_source_focused {
-            *DIALOG_SELECTION_STYLE
+            theme.dialog_selection_style()
         } else {
             base_text_style
         };
@@ -862,7 +1891,7 @@ fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
         };
         frame.render_widget(
             Paragraph::new(source_input_display).style(source_style),
-            chunks[5],
+            chunks[11],
         );
 
         if list_area_should_show {
@@ -873,19 +1902,54 @@ fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
                     .map(|(_, name)| ListItem::new(name.clone()).style(base_text_style))
                     .collect();
                 let list = List::new(list_items)
-                    .highlight_style(*SELECTION_STYLE)
+                    .highlight_style(theme.selection_style())
                     .style(base_text_style)
                     .highlight_symbol(">");
                 let mut list_state = ListState::default();
                 list_state.select(app_state.filter_dialog_filtered_source_selection);
-                frame.render_stateful_widget(list, chunks[6], &mut list_state);
+                frame.render_stateful_widget(list, chunks[12], &mut list_state);
             } else {
                 let no_sources_msg = Paragraph::new("No matching sources found")
                     .style(base_text_style.add_modifier(Modifier::ITALIC));
-                frame.render_widget(no_sources_msg, chunks[6]);
+                frame.render_widget(no_sources_msg, chunks[12]);
             }
         }
 
+        let is_recent_focused = app_state.filter_dialog_focus == FilterFieldFocus::Recent;
+        let recent_name_style = if is_recent_focused {
+            theme.dialog_selection_style()
+        } else {
+            base_text_style
+        };
+        let recent_arrow_style = if is_recent_focused {
+            theme.selection_style()
+        } else {
+            base_text_style
+        };
+        let recent_filters = &app_state.config.recent_filters;
+        let recent = app_state
+            .filter_dialog_recent
+            .and_then(|index| Some((index, recent_filters.get(index)?)));
+        let recent_name = match recent {
+            _ if recent_filters.is_empty() => "[None yet]".to_string(),
+            Some((index, filter)) => {
+                format!(
+                    "{}/{} {}",
+                    index + 1,
+                    recent_filters.len(),
+                    filter.summary()
+                )
+            }
+            None => "Current".to_string(),
+        };
+        let recent_text = Line::from(vec![
+            Span::raw("Recent: ").style(base_text_style),
+            Span::styled("< ", recent_arrow_style),
+            Span::styled(recent_name, recent_name_style),
+            Span::styled(" >", recent_arrow_style),
+        ]);
+        frame.render_widget(Paragraph::new(recent_text), chunks[13]);
+
         let apply_focused = app_state.filter_dialog_focus == FilterFieldFocus::Apply;
         let clear_focused = app_state.filter_dialog_focus == FilterFieldFocus::Clear;
 
@@ -908,12 +1972,12 @@ fn render_filter_dialog(frame: &mut Frame, app_state: &mut AppState) {
         .alignment(Alignment::Center);
         frame.render_widget(
             Paragraph::new(button_line).style(base_text_style),
-            chunks[8],
+            chunks[15],
         );
     }
 }
 
-fn render_help_dialog(frame: &mut Frame, app_state: &mut AppState) {
+fn render_help_dialog(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
     if app_state.help_dialog_visible {
         let help_width = 80.min(frame.size().width.saturating_sub(4));
         let help_height = 30.min(frame.size().height.saturating_sub(4));
@@ -921,7 +1985,7 @@ fn render_help_dialog(frame: &mut Frame, app_state: &mut AppState) {
 
         frame.render_widget(Clear, help_area);
 
-        let dialog_style = *DIALOG_DEFAULT_STYLE;
+        let dialog_style = theme.dialog_style();
         let inverted_style = Style {
             fg: dialog_style.bg,
             bg: dialog_style.fg,
@@ -945,88 +2009,2145 @@ fn render_help_dialog(frame: &mut Frame, app_state: &mut AppState) {
         let content_area = help_block.inner(help_area);
         frame.render_widget(help_block, help_area);
 
-        let help_text = HELP_TEXT_LINES.clone();
-        let total_lines = help_text.len();
+        let help_paragraph = Paragraph::new(help_text_lines(theme))
+            .wrap(Wrap { trim: false })
+            .style(theme.dialog_style());
+        let total_lines = help_paragraph.line_count(content_area.width);
         let visible_height = content_area.height as usize;
 
         let max_scroll = total_lines.saturating_sub(visible_height);
         app_state.help_scroll_position = app_state.help_scroll_position.min(max_scroll);
         let current_scroll = app_state.help_scroll_position;
 
-        let help_paragraph = Paragraph::new(help_text)
-            .wrap(Wrap { trim: false })
-            .style(*HELP_BODY_STYLE)
-            .scroll((current_scroll as u16, 0));
-
-        frame.render_widget(help_paragraph, content_area);
+        frame.render_widget(
+            help_paragraph.scroll((current_scroll as u16, 0)),
+            content_area,
+        );
 
-        render_scroll_indicator(
+        render_scrollbar(
             frame,
-            content_area,
-            current_scroll + 1,
-            total_lines,
-            *TITLE_STYLE,
+            help_area.inner(&Margin::new(0, 1)),
+            current_scroll,
+            max_scroll,
+            visible_height,
+            dialog_style,
         );
     }
 }
 
-fn render_bottom_bar(frame: &mut Frame, app_state: &mut AppState, area: Rect) {
-    let mut spans = Vec::with_capacity(16);
-    spans.extend([
-        KEY_Q.clone(),
-        Span::raw(" Quit | ").style(*FOOTER_STYLE),
-        KEY_F1.clone(),
-        Span::raw(" Help | ").style(*FOOTER_STYLE),
-    ]);
+fn render_column_dialog(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    if !app_state.is_column_dialog_visible {
+        return;
+    }
+    let columns = &app_state.config.columns;
+    let dialog_width = 64.min(frame.size().width.saturating_sub(4));
+    let dialog_height = (columns.len() as u16 + 4).min(frame.size().height.saturating_sub(2));
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
 
-    match app_state.focus {
-        PanelFocus::Events => {
-            spans.extend([
-                KEY_S_SORT.clone(),
-                Span::raw(" Sort | ").style(*FOOTER_STYLE),
-                KEY_L_LEVEL.clone(),
-                Span::raw(format!(" Lvl ({}) | ", app_state.get_current_level_name()))
-                    .style(*FOOTER_STYLE),
-                KEY_F_FILTER.clone(),
-                Span::raw(format!(
-                    " Adv Filter ({}) | ",
-                    app_state.get_filter_status()
-                ))
-                .style(*FOOTER_STYLE),
-                KEY_SLASH_SEARCH.clone(),
-                Span::raw(" Search").style(*FOOTER_STYLE),
-            ]);
-            if app_state.last_search_term.is_some() {
-                spans.extend([
-                    Span::raw(" | ").style(*FOOTER_STYLE),
-                    KEY_N_NEXT.clone(),
-                    Span::raw(" Next | ").style(*FOOTER_STYLE),
-                    KEY_P_PREV.clone(),
-                    Span::raw(" Prev").style(*FOOTER_STYLE),
-                ]);
-            }
-        }
-        PanelFocus::Preview => {
-            spans.extend([
-                KEY_ESC_LEFT.clone(),
-                Span::raw(" Return | ").style(*FOOTER_STYLE),
-                KEY_V_TOGGLE.clone(),
-                Span::raw(" Toggle View | ").style(*FOOTER_STYLE),
-                KEY_S_SAVE.clone(),
-                Span::raw(" Save | ").style(*FOOTER_STYLE),
-                KEY_SCROLL.clone(),
-                Span::raw(" Scroll").style(*FOOTER_STYLE),
-            ]);
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+
+    let bottom_line = Line::from(vec![
+        Span::styled(" [Space] ", inverted_style),
+        Span::styled("Show ", dialog_style),
+        Span::styled(" [Shift+↑↓] ", inverted_style),
+        Span::styled("Move ", dialog_style),
+        Span::styled(" [←→] ", inverted_style),
+        Span::styled("Width ", dialog_style),
+        Span::styled(" [a] ", inverted_style),
+        Span::styled("Auto ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+
+    let block = create_dialog_block("Columns", bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let lines: Vec<Line> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, settings)| {
+            let check = if settings.visible { "[x]" } else { "[ ]" };
+            let width = match settings.width {
+                _ if settings.column == EventColumn::Message => {
+                    format!("{}%", app_state.config.message_column_percent)
+                }
+                0 => "auto".to_string(),
+                width => width.to_string(),
+            };
+            let text = format!(
+                " {} {:<20} {:>5} ",
+                check,
+                settings.column.display_name(),
+                width
+            );
+            let style = if i == app_state.column_dialog_selection {
+                theme.dialog_selection_style()
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines)
+            .style(dialog_style)
+            .alignment(Alignment::Center),
+        content_area,
+    );
+}
+
+#[cfg(target_os = "windows")]
+fn render_device_view(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(view) = &mut app_state.device_view else {
+        return;
+    };
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let open_device = view.open_device.and_then(|index| view.devices.get(index));
+    let bottom_line = Line::from(match open_device {
+        Some(_) => vec![
+            Span::styled(" [↑↓] ", inverted_style),
+            Span::styled("Scroll ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Back", dialog_style),
+        ],
+        None => vec![
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("Timeline ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Close", dialog_style),
+        ],
+    })
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let title = match open_device {
+        Some(device) => format!("Device Activity: {}", device.name),
+        None => format!("Device Activity ({} devices)", view.devices.len()),
+    };
+    let block = create_dialog_block(&title, bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    if let Some(device) = open_device {
+        let lines: Vec<Line> = device.lines().into_iter().map(Line::from).collect();
+        let max_scroll = lines.len().saturating_sub(content_area.height as usize);
+        view.detail_scroll = view.detail_scroll.min(max_scroll);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(dialog_style)
+                .scroll((view.detail_scroll as u16, 0)),
+            content_area,
+        );
+        return;
+    }
+
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:<19}  {:>8} {:>11}  {:<36} {}",
+            "Last Seen", "Connects", "Disconnects", "Device", "Serial/ID"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    if view.devices.is_empty() {
+        let mut message = "No device activity was found.".to_string();
+        for unavailable in &view.unavailable {
+            message.push_str("\n\n");
+            message.push_str(unavailable);
         }
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            list_area,
+        );
+        return;
     }
 
-    if app_state.is_loading {
-        spans.push(Span::raw(" | ").style(*FOOTER_STYLE));
-        spans.push(Span::styled("Loading...", *ALT_FG_STYLE));
+    // Keep the selection in view when there are more rows than fit.
+    let visible_rows = list_area.height as usize;
+    let first = view
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = view
+        .devices
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, device)| {
+            let text = format!(
+                " {}  {:>8} {:>11}  {:<36} {}",
+                helpers::format_local_time(device.last_seen()),
+                device.count(DeviceAction::Connected),
+                device.count(DeviceAction::Disconnected),
+                helpers::truncate_at_word(&device.name, 36),
+                device.key
+            );
+            let style = if i == view.selection {
+                theme.dialog_selection_style()
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+#[cfg(target_os = "windows")]
+fn render_servicing_view(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(view) = &mut app_state.servicing_view else {
+        return;
+    };
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let open_package = view.open_package.and_then(|index| view.packages.get(index));
+    let bottom_line = Line::from(match open_package {
+        Some(_) => vec![
+            Span::styled(" [↑↓] ", inverted_style),
+            Span::styled("Scroll ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Back", dialog_style),
+        ],
+        None => vec![
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("History ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Close", dialog_style),
+        ],
+    })
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let title = match open_package {
+        Some(package) => format!("Update Failures: {}", package.key),
+        None => "Update Failures".to_string(),
+    };
+    let block = create_dialog_block(&title, bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    if let Some(package) = open_package {
+        let lines: Vec<Line> = package
+            .lines()
+            .into_iter()
+            .map(|line| {
+                if line.contains("  Failed") {
+                    Line::styled(line, dialog_style.fg(theme.dialog_error_fg))
+                } else {
+                    Line::from(line)
+                }
+            })
+            .collect();
+        let max_scroll = lines.len().saturating_sub(content_area.height as usize);
+        view.detail_scroll = view.detail_scroll.min(max_scroll);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(dialog_style)
+                .wrap(Wrap { trim: false })
+                .scroll((view.detail_scroll as u16, 0)),
+            content_area,
+        );
+        return;
+    }
+
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:<12} {:<42} {:>8}  {:<9} {:<10} {}",
+            "Update", "Title", "Failures", "Status", "Last Error", "Last Attempt"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    if view.packages.is_empty() {
+        let mut message = "No failed updates were found in the Setup and System logs.".to_string();
+        for unavailable in &view.unavailable {
+            message.push_str("\n\n");
+            message.push_str(unavailable);
+        }
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view when there are more rows than fit.
+    let visible_rows = list_area.height as usize;
+    let first = view
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = view
+        .packages
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, package)| {
+            let resolved = package.resolved();
+            let text = format!(
+                " {:<12} {:<42} {:>8}  {:<9} {:<10} {}",
+                helpers::truncate_at_word(&package.key, 12),
+                helpers::truncate_at_word(&package.title, 42),
+                package.failures,
+                if resolved { "Installed" } else { "Failing" },
+                package
+                    .errors
+                    .first()
+                    .map(|code| format!("0x{:08X}", code))
+                    .unwrap_or_default(),
+                helpers::format_local_time(package.last_time())
+            );
+            let style = if i == view.selection {
+                theme.dialog_selection_style()
+            } else if !resolved {
+                dialog_style.fg(theme.dialog_error_fg)
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+fn render_audit_policy(frame: &mut Frame, app_state: &AppState, theme: &Theme) {
+    let Some(view) = &app_state.audit_policy else {
+        return;
+    };
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_line = Line::from(vec![
+        Span::styled(" [↑↓] ", inverted_style),
+        Span::styled("Scroll ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let title = match view.flagged_count() {
+        0 => "Audit Policy".to_string(),
+        flagged => format!(
+            "Audit Policy ({} subcategories the filter needs are off)",
+            flagged
+        ),
+    };
+    let block = create_dialog_block(&title, bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:<44} {:<22} {}",
+            "Subcategory", "Setting", "Needed for Event IDs"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    // Keep the selection in view when there are more rows than fit.
+    let visible_rows = list_area.height as usize;
+    let first = view
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = view
+        .subcategories
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, subcategory)| {
+            let needed_for = subcategory
+                .needed_for
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let text = format!(
+                " {:<44} {:<22} {}",
+                helpers::truncate_at_word(&subcategory.name, 44),
+                subcategory.setting,
+                needed_for
+            );
+            let style = if i == view.selection {
+                theme.dialog_selection_style()
+            } else if subcategory.flagged() {
+                dialog_style.fg(theme.dialog_error_fg)
+            } else if !subcategory.needed_for.is_empty() {
+                dialog_style.add_modifier(Modifier::BOLD)
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+#[cfg(target_os = "windows")]
+fn render_auth_flow(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(view) = &mut app_state.auth_flow else {
+        return;
+    };
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let open_step = view.open_step.and_then(|index| view.steps.get(index));
+    let bottom_line = Line::from(match open_step {
+        Some(_) => vec![
+            Span::styled(" [↑↓] ", inverted_style),
+            Span::styled("Scroll ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Back", dialog_style),
+        ],
+        None => vec![
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("Decode ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Close", dialog_style),
+        ],
+    })
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let title = format!(
+        "Authentication Flow: {} (±{} minutes)",
+        view.account,
+        authflow::FLOW_WINDOW_MINUTES
+    );
+    let block = create_dialog_block(&title, bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    if let Some(step) = open_step {
+        let failed = step.failure.is_some();
+        let lines: Vec<Line> = step
+            .lines()
+            .into_iter()
+            .map(|line| {
+                if failed && line.starts_with("Result:") {
+                    Line::styled(line, dialog_style.fg(theme.dialog_error_fg))
+                } else {
+                    Line::from(line)
+                }
+            })
+            .collect();
+        let max_scroll = lines.len().saturating_sub(content_area.height as usize);
+        view.detail_scroll = view.detail_scroll.min(max_scroll);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(dialog_style)
+                .wrap(Wrap { trim: false })
+                .scroll((view.detail_scroll as u16, 0)),
+            content_area,
+        );
+        return;
+    }
+
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:<19} {:>9} {:<8} {:<38} {:<15} {}",
+            "Time", "Delta", "Protocol", "Step", "Client", "Result"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    if view.steps.is_empty() {
+        let mut message = format!(
+            "No Kerberos, NTLM or logon events for {} were found in the Security log.",
+            view.account
+        );
+        if let Some(unavailable) = &view.unavailable {
+            message.push_str("\n\n");
+            message.push_str(unavailable);
+        }
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view when there are more rows than fit.
+    let visible_rows = list_area.height as usize;
+    let first = view
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = view
+        .steps
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, step)| {
+            let text = format!(
+                " {:<19} {:>9} {:<8} {:<38} {:<15} {}",
+                helpers::format_local_time(step.time),
+                view.delta(i),
+                step.protocol,
+                helpers::truncate_at_word(&step.step, 38),
+                helpers::truncate_at_word(step.client.as_deref().unwrap_or(""), 15),
+                step.failure.as_deref().unwrap_or("OK")
+            );
+            let style = if i == view.selection {
+                theme.dialog_selection_style()
+            } else if step.failure.is_some() {
+                dialog_style.fg(theme.dialog_error_fg)
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+#[cfg(target_os = "windows")]
+fn render_object_access(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(view) = &mut app_state.object_access else {
+        return;
+    };
+    let objects = view.objects();
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let open_object = view.open_object.and_then(|index| objects.get(index));
+    let bottom_line = Line::from(match open_object {
+        Some(_) => vec![
+            Span::styled(" [↑↓] ", inverted_style),
+            Span::styled("Scroll ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Back", dialog_style),
+        ],
+        None => vec![
+            Span::styled(" [Type] ", inverted_style),
+            Span::styled("Path Prefix ", dialog_style),
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("Accesses ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Close", dialog_style),
+        ],
+    })
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let total: usize = objects.iter().map(|object| object.accesses.len()).sum();
+    let block = create_dialog_block(
+        &format!("Object Access ({} on {} objects)", total, objects.len()),
+        bottom_title,
+        dialog_style,
+    );
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    if let Some(object) = open_object {
+        let lines: Vec<Line> = object
+            .lines()
+            .into_iter()
+            .map(|line| {
+                if line.contains("  Denied ") {
+                    Line::styled(line, dialog_style.fg(theme.dialog_error_fg))
+                } else {
+                    Line::from(line)
+                }
+            })
+            .collect();
+        let max_scroll = lines.len().saturating_sub(content_area.height as usize);
+        view.detail_scroll = view.detail_scroll.min(max_scroll);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(dialog_style)
+                .wrap(Wrap { trim: false })
+                .scroll((view.detail_scroll as u16, 0)),
+            content_area,
+        );
+        return;
+    }
+
+    let [prefix_area, header_area, list_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .areas(content_area);
+
+    let mut prefix_text = view.prefix.clone();
+    let byte_idx = prefix_text
+        .char_indices()
+        .nth(view.prefix_cursor)
+        .map(|(idx, _)| idx)
+        .unwrap_or(prefix_text.len());
+    prefix_text.insert(byte_idx, '_');
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(" Path: ", dialog_style.add_modifier(Modifier::BOLD)),
+            Span::styled(prefix_text, theme.dialog_selection_style()),
+        ]))
+        .style(dialog_style),
+        prefix_area,
+    );
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:>6} {:>6}  {:<19}  {:<5} {:<8} {}",
+            "Count", "Denied", "Last Seen", "Type", "Modified", "Object"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    if objects.is_empty() {
+        let mut message = if view.accesses.is_empty() {
+            "No object access audits (4656, 4663) were found in the Security log. They are only logged when the File System or Registry audit subcategory is on and the object's SACL audits the access.".to_string()
+        } else {
+            "No audited objects under this path.".to_string()
+        };
+        if let Some(unavailable) = &view.unavailable {
+            message.push_str("\n\n");
+            message.push_str(unavailable);
+        }
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view when there are more objects than rows.
+    let visible_rows = list_area.height as usize;
+    let first = view
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = objects
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, object)| {
+            let text = format!(
+                " {:>6} {:>6}  {}  {:<5} {:<8} {}",
+                object.accesses.len(),
+                object.failures,
+                helpers::format_local_time(object.last_seen()),
+                object.object_type,
+                if object.modified() { "yes" } else { "" },
+                object.path
+            );
+            let style = if i == view.selection {
+                theme.dialog_selection_style()
+            } else if object.failures > 0 {
+                dialog_style.fg(theme.dialog_error_fg)
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+#[cfg(target_os = "windows")]
+fn render_wmi_analysis(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(analysis) = &app_state.wmi_analysis else {
+        return;
+    };
+    let groups = analysis.groups();
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let open_group = analysis.open_group.and_then(|index| groups.get(index));
+    let bottom_line = Line::from(match open_group {
+        Some(_) => vec![
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Back", dialog_style),
+        ],
+        None => vec![
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("Details ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Close", dialog_style),
+        ],
+    })
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let block = create_dialog_block(
+        &format!(
+            "WMI Client Failures ({} in {} groups)",
+            analysis.failures.len(),
+            groups.len()
+        ),
+        bottom_title,
+        dialog_style,
+    );
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    // Image path for details, file name for the list.
+    let process_name =
+        |process_id: u32| analysis.process_names.get(&process_id).map(String::as_str);
+
+    if let Some(group) = open_group {
+        let lines: Vec<Line> = group
+            .lines(process_name(group.process_id))
+            .into_iter()
+            .map(Line::from)
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(dialog_style)
+                .wrap(Wrap { trim: false }),
+            content_area,
+        );
+        return;
+    }
+
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:>6}  {:<19}  {:<28} {:<11} {}",
+            "Count", "Last Seen", "Client Process", "Result", "Operation"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    if groups.is_empty() {
+        let mut message = "No failed WMI client operations (5858) were found.".to_string();
+        if let Some(unavailable) = &analysis.unavailable {
+            message.push_str("\n\n");
+            message.push_str(unavailable);
+        }
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view when there are more groups than rows.
+    let visible_rows = list_area.height as usize;
+    let first = analysis
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = groups
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, group)| {
+            let process = match process_name(group.process_id) {
+                Some(path) => format!(
+                    "{} ({})",
+                    path.rsplit('\\').next().unwrap_or(path),
+                    group.process_id
+                ),
+                None => group.process_id.to_string(),
+            };
+            let text = format!(
+                " {:>6}  {}  {:<28} {:<11} {}",
+                group.count,
+                helpers::format_local_time(group.last_seen),
+                helpers::truncate_at_word(&process, 28),
+                group.result_code,
+                group.operation
+            );
+            let style = if i == analysis.selection {
+                theme.dialog_selection_style()
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+/// Renders the Sigma rules the loaded events matched, with their hit counts.
+fn render_sigma_view(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(view) = &app_state.sigma_view else {
+        return;
+    };
+    let hits = sigma::rule_hits(&app_state.sigma_rules, &app_state.events);
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_line = Line::from(vec![
+        Span::styled(" [Enter] ", inverted_style),
+        Span::styled("Show Events ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let block = create_dialog_block(
+        &format!(
+            "Sigma Rule Hits ({} of {} rules matched, {} skipped)",
+            hits.len(),
+            app_state.sigma_rules.len(),
+            app_state.sigma_skipped
+        ),
+        bottom_title,
+        dialog_style,
+    );
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    if app_state.sigma_rules.is_empty() {
+        let folder = sigma::rules_dir(app_state.config.sigma_rules_dir.as_deref())
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|| "the sigma_rules_dir folder".to_string());
+        frame.render_widget(
+            Paragraph::new(format!(
+                "No Sigma rules are loaded.\n\nPut .yml rules in {} and restart, or set \
+                 sigma_rules_dir in the configuration.",
+                folder
+            ))
+            .style(dialog_style)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+            content_area,
+        );
+        return;
+    }
+
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:>6}  {:<13}  {:<19}  {}",
+            "Hits", "Level", "Last Seen", "Rule"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    let total = app_state
+        .events
+        .iter()
+        .filter(|event| !event.sigma_hits.is_empty())
+        .count();
+    let any_rule = format!(" {:>6}  {:<13}  {:<19}  {}", total, "", "", "Any rule");
+    let rows = std::iter::once((any_rule, None)).chain(hits.iter().map(|hit| {
+        let text = format!(
+            " {:>6}  {:<13}  {}  {}",
+            hit.count,
+            hit.level,
+            helpers::format_event_time(hit.last_seen),
+            hit.title
+        );
+        (text, Some(hit.level.as_str()))
+    }));
+
+    // Keep the selection in view when there are more rules than rows.
+    let visible_rows = list_area.height as usize;
+    let first = view
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = rows
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, (text, level))| {
+            let style = if i == view.selection {
+                theme.dialog_selection_style()
+            } else if matches!(level, Some("critical" | "high")) {
+                dialog_style.patch(theme.error_fg_style())
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+/// Renders the Event ID counts of two time windows, new and spiking event types first.
+fn render_comparison(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(comparison) = &app_state.comparison else {
+        return;
+    };
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_line = Line::from(vec![
+        Span::styled(" [←/→] ", inverted_style),
+        Span::styled("Windows ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let block = create_dialog_block(
+        &format!(
+            "Time Window Comparison: {} ({})",
+            comparison.preset.label(),
+            comparison.log
+        ),
+        bottom_title,
+        dialog_style,
+    );
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let (Some(later), Some(earlier)) = (comparison.later, comparison.earlier) else {
+        frame.render_widget(
+            Paragraph::new(comparison.notes.join("\n"))
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            content_area,
+        );
+        return;
+    };
+
+    let notes_height = comparison.notes.len().min(3) as u16;
+    let [windows_area, header_area, list_area, notes_area] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(notes_height),
+    ])
+    .areas(content_area);
+    let window_line = |name: &str, (start, end): Window, total: usize| {
+        format!(
+            " {:<8} {} to {}  {} events",
+            name,
+            helpers::format_local_time(start),
+            helpers::format_local_time(end),
+            total
+        )
+    };
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(window_line("Later", later, comparison.later_total)),
+            Line::from(window_line("Earlier", earlier, comparison.earlier_total)),
+        ])
+        .style(dialog_style),
+        windows_area,
+    );
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:<6}  {:>7}  {:>7}  {:>7}  {:<30}  {}",
+            "Trend", "Later", "Earlier", "Change", "Source", "Event ID"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+    frame.render_widget(
+        Paragraph::new(comparison.notes.join(" "))
+            .style(dialog_style.patch(theme.warn_fg_style()))
+            .wrap(Wrap { trim: true }),
+        notes_area,
+    );
+
+    if comparison.deltas.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No events in either window.")
+                .style(dialog_style)
+                .alignment(Alignment::Center),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view when there are more event types than rows.
+    let visible_rows = list_area.height as usize;
+    let first = comparison
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = comparison
+        .deltas
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, delta)| {
+            let text = format!(
+                " {:<6}  {:>7}  {:>7}  {:>7}  {:<30}  {}",
+                delta.trend.label(),
+                delta.later,
+                delta.earlier,
+                delta.change(),
+                delta.source.chars().take(30).collect::<String>(),
+                delta.event_id
+            );
+            let style = if i == comparison.selection {
+                theme.dialog_selection_style()
+            } else if delta.trend.is_notable() {
+                dialog_style.patch(theme.error_fg_style())
+            } else if delta.trend == Trend::Gone {
+                dialog_style.add_modifier(Modifier::DIM)
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+/// Renders the actions offered for the selected event.
+fn render_context_menu(frame: &mut Frame, app_state: &AppState, theme: &Theme) {
+    let Some(selection) = app_state.context_menu_selection else {
+        return;
+    };
+    let dialog_width = 40.min(frame.size().width.saturating_sub(4));
+    let dialog_height = EventAction::ALL.len() as u16 + 2;
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_line = Line::from(vec![
+        Span::styled(" [Enter] ", inverted_style),
+        Span::styled("Run ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let block = create_dialog_block("Event Actions", bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let lines: Vec<Line> = EventAction::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let text = format!(" {:<28}{:>6} ", action.label(), action.shortcut());
+            let style = if i == selection {
+                theme.dialog_selection_style()
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), content_area);
+}
+
+/// Renders the administration actions for the selected log.
+fn render_admin_menu(frame: &mut Frame, app_state: &AppState, theme: &Theme) {
+    let Some(selection) = app_state.admin_menu_selection else {
+        return;
+    };
+    let dialog_width = 40.min(frame.size().width.saturating_sub(4));
+    let dialog_height = AdminAction::ALL.len() as u16 + 2;
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_line = Line::from(vec![
+        Span::styled(" [Enter] ", inverted_style),
+        Span::styled("Run ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let block = create_dialog_block("Log Administration", bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let lines: Vec<Line> = AdminAction::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let style = if i == selection {
+                theme.dialog_selection_style()
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(format!(" {}", action.label()), style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), content_area);
+}
+
+fn render_elevation_prompt(frame: &mut Frame, app_state: &AppState, theme: &Theme) {
+    let Some(log_name) = &app_state.elevation_prompt else {
+        return;
+    };
+    let dialog_width = 70.min(frame.size().width.saturating_sub(4));
+    let dialog_height = 11;
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_line = Line::from(vec![
+        Span::styled(" [Enter] ", inverted_style),
+        Span::styled("Relaunch as administrator ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let block = create_dialog_block("Administrator Rights Needed", bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let message = format!(
+        "Event Commander is not running as administrator, so Windows denies it the '{}' log. Only administrators and accounts holding the 'Manage auditing and security log' right can read it.\n\nRelaunching opens Event Commander in a new elevated window after the UAC prompt and closes this one.",
+        log_name
+    );
+    frame.render_widget(
+        Paragraph::new(message)
+            .style(dialog_style)
+            .wrap(Wrap { trim: true }),
+        content_area,
+    );
+}
+
+fn render_bookmarks_dialog(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    if !app_state.is_bookmarks_dialog_visible {
+        return;
+    }
+    let dialog_width = 100.min(frame.size().width.saturating_sub(4));
+    let dialog_height =
+        (app_state.bookmarks.len().max(1) as u16 + 4).min(frame.size().height.saturating_sub(2));
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+
+    let bottom_line = Line::from(vec![
+        Span::styled(" [Enter] ", inverted_style),
+        Span::styled("Go to ", dialog_style),
+        Span::styled(" [x] ", inverted_style),
+        Span::styled("Remove ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+
+    let block = create_dialog_block("Bookmarks", bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    if app_state.bookmarks.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No bookmarks. Press [b] on an event to bookmark it.")
+                .style(dialog_style)
+                .alignment(Alignment::Center),
+            content_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view when there are more bookmarks than rows.
+    let visible_rows = content_area.height as usize;
+    let first = app_state
+        .bookmarks_selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let summary_chars = (content_area.width as usize).saturating_sub(58);
+    let lines: Vec<Line> = app_state
+        .bookmarks
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, bookmark)| {
+            let text = format!(
+                " {:<12} {} {:>6}  {:<16} {}",
+                helpers::truncate_at_word(&bookmark.channel, 12),
+                helpers::format_event_time(bookmark.timestamp),
+                bookmark.event_id,
+                helpers::truncate_at_word(&bookmark.source, 16),
+                helpers::truncate_at_word(&bookmark.summary, summary_chars)
+            );
+            let style = if i == app_state.bookmarks_selection {
+                theme.dialog_selection_style()
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), content_area);
+}
+
+/// Renders the host groups of `hosts.toml` with tick marks and connectivity status.
+fn render_host_picker(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(picker) = &app_state.host_picker else {
+        return;
+    };
+    let rows = app_state.hosts.rows();
+    let dialog_width = 100.min(frame.size().width.saturating_sub(4));
+    let dialog_height = (rows.len() as u16 + 4).min(frame.size().height.saturating_sub(2));
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_line = Line::from(vec![
+        Span::styled(" [Space] ", inverted_style),
+        Span::styled("Tick ", dialog_style),
+        Span::styled(" [c] ", inverted_style),
+        Span::styled("Check ", dialog_style),
+        Span::styled(" [Enter] ", inverted_style),
+        Span::styled("Merge ", dialog_style),
+        Span::styled(" [e] ", inverted_style),
+        Span::styled("Type Names ", dialog_style),
+        Span::styled(" [p] ", inverted_style),
+        Span::styled("Credential ", dialog_style),
+        Span::styled(" [x] ", inverted_style),
+        Span::styled("Forget ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let block = create_dialog_block("Hosts", bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    // Keep the selection in view when there are more rows than fit.
+    let visible_rows = content_area.height as usize;
+    let first = picker
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let status_chars = (content_area.width as usize).saturating_sub(62);
+    let lines: Vec<Line> = rows
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, &row)| {
+            let hosts = app_state.hosts.hosts_of(row);
+            let ticked = hosts
+                .iter()
+                .filter(|host| picker.checked.contains(&host.name.to_lowercase()))
+                .count();
+            let mark = match ticked {
+                0 => "[ ]",
+                n if n == hosts.len() => "[x]",
+                _ => "[-]",
+            };
+            let selected = i == picker.selection;
+            let base_style = if selected {
+                theme.dialog_selection_style()
+            } else {
+                dialog_style
+            };
+            match row {
+                PickerRow::Group(g) => {
+                    let group = &app_state.hosts.groups[g];
+                    Line::from(Span::styled(
+                        format!(" {} {} ({} hosts)", mark, group.name, group.hosts.len()),
+                        base_style.add_modifier(Modifier::BOLD),
+                    ))
+                }
+                PickerRow::Host(g, h) => {
+                    let host = &app_state.hosts.groups[g].hosts[h];
+                    let credential = match app_state.hosts.credential_for(&host.name) {
+                        Some(target) => match target.strip_prefix(hosts::SAVED_CREDENTIAL_PREFIX) {
+                            Some(name) if name.starts_with('@') => format!("saved for {}", name),
+                            Some(_) => "saved".to_string(),
+                            None => target,
+                        },
+                        None => "current user".to_string(),
+                    };
+                    let credential = match app_state.hosts.login_for(&host.name).auth {
+                        RemoteAuth::Default => credential,
+                        auth => format!("{}, {}", credential, auth.name()),
+                    };
+                    let (status, status_style) =
+                        match app_state.host_status.get(&host.name.to_lowercase()) {
+                            None => ("not checked".to_string(), base_style),
+                            Some(HostStatus::Checking) => ("checking...".to_string(), base_style),
+                            Some(HostStatus::Reachable) => (
+                                "reachable".to_string(),
+                                base_style.add_modifier(Modifier::BOLD),
+                            ),
+                            Some(HostStatus::Unreachable(msg)) => (
+                                helpers::truncate_at_word(msg, status_chars),
+                                base_style.fg(theme.dialog_error_fg),
+                            ),
+                        };
+                    Line::from(vec![
+                        Span::styled(
+                            format!(
+                                "     {} {:<30} {:<22} ",
+                                mark,
+                                helpers::truncate_at_word(&host.name, 30),
+                                helpers::truncate_at_word(&credential, 22)
+                            ),
+                            base_style,
+                        ),
+                        Span::styled(status, status_style),
+                    ])
+                }
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), content_area);
+}
+
+/// Renders failed DNS resolutions grouped by name and response code.
+#[cfg(target_os = "windows")]
+fn render_dns_analysis(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(analysis) = &app_state.dns_analysis else {
+        return;
+    };
+    let groups = analysis.groups();
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_line = Line::from(vec![
+        Span::styled(" [Type] ", inverted_style),
+        Span::styled("Domain ", dialog_style),
+        Span::styled(" [Enter] ", inverted_style),
+        Span::styled("Narrow to Name ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let total: usize = groups.iter().map(|group| group.count).sum();
+    let block = create_dialog_block(
+        &format!("DNS Failures ({} in {} groups)", total, groups.len()),
+        bottom_title,
+        dialog_style,
+    );
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let [domain_area, header_area, list_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ])
+    .areas(content_area);
+
+    let mut domain_text = analysis.domain.clone();
+    let byte_idx = domain_text
+        .char_indices()
+        .nth(analysis.domain_cursor)
+        .map(|(idx, _)| idx)
+        .unwrap_or(domain_text.len());
+    domain_text.insert(byte_idx, '_');
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(" Domain: ", dialog_style.add_modifier(Modifier::BOLD)),
+            Span::styled(domain_text, theme.dialog_selection_style()),
+        ]))
+        .style(dialog_style),
+        domain_area,
+    );
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:>6}  {:<18} {:<7} {:<19}  {}",
+            "Count", "Response", "Origin", "Last Seen", "Name"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    if groups.is_empty() {
+        let mut message = if analysis.failures.is_empty() {
+            "No failed resolutions found. The DNS Client Operational log is disabled by default; enable it with:\n\nwevtutil sl Microsoft-Windows-DNS-Client/Operational /e:true".to_string()
+        } else {
+            "No failed resolutions under this domain.".to_string()
+        };
+        for unavailable in &analysis.unavailable {
+            message.push_str("\n\n");
+            message.push_str(unavailable);
+        }
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view when there are more groups than rows.
+    let visible_rows = list_area.height as usize;
+    let first = analysis
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = groups
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, group)| {
+            let text = format!(
+                " {:>6}  {:<18} {:<7} {}  {}",
+                group.count,
+                group.rcode,
+                group.origin,
+                helpers::format_local_time(group.last_seen),
+                group.name
+            );
+            let style = if i == analysis.selection {
+                theme.dialog_selection_style()
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+/// Renders the Hyper-V guests with their last state, or one guest's timeline.
+#[cfg(target_os = "windows")]
+fn render_hyperv_view(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(view) = &app_state.hyperv_view else {
+        return;
+    };
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let open_guest = view.open_guest.and_then(|index| view.guests.get(index));
+    let bottom_line = Line::from(match open_guest {
+        Some(_) => vec![
+            Span::styled(" [s] ", inverted_style),
+            Span::styled("Show Guest's Events ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Back", dialog_style),
+        ],
+        None => vec![
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("Timeline ", dialog_style),
+            Span::styled(" [s] ", inverted_style),
+            Span::styled("Show Guest's Events ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Close", dialog_style),
+        ],
+    })
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let title = match open_guest {
+        Some(guest) => format!("Hyper-V Guest: {}", guest.name),
+        None => format!("Hyper-V Guests ({})", view.guests.len()),
+    };
+    let block = create_dialog_block(&title, bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+    let header = match open_guest {
+        Some(_) => format!(
+            " {:<19}  {:>6}  {:<22} {}",
+            "Time", "ID", "State", "Message"
+        ),
+        None => format!(
+            " {:<30} {:>6}  {:<22} {}",
+            "Guest", "Events", "Last State", "Since"
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(header).style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    if view.guests.is_empty() {
+        let mut message = "No Hyper-V events naming a virtual machine were found.".to_string();
+        for unavailable in &view.unavailable {
+            message.push_str("\n\n");
+            message.push_str(unavailable);
+        }
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            list_area,
+        );
+        return;
+    }
+
+    let (rows, selection): (Vec<(String, bool)>, usize) = match open_guest {
+        Some(guest) => (
+            guest
+                .entries
+                .iter()
+                .map(|entry| {
+                    (
+                        format!(
+                            " {}  {:>6}  {:<22} {}",
+                            helpers::format_local_time(entry.time),
+                            entry.event_id,
+                            entry.state.unwrap_or(""),
+                            entry.summary
+                        ),
+                        entry.state.is_some(),
+                    )
+                })
+                .collect(),
+            view.timeline_selection,
+        ),
+        None => (
+            view.guests
+                .iter()
+                .map(|guest| {
+                    let (state, since) = match guest.last_state() {
+                        Some((state, time)) => (state, helpers::format_local_time(time)),
+                        None => ("unknown", String::new()),
+                    };
+                    (
+                        format!(
+                            " {:<30} {:>6}  {:<22} {}",
+                            helpers::truncate_at_word(&guest.name, 30),
+                            guest.entries.len(),
+                            state,
+                            since
+                        ),
+                        false,
+                    )
+                })
+                .collect(),
+            view.selection,
+        ),
+    };
+
+    // Keep the selection in view when there are more rows than fit.
+    let visible_rows = list_area.height as usize;
+    let first = selection.saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, (text, is_state_change))| {
+            let style = if i == selection {
+                theme.dialog_selection_style()
+            } else if is_state_change {
+                dialog_style.add_modifier(Modifier::BOLD)
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+#[cfg(target_os = "windows")]
+fn render_installer_view(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(view) = &mut app_state.installer_view else {
+        return;
+    };
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let open_transaction = view
+        .open_transaction
+        .and_then(|index| view.transactions.get(index));
+    let bottom_line = Line::from(match open_transaction {
+        Some(_) => vec![
+            Span::styled(" [↑↓] ", inverted_style),
+            Span::styled("Scroll ", dialog_style),
+            Span::styled(" [s] ", inverted_style),
+            Span::styled("Show Events at the Time ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Back", dialog_style),
+        ],
+        None => vec![
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("Details ", dialog_style),
+            Span::styled(" [s] ", inverted_style),
+            Span::styled("Show Events at the Time ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Close", dialog_style),
+        ],
+    })
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let title = match open_transaction {
+        Some(transaction) => format!("Installer Transaction: {}", transaction.name()),
+        None => format!("Installer Transactions ({})", view.transactions.len()),
+    };
+    let block = create_dialog_block(&title, bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    if let Some(transaction) = open_transaction {
+        let lines: Vec<Line> = transaction.lines().into_iter().map(Line::from).collect();
+        let max_scroll = lines.len().saturating_sub(content_area.height as usize);
+        view.detail_scroll = view.detail_scroll.min(max_scroll);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(dialog_style)
+                .scroll((view.detail_scroll as u16, 0)),
+            content_area,
+        );
+        return;
+    }
+
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:<19}  {:<15} {:<14} {:<16} {}",
+            "Time", "Action", "Result", "Version", "Product"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    if view.transactions.is_empty() {
+        let mut message =
+            "No Windows Installer events were found in the Application log.".to_string();
+        if let Some(unavailable) = &view.unavailable {
+            message.push_str("\n\n");
+            message.push_str(unavailable);
+        }
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view when there are more rows than fit.
+    let visible_rows = list_area.height as usize;
+    let first = view
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = view
+        .transactions
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, transaction)| {
+            let text = format!(
+                " {}  {:<15} {:<14} {:<16} {}",
+                helpers::format_local_time(transaction.time()),
+                transaction.action.unwrap_or(""),
+                transaction.result(),
+                helpers::truncate_at_word(transaction.version.as_deref().unwrap_or(""), 16),
+                transaction.name()
+            );
+            let style = if i == view.selection {
+                theme.dialog_selection_style()
+            } else if matches!(transaction.result(), "Failed" | "Errors") {
+                dialog_style.fg(theme.dialog_error_fg)
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+#[cfg(target_os = "windows")]
+fn render_print_view(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(view) = &mut app_state.print_view else {
+        return;
+    };
+    let dialog_width = 110.min(frame.size().width.saturating_sub(4));
+    let dialog_height = frame.size().height.saturating_sub(4);
+    let dialog_area = helpers::centered_fixed_rect(dialog_width, dialog_height, frame.size());
+    frame.render_widget(Clear, dialog_area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let open_summary = view
+        .open_summary
+        .and_then(|index| view.summaries.get(index));
+    let bottom_line = Line::from(match open_summary {
+        Some(_) => vec![
+            Span::styled(" [↑↓] ", inverted_style),
+            Span::styled("Scroll ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Back", dialog_style),
+        ],
+        None => vec![
+            Span::styled(" [Enter] ", inverted_style),
+            Span::styled("Jobs ", dialog_style),
+            Span::styled(" [Esc] ", inverted_style),
+            Span::styled("Close", dialog_style),
+        ],
+    })
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let title = match open_summary {
+        Some(summary) => format!("Print Jobs: {} on {}", summary.user, summary.printer),
+        None => "Print Jobs by Printer and User".to_string(),
+    };
+    let block = create_dialog_block(&title, bottom_title, dialog_style);
+    let content_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    if let Some(summary) = open_summary {
+        let lines: Vec<Line> = summary
+            .lines()
+            .into_iter()
+            .map(|line| {
+                if line.contains("  FAILED, error ") {
+                    Line::styled(line, dialog_style.fg(theme.dialog_error_fg))
+                } else {
+                    Line::from(line)
+                }
+            })
+            .collect();
+        let max_scroll = lines.len().saturating_sub(content_area.height as usize);
+        view.detail_scroll = view.detail_scroll.min(max_scroll);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(dialog_style)
+                .scroll((view.detail_scroll as u16, 0)),
+            content_area,
+        );
+        return;
+    }
+
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(content_area);
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:<34} {:<24} {:>7} {:>7} {:>7}  {}",
+            "Printer", "User", "Printed", "Pages", "Failed", "Last Job"
+        ))
+        .style(dialog_style.add_modifier(Modifier::BOLD)),
+        header_area,
+    );
+
+    if view.summaries.is_empty() {
+        let mut message = "No print jobs were found. The PrintService Operational log is disabled by default; enable it to record printed documents.".to_string();
+        for unavailable in &view.unavailable {
+            message.push_str("\n\n");
+            message.push_str(unavailable);
+        }
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(dialog_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view when there are more rows than fit.
+    let visible_rows = list_area.height as usize;
+    let first = view
+        .selection
+        .saturating_sub(visible_rows.saturating_sub(1));
+    let lines: Vec<Line> = view
+        .summaries
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows)
+        .map(|(i, summary)| {
+            let text = format!(
+                " {:<34} {:<24} {:>7} {:>7} {:>7}  {}",
+                helpers::truncate_at_word(&summary.printer, 34),
+                helpers::truncate_at_word(&summary.user, 24),
+                summary.printed,
+                summary.pages,
+                summary.failed,
+                helpers::format_local_time(summary.last_time)
+            );
+            let style = if i == view.selection {
+                theme.dialog_selection_style()
+            } else if summary.failed > 0 {
+                dialog_style.fg(theme.dialog_error_fg)
+            } else {
+                dialog_style
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(dialog_style), list_area);
+}
+
+/// Renders the selected event as labeled fields over the whole screen.
+fn render_detail_view(frame: &mut Frame, app_state: &mut AppState, theme: &Theme) {
+    let Some(fields) = &app_state.detail_fields else {
+        return;
+    };
+    let area = frame.size();
+    frame.render_widget(Clear, area);
+
+    let dialog_style = theme.dialog_style();
+    let inverted_style = Style {
+        fg: dialog_style.bg,
+        bg: dialog_style.fg,
+        ..dialog_style
+    };
+    let bottom_line = Line::from(vec![
+        Span::styled(" [↑↓] ", inverted_style),
+        Span::styled("Select ", dialog_style),
+        Span::styled(" [Enter/y] ", inverted_style),
+        Span::styled("Copy Value ", dialog_style),
+        Span::styled(" [Esc] ", inverted_style),
+        Span::styled("Close", dialog_style),
+    ])
+    .alignment(Alignment::Center);
+    let bottom_title = Title::from(bottom_line)
+        .position(Position::Bottom)
+        .alignment(Alignment::Center);
+    let block = create_dialog_block("Event Details", bottom_title, dialog_style);
+    let content_area = block.inner(area);
+
+    let name_width = fields
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(40) as u16;
+    let value_width = content_area.width.saturating_sub(name_width + 1).max(1) as usize;
+    let key_style = dialog_style.add_modifier(Modifier::BOLD);
+    let rows: Vec<Row> = fields
+        .iter()
+        .map(|(name, value)| {
+            // Cells do not wrap, so long values are split to the column width here.
+            let lines: Vec<Line> = value
+                .lines()
+                .flat_map(|line| {
+                    let chars: Vec<char> = line.chars().collect();
+                    if chars.is_empty() {
+                        return vec![Line::from("")];
+                    }
+                    chars
+                        .chunks(value_width)
+                        .map(|chunk| Line::from(chunk.iter().collect::<String>()))
+                        .collect()
+                })
+                .collect();
+            let height = lines.len().max(1) as u16;
+            Row::new(vec![
+                Cell::from(Span::styled(name.clone(), key_style)),
+                Cell::from(Text::from(lines)),
+            ])
+            .height(height)
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Length(name_width), Constraint::Min(10)])
+        .block(block)
+        .style(dialog_style)
+        .highlight_style(theme.dialog_selection_style());
+    let mut table_state = TableState::default().with_selected(Some(app_state.detail_selection));
+    frame.render_stateful_widget(table, area, &mut table_state);
+}
+
+fn render_bottom_bar(
+    frame: &mut Frame,
+    app_state: &mut AppState,
+    area: Rect,
+    search_matches: &[usize],
+    theme: &Theme,
+) {
+    let mut spans = Vec::with_capacity(16);
+    spans.extend([
+        key_span("[q]", theme),
+        Span::raw(" Quit | ").style(theme.footer_style()),
+        key_span("[F1]", theme),
+        Span::raw(" Help | ").style(theme.footer_style()),
+    ]);
+
+    let pending_keys = match (app_state.pending_mark, app_state.key_count) {
+        (Some(MarkKey::Set), _) => Some("'".to_string()),
+        (Some(MarkKey::Jump), _) => Some("`".to_string()),
+        (None, Some(count)) => Some(count.to_string()),
+        (None, None) => None,
+    };
+    if let Some(keys) = pending_keys {
+        spans.push(Span::styled(format!("{} ", keys), theme.title_style()));
+    }
+
+    match app_state.focus {
+        PanelFocus::Events => {
+            spans.extend([
+                key_span("[s]", theme),
+                Span::raw(format!(" Sort ({}) | ", app_state.sort_key.display_name()))
+                    .style(theme.footer_style()),
+                key_span("[l]", theme),
+                Span::raw(format!(" Lvl ({}) | ", app_state.get_current_level_name()))
+                    .style(theme.footer_style()),
+                key_span("[f]", theme),
+                Span::raw(format!(
+                    " Adv Filter ({}) | ",
+                    app_state.get_filter_status()
+                ))
+                .style(theme.footer_style()),
+                key_span("[x]", theme),
+                Span::raw(" Query | ").style(theme.footer_style()),
+                key_span("[/]", theme),
+                Span::raw(" Search").style(theme.footer_style()),
+            ]);
+            if app_state.last_search_term.is_some() {
+                let position = app_state
+                    .table_state
+                    .selected()
+                    .and_then(|selected| search_matches.binary_search(&selected).ok())
+                    .map_or("-".to_string(), |index| (index + 1).to_string());
+                spans.extend([
+                    Span::raw(format!(
+                        " | {} of {} matches | ",
+                        position,
+                        search_matches.len()
+                    ))
+                    .style(theme.footer_style()),
+                    key_span("[n]", theme),
+                    Span::raw(" Next | ").style(theme.footer_style()),
+                    key_span("[p]", theme),
+                    Span::raw(" Prev").style(theme.footer_style()),
+                ]);
+            }
+        }
+        PanelFocus::Preview => {
+            spans.extend([
+                key_span("[Esc/←]", theme),
+                Span::raw(" Return | ").style(theme.footer_style()),
+                key_span("[v]", theme),
+                Span::raw(" Toggle View | ").style(theme.footer_style()),
+                key_span("[s]", theme),
+                Span::raw(" Save | ").style(theme.footer_style()),
+                key_span("[w]", theme),
+                Span::raw(" Wrap | ").style(theme.footer_style()),
+                key_span("[↑↓ PgUpDn HmEnd]", theme),
+                Span::raw(" Scroll").style(theme.footer_style()),
+            ]);
+        }
+    }
+
+    if let Some(progress) = app_state.load_progress {
+        let batch_size = progress.batch_size.max(1);
+        let filled = (progress.fetched * 10 / batch_size).min(10);
+        spans.push(Span::raw(" | ").style(theme.footer_style()));
+        spans.push(Span::styled(
+            format!(
+                "Loading [{}{}] {}/{} events, {:.1}s ",
+                "#".repeat(filled),
+                "-".repeat(10 - filled),
+                progress.fetched,
+                batch_size,
+                progress.started.elapsed().as_secs_f64()
+            ),
+            theme.alt_fg_style(),
+        ));
+        spans.extend([
+            key_span("[Esc]", theme),
+            Span::raw(" Cancel").style(theme.footer_style()),
+        ]);
+    } else if app_state.is_loading {
+        spans.push(Span::raw(" | ").style(theme.footer_style()));
+        spans.push(Span::styled("Loading...", theme.alt_fg_style()));
+    }
+    if app_state.auto_refresh {
+        spans.push(Span::raw(" | ").style(theme.footer_style()));
+        spans.push(Span::styled(
+            format!(
+                "auto-refresh: {}s",
+                app_state.config.auto_refresh_secs.max(1)
+            ),
+            theme.alt_fg_style(),
+        ));
+    }
+    if let Some((notice, _)) = app_state
+        .footer_notice
+        .as_ref()
+        .filter(|(_, at)| at.elapsed().as_secs() < FOOTER_NOTICE_SECS)
+    {
+        spans.push(Span::raw(" | ").style(theme.footer_style()));
+        spans.push(Span::styled(notice.clone(), theme.warn_fg_style()));
     }
 
     frame.render_widget(
-        Paragraph::new(Line::from(spans).alignment(Alignment::Left)).style(*FOOTER_STYLE),
+        Paragraph::new(Line::from(spans).alignment(Alignment::Left)).style(theme.footer_style()),
         area,
     );
 }
@@ -0,0 +1,58 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{FilterCriteria, Incident, SortKey};
+
+/// Prefix of encoded view tokens, versioned so the format can change later.
+const TOKEN_PREFIX: &str = "ec1.";
+
+/// Everything needed to reproduce what is on screen on another machine: the channel, how its
+/// events are selected and ordered, and which event is open.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewState {
+    pub channel: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<FilterCriteria>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_query: Option<String>,
+    #[serde(default)]
+    pub sort_key: SortKey,
+    #[serde(default = "default_sort_descending")]
+    pub sort_descending: bool,
+    /// `EventRecordID` of the selected event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record_id: Option<u64>,
+    #[serde(default)]
+    pub preview_scroll: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incident: Option<Incident>,
+}
+
+fn default_sort_descending() -> bool {
+    true
+}
+
+impl ViewState {
+    /// Encodes the view as a single-line token that is safe to paste into chat or a ticket.
+    pub fn to_token(&self) -> String {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        format!("{}{}", TOKEN_PREFIX, URL_SAFE_NO_PAD.encode(json))
+    }
+
+    /// Decodes a view from a token, or from the plain JSON it wraps.
+    pub fn from_token(token: &str) -> Result<Self, String> {
+        let token = token.trim();
+        let json = if token.starts_with('{') {
+            token.as_bytes().to_vec()
+        } else {
+            let encoded = token
+                .strip_prefix(TOKEN_PREFIX)
+                .ok_or("Not a view token. Tokens start with 'ec1.'.")?;
+            URL_SAFE_NO_PAD
+                .decode(encoded)
+                .map_err(|e| format!("The view token is damaged: {}", e))?
+        };
+        serde_json::from_slice(&json).map_err(|e| format!("The view token is not valid: {}", e))
+    }
+}
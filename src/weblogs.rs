@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use quick_xml::escape::escape;
+
+use crate::models::{DisplayEvent, EventData, EventLevel};
+
+/// Default folders holding HTTP.sys error logs and IIS site logs. IIS keeps one subfolder per
+/// site (W3SVC1, W3SVC2, ...).
+pub const DEFAULT_WEB_LOG_DIRS: [&str; 2] = [
+    r"C:\Windows\System32\LogFiles\HTTPERR",
+    r"C:\inetpub\logs\LogFiles",
+];
+
+/// Most web log entries merged into the event list.
+pub const MAX_WEB_LOG_ENTRIES: usize = 20000;
+
+/// Reads the W3C text logs under `dirs` and their site subfolders and returns the entries
+/// logged between `start` and `end` as synthetic events, with the source named after the
+/// folder. Files last written before `start` are skipped. Missing folders are ignored.
+pub fn read_web_logs(
+    dirs: &[String],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<DisplayEvent>, String> {
+    let host = crate::helpers::local_host_name();
+    let mut events = Vec::new();
+    for dir in dirs {
+        for path in log_files(Path::new(dir)) {
+            let written = fs::metadata(&path)
+                .and_then(|meta| meta.modified())
+                .map(DateTime::<Utc>::from);
+            if written.is_ok_and(|written| written < start) {
+                continue;
+            }
+            let source = path
+                .parent()
+                .and_then(|parent| parent.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let text = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+            events.extend(parse_w3c_log(&text, &source, &host).filter(|event| {
+                event
+                    .timestamp
+                    .is_some_and(|time| (start..=end).contains(&time))
+            }));
+            if events.len() >= MAX_WEB_LOG_ENTRIES {
+                events.truncate(MAX_WEB_LOG_ENTRIES);
+                return Ok(events);
+            }
+        }
+    }
+    Ok(events)
+}
+
+/// Lists the `.log` files in `dir` and in its immediate subfolders.
+fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            files.extend(log_files_in(&path));
+        } else if is_log_file(&path) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+fn log_files_in(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| is_log_file(path))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn is_log_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("log"))
+}
+
+/// Parses a W3C extended log: `#Fields:` directives name the space-separated columns of the
+/// lines after them, and `date` and `time` are in UTC.
+fn parse_w3c_log<'a>(
+    text: &'a str,
+    source: &'a str,
+    host: &'a str,
+) -> impl Iterator<Item = DisplayEvent> + 'a {
+    let mut fields: Vec<&str> = Vec::new();
+    text.lines().filter_map(move |line| {
+        if let Some(directive) = line.strip_prefix("#Fields:") {
+            fields = directive.split_whitespace().collect();
+            return None;
+        }
+        if line.starts_with('#') || line.trim().is_empty() {
+            return None;
+        }
+        let values: Vec<(&str, &str)> = fields
+            .iter()
+            .copied()
+            .zip(line.split(' '))
+            .filter(|(_, value)| *value != "-")
+            .collect();
+        web_log_event(&values, source, host)
+    })
+}
+
+fn web_log_event(values: &[(&str, &str)], source: &str, host: &str) -> Option<DisplayEvent> {
+    let value = |name: &str| {
+        values
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| *value)
+    };
+    let timestamp = NaiveDateTime::parse_from_str(
+        &format!("{} {}", value("date")?, value("time")?),
+        "%Y-%m-%d %H:%M:%S",
+    )
+    .ok()?
+    .and_utc();
+    let status: u32 = value("sc-status").and_then(|s| s.parse().ok()).unwrap_or(0);
+    let level = match status {
+        500.. => EventLevel::Error,
+        400..=499 => EventLevel::Warning,
+        _ => EventLevel::Information,
+    };
+    let uri = value("cs-uri")
+        .or_else(|| value("cs-uri-stem"))
+        .unwrap_or("");
+    let mut message = format!(
+        "{} {} -> {}",
+        value("cs-method").unwrap_or("-"),
+        uri,
+        value("sc-status").unwrap_or("-")
+    );
+    if let Some(reason) = value("s-reason") {
+        message.push_str(&format!(" ({})", reason));
+    }
+    if let Some(client) = value("c-ip") {
+        message.push_str(&format!(" from {}", client));
+    }
+    if let Some(taken) = value("time-taken") {
+        message.push_str(&format!(" in {} ms", taken));
+    }
+
+    let event_data = EventData {
+        fields: values
+            .iter()
+            .filter(|(name, _)| !matches!(*name, "date" | "time"))
+            .map(|(name, value)| (Some(name.to_string()), value.replace('+', " ")))
+            .collect(),
+    };
+    let data_xml: String = event_data
+        .fields
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "<Data Name=\"{}\">{}</Data>",
+                escape(name.as_deref().unwrap_or_default()),
+                escape(value)
+            )
+        })
+        .collect();
+    let raw_data = format!(
+        "<Event><System><Provider Name=\"{}\"/><EventID>{}</EventID><Level>{}</Level>\
+         <TimeCreated SystemTime=\"{}\"/><Computer>{}</Computer></System>\
+         <EventData>{}</EventData></Event>",
+        escape(source),
+        status,
+        level.code(),
+        timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        escape(host),
+        data_xml
+    );
+    Some(DisplayEvent {
+        level,
+        timestamp: Some(timestamp),
+        source: source.to_string(),
+        provider_name_original: source.to_string(),
+        event_id: status,
+        channel: source.to_string(),
+        computer: host.to_string(),
+        user: value("cs-username").unwrap_or_default().to_string(),
+        task: 0,
+        task_category: None,
+        keywords: 0,
+        record_id: 0,
+        activity_id: None,
+        related_activity_id: None,
+        process_id: None,
+        thread_id: None,
+        message: message.clone(),
+        event_data,
+        raw_data,
+        #[cfg(target_os = "windows")]
+        raw_dropped: false,
+        formatted_message: Some(message),
+        sigma_hits: Vec::new(),
+    })
+}
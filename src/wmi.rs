@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::analysis;
+use crate::helpers;
+use crate::models::DisplayEvent;
+
+pub const WMI_CHANNEL: &str = "Microsoft-Windows-WMI-Activity/Operational";
+/// Operations a WMI client started that failed.
+pub const CLIENT_FAILURES_QUERY: &str = "*[System[EventID=5858]]";
+
+/// Most failures read.
+pub const MAX_FAILURE_EVENTS: usize = 5000;
+
+/// WBEM error codes seen in 5858 events.
+const WBEM_ERRORS: &[(u32, &str)] = &[
+    (0x80041001, "WBEM_E_FAILED"),
+    (0x80041002, "WBEM_E_NOT_FOUND"),
+    (0x80041003, "WBEM_E_ACCESS_DENIED"),
+    (0x80041004, "WBEM_E_PROVIDER_FAILURE"),
+    (0x80041005, "WBEM_E_TYPE_MISMATCH"),
+    (0x80041006, "WBEM_E_OUT_OF_MEMORY"),
+    (0x80041008, "WBEM_E_INVALID_PARAMETER"),
+    (0x80041009, "WBEM_E_NOT_AVAILABLE"),
+    (0x8004100A, "WBEM_E_CRITICAL_ERROR"),
+    (0x8004100C, "WBEM_E_NOT_SUPPORTED"),
+    (0x8004100E, "WBEM_E_INVALID_NAMESPACE"),
+    (0x8004100F, "WBEM_E_INVALID_OBJECT"),
+    (0x80041010, "WBEM_E_INVALID_CLASS"),
+    (0x80041011, "WBEM_E_PROVIDER_NOT_FOUND"),
+    (0x80041013, "WBEM_E_PROVIDER_LOAD_FAILURE"),
+    (0x80041017, "WBEM_E_INVALID_QUERY"),
+    (0x80041032, "WBEM_E_CALL_CANCELLED"),
+    (0x80041033, "WBEM_E_SHUTTING_DOWN"),
+    (0x8004106C, "WBEM_E_QUOTA_VIOLATION"),
+];
+
+/// One failed client operation, reduced to what the analysis groups by.
+#[derive(Debug, Clone)]
+pub struct WmiFailure {
+    pub time: DateTime<Utc>,
+    pub process_id: u32,
+    pub operation: String,
+    pub result_code: String,
+    pub user: String,
+    pub client_machine: String,
+}
+
+/// Failures of one operation started by one client process with one result.
+#[derive(Debug, Clone)]
+pub struct WmiFailureGroup {
+    pub process_id: u32,
+    pub operation: String,
+    pub result_code: String,
+    pub count: usize,
+    pub last_seen: DateTime<Utc>,
+    pub users: Vec<String>,
+    pub client_machines: Vec<String>,
+}
+
+impl WmiFailureGroup {
+    /// Describes the group for the detail pane; `process` is the client's resolved name.
+    pub fn lines(&self, process: Option<&str>) -> Vec<String> {
+        vec![
+            format!(
+                "Process:      {} ({})",
+                process.unwrap_or("unknown"),
+                self.process_id
+            ),
+            format!("Result:       {}", result_name(&self.result_code)),
+            format!("Failures:     {}", self.count),
+            format!(
+                "Last seen:    {}",
+                helpers::format_local_time(self.last_seen)
+            ),
+            format!("Users:        {}", self.users.join(", ")),
+            format!("Clients:      {}", self.client_machines.join(", ")),
+            String::new(),
+            "--- Operation ---".to_string(),
+            self.operation.clone(),
+        ]
+    }
+}
+
+/// State of the WMI failures view.
+#[derive(Debug, Clone, Default)]
+pub struct WmiAnalysis {
+    pub failures: Vec<WmiFailure>,
+    /// Why the WMI-Activity log could not be read.
+    pub unavailable: Option<String>,
+    /// Image names of the client processes, by process ID, where they could be found.
+    pub process_names: HashMap<u32, String>,
+    pub selection: usize,
+    /// Group whose details are shown instead of the list.
+    pub open_group: Option<usize>,
+}
+
+impl WmiAnalysis {
+    /// Groups the failures by client process, operation and result, most frequent first.
+    pub fn groups(&self) -> Vec<WmiFailureGroup> {
+        let mut groups: Vec<WmiFailureGroup> = Vec::new();
+        for failure in &self.failures {
+            let index = match groups.iter().position(|group| {
+                group.process_id == failure.process_id
+                    && group.operation == failure.operation
+                    && group.result_code == failure.result_code
+            }) {
+                Some(index) => index,
+                None => {
+                    groups.push(WmiFailureGroup {
+                        process_id: failure.process_id,
+                        operation: failure.operation.clone(),
+                        result_code: failure.result_code.clone(),
+                        count: 0,
+                        last_seen: failure.time,
+                        users: Vec::new(),
+                        client_machines: Vec::new(),
+                    });
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[index];
+            group.count += 1;
+            group.last_seen = group.last_seen.max(failure.time);
+            if !failure.user.is_empty() && !group.users.contains(&failure.user) {
+                group.users.push(failure.user.clone());
+            }
+            if !failure.client_machine.is_empty()
+                && !group.client_machines.contains(&failure.client_machine)
+            {
+                group.client_machines.push(failure.client_machine.clone());
+            }
+        }
+        groups.sort_by(|a, b| b.count.cmp(&a.count).then(b.last_seen.cmp(&a.last_seen)));
+        groups
+    }
+
+    /// Client processes to resolve, each with the time of its last failure.
+    pub fn client_processes(&self) -> Vec<(u32, DateTime<Utc>)> {
+        let mut processes: Vec<(u32, DateTime<Utc>)> = Vec::new();
+        for failure in self
+            .failures
+            .iter()
+            .filter(|failure| failure.process_id != 0)
+        {
+            match processes
+                .iter_mut()
+                .find(|(process_id, _)| *process_id == failure.process_id)
+            {
+                Some((_, last)) => *last = (*last).max(failure.time),
+                None => processes.push((failure.process_id, failure.time)),
+            }
+        }
+        processes
+    }
+}
+
+/// Reduces a 5858 event to the failed operation and its client. The fields are elements of
+/// `UserData/Operation_ClientFailure`.
+pub fn failure_of(event: &DisplayEvent) -> Option<WmiFailure> {
+    if event.event_id != 5858 {
+        return None;
+    }
+    let field = |name| analysis::xml_element_text(&event.raw_data, name).unwrap_or_default();
+    Some(WmiFailure {
+        time: event.timestamp?,
+        process_id: field("ClientProcessId").parse().ok()?,
+        operation: field("Operation"),
+        result_code: field("ResultCode"),
+        user: field("User"),
+        client_machine: field("ClientMachine"),
+    })
+}
+
+/// Names a WBEM result code such as "0x80041032", keeping the code.
+pub fn result_name(code: &str) -> String {
+    u32::from_str_radix(code.trim_start_matches("0x").trim_start_matches("0X"), 16)
+        .ok()
+        .and_then(|value| WBEM_ERRORS.iter().find(|(known, _)| *known == value))
+        .map_or_else(
+            || code.to_string(),
+            |(_, name)| format!("{} ({})", code, name),
+        )
+}